@@ -1,6 +1,18 @@
+use pattern_core::wasm::WasmPattern;
 use serde::Serialize;
 use wasm_bindgen::prelude::*;
 
+/// Convert a parse error into a JS `Error` carrying `line` and `column`
+/// properties (1-indexed) when the error has a location.
+fn parse_error_to_js(e: &gram_codec::ParseError) -> JsValue {
+    let error = js_sys::Error::new(&e.to_string());
+    if let Some(location) = e.location() {
+        js_sys::Reflect::set(&error, &"line".into(), &(location.line as u32).into()).ok();
+        js_sys::Reflect::set(&error, &"column".into(), &(location.column as u32).into()).ok();
+    }
+    error.into()
+}
+
 /// Gram namespace: parse and serialize gram notation via WebAssembly.
 #[wasm_bindgen]
 pub struct Gram;
@@ -17,8 +29,7 @@ impl Gram {
                 .serialize(&serializer)
                 .map_err(|e| JsValue::from_str(&e.to_string()));
         }
-        let patterns =
-            gram_codec::parse_gram(gram).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let patterns = gram_codec::parse_gram(gram).map_err(|e| parse_error_to_js(&e))?;
         let asts: Vec<gram_codec::AstPattern> = patterns
             .iter()
             .map(gram_codec::AstPattern::from_pattern)
@@ -28,6 +39,31 @@ impl Gram {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Parse gram notation into an array of `WasmPattern` objects with
+    /// `WasmSubject` values.
+    ///
+    /// Throws an `Error` with `line` and `column` properties if parsing fails.
+    #[wasm_bindgen(js_name = parsePatterns)]
+    pub fn parse_patterns(gram: &str) -> Result<js_sys::Array, JsValue> {
+        if gram.trim().is_empty() {
+            return Ok(js_sys::Array::new());
+        }
+        let patterns = gram_codec::parse_gram(gram).map_err(|e| parse_error_to_js(&e))?;
+        Ok(patterns
+            .iter()
+            .map(|p| JsValue::from(WasmPattern::from_subject_pattern(p)))
+            .collect())
+    }
+
+    /// Serialize a single `WasmPattern` with `WasmSubject` values to gram notation.
+    #[wasm_bindgen]
+    pub fn serialize(pattern: &WasmPattern) -> Result<String, JsValue> {
+        let pattern = pattern
+            .to_subject_pattern()
+            .ok_or_else(|| js_sys::Error::new("pattern values must be Subjects"))?;
+        gram_codec::to_gram_pattern(&pattern).map_err(|e| js_sys::Error::new(&e.to_string()).into())
+    }
+
     /// Serialize an array of AstPattern objects (JS value) to gram notation.
     ///
     /// Uses `JSON.stringify` on the JavaScript side before deserializing with
@@ -67,8 +103,8 @@ impl Gram {
     /// optional leading bare record.
     #[wasm_bindgen(js_name = parseWithHeader)]
     pub fn parse_with_header(gram: &str) -> Result<JsValue, JsValue> {
-        let (header, patterns) = gram_codec::parse_gram_with_header(gram)
-            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let (header, patterns) =
+            gram_codec::parse_gram_with_header(gram).map_err(|e| parse_error_to_js(&e))?;
         let result = gram_codec::ParseWithHeaderResult::from_parts(header, patterns);
         let serializer = serde_wasm_bindgen::Serializer::json_compatible();
         result
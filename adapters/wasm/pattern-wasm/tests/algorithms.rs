@@ -0,0 +1,92 @@
+//! Browser/Node tests for the graph algorithm bindings; run with `wasm-pack test --node`.
+
+#![cfg(target_arch = "wasm32")]
+
+use std::rc::Rc;
+
+use pattern_core::wasm::{a_star, WasmGraphQuery, WasmPattern};
+use pattern_core::{canonical_classifier, from_patterns, graph_query_from_pattern_graph};
+use pattern_core::{Pattern, Subject};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// The chain a → b → c, with handles for its end nodes
+fn chain() -> (WasmGraphQuery, WasmPattern, WasmPattern) {
+    let node = |id: &str| Pattern::point(Subject::from_id(id));
+    let rel = |id: &str, source: &str, target: &str| {
+        Pattern::pattern(Subject::from_id(id), vec![node(source), node(target)])
+    };
+    let graph = from_patterns(
+        &canonical_classifier(),
+        vec![
+            node("a"),
+            node("b"),
+            node("c"),
+            rel("ab", "a", "b"),
+            rel("bc", "b", "c"),
+        ],
+    );
+    let query = WasmGraphQuery {
+        inner: graph_query_from_pattern_graph(Rc::new(graph)),
+    };
+    (
+        query,
+        WasmPattern::from_subject_pattern(&node("a")),
+        WasmPattern::from_subject_pattern(&node("c")),
+    )
+}
+
+fn heuristic(body: &str) -> js_sys::Function {
+    js_sys::Function::new_with_args("node", body)
+}
+
+fn a_star_error(body: &str) -> String {
+    let (query, start, end) = chain();
+    a_star(&query, &start, &end, &heuristic(body), JsValue::UNDEFINED)
+        .unwrap_err()
+        .as_string()
+        .unwrap()
+}
+
+#[wasm_bindgen_test]
+fn a_star_finds_path_with_valid_heuristic() {
+    let (query, start, end) = chain();
+    let path = a_star(
+        &query,
+        &start,
+        &end,
+        &heuristic("return 0"),
+        JsValue::UNDEFINED,
+    )
+    .unwrap();
+    assert_eq!(js_sys::Array::from(&path).length(), 3);
+}
+
+#[wasm_bindgen_test]
+fn a_star_reports_thrown_heuristic_errors() {
+    assert!(a_star_error("throw new Error('boom')").starts_with("Heuristic error"));
+}
+
+#[wasm_bindgen_test]
+fn a_star_rejects_non_numeric_estimates() {
+    assert_eq!(
+        a_star_error("return 'far'"),
+        "Heuristic must return a number"
+    );
+}
+
+#[wasm_bindgen_test]
+fn a_star_rejects_negative_estimates() {
+    assert_eq!(
+        a_star_error("return -1"),
+        "Heuristic must return a non-negative number, got -1"
+    );
+}
+
+#[wasm_bindgen_test]
+fn a_star_rejects_nan_estimates() {
+    assert_eq!(
+        a_star_error("return NaN"),
+        "Heuristic must return a non-negative number, got NaN"
+    );
+}
@@ -0,0 +1,148 @@
+//! Browser/Node conformance tests for `WasmPattern` against the shared fixture
+//! in `tests/common/pattern_ops_conformance.json`; run with `wasm-pack test --node`.
+
+#![cfg(target_arch = "wasm32")]
+
+use pattern_core::wasm::{WasmPattern, WasmSubject};
+use pattern_core::{Pattern, Subject};
+use serde_json::Value as Json;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const FIXTURE: &str = include_str!("../../../../tests/common/pattern_ops_conformance.json");
+
+fn cases() -> Vec<Json> {
+    let fixture: Json = serde_json::from_str(FIXTURE).expect("conformance fixture is JSON");
+    fixture["cases"]
+        .as_array()
+        .expect("fixture has cases")
+        .clone()
+}
+
+fn from_json(json: &Json) -> WasmPattern {
+    let mut pattern = WasmPattern::pattern(JsValue::from_str(json["value"].as_str().unwrap()));
+    for element in json["elements"].as_array().unwrap() {
+        pattern.add_element(&from_json(element));
+    }
+    pattern
+}
+
+fn function(args: &str, body: &str) -> js_sys::Function {
+    js_sys::Function::new_with_args(args, body)
+}
+
+#[wasm_bindgen_test]
+fn operations_match_the_fixture() {
+    for case in cases() {
+        let name = case["name"].as_str().unwrap_or("<unnamed>");
+        let pattern = from_json(&case["pattern"]);
+        let expected = &case["expected"];
+
+        let mapped = pattern.map(&function("v", "return v + '!'")).unwrap();
+        assert!(
+            mapped.matches(&from_json(&expected["map"])),
+            "{}: map",
+            name
+        );
+
+        let folded = pattern
+            .fold(
+                JsValue::from_str(""),
+                &function("acc, v", "return acc + v + '.'"),
+            )
+            .unwrap();
+        assert_eq!(
+            folded.as_string().as_deref(),
+            expected["fold"].as_str(),
+            "{}: fold",
+            name
+        );
+
+        let rendered = pattern
+            .para(&function(
+                "p, children",
+                "return p.value + '(' + children.join(',') + ')'",
+            ))
+            .unwrap();
+        assert_eq!(
+            rendered.as_string().as_deref(),
+            expected["para"].as_str(),
+            "{}: para",
+            name
+        );
+
+        assert_eq!(
+            Some(pattern.size() as u64),
+            expected["size"].as_u64(),
+            "{}: size",
+            name
+        );
+        assert_eq!(
+            Some(pattern.depth() as u64),
+            expected["depth"].as_u64(),
+            "{}: depth",
+            name
+        );
+
+        let values: Vec<String> = pattern
+            .values()
+            .iter()
+            .filter_map(|v| v.as_string())
+            .collect();
+        let expected_values: Vec<&str> = expected["values"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect();
+        assert_eq!(values, expected_values, "{}: values", name);
+    }
+}
+
+#[wasm_bindgen_test]
+fn fold_threads_the_accumulator_in_pre_order() {
+    let mut pattern = WasmPattern::pattern(JsValue::from_f64(10.0));
+    pattern.add_element(&WasmPattern::of(JsValue::from_f64(20.0)));
+    pattern.add_element(&WasmPattern::of(JsValue::from_f64(30.0)));
+
+    let sum = pattern
+        .fold(
+            JsValue::from_f64(0.0),
+            &function("acc, v", "return acc + v"),
+        )
+        .unwrap();
+    assert_eq!(sum.as_f64(), Some(60.0));
+
+    let order = pattern
+        .fold(
+            js_sys::Array::new().into(),
+            &function("acc, v", "acc.push(v); return acc"),
+        )
+        .unwrap();
+    let order: Vec<f64> = js_sys::Array::from(&order)
+        .iter()
+        .filter_map(|v| v.as_f64())
+        .collect();
+    assert_eq!(order, [10.0, 20.0, 30.0]);
+}
+
+#[wasm_bindgen_test]
+fn matches_compares_subjects_by_content() {
+    let subject = || Pattern::point(Subject::from_id("a"));
+    let first = WasmPattern::from_subject_pattern(&subject());
+    let second = WasmPattern::from_subject_pattern(&subject());
+    assert!(first.matches(&second));
+
+    let copy = WasmPattern::of(WasmSubject::from_subject(Subject::from_id("a")).to_js_value());
+    assert!(first.matches(&copy));
+
+    let other = WasmPattern::from_subject_pattern(&Pattern::point(Subject::from_id("b")));
+    assert!(!first.matches(&other));
+}
+
+#[wasm_bindgen_test]
+fn matches_treats_identical_values_as_equal() {
+    let undefined = WasmPattern::of(JsValue::UNDEFINED);
+    assert!(undefined.matches(&WasmPattern::of(JsValue::UNDEFINED)));
+    assert!(!undefined.matches(&WasmPattern::of(JsValue::NULL)));
+}
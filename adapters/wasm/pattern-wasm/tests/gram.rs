@@ -0,0 +1,31 @@
+//! Browser/Node tests for the `Gram` bindings; run with `wasm-pack test --node`.
+
+#![cfg(target_arch = "wasm32")]
+
+use pattern_core::wasm::WasmPattern;
+use pattern_wasm::Gram;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn serialize_round_trips_a_parsed_pattern() {
+    let input = r#"(alice:Person {name: "Alice"})"#;
+    assert_eq!(Gram::parse_patterns(input).unwrap().length(), 1);
+
+    let parsed = gram_codec::parse_gram(input).unwrap();
+    let pattern = WasmPattern::from_subject_pattern(&parsed[0]);
+    let subject = pattern.to_subject_pattern().expect("subject values");
+    assert_eq!(subject.value.identity.0, "alice");
+    assert!(subject.value.labels.contains("Person"));
+
+    assert_eq!(Gram::serialize(&pattern).unwrap(), input);
+}
+
+#[wasm_bindgen_test]
+fn parse_errors_carry_their_location() {
+    let error = Gram::parse_patterns("(unclosed").unwrap_err();
+    assert!(error.is_instance_of::<js_sys::Error>());
+    let get = |key: &str| js_sys::Reflect::get(&error, &JsValue::from_str(key)).unwrap();
+    assert_eq!(get("line").as_f64(), Some(1.0));
+    assert_eq!(get("column").as_f64(), Some(10.0));
+}
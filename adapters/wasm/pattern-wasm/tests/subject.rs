@@ -0,0 +1,75 @@
+//! Browser/Node tests for converting subjects to and from JavaScript; run with `wasm-pack test --node`.
+
+#![cfg(target_arch = "wasm32")]
+
+use pattern_core::wasm::{WasmPattern, WasmSubject};
+use pattern_core::{Direction, Pattern, Quantifier, Subject};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn backward_quantified_relationship_round_trips() {
+    let parsed = gram_codec::parse_gram("(a)<-[r:KNOWS*1..3]-(b)").unwrap();
+    let relationship = &parsed[0].value;
+    assert_eq!(relationship.direction(), Direction::Backward);
+    assert_eq!(
+        relationship.quantifier(),
+        Some(Quantifier {
+            min: Some(1),
+            max: Some(3)
+        })
+    );
+
+    let value = WasmSubject::from_subject(relationship.clone()).to_js_value();
+    let subject = WasmSubject::from_js_value(&value).unwrap().into_subject();
+    assert_eq!(&subject, relationship);
+
+    let pattern = WasmPattern::from_subject_pattern(&parsed[0]);
+    assert_eq!(pattern.to_subject_pattern().as_ref(), Some(&parsed[0]));
+}
+
+#[wasm_bindgen_test]
+fn plain_objects_are_not_subjects() {
+    let lookalike = js_sys::Object::new();
+    for (key, value) in [("_type", "Subject"), ("identity", "a")] {
+        js_sys::Reflect::set(
+            &lookalike,
+            &JsValue::from_str(key),
+            &JsValue::from_str(value),
+        )
+        .unwrap();
+    }
+    js_sys::Reflect::set(
+        &lookalike,
+        &JsValue::from_str("labels"),
+        &js_sys::Array::new(),
+    )
+    .unwrap();
+    js_sys::Reflect::set(
+        &lookalike,
+        &JsValue::from_str("properties"),
+        &js_sys::Object::new(),
+    )
+    .unwrap();
+    assert!(WasmSubject::from_js_value(&lookalike.into()).is_none());
+}
+
+#[wasm_bindgen_test]
+fn patterns_with_non_subject_elements_do_not_convert() {
+    let mut relationship =
+        WasmPattern::pattern(WasmSubject::from_subject(Subject::from_id("r")).to_js_value());
+    relationship.add_element(&WasmPattern::from_subject_pattern(&Pattern::point(
+        Subject::from_id("a"),
+    )));
+    relationship.add_element(&WasmPattern::of(JsValue::from_str("b")));
+    assert_eq!(relationship.to_subject_pattern(), None);
+    assert!(WasmPattern::from_js_subject_pattern(&JsValue::from(relationship)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn from_subject_pattern_accepts_js_patterns() {
+    let parsed = gram_codec::parse_gram("(a)-[r:KNOWS]->(b)").unwrap();
+    let pattern = JsValue::from(WasmPattern::from_subject_pattern(&parsed[0]));
+    let converted = WasmPattern::from_js_subject_pattern(&pattern).unwrap();
+    assert_eq!(converted.to_subject_pattern().as_ref(), Some(&parsed[0]));
+}
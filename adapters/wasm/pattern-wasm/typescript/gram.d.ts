@@ -158,4 +158,23 @@ export namespace Gram {
    * ```
    */
   function parseOne(gram: string): Pattern<Subject> | null;
+
+  /**
+   * Serialize a single Pattern<Subject> to gram notation.
+   *
+   * @param pattern - A Pattern with Subject values
+   * @returns Gram notation string representation
+   * @throws Error if a value is not a Subject or cannot be serialized
+   */
+  function serialize(pattern: Pattern<Subject>): string;
+
+  /**
+   * Parse gram notation into an array of Pattern<Subject> instances.
+   *
+   * @param gram - Gram notation string
+   * @returns Array of Pattern<Subject> instances (empty array for empty input)
+   * @throws Error with 1-indexed `line` and `column` properties if the
+   *   gram notation is invalid
+   */
+  function parsePatterns(gram: string): Pattern<Subject>[];
 }
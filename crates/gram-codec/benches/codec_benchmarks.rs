@@ -1,6 +1,6 @@
 // Performance benchmarks for gram codec
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use gram_codec::{parse_gram_notation, to_gram, to_gram_pattern};
+use gram_codec::{parse_gram, parse_gram_notation, to_gram, to_gram_pattern};
 use pattern_core::{Pattern, Subject, Symbol};
 use std::collections::{HashMap, HashSet};
 
@@ -182,6 +182,28 @@ fn bench_parse_complex(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark a large flat file: 100,000 top-level nodes, one per line
+///
+/// The most common real-world workload. Exercises collection of the top-level
+/// pattern list, so regressions in its allocation strategy show up here.
+fn bench_parse_large_flat_file(c: &mut Criterion) {
+    let count = 100_000;
+    let nodes = (0..count)
+        .map(|i| format!("(node_{}:Item {{n: {}}})", i, i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let with_header = format!("{{version: 1}}\n{}", nodes);
+
+    let mut group = c.benchmark_group("parse_large_flat_file");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(count as u64));
+    group.bench_function("nodes", |b| b.iter(|| parse_gram(black_box(&nodes))));
+    group.bench_function("nodes_with_header", |b| {
+        b.iter(|| parse_gram(black_box(&with_header)))
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_parse_simple_nodes,
@@ -192,5 +214,6 @@ criterion_group!(
     bench_serialize_multiple,
     bench_round_trip,
     bench_parse_complex,
+    bench_parse_large_flat_file,
 );
 criterion_main!(benches);
@@ -215,11 +215,13 @@ impl AstPattern {
 
     pub fn from_pattern(pattern: &Pattern<Subject>) -> Self {
         let subject = pattern.value();
+        let mut labels: Vec<String> = subject.labels.iter().cloned().collect();
+        labels.sort(); // Consistent ordering
 
         AstPattern {
             subject: AstSubject {
                 identity: subject.identity.0.clone(),
-                labels: subject.labels.iter().cloned().collect(),
+                labels,
                 properties: subject
                     .properties
                     .iter()
@@ -248,7 +250,7 @@ impl AstPattern {
 /// This matches the gram-hs canonical format:
 /// - Numbers (integer/decimal) use native JSON
 /// - Complex types use lowercase type discriminators
-fn value_to_json(value: &Value) -> serde_json::Value {
+pub(crate) fn value_to_json(value: &Value) -> serde_json::Value {
     match value {
         // Native JSON types (simple, common)
         Value::VInteger(i) => serde_json::Value::Number((*i).into()),
@@ -262,6 +264,8 @@ fn value_to_json(value: &Value) -> serde_json::Value {
 
         Value::VBoolean(b) => serde_json::Value::Bool(*b),
 
+        Value::VNull => serde_json::Value::Null,
+
         Value::VString(s) => serde_json::Value::String(s.clone()),
 
         Value::VArray(arr) => serde_json::Value::Array(arr.iter().map(value_to_json).collect()),
@@ -305,9 +309,7 @@ pub(crate) fn json_to_value(v: &serde_json::Value) -> Result<Value, String> {
     match v {
         serde_json::Value::String(s) => Ok(Value::VString(s.clone())),
         serde_json::Value::Bool(b) => Ok(Value::VBoolean(*b)),
-        serde_json::Value::Null => {
-            Err("JSON null is not representable as a gram value".to_string())
-        }
+        serde_json::Value::Null => Ok(Value::VNull),
         serde_json::Value::Number(n) => {
             if let Some(i) = n.as_i64() {
                 Ok(Value::VInteger(i))
@@ -1,6 +1,7 @@
 //! Lowering from syntax-preserving CST nodes to semantic patterns.
 
 use crate::cst::{Annotation, ArrowKind, SyntaxKind, SyntaxNode};
+use crate::direction::Direction;
 use crate::{Pattern, Subject};
 use pattern_core::{RangeValue, Symbol, Value};
 use std::collections::{HashMap, HashSet};
@@ -62,9 +63,14 @@ fn lower_relationship(node: Pattern<SyntaxNode>) -> Pattern<Subject> {
     )
     .expect("relationship operands should lower to patterns");
 
-    for ((arrow_kind, subject), operand) in relationships.into_iter().zip(operands) {
+    for ((arrow_kind, mut subject), operand) in relationships.into_iter().zip(operands) {
         let next =
             lower_node(operand).expect("relationship chain operands should lower to patterns");
+        subject.set_direction(match arrow_kind {
+            ArrowKind::Left => Direction::Backward,
+            ArrowKind::Undirected => Direction::Undirected,
+            ArrowKind::Right | ArrowKind::Bidirectional => Direction::Forward,
+        });
         let elements = if matches!(arrow_kind, ArrowKind::Left) {
             vec![next, acc]
         } else {
@@ -150,6 +156,7 @@ fn lower_annotation_value(value: &crate::Value) -> Value {
         crate::Value::Integer(value) => Value::VInteger(*value),
         crate::Value::Decimal(value) => Value::VDecimal(*value),
         crate::Value::Boolean(value) => Value::VBoolean(*value),
+        crate::Value::Null => Value::VNull,
         crate::Value::Array(values) => Value::VArray(
             values
                 .iter()
@@ -164,6 +171,12 @@ fn lower_annotation_value(value: &crate::Value) -> Value {
             tag: tag.clone(),
             content: content.clone(),
         },
+        crate::Value::Map(entries) => Value::VMap(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), lower_annotation_value(value)))
+                .collect(),
+        ),
     }
 }
 
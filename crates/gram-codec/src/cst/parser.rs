@@ -1,6 +1,8 @@
 //! tree-sitter-backed CST parsing entry points.
 
 use crate::cst::{Annotation, ArrowKind, CstParseResult, SourceSpan, SyntaxKind, SyntaxNode};
+use crate::direction::reserved_property;
+use crate::parser::ParseContext;
 use crate::{Pattern, Subject, Value};
 use std::collections::HashSet;
 use tree_sitter::{Node, Parser};
@@ -125,10 +127,18 @@ fn convert_relationship_pattern(
             ArrowKind::Right
         });
 
+    // The arrow sets the direction, so the record may not also write it
+    let subject = arrow_node.and_then(|kind| extract_subject(kind, input));
+    if let (Some(kind), Some(subject)) = (arrow_node, &subject) {
+        if reserved_property(subject).is_some() {
+            record_error(errors, kind);
+        }
+    }
+
     Pattern::pattern(
         SyntaxNode {
             kind: SyntaxKind::Relationship(arrow_kind),
-            subject: arrow_node.and_then(|kind| extract_subject(kind, input)),
+            subject,
             span: span_from_node(node),
             annotations: vec![],
             text: None,
@@ -307,7 +317,7 @@ fn extract_identified_annotation(node: Node<'_>, input: &str) -> Annotation {
 
 fn extract_annotation_value(node: Node<'_>, input: &str) -> Value {
     let raw = node_text(node, input);
-    let parsed = crate::parser::value::value_parser(raw)
+    let parsed = crate::parser::value::value_parser(&ParseContext::default(), raw)
         .ok()
         .and_then(|(remaining, value)| remaining.trim().is_empty().then_some(value));
 
@@ -317,6 +327,7 @@ fn extract_annotation_value(node: Node<'_>, input: &str) -> Value {
         Some(pattern_core::Value::VInteger(value)) => Value::Integer(value),
         Some(pattern_core::Value::VDecimal(value)) => Value::Decimal(value),
         Some(pattern_core::Value::VBoolean(value)) => Value::Boolean(value),
+        Some(pattern_core::Value::VNull) => Value::Null,
         Some(pattern_core::Value::VArray(values)) => Value::Array(
             values
                 .into_iter()
@@ -349,6 +360,7 @@ fn pattern_value_to_annotation_value(value: pattern_core::Value) -> Value {
         pattern_core::Value::VInteger(value) => Value::Integer(value),
         pattern_core::Value::VDecimal(value) => Value::Decimal(value),
         pattern_core::Value::VBoolean(value) => Value::Boolean(value),
+        pattern_core::Value::VNull => Value::Null,
         pattern_core::Value::VArray(values) => Value::Array(
             values
                 .into_iter()
@@ -365,7 +377,11 @@ fn pattern_value_to_annotation_value(value: pattern_core::Value) -> Value {
             _ => Value::String(format!("{range}")),
         },
         pattern_core::Value::VTaggedString { tag, content } => Value::TaggedString { tag, content },
-        pattern_core::Value::VMap(map) => Value::String(pattern_core::Value::VMap(map).to_string()),
+        pattern_core::Value::VMap(map) => Value::Map(
+            map.into_iter()
+                .map(|(key, value)| (key, pattern_value_to_annotation_value(value)))
+                .collect(),
+        ),
         pattern_core::Value::VMeasurement { unit, value } => {
             Value::String(format!("{value}{unit}"))
         }
@@ -412,7 +428,7 @@ fn extract_record_subject(node: Node<'_>, input: &str) -> Subject {
 
 fn extract_record(node: Node<'_>, input: &str) -> pattern_core::PropertyRecord {
     let raw = node_text(node, input);
-    crate::parser::subject::record(raw)
+    crate::parser::subject::record(&ParseContext::default(), raw)
         .ok()
         .and_then(|(remaining, record)| remaining.trim().is_empty().then_some(record))
         .unwrap_or_default()
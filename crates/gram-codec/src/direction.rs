@@ -0,0 +1,98 @@
+//! Arrow direction of parsed relationships
+//!
+//! Relationships are stored source-first whatever arrow was written, so
+//! `(b)<--(a)` and `(a)-->(b)` have the same elements. To let serialization
+//! write the arrow back as it was read, the parser records any direction other
+//! than [`Direction::Forward`] on the relationship's subject, under
+//! [`DIRECTION_PROPERTY`](pattern_core::DIRECTION_PROPERTY), where
+//! [`Subject::direction`](pattern_core::Subject::direction) reads it. Labels
+//! and other properties are left as written.
+//!
+//! The arrow owns that key, so a relationship's record may not also write
+//! it: `(a)-[r {_direction: backward}]->(b)` is a parse error rather than a
+//! property that the arrow would silently replace. The same holds for
+//! [`QUANTIFIER_PROPERTY`](pattern_core::QUANTIFIER_PROPERTY).
+//!
+//! ```rust
+//! use gram_codec::{parse_gram, to_gram, Direction};
+//!
+//! let patterns = parse_gram("(a)<-[:OWNS]-(b)").unwrap();
+//! assert_eq!(patterns[0].value.direction(), Direction::Backward);
+//! assert_eq!(patterns[0].elements[0].value.identity.0, "b");
+//! assert_eq!(to_gram(&patterns).unwrap(), "(a)<-[:OWNS]-(b)");
+//! ```
+
+use crate::parser::types::ArrowType;
+use pattern_core::{Pattern, Subject, DIRECTION_PROPERTY, QUANTIFIER_PROPERTY};
+
+pub use pattern_core::Direction;
+
+impl From<ArrowType> for Direction {
+    /// Double (`==>`) and squiggle (`~~>`) arrows map like their single-line forms
+    fn from(arrow: ArrowType) -> Direction {
+        if arrow.is_backward() {
+            Direction::Backward
+        } else if arrow.is_undirected() {
+            Direction::Undirected
+        } else {
+            Direction::Forward
+        }
+    }
+}
+
+/// The first of the keys set by relationship syntax that `subject` holds
+///
+/// Neither [`DIRECTION_PROPERTY`] nor [`QUANTIFIER_PROPERTY`] may be written
+/// in a relationship's record, since the arrow and quantifier would replace
+/// them.
+pub(crate) fn reserved_property(subject: &Subject) -> Option<&'static str> {
+    [DIRECTION_PROPERTY, QUANTIFIER_PROPERTY]
+        .into_iter()
+        .find(|key| subject.properties.contains_key(*key))
+}
+
+/// Rewrite backward relationships in `patterns` into forward form
+///
+/// Every relationship recorded as [`Direction::Backward`], at any depth, is
+/// flipped to [`Direction::Forward`], which swaps its endpoints as written:
+/// `(b)<--(a)` becomes `(a)-->(b)`. The elements are already stored
+/// source-first, so they keep their order and the relationship still means
+/// the same edge. Undirected relationships are left alone.
+///
+/// ```rust
+/// use gram_codec::{normalize_relationship_directions, parse_gram, to_gram};
+///
+/// let mut patterns = parse_gram("(b)<-[:OWNS]-(a)").unwrap();
+/// normalize_relationship_directions(&mut patterns);
+/// assert_eq!(to_gram(&patterns).unwrap(), "(a)-[:OWNS]->(b)");
+/// ```
+pub fn normalize_relationship_directions(patterns: &mut [Pattern<Subject>]) {
+    for pattern in patterns {
+        if pattern.value.direction() == Direction::Backward {
+            pattern.value.set_direction(Direction::Forward);
+        }
+        normalize_relationship_directions(&mut pattern.elements);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arrow_type() {
+        assert_eq!(Direction::from(ArrowType::Right), Direction::Forward);
+        assert_eq!(
+            Direction::from(ArrowType::Bidirectional),
+            Direction::Forward
+        );
+        assert_eq!(
+            Direction::from(ArrowType::SquiggleLeft),
+            Direction::Backward
+        );
+        assert_eq!(
+            Direction::from(ArrowType::DoubleUndirected),
+            Direction::Undirected
+        );
+    }
+}
@@ -0,0 +1,73 @@
+//! Bulk edits over parsed patterns
+//!
+//! Helpers for ETL-style updates that would otherwise need hand-written
+//! recursion over `Pattern<Subject>` trees.
+//!
+//! ```rust
+//! use gram_codec::{parse_gram, set_property_where};
+//! use pattern_core::Value;
+//!
+//! let mut patterns = parse_gram("(alice:Person)-->(acme:Company)").unwrap();
+//! set_property_where(
+//!     &mut patterns,
+//!     |s| s.labels.contains("Person"),
+//!     "migrated",
+//!     Value::VBoolean(true),
+//! );
+//! assert_eq!(
+//!     patterns[0].elements[0].value.properties.get("migrated"),
+//!     Some(&Value::VBoolean(true))
+//! );
+//! ```
+
+use pattern_core::{Pattern, Subject, Value};
+
+/// Set `key` to `value` on every subject matching `pred`, in place.
+///
+/// Every subject in each pattern is visited, including relationship
+/// endpoints and the elements of subject patterns and annotations. An existing
+/// value for `key` is overwritten.
+pub fn set_property_where<F>(patterns: &mut [Pattern<Subject>], pred: F, key: &str, value: Value)
+where
+    F: Fn(&Subject) -> bool,
+{
+    for pattern in patterns {
+        set_property_where_recursive(pattern, &pred, key, &value);
+    }
+}
+
+/// Return copies of `patterns` with `key` set to `value` on every subject matching `pred`.
+///
+/// Immutable variant of [`set_property_where`]; the input is left unchanged.
+pub fn with_property_where<F>(
+    patterns: &[Pattern<Subject>],
+    pred: F,
+    key: &str,
+    value: Value,
+) -> Vec<Pattern<Subject>>
+where
+    F: Fn(&Subject) -> bool,
+{
+    let mut result = patterns.to_vec();
+    set_property_where(&mut result, pred, key, value);
+    result
+}
+
+fn set_property_where_recursive<F>(
+    pattern: &mut Pattern<Subject>,
+    pred: &F,
+    key: &str,
+    value: &Value,
+) where
+    F: Fn(&Subject) -> bool,
+{
+    if pred(&pattern.value) {
+        pattern
+            .value
+            .properties
+            .insert(key.to_string(), value.clone());
+    }
+    for element in &mut pattern.elements {
+        set_property_where_recursive(element, pred, key, value);
+    }
+}
@@ -0,0 +1,618 @@
+//! Equivalence checking against reference S-expressions
+//!
+//! A reference implementation such as gram-hs can describe what a gram
+//! document parses to as one S-expression per top-level pattern, such as
+//! `(node (identity alice) (label Person) (property age 30))`.
+//! [`check_equivalence`] parses the gram text with this crate and compares
+//! the result against those S-expressions: pattern kind, identity, labels,
+//! property values, and elements, reporting the first place where they
+//! diverge.
+//!
+//! Property values are compared as values, so `'Alice'` in the reference
+//! matches a parsed `"Alice"`. Values the value parser cannot read are
+//! compared by their text.
+//!
+//! Expectations from the tree-sitter corpus, which start with
+//! `(gram_pattern ...)`, are accepted too. Those trees carry no identifiers
+//! or values, so they are compared structurally with the same rules the
+//! corpus tests use: the number of top-level patterns, and for each pattern
+//! whether its shape (node, relationship or path, subject, annotation, or
+//! document record) and its identifier, labels, and record appear in the
+//! tree. See [`check_corpus_patterns`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use gram_codec::check_equivalence;
+//!
+//! let sexp = "(node (identity alice) (label Person))";
+//! assert!(check_equivalence("(alice:Person)", sexp).is_ok());
+//! assert!(check_equivalence("(bob:Person)", sexp).is_err());
+//! ```
+
+use crate::serializer::quote_identifier;
+use crate::sexp::{pattern_kind, value_text, Sexp, SexpError};
+use crate::ParseError;
+use pattern_core::{Pattern, Subject};
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// Error returned by [`check_equivalence`]
+#[derive(Debug, Error)]
+pub enum EquivalenceError {
+    /// The gram text could not be parsed, but the reference accepts it
+    #[error("failed to parse gram text: {0}")]
+    Parse(#[from] ParseError),
+
+    /// The expected S-expression could not be read
+    #[error(transparent)]
+    InvalidSexp(#[from] SexpError),
+
+    /// The expected S-expression is not a pattern S-expression
+    #[error("unexpected S-expression at {path}: {message}")]
+    UnexpectedSexp {
+        /// Location in the expected patterns, e.g. `patterns[0].elements[1]`
+        path: String,
+        /// Description of the problem
+        message: String,
+    },
+
+    /// The parsed patterns differ from the S-expression
+    #[error("divergence at {path}: expected {expected}, found {actual}")]
+    Divergence {
+        /// Location of the divergence, e.g. `patterns[0].elements[1]`
+        path: String,
+        /// What the reference S-expression describes at this location
+        expected: String,
+        /// What the parser produced at this location
+        actual: String,
+    },
+}
+
+/// Check that gram text parses to the patterns described by a reference S-expression
+///
+/// The S-expression holds one `node`, `relationship`, or `subject` list per
+/// top-level pattern, with `(identity id)`, `(label L)`, and
+/// `(property key value)` fields before the elements, or is a corpus
+/// `(gram_pattern ...)` tree, which is compared as in
+/// [`check_corpus_patterns`]. If it contains an `ERROR` or `MISSING` list,
+/// the input is expected to be rejected.
+///
+/// # Arguments
+///
+/// * `gram_text` - Gram notation to parse
+/// * `expected_hs_sexp` - Reference S-expression for the same input
+///
+/// # Returns
+///
+/// * `Ok(())` - The parsed patterns match the reference
+/// * `Err(EquivalenceError)` - The first divergence, or why the check could not run
+pub fn check_equivalence(gram_text: &str, expected_hs_sexp: &str) -> Result<(), EquivalenceError> {
+    let roots = Sexp::parse(expected_hs_sexp)?;
+
+    if roots.iter().any(has_error) {
+        return match crate::parse_gram(gram_text) {
+            Err(_) => Ok(()),
+            Ok(patterns) => Err(EquivalenceError::Divergence {
+                path: "document".to_string(),
+                expected: "a parse error".to_string(),
+                actual: count(patterns.len(), "pattern"),
+            }),
+        };
+    }
+
+    let actual = crate::parse_gram(gram_text)?;
+    if roots.iter().any(|root| root.kind() == Some("gram_pattern")) {
+        return compare_corpus(&actual, &roots);
+    }
+    for (i, (expected, actual)) in roots.iter().zip(&actual).enumerate() {
+        compare(expected, actual, format!("patterns[{}]", i))?;
+    }
+    if roots.len() != actual.len() {
+        return Err(EquivalenceError::Divergence {
+            path: "document".to_string(),
+            expected: count(roots.len(), "pattern"),
+            actual: count(actual.len(), "pattern"),
+        });
+    }
+
+    Ok(())
+}
+
+/// Check parsed patterns against a tree-sitter corpus S-expression
+///
+/// Corpus trees such as `(gram_pattern (node_pattern identifier: (symbol)))`
+/// record the syntax of a document but not its identifiers or values, so the
+/// comparison is structural: the number of top-level patterns must match,
+/// and each pattern's shape must appear in the tree, along with an
+/// `identifier:`, `labels:`, or `record:` field for a node that has one.
+/// Paths, which the tree nests as relationship patterns, match any
+/// `relationship_pattern`.
+///
+/// # Arguments
+///
+/// * `patterns` - Patterns parsed from the corpus input
+/// * `expected_sexp` - The corpus S-expression for the same input
+///
+/// # Returns
+///
+/// * `Ok(())` - The patterns match the tree
+/// * `Err(EquivalenceError)` - The first divergence, or why the check could not run
+pub fn check_corpus_patterns(
+    patterns: &[Pattern<Subject>],
+    expected_sexp: &str,
+) -> Result<(), EquivalenceError> {
+    let roots = Sexp::parse(expected_sexp)?;
+    if !contains_kind(&roots, "gram_pattern") {
+        return Err(EquivalenceError::UnexpectedSexp {
+            path: "document".to_string(),
+            message: "expected a `gram_pattern` tree".to_string(),
+        });
+    }
+    compare_corpus(patterns, &roots)
+}
+
+fn compare_corpus(patterns: &[Pattern<Subject>], roots: &[Sexp]) -> Result<(), EquivalenceError> {
+    let expected = count_gram_patterns(roots);
+    if patterns.len() != expected {
+        return Err(EquivalenceError::Divergence {
+            path: "document".to_string(),
+            expected: count(expected, "pattern"),
+            actual: count(patterns.len(), "pattern"),
+        });
+    }
+
+    for (i, pattern) in patterns.iter().enumerate() {
+        compare_corpus_pattern(pattern, roots).map_err(|(expected, actual)| {
+            EquivalenceError::Divergence {
+                path: format!("patterns[{}]", i),
+                expected,
+                actual,
+            }
+        })?;
+    }
+    Ok(())
+}
+
+/// Number of top-level patterns a corpus tree describes
+///
+/// A document record (`root: (record ...)`) counts as a pattern of its own,
+/// and a tree with no pattern children still describes one pattern.
+fn count_gram_patterns(roots: &[Sexp]) -> usize {
+    let root_record = roots
+        .iter()
+        .any(|root| root.field("root").and_then(Sexp::kind) == Some("record"));
+    let children = roots
+        .iter()
+        .flat_map(Sexp::items)
+        .filter(|child| {
+            matches!(
+                child.kind(),
+                Some(
+                    "node_pattern"
+                        | "relationship_pattern"
+                        | "subject_pattern"
+                        | "annotated_pattern"
+                )
+            )
+        })
+        .count();
+
+    (usize::from(root_record) + children).max(1)
+}
+
+/// Compare one parsed pattern with the corpus tree, returning what was
+/// expected and what was found on a mismatch
+fn compare_corpus_pattern(
+    pattern: &Pattern<Subject>,
+    roots: &[Sexp],
+) -> Result<(), (String, String)> {
+    let expects = |kind: &str| contains_kind(roots, kind);
+    let anonymous = pattern.value.identity.0.is_empty() && pattern.value.labels.is_empty();
+    let elements = pattern.elements.len();
+
+    match elements {
+        0 => {
+            // `()`, `[]`, `[s]`, and a bare record all parse without elements
+            if expects("record") && anonymous {
+                return Ok(());
+            }
+            if expects("node_pattern") {
+                return compare_corpus_fields(pattern, roots);
+            }
+            if expects("subject_pattern") || expects("record") {
+                return Ok(());
+            }
+            Err((
+                "a node_pattern, subject_pattern, or record".to_string(),
+                "a pattern with no elements".to_string(),
+            ))
+        }
+        2 if expects("relationship_pattern") => Ok(()),
+        2 if anonymous
+            && pattern.value.properties.is_empty()
+            && !expects("subject_pattern")
+            && count_kind(roots, "node_pattern") == 2 =>
+        {
+            Ok(())
+        }
+        2 if expects("subject_pattern") => Ok(()),
+        2 => Err((
+            "a relationship_pattern, subject_pattern, or 2 node_patterns".to_string(),
+            "a pattern with 2 elements".to_string(),
+        )),
+        _ => {
+            let root_record = roots
+                .iter()
+                .any(|root| root.field("root").and_then(Sexp::kind) == Some("record"));
+            if root_record || expects("subject_pattern") || expects("annotated_pattern") {
+                return Ok(());
+            }
+            let children =
+                count_kind(roots, "node_pattern") + count_kind(roots, "relationship_pattern");
+            if anonymous && children == elements {
+                return Ok(());
+            }
+            Err((
+                "a subject_pattern, annotated_pattern, or record".to_string(),
+                format!("a pattern with {}", count(elements, "element")),
+            ))
+        }
+    }
+}
+
+/// Check that a node's identifier, labels, and record appear in the tree
+fn compare_corpus_fields(
+    pattern: &Pattern<Subject>,
+    roots: &[Sexp],
+) -> Result<(), (String, String)> {
+    let fields = [
+        (
+            "identifier",
+            !pattern.value.identity.0.is_empty(),
+            "an identifier",
+        ),
+        ("labels", !pattern.value.labels.is_empty(), "labels"),
+        ("record", !pattern.value.properties.is_empty(), "a record"),
+    ];
+    for (field, present, description) in fields {
+        if present && !contains_field(roots, field) {
+            return Err((
+                format!("no {}: field", field),
+                format!("a node with {}", description),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Number of lists of the given kind anywhere in the S-expressions
+fn count_kind(sexp: &[Sexp], kind: &str) -> usize {
+    sexp.iter()
+        .map(|node| usize::from(node.kind() == Some(kind)) + count_kind(node.items(), kind))
+        .sum()
+}
+
+fn contains_kind(sexp: &[Sexp], kind: &str) -> bool {
+    count_kind(sexp, kind) > 0
+}
+
+/// Whether any list in the S-expressions has the field `name:`
+fn contains_field(sexp: &[Sexp], name: &str) -> bool {
+    sexp.iter()
+        .any(|node| node.field(name).is_some() || contains_field(node.items(), name))
+}
+
+/// A pattern S-expression split into its parts
+struct Expected<'a> {
+    kind: &'a str,
+    identity: Option<&'a str>,
+    labels: BTreeSet<&'a str>,
+    properties: Vec<(&'a str, &'a str)>,
+    elements: Vec<&'a Sexp>,
+}
+
+impl<'a> Expected<'a> {
+    fn read(sexp: &'a Sexp, path: &str) -> Result<Self, EquivalenceError> {
+        let unexpected = |message: String| EquivalenceError::UnexpectedSexp {
+            path: path.to_string(),
+            message,
+        };
+
+        let kind = match sexp.kind() {
+            Some(kind @ ("node" | "relationship" | "subject")) => kind,
+            Some(other) => return Err(unexpected(format!("unknown pattern kind `{}`", other))),
+            None => return Err(unexpected(format!("expected a pattern, found `{}`", sexp))),
+        };
+        let mut expected = Expected {
+            kind,
+            identity: None,
+            labels: BTreeSet::new(),
+            properties: Vec::new(),
+            elements: Vec::new(),
+        };
+
+        for item in sexp.items() {
+            let atoms: Vec<&str> = item.items().iter().filter_map(Sexp::as_atom).collect();
+            match (item.kind(), atoms.as_slice()) {
+                (Some("identity"), [identity]) => expected.identity = Some(identity),
+                (Some("label"), [label]) => {
+                    expected.labels.insert(label);
+                }
+                (Some("property"), [key, value]) => expected.properties.push((key, value)),
+                (Some("node" | "relationship" | "subject"), _) => expected.elements.push(item),
+                _ => return Err(unexpected(format!("unexpected item `{}`", item))),
+            }
+        }
+        Ok(expected)
+    }
+}
+
+fn compare(
+    expected: &Sexp,
+    actual: &Pattern<Subject>,
+    path: String,
+) -> Result<(), EquivalenceError> {
+    let expected = Expected::read(expected, &path)?;
+    let diverge = |expected: String, actual: String| {
+        Err(EquivalenceError::Divergence {
+            path: path.clone(),
+            expected,
+            actual,
+        })
+    };
+
+    let kind = pattern_kind(actual);
+    if expected.kind != kind {
+        return diverge(format!("a {}", expected.kind), format!("a {}", kind));
+    }
+
+    let identity =
+        (!actual.value.identity.0.is_empty()).then(|| quote_identifier(&actual.value.identity.0));
+    if expected.identity != identity.as_deref() {
+        let describe = |identity: Option<&str>| match identity {
+            Some(identity) => format!("identity {}", identity),
+            None => "no identity".to_string(),
+        };
+        return diverge(describe(expected.identity), describe(identity.as_deref()));
+    }
+
+    let labels: Vec<String> = actual
+        .value
+        .labels
+        .iter()
+        .map(|l| quote_identifier(l))
+        .collect();
+    let labels: BTreeSet<&str> = labels.iter().map(String::as_str).collect();
+    if expected.labels != labels {
+        let describe = |labels: &BTreeSet<&str>| match labels.len() {
+            0 => "no labels".to_string(),
+            _ => format!(
+                "labels {}",
+                labels.iter().copied().collect::<Vec<_>>().join(", ")
+            ),
+        };
+        return diverge(describe(&expected.labels), describe(&labels));
+    }
+
+    let mut keys: BTreeSet<String> = actual
+        .value
+        .properties
+        .keys()
+        .map(|k| quote_identifier(k))
+        .collect();
+    keys.extend(expected.properties.iter().map(|(key, _)| key.to_string()));
+    for key in keys {
+        let expected_value = expected
+            .properties
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| *value);
+        let actual_value = actual
+            .value
+            .properties
+            .iter()
+            .find(|(k, _)| quote_identifier(k) == key)
+            .map(|(_, value)| value);
+        let describe = |value: Option<String>| match value {
+            Some(value) => format!("property {} = {}", key, value),
+            None => format!("no property {}", key),
+        };
+        let equal = match (expected_value, actual_value) {
+            (Some(text), Some(value)) => {
+                crate::parse_value(text).ok().as_ref() == Some(value) || text == value_text(value)
+            }
+            _ => false,
+        };
+        if !equal {
+            return diverge(
+                describe(expected_value.map(str::to_string)),
+                describe(actual_value.map(value_text)),
+            );
+        }
+    }
+
+    if expected.elements.len() != actual.elements.len() {
+        return diverge(
+            count(expected.elements.len(), "element"),
+            count(actual.elements.len(), "element"),
+        );
+    }
+    for (i, (expected, actual)) in expected
+        .elements
+        .into_iter()
+        .zip(&actual.elements)
+        .enumerate()
+    {
+        compare(expected, actual, format!("{}.elements[{}]", path, i))?;
+    }
+
+    Ok(())
+}
+
+fn has_error(sexp: &Sexp) -> bool {
+    matches!(sexp.kind(), Some("ERROR" | "MISSING")) || sexp.items().iter().any(has_error)
+}
+
+fn count(n: usize, noun: &str) -> String {
+    match (n, noun) {
+        (1, _) => format!("1 {}", noun),
+        (_, "property") => format!("{} properties", n),
+        _ => format!("{} {}s", n, noun),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn divergence(result: Result<(), EquivalenceError>) -> (String, String, String) {
+        match result {
+            Err(EquivalenceError::Divergence {
+                path,
+                expected,
+                actual,
+            }) => (path, expected, actual),
+            other => panic!("expected a divergence, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_node_matches() {
+        let sexp = "(node (identity a) (label Person) (property age 30) (property name \"Alice\"))";
+        assert!(check_equivalence("(a:Person {age: 30, name: 'Alice'})", sexp).is_ok());
+    }
+
+    #[test]
+    fn test_identity_divergence() {
+        let (path, expected, actual) = divergence(check_equivalence(
+            "(b:Person)",
+            "(node (identity a) (label Person))",
+        ));
+        assert_eq!(path, "patterns[0]");
+        assert_eq!(expected, "identity a");
+        assert_eq!(actual, "identity b");
+    }
+
+    #[test]
+    fn test_label_divergence() {
+        let (_, expected, actual) = divergence(check_equivalence(
+            "(a:Admin)",
+            "(node (identity a) (label Person))",
+        ));
+        assert_eq!(expected, "labels Person");
+        assert_eq!(actual, "labels Admin");
+    }
+
+    #[test]
+    fn test_property_value_divergence() {
+        let sexp = "(node (identity a) (property age 30))";
+        let (_, expected, actual) = divergence(check_equivalence("(a {age: 31})", sexp));
+        assert_eq!(expected, "property age = 30");
+        assert_eq!(actual, "property age = 31");
+
+        let (_, expected, actual) = divergence(check_equivalence("(a)", sexp));
+        assert_eq!(expected, "property age = 30");
+        assert_eq!(actual, "no property age");
+    }
+
+    #[test]
+    fn test_first_divergence_is_nested() {
+        let sexp = "(relationship (identity g) (node (identity a)) (node (identity b)))";
+        let (path, expected, actual) = divergence(check_equivalence("[g | (a), (c)]", sexp));
+        assert_eq!(path, "patterns[0].elements[1]");
+        assert_eq!(expected, "identity b");
+        assert_eq!(actual, "identity c");
+    }
+
+    #[test]
+    fn test_kind_divergence() {
+        let (_, expected, actual) =
+            divergence(check_equivalence("[g | (a)]", "(node (identity g))"));
+        assert_eq!(expected, "a node");
+        assert_eq!(actual, "a subject");
+    }
+
+    #[test]
+    fn test_pattern_count_divergence() {
+        let (path, expected, actual) = divergence(check_equivalence("()", "(node) (node)"));
+        assert_eq!(path, "document");
+        assert_eq!(expected, "2 patterns");
+        assert_eq!(actual, "1 pattern");
+    }
+
+    #[test]
+    fn test_expected_error() {
+        let sexp = "(ERROR (node (identity a)))";
+        assert!(check_equivalence("(a", sexp).is_ok());
+        let (_, expected, _) = divergence(check_equivalence("(a)", sexp));
+        assert_eq!(expected, "a parse error");
+    }
+
+    #[test]
+    fn test_parse_failure_is_reported() {
+        assert!(matches!(
+            check_equivalence("(a", "(node (identity a))"),
+            Err(EquivalenceError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_sexp() {
+        assert!(matches!(
+            check_equivalence("()", "(node"),
+            Err(EquivalenceError::InvalidSexp(_))
+        ));
+        assert!(matches!(
+            check_equivalence("()", "(mystery)"),
+            Err(EquivalenceError::UnexpectedSexp { .. })
+        ));
+        assert!(matches!(
+            check_equivalence("(a)", "(node (identity a b))"),
+            Err(EquivalenceError::UnexpectedSexp { .. })
+        ));
+    }
+
+    #[test]
+    fn test_corpus_tree_matches() {
+        let sexp = "(gram_pattern\n  (node_pattern\n    identifier: (symbol)\n    labels: (labels (symbol))))";
+        assert!(check_equivalence("(a:Person)", sexp).is_ok());
+
+        let sexp = "(gram_pattern\n  (relationship_pattern\n    left: (node_pattern\n      identifier: (symbol))\n    kind: (right_arrow)\n    right: (node_pattern\n      identifier: (symbol))))";
+        assert!(check_equivalence("(a)-->(b)", sexp).is_ok());
+    }
+
+    #[test]
+    fn test_corpus_tree_divergence() {
+        let sexp = "(gram_pattern\n  (node_pattern))";
+        let (path, expected, actual) = divergence(check_equivalence("(a)", sexp));
+        assert_eq!(path, "patterns[0]");
+        assert_eq!(expected, "no identifier: field");
+        assert_eq!(actual, "a node with an identifier");
+
+        let (path, expected, actual) = divergence(check_equivalence("() ()", sexp));
+        assert_eq!(path, "document");
+        assert_eq!(expected, "1 pattern");
+        assert_eq!(actual, "2 patterns");
+    }
+
+    #[test]
+    fn test_corpus_tree_expected_error() {
+        let sexp = "(gram_pattern\n  (ERROR\n    (symbol)))";
+        assert!(check_equivalence("(a", sexp).is_ok());
+    }
+
+    #[test]
+    fn test_count_gram_patterns() {
+        let count = |sexp: &str| count_gram_patterns(&Sexp::parse(sexp).unwrap());
+        assert_eq!(
+            count("(gram_pattern (node_pattern) (relationship_pattern) (comment))"),
+            2
+        );
+        assert_eq!(
+            count("(gram_pattern root: (record (record_property key: (symbol))))"),
+            1
+        );
+        assert_eq!(count("(gram_pattern root: (record) (node_pattern))"), 2);
+    }
+}
@@ -196,6 +196,14 @@ impl From<std::io::Error> for SerializeError {
     }
 }
 
+impl From<fmt::Error> for SerializeError {
+    fn from(err: fmt::Error) -> Self {
+        Self::IoError {
+            message: err.to_string(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
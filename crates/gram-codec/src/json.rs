@@ -24,7 +24,7 @@
 //! - Primitives: native JSON (string, number, boolean)
 //! - Complex types: tagged objects `{ "type": "symbol"|"range"|"tagged"|"measurement", ... }`
 
-use crate::ast::AstPattern;
+use crate::ast::{AstPattern, AstSubject};
 use pattern_core::{Pattern, RangeValue, Subject, Symbol, Value};
 use std::collections::{HashMap, HashSet};
 
@@ -117,7 +117,7 @@ pub fn ast_to_pattern(ast: &AstPattern) -> Result<Pattern<Subject>, String> {
             .subject
             .properties
             .iter()
-            .map(|(k, v)| json_to_value(v).map(|val| (k.clone(), val)))
+            .map(|(k, v)| value_from_json(v).map(|val| (k.clone(), val)))
             .collect::<Result<HashMap<_, _>, _>>()?,
     };
     let elements: Vec<Pattern<Subject>> = ast
@@ -132,14 +132,72 @@ pub fn ast_to_pattern(ast: &AstPattern) -> Result<Pattern<Subject>, String> {
     }
 }
 
-/// Convert a `serde_json::Value` back to a `pattern_core::Value`.
-fn json_to_value(v: &serde_json::Value) -> Result<Value, String> {
+/// Convert a `Subject` to its JSON interchange form.
+///
+/// The result is an `AstSubject` object: `{"identity", "labels",
+/// "properties"}`, with labels sorted and property values as by
+/// [`value_to_json`]. The direction and quantifier of a relationship are
+/// properties like any other (see [`pattern_core::DIRECTION_PROPERTY`]).
+pub fn subject_to_json(subject: &Subject) -> serde_json::Value {
+    let mut labels: Vec<String> = subject.labels.iter().cloned().collect();
+    labels.sort();
+    serde_json::json!({
+        "identity": subject.identity.0,
+        "labels": labels,
+        "properties": subject
+            .properties
+            .iter()
+            .map(|(k, v)| (k.clone(), value_to_json(v)))
+            .collect::<serde_json::Map<_, _>>(),
+    })
+}
+
+/// Convert the JSON interchange form of a subject back to a `Subject`.
+///
+/// Accepts what [`subject_to_json`] produces. Fails if a field is missing or
+/// a property value is not representable (see [`value_from_json`]).
+pub fn subject_from_json(json: &serde_json::Value) -> Result<Subject, String> {
+    let ast: AstSubject = serde_json::from_value(json.clone()).map_err(|e| e.to_string())?;
+    Ok(Subject {
+        identity: Symbol(ast.identity),
+        labels: ast.labels.into_iter().collect(),
+        properties: ast
+            .properties
+            .iter()
+            .map(|(k, v)| value_from_json(v).map(|val| (k.clone(), val)))
+            .collect::<Result<HashMap<_, _>, _>>()?,
+    })
+}
+
+/// Convert a `pattern_core::Value` to its JSON interchange form.
+///
+/// Integers, decimals, booleans, strings, arrays, and maps are written as
+/// native JSON. Other values are objects with a `"type"` discriminator:
+///
+/// - `VSymbol` → `{"type": "symbol", "value": s}`
+/// - `VRange` → `{"type": "range", "lower": n|null, "upper": n|null}`
+/// - `VMeasurement` → `{"type": "measurement", "unit": s, "value": n}`
+/// - `VTaggedString` → `{"type": "tagged", "tag": s, "content": s}`
+///
+/// `VNull` is written as `null`. Two values do not round-trip through
+/// [`value_from_json`]: non-finite decimals, which JSON cannot represent, are
+/// also written as `null` and read back as `VNull`; and a map with a string
+/// `"type"` entry reads back as a discriminated value.
+pub fn value_to_json(value: &Value) -> serde_json::Value {
+    crate::ast::value_to_json(value)
+}
+
+/// Convert a JSON value in interchange form back to a `pattern_core::Value`.
+///
+/// The inverse of [`value_to_json`]. Integral JSON numbers that fit an `i64`
+/// become `VInteger`, other numbers `VDecimal`, and `null` becomes `VNull`.
+/// Fails on an unknown `"type"` discriminator and on discriminated objects
+/// with missing fields.
+pub fn value_from_json(v: &serde_json::Value) -> Result<Value, String> {
     match v {
         serde_json::Value::String(s) => Ok(Value::VString(s.clone())),
         serde_json::Value::Bool(b) => Ok(Value::VBoolean(*b)),
-        serde_json::Value::Null => {
-            Err("JSON null is not representable as a gram value".to_string())
-        }
+        serde_json::Value::Null => Ok(Value::VNull),
         serde_json::Value::Number(n) => {
             if let Some(i) = n.as_i64() {
                 Ok(Value::VInteger(i))
@@ -155,7 +213,7 @@ fn json_to_value(v: &serde_json::Value) -> Result<Value, String> {
         serde_json::Value::Array(arr) => {
             let items: Vec<Value> = arr
                 .iter()
-                .map(json_to_value)
+                .map(value_from_json)
                 .collect::<Result<Vec<_>, _>>()?;
             Ok(Value::VArray(items))
         }
@@ -207,7 +265,7 @@ fn json_to_value(v: &serde_json::Value) -> Result<Value, String> {
                 // Plain JSON object → VMap
                 let map: HashMap<String, Value> = obj
                     .iter()
-                    .map(|(k, v)| json_to_value(v).map(|val| (k.clone(), val)))
+                    .map(|(k, v)| value_from_json(v).map(|val| (k.clone(), val)))
                     .collect::<Result<HashMap<_, _>, _>>()?;
                 Ok(Value::VMap(map))
             }
@@ -263,6 +321,52 @@ mod tests {
         assert_eq!(p1[0]["subject"]["labels"], p2[0]["subject"]["labels"]);
     }
 
+    #[test]
+    fn test_stringify_round_trip_keeps_arrow_direction() {
+        for original in ["(a)--(b)", "(a)<--(b)", "(a)<-[r:OWNS]-(b)", "(a)-[r]-(b)"] {
+            let json = gram_parse_to_json(original).unwrap();
+            assert_eq!(gram_stringify_from_json(&json).unwrap(), original);
+        }
+
+        let parsed: Vec<serde_json::Value> =
+            serde_json::from_str(&gram_parse_to_json("(a)<--(b) (c)--(d) (e)-->(f)").unwrap())
+                .unwrap();
+        let direction = |i: usize| {
+            parsed[i]["subject"]["properties"]
+                .get("_direction")
+                .cloned()
+        };
+        assert_eq!(
+            direction(0),
+            Some(serde_json::json!({"type": "symbol", "value": "backward"}))
+        );
+        assert_eq!(
+            direction(1),
+            Some(serde_json::json!({"type": "symbol", "value": "undirected"}))
+        );
+        // Forward is the default and is left out
+        assert_eq!(direction(2), None);
+    }
+
+    #[test]
+    fn test_stringify_round_trip_keeps_quantifier() {
+        for original in ["(a)-[:KNOWS*1..3]->(b)", "(a)<-[*]-(b)", "(a)-[r*2..]-(b)"] {
+            let json = gram_parse_to_json(original).unwrap();
+            assert_eq!(gram_stringify_from_json(&json).unwrap(), original);
+        }
+
+        let parsed: Vec<serde_json::Value> =
+            serde_json::from_str(&gram_parse_to_json("(a)-[*..4]->(b) (c)-[:R]->(d)").unwrap())
+                .unwrap();
+        assert_eq!(
+            parsed[0]["subject"]["properties"]["_quantifier"],
+            serde_json::json!({"type": "range", "lower": null, "upper": 4.0})
+        );
+        assert!(parsed[1]["subject"]["properties"]
+            .get("_quantifier")
+            .is_none());
+    }
+
     #[test]
     fn test_validate_valid_input() {
         let result = gram_validate_to_json("(alice:Person)");
@@ -299,47 +403,132 @@ mod tests {
     #[test]
     fn test_json_to_value_tagged_types() {
         // symbol
-        let v = json_to_value(&serde_json::json!({"type": "symbol", "value": "foo"})).unwrap();
+        let v = value_from_json(&serde_json::json!({"type": "symbol", "value": "foo"})).unwrap();
         assert!(matches!(v, Value::VSymbol(_)));
 
         // measurement
-        let v =
-            json_to_value(&serde_json::json!({"type": "measurement", "unit": "kg", "value": 5.0}))
-                .unwrap();
+        let v = value_from_json(
+            &serde_json::json!({"type": "measurement", "unit": "kg", "value": 5.0}),
+        )
+        .unwrap();
         assert!(matches!(v, Value::VMeasurement { .. }));
 
         // tagged string
-        let v = json_to_value(
+        let v = value_from_json(
             &serde_json::json!({"type": "tagged", "tag": "date", "content": "2024-01-01"}),
         )
         .unwrap();
         assert!(matches!(v, Value::VTaggedString { .. }));
 
         // range
-        let v = json_to_value(&serde_json::json!({"type": "range", "lower": 1.0, "upper": 10.0}))
+        let v = value_from_json(&serde_json::json!({"type": "range", "lower": 1.0, "upper": 10.0}))
             .unwrap();
         assert!(matches!(v, Value::VRange(_)));
     }
 
     #[test]
-    fn test_json_to_value_rejects_null() {
-        let err = json_to_value(&serde_json::Value::Null).unwrap_err();
-        assert!(err.contains("not representable"));
+    fn test_value_round_trips_through_json() {
+        let values = [
+            Value::VInteger(-7),
+            Value::VDecimal(2.5),
+            Value::VBoolean(true),
+            Value::VString("hello".to_string()),
+            Value::VSymbol("sym".to_string()),
+            Value::VTaggedString {
+                tag: "date".to_string(),
+                content: "2024-01-01".to_string(),
+            },
+            Value::VArray(vec![Value::VInteger(1), Value::VSymbol("x".to_string())]),
+            Value::VMap(HashMap::from([
+                ("name".to_string(), Value::VString("Alice".to_string())),
+                (
+                    "nested".to_string(),
+                    Value::VMap(HashMap::from([("n".to_string(), Value::VDecimal(0.5))])),
+                ),
+            ])),
+            Value::VRange(RangeValue {
+                lower: Some(1.0),
+                upper: None,
+            }),
+            Value::VRange(RangeValue {
+                lower: Some(1.0),
+                upper: Some(10.0),
+            }),
+            Value::VMeasurement {
+                unit: "kg".to_string(),
+                value: 5.5,
+            },
+            Value::VNull,
+        ];
+        for value in values {
+            let json = value_to_json(&value);
+            assert_eq!(value_from_json(&json).unwrap(), value, "{}", json);
+        }
+
+        assert_eq!(
+            value_to_json(&Value::VMeasurement {
+                unit: "kg".to_string(),
+                value: 5.0
+            }),
+            serde_json::json!({"type": "measurement", "unit": "kg", "value": 5.0})
+        );
+        assert_eq!(
+            value_to_json(&Value::VDecimal(f64::NAN)),
+            serde_json::Value::Null
+        );
+    }
+
+    #[test]
+    fn test_subject_round_trips_through_json() {
+        let subject = Subject {
+            identity: Symbol("alice".to_string()),
+            labels: HashSet::from(["Person".to_string(), "Admin".to_string()]),
+            properties: HashMap::from([
+                ("age".to_string(), Value::VInteger(30)),
+                ("code".to_string(), Value::VSymbol("a1".to_string())),
+            ]),
+        };
+        let json = subject_to_json(&subject);
+        assert_eq!(json["identity"], "alice");
+        assert_eq!(json["labels"], serde_json::json!(["Admin", "Person"]));
+        assert_eq!(json["properties"]["age"], 30);
+        assert_eq!(subject_from_json(&json).unwrap(), subject);
+
+        let mut relationship = subject;
+        relationship.set_direction(pattern_core::Direction::Undirected);
+        relationship.set_quantifier(Some(pattern_core::Quantifier {
+            min: Some(1),
+            max: Some(3),
+        }));
+        let json = subject_to_json(&relationship);
+        assert_eq!(subject_from_json(&json).unwrap(), relationship);
+
+        let err = subject_from_json(&serde_json::json!({"identity": "a"})).unwrap_err();
+        assert!(err.contains("labels"));
+    }
+
+    #[test]
+    fn test_json_null_is_null_value() {
+        assert_eq!(
+            value_from_json(&serde_json::Value::Null).unwrap(),
+            Value::VNull
+        );
+        assert_eq!(value_to_json(&Value::VNull), serde_json::Value::Null);
     }
 
     #[test]
     fn test_json_to_value_rejects_unknown_tagged_type() {
-        let err = json_to_value(&serde_json::json!({"type": "unknown", "value": 1})).unwrap_err();
+        let err = value_from_json(&serde_json::json!({"type": "unknown", "value": 1})).unwrap_err();
         assert!(err.contains("unknown tagged value type"));
     }
 
     #[test]
-    fn test_stringify_rejects_null_property_in_json() {
-        let err = gram_stringify_from_json(
+    fn test_stringify_writes_null_property_in_json() {
+        let gram = gram_stringify_from_json(
             r#"[{"subject":{"identity":"alice","labels":["Person"],"properties":{"nickname":null}},"elements":[]}]"#,
         )
-        .unwrap_err();
-        assert!(err.contains("not representable"));
+        .unwrap();
+        assert_eq!(gram, "(alice:Person {nickname: null})");
     }
 
     #[test]
@@ -38,10 +38,21 @@
 
 // Module declarations
 pub mod ast;
+pub mod direction;
+pub mod edit;
+pub mod equivalence;
 mod error;
 pub mod json;
+pub mod merge;
+pub mod profile;
+pub mod quantifier;
+pub mod recovery;
+pub mod round_trip;
 mod serializer;
+pub mod sexp;
+pub mod spans;
 pub mod standard_graph;
+pub mod template;
 mod value;
 
 // TODO: Temporarily commented out during migration to nom parser
@@ -64,13 +75,31 @@ mod python;
 
 // Public API exports
 pub use ast::{AstPattern, AstSubject, ParseWithHeaderResult};
+pub use direction::{normalize_relationship_directions, Direction};
+pub use edit::{set_property_where, with_property_where};
+pub use equivalence::{check_corpus_patterns, check_equivalence, EquivalenceError};
 pub use error::{Location, SerializeError};
-pub use json::{gram_parse_to_json, gram_stringify_from_json, gram_validate_to_json};
+pub use json::{
+    gram_parse_to_json, gram_stringify_from_json, gram_validate_to_json, subject_from_json,
+    subject_to_json, value_from_json, value_to_json,
+};
+pub use merge::{merge_gram, MergeError};
+pub use profile::value_histogram;
+pub use quantifier::Quantifier;
+pub use recovery::parse_gram_lossy;
+pub use round_trip::round_trip_diff;
+pub use sexp::pattern_to_sexp;
+pub use spans::parse_gram_with_spans;
+pub use template::{match_template, Bindings, TemplateError};
 // Use the new nom-based ParseError from the parser module
 #[cfg(feature = "cst")]
 pub use cst::{lower, parse_gram_cst, CstParseResult};
-pub use parser::ParseError;
-pub use serializer::{to_gram, to_gram_pattern, to_gram_with_header};
+pub use parser::{ParseError, DEFAULT_MAX_DEPTH};
+pub use serializer::{
+    serialize_graph, to_gram, to_gram_pattern, to_gram_pattern_with_options, to_gram_with_header,
+    to_gram_with_options, write_gram, write_gram_fmt, write_gram_pattern, EmptyNodeStyle,
+    SerializeOptions,
+};
 pub use value::Value;
 
 // Re-export Pattern and Subject from pattern-core for convenience
@@ -84,6 +113,11 @@ pub use pattern_core::{Pattern, PropertyRecord as Record, Subject};
 /// including any leading record (which appears as a bare pattern with properties but
 /// no identity, labels, or elements).
 ///
+/// Relationships are stored source-first: a backward arrow such as `(b)<--(a)` parses
+/// with elements `[a, b]`, like `(a)-->(b)`. Backward and undirected arrows are
+/// recorded as the [`Direction`] of the relationship's subject so they serialize
+/// as written; [`normalize_relationship_directions`] rewrites them as forward arrows.
+///
 /// # Arguments
 ///
 /// * `input` - Gram notation text to parse
@@ -93,33 +127,191 @@ pub use pattern_core::{Pattern, PropertyRecord as Record, Subject};
 /// * `Ok(Vec<Pattern<Subject>>)` - Successfully parsed patterns
 /// * `Err(ParseError)` - Parse error with location information
 pub fn parse_gram(input: &str) -> Result<Vec<Pattern<Subject>>, ParseError> {
+    parse_gram_with_options(input, &ParseOptions::default())
+}
+
+/// Options controlling how gram notation is parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ParseOptions {
+    /// Label applied to every parsed node that has no labels.
+    ///
+    /// Nodes are patterns written in parentheses: standalone nodes,
+    /// relationship endpoints, and node elements of subject patterns.
+    /// Relationships, subject patterns, annotations, pattern references (bare
+    /// identifiers such as `a` in `[g | a]`), and the leading record are left
+    /// unchanged. `None` (the default) leaves unlabeled nodes unlabeled.
+    pub default_label: Option<String>,
+
+    /// Maximum number of elements allowed in any one pattern.
+    ///
+    /// Applies to the elements of each subject pattern (`[s | e1, e2, ...]`)
+    /// and to the top-level patterns of the document; the leading record is
+    /// not counted. Parsing stops at the first element over the limit with
+    /// [`ParseError::ResourceLimit`], so oversized input is rejected before it
+    /// is fully read. `None` (the default) is unlimited.
+    pub max_elements_per_node: Option<usize>,
+
+    /// Maximum nesting depth of patterns and values.
+    ///
+    /// Each element of a subject pattern and each annotated pattern is one
+    /// level deeper than the pattern containing it, so `[a | [b | (c)]]` is
+    /// two levels deep. Arrays and maps in property values count the same
+    /// way, on top of the depth of the pattern they belong to, so
+    /// `({k: [[1]]})` is two levels deep. Parsing stops at the first pattern
+    /// or value too deep with
+    /// [`ParseError::TooDeep`] instead of exhausting the stack. `None` (the
+    /// default) allows [`DEFAULT_MAX_DEPTH`] levels.
+    pub max_depth: Option<usize>,
+
+    /// Whether top-level patterns are wrapped in a single document pattern.
+    ///
+    /// See [`DocumentMode`]. The default, [`DocumentMode::Auto`], keeps the
+    /// historical behavior of each entry point.
+    pub document_mode: DocumentMode,
+}
+
+/// How parsed top-level patterns are packaged into a result.
+///
+/// A document pattern has an empty identity, no labels, the leading record's
+/// properties (if any), and the remaining top-level patterns as its elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DocumentMode {
+    /// Each entry point's historical behavior: [`parse_gram_with_options`]
+    /// returns the top-level patterns unwrapped, and
+    /// [`parse_to_ast_with_options`] returns a lone pattern as-is and wraps
+    /// anything else in a document pattern.
+    #[default]
+    Auto,
+
+    /// Always return exactly one document pattern, even for empty input or a
+    /// single top-level pattern.
+    AlwaysWrap,
+
+    /// Always return the top-level patterns unwrapped, with a leading record
+    /// as a bare first pattern.
+    NeverWrap,
+}
+
+/// Parse gram notation text into a collection of Pattern structures using the given options.
+///
+/// With default options this is identical to [`parse_gram`].
+///
+/// # Arguments
+///
+/// * `input` - Gram notation text to parse
+/// * `options` - Parse options
+///
+/// # Returns
+///
+/// * `Ok(Vec<Pattern<Subject>>)` - Successfully parsed patterns
+/// * `Err(ParseError)` - Parse error with location information
+pub fn parse_gram_with_options(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<Vec<Pattern<Subject>>, ParseError> {
+    let ctx = parser::ParseContext::new(options.max_elements_per_node)
+        .with_max_depth(options.max_depth.unwrap_or(DEFAULT_MAX_DEPTH))
+        .with_default_label(options.default_label.clone());
+    parse_gram_with_context(&ctx, input, options)
+}
+
+/// Parse gram notation text with patterns nested at most `max_depth` levels deep.
+///
+/// Shorthand for [`parse_gram_with_options`] with only
+/// [`ParseOptions::max_depth`] set. Input nested deeper fails with
+/// [`ParseError::TooDeep`] at the first pattern over the limit.
+///
+/// # Example
+///
+/// ```
+/// use gram_codec::{parse_gram_with_limits, ParseError};
+///
+/// assert!(parse_gram_with_limits("[a | [b | (c)]]", 2).is_ok());
+/// assert!(matches!(
+///     parse_gram_with_limits("[a | [b | (c)]]", 1),
+///     Err(ParseError::TooDeep { depth: 1, .. })
+/// ));
+/// ```
+pub fn parse_gram_with_limits(
+    input: &str,
+    max_depth: usize,
+) -> Result<Vec<Pattern<Subject>>, ParseError> {
+    let options = ParseOptions {
+        max_depth: Some(max_depth),
+        ..ParseOptions::default()
+    };
+    parse_gram_with_options(input, &options)
+}
+
+/// Parse as [`parse_gram_with_options`] does, with the limits and settings of
+/// `ctx` in place of the limits of `options`.
+pub(crate) fn parse_gram_with_context(
+    ctx: &parser::ParseContext,
+    input: &str,
+    options: &ParseOptions,
+) -> Result<Vec<Pattern<Subject>>, ParseError> {
+    // Handle empty/whitespace-only input
+    if input.trim().is_empty() {
+        return match options.document_mode {
+            DocumentMode::AlwaysWrap => Ok(vec![into_document(vec![])]),
+            DocumentMode::Auto | DocumentMode::NeverWrap => Ok(vec![]),
+        };
+    }
+
+    let (header, mut patterns) = parse_header_and_patterns(ctx, input)?;
+
+    if let Some(properties) = header {
+        let header_subject = Subject {
+            identity: pattern_core::Symbol(String::new()),
+            labels: std::collections::HashSet::new(),
+            properties,
+        };
+        patterns.insert(0, Pattern::point(header_subject));
+    }
+
+    match options.document_mode {
+        DocumentMode::Auto | DocumentMode::NeverWrap => Ok(patterns),
+        DocumentMode::AlwaysWrap => Ok(vec![into_document(patterns)]),
+    }
+}
+
+/// Parse a whole document in one pass, keeping its leading record apart
+///
+/// Fails with [`ParseError::UnexpectedInput`] if anything but whitespace and
+/// comments follows the last pattern.
+fn parse_header_and_patterns(
+    ctx: &parser::ParseContext,
+    input: &str,
+) -> Result<(Option<Record>, Vec<Pattern<Subject>>), ParseError> {
     // Handle empty/whitespace-only input
     if input.trim().is_empty() {
-        return Ok(vec![]);
+        return Ok((None, vec![]));
     }
 
     // Parse using nom parser
-    match parser::gram_patterns(input) {
-        Ok((remaining, patterns)) => {
-            // Check if all input was consumed
-            if !remaining.trim().is_empty() {
-                let offset = input.len() - remaining.len();
-                let location = parser::Location::from_offset(input, offset);
-                return Err(ParseError::UnexpectedInput {
-                    location,
-                    snippet: remaining.chars().take(20).collect(),
-                });
-            }
-            Ok(patterns)
-        }
-        Err(e) => Err(parser::ParseError::from_nom_error(input, e)),
+    let (remaining, (header, patterns)) = parser::parse_document(ctx, input)?;
+
+    // Check if all input was consumed
+    if !remaining.trim().is_empty() {
+        let offset = input.len() - remaining.len();
+        let location = parser::Location::from_offset(input, offset);
+        return Err(ParseError::UnexpectedInput {
+            location,
+            snippet: remaining.chars().take(20).collect(),
+        });
     }
+
+    Ok((header, patterns))
 }
 
 /// Parse gram notation, separating an optional header record from the patterns.
 ///
-/// If the first element is a bare record (identity and labels are empty, and it has no elements),
-/// it is returned separately as the header.
+/// The header is a bare record `{...}` before the first pattern. The parser
+/// recognizes it directly, so a pattern that merely looks like a record, such
+/// as `[{k: 1}]`, is never taken for one. The header may be written as several
+/// records in a row, with comments between them; they are merged, and a key
+/// given twice takes its last value. A document holding only a header yields
+/// `(Some(header), [])`.
 ///
 /// # Arguments
 ///
@@ -132,24 +324,7 @@ pub fn parse_gram(input: &str) -> Result<Vec<Pattern<Subject>>, ParseError> {
 pub fn parse_gram_with_header(
     input: &str,
 ) -> Result<(Option<Record>, Vec<Pattern<Subject>>), ParseError> {
-    let mut patterns = parse_gram(input)?;
-
-    if patterns.is_empty() {
-        return Ok((None, vec![]));
-    }
-
-    // Check if the first pattern is a bare record
-    let first = &patterns[0];
-    if first.value.identity.0.is_empty()
-        && first.value.labels.is_empty()
-        && first.elements.is_empty()
-        && !first.value.properties.is_empty()
-    {
-        let header_record = patterns.remove(0).value.properties;
-        Ok((Some(header_record), patterns))
-    } else {
-        Ok((None, patterns))
-    }
+    parse_header_and_patterns(&parser::ParseContext::default(), input)
 }
 
 /// Parse gram notation to AST (Abstract Syntax Tree).
@@ -188,6 +363,38 @@ pub fn parse_to_ast(input: &str) -> Result<AstPattern, ParseError> {
     Ok(AstPattern::from_pattern(&document_pattern))
 }
 
+/// Parse gram notation to AST using the given options.
+///
+/// The result shape follows [`ParseOptions::document_mode`]:
+///
+/// * [`DocumentMode::Auto`] - One pattern, exactly as [`parse_to_ast`] returns
+/// * [`DocumentMode::AlwaysWrap`] - One document pattern
+/// * [`DocumentMode::NeverWrap`] - One pattern per top-level pattern, with a
+///   leading record as a bare first pattern; empty for empty input
+///
+/// # Arguments
+///
+/// * `input` - Gram notation text to parse
+/// * `options` - Parse options
+///
+/// # Returns
+///
+/// * `Ok(Vec<AstPattern>)` - The parsed patterns as AST
+/// * `Err(ParseError)` - If parsing fails
+pub fn parse_to_ast_with_options(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<Vec<AstPattern>, ParseError> {
+    let patterns = parse_gram_with_options(input, options)?;
+
+    let patterns = match options.document_mode {
+        DocumentMode::Auto if patterns.is_empty() => return Ok(vec![AstPattern::empty()]),
+        DocumentMode::Auto => vec![wrap_as_document(patterns)],
+        DocumentMode::AlwaysWrap | DocumentMode::NeverWrap => patterns,
+    };
+    Ok(patterns.iter().map(AstPattern::from_pattern).collect())
+}
+
 /// Internal helper to wrap multiple patterns into a single document-level pattern.
 fn wrap_as_document(mut patterns: Vec<Pattern<Subject>>) -> Pattern<Subject> {
     if patterns.len() == 1 {
@@ -204,8 +411,12 @@ fn wrap_as_document(mut patterns: Vec<Pattern<Subject>>) -> Pattern<Subject> {
         }
     }
 
-    // Otherwise wrap everything (including the bare record if present)
-    // Actually, if the first is a bare record, it becomes the document's properties
+    into_document(patterns)
+}
+
+/// Wrap patterns in a document pattern, moving a leading bare record's
+/// properties onto the document.
+fn into_document(mut patterns: Vec<Pattern<Subject>>) -> Pattern<Subject> {
     let mut properties = Record::new();
     if !patterns.is_empty() {
         let first = &patterns[0];
@@ -231,6 +442,41 @@ pub fn validate_gram(input: &str) -> Result<(), ParseError> {
     parse_gram(input).map(|_| ())
 }
 
+/// Parse a standalone gram value literal, such as a property value.
+///
+/// Accepts any value that can appear in a property record: strings, numbers,
+/// measurements, booleans, symbols, arrays, ranges, maps, and tagged strings.
+/// Surrounding whitespace and comments are ignored; any other trailing content
+/// is an error.
+///
+/// Returns the same [`pattern_core::Value`] that the parser stores in
+/// `Subject::properties` (not the legacy [`Value`] enum of this crate).
+///
+/// # Examples
+///
+/// ```rust
+/// use gram_codec::parse_value;
+/// use pattern_core::Value;
+///
+/// assert_eq!(parse_value(" 42 ").unwrap(), Value::VInteger(42));
+/// assert_eq!(parse_value("\"hi\"").unwrap(), Value::VString("hi".to_string()));
+/// assert!(parse_value("1 2").is_err());
+/// ```
+pub fn parse_value(input: &str) -> Result<pattern_core::Value, ParseError> {
+    let ctx = parser::ParseContext::default();
+    let (remaining, value) =
+        parser::value::value_parser(&ctx, input).map_err(|err| ctx.error(input, err))?;
+
+    if !remaining.is_empty() {
+        let offset = input.len() - remaining.len();
+        return Err(ParseError::UnexpectedInput {
+            location: parser::Location::from_offset(input, offset),
+            snippet: remaining.chars().take(20).collect(),
+        });
+    }
+    Ok(value)
+}
+
 /// Parse a single Gram pattern from text.
 pub fn parse_single_pattern(input: &str) -> Result<Pattern<Subject>, ParseError> {
     let patterns = parse_gram(input)?;
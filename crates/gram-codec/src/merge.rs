@@ -0,0 +1,116 @@
+//! Merging gram documents by identity.
+//!
+//! [`merge_gram`] parses two documents, reconciles their patterns into one
+//! graph under a [`ReconciliationPolicy`], and writes the graph back out, so
+//! combining two gram files is a single call:
+//!
+//! ```
+//! use gram_codec::merge_gram;
+//! use pattern_core::reconcile::ReconciliationPolicy;
+//!
+//! let merged = merge_gram(
+//!     "(alice:Person)",
+//!     "(alice)-[:KNOWS]->(bob)",
+//!     &ReconciliationPolicy::merge_builder().build(),
+//! )
+//! .unwrap();
+//! assert!(merged.contains("(alice:Person)"));
+//! ```
+
+use pattern_core::reconcile::{ReconciliationPolicy, SubjectMergeStrategy};
+use pattern_core::{canonical_classifier, from_patterns_with_policy};
+use thiserror::Error;
+
+use crate::{parse_gram_with_header, serialize_graph, to_gram_with_header, ParseError};
+use crate::{Record, SerializeError};
+
+/// Error returned by [`merge_gram`]
+#[derive(Debug, Error)]
+pub enum MergeError {
+    /// One of the documents is not valid gram notation
+    #[error("failed to parse {document} document: {source}")]
+    Parse {
+        /// Which document failed, `"first"` or `"second"`
+        document: &'static str,
+        /// The parse error
+        source: ParseError,
+    },
+
+    /// Under `Strict`, some identities are defined with different content
+    #[error("conflicting definitions for {}", .0.join(", "))]
+    Conflict(
+        /// The conflicting identities, sorted
+        Vec<String>,
+    ),
+
+    /// The merged graph could not be written as gram notation
+    #[error("failed to serialize merged graph: {0}")]
+    Serialize(#[from] SerializeError),
+}
+
+/// Merge two gram documents into one, reconciling by identity
+///
+/// The patterns of `a` and then `b` are built into a single graph as
+/// [`from_patterns_with_policy`] does, so each identity is reconciled across
+/// both documents in that order under `policy`, and the graph is written back
+/// with [`serialize_graph`]: nodes first, then relationships, walks,
+/// annotations, and other patterns, each group sorted by identity.
+///
+/// A bare reference such as the `(alice)` in `(alice)-->(bob)` refers to the
+/// node defined elsewhere and never overrides or conflicts with it, so merging
+/// a document's own output gives the output back under every policy.
+/// Anonymous patterns receive generated identities
+/// ([`ANONYMOUS_ID_PREFIX`](pattern_core::ANONYMOUS_ID_PREFIX)), which appear
+/// in the output.
+///
+/// Header records are merged key by key, with `b` winning on shared keys, and
+/// written before the patterns.
+///
+/// # Arguments
+///
+/// * `a` - The first gram document
+/// * `b` - The second gram document, reconciled after `a`
+/// * `policy` - How to reconcile occurrences of the same identity
+///
+/// # Returns
+///
+/// * `Ok(String)` - The merged document, one statement per line
+/// * `Err(MergeError::Conflict)` - Under `Strict`, listing every identity
+///   whose occurrences differ
+/// * `Err(MergeError)` - If either document fails to parse, or the merged
+///   graph cannot be serialized
+pub fn merge_gram(
+    a: &str,
+    b: &str,
+    policy: &ReconciliationPolicy<SubjectMergeStrategy>,
+) -> Result<String, MergeError> {
+    let parse = |document, input| {
+        parse_gram_with_header(input).map_err(|source| MergeError::Parse { document, source })
+    };
+    let (header_a, patterns_a) = parse("first", a)?;
+    let (header_b, patterns_b) = parse("second", b)?;
+
+    let graph = from_patterns_with_policy(
+        &canonical_classifier(),
+        policy,
+        patterns_a.into_iter().chain(patterns_b),
+    );
+    if !graph.pg_conflicts.is_empty() {
+        let mut ids: Vec<String> = graph.pg_conflicts.keys().map(|id| id.0.clone()).collect();
+        ids.sort();
+        return Err(MergeError::Conflict(ids));
+    }
+
+    let body = serialize_graph(&graph)?;
+    let mut header: Record = header_a.unwrap_or_default();
+    header.extend(header_b.unwrap_or_default());
+    if header.is_empty() {
+        return Ok(body);
+    }
+    let header = to_gram_with_header(header, &[])?;
+    if body.is_empty() {
+        Ok(header)
+    } else {
+        Ok(format!("{}\n{}", header, body))
+    }
+}
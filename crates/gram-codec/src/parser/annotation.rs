@@ -1,16 +1,17 @@
 //! Annotation parser for gram notation
 
-use super::combinators::ws;
+use super::combinators::{comma_list0, ws};
 use super::types::ParseResult;
-use super::value::identifier;
 use super::value::value_parser;
+use super::value::{identifier, key_name, label_name};
+use super::ParseContext;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::char,
     combinator::{cut, map, opt},
     multi::{many0, many1},
-    sequence::{delimited, pair, preceded},
+    sequence::{delimited, pair, preceded, separated_pair},
 };
 
 /// Intermediate type for annotations during parsing
@@ -27,26 +28,83 @@ pub struct IdentifiedAnnotation {
     pub labels: Vec<String>,
 }
 
-/// Parse a property annotation: @key or @key(value)
-pub fn property_annotation(input: &str) -> ParseResult<'_, Annotation> {
+/// One argument of `@key(...)`: a value, or a named value `name: value`
+enum Argument {
+    Positional(pattern_core::Value),
+    Named(String, pattern_core::Value),
+}
+
+fn argument<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Argument> {
+    alt((
+        map(
+            separated_pair(delimited(ws, key_name, ws), char(':'), |i| {
+                value_parser(ctx, i)
+            }),
+            |(name, value)| Argument::Named(name, value),
+        ),
+        map(|i| value_parser(ctx, i), Argument::Positional),
+    ))(input)
+}
+
+/// The value of an annotation's argument list
+///
+/// - one positional argument is the value itself: `@since(v1_0)`
+/// - several positional arguments form an array: `@tags(a, b)`
+/// - named arguments alone form a map: `@source(system: "db")`
+/// - positional and named arguments together form an array of the
+///   positional values followed by a map of the named ones:
+///   `@source("db", timestamp: 2024)` is `["db", {timestamp: 2024}]`
+///
+/// Each form reads the same as writing that value directly, so the serializer
+/// writes every annotation as `@key(value)`.
+fn arguments_value(arguments: Vec<Argument>) -> pattern_core::Value {
+    let mut positional = Vec::new();
+    let mut named = std::collections::HashMap::new();
+    for argument in arguments {
+        match argument {
+            Argument::Positional(value) => positional.push(value),
+            Argument::Named(name, value) => {
+                named.insert(name, value);
+            }
+        }
+    }
+    match (positional.len(), named.is_empty()) {
+        (1, true) => positional.pop().unwrap(),
+        (_, true) => pattern_core::Value::VArray(positional),
+        (0, false) => pattern_core::Value::VMap(named),
+        (_, false) => {
+            positional.push(pattern_core::Value::VMap(named));
+            pattern_core::Value::VArray(positional)
+        }
+    }
+}
+
+/// Parse a property annotation: `@key` or `@key(arguments)`
+///
+/// The arguments are comma-separated values or named values `name: value`;
+/// see [`arguments_value`] for the value they produce. An empty list,
+/// `@key()`, is the same as `@key`.
+pub fn property_annotation<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Annotation> {
     map(
         preceded(
             char('@'),
             pair(
                 identifier,
                 opt(delimited(
-                    char('('),
-                    delimited(ws, value_parser, ws),
-                    cut(char(')')),
+                    pair(char('('), ws),
+                    comma_list0(|i| argument(ctx, i)),
+                    cut(pair(ws, char(')'))),
                 )),
             ),
         ),
-        |(key, value)| Annotation {
+        |(key, arguments)| Annotation {
             key,
-            value: value.map_or_else(
-                || pattern_core::Value::VBoolean(true),
-                normalize_annotation_value,
-            ),
+            value: match arguments {
+                Some(arguments) if !arguments.is_empty() => {
+                    normalize_annotation_value(arguments_value(arguments))
+                }
+                _ => pattern_core::Value::VBoolean(true),
+            },
         },
     )(input)
 }
@@ -58,7 +116,7 @@ pub fn identified_annotation(input: &str) -> ParseResult<'_, IdentifiedAnnotatio
             tag("@@"),
             pair(
                 opt(identifier),
-                opt(many1(preceded(alt((tag("::"), tag(":"))), identifier))),
+                opt(many1(preceded(alt((tag("::"), tag(":"))), label_name))),
             ),
         ),
         |(identity, labels)| IdentifiedAnnotation {
@@ -69,19 +127,20 @@ pub fn identified_annotation(input: &str) -> ParseResult<'_, IdentifiedAnnotatio
 }
 
 /// Parse the full annotation sequence accepted by the v0.3.4 grammar.
-pub fn annotations(
-    input: &str,
-) -> ParseResult<'_, (Option<IdentifiedAnnotation>, Vec<Annotation>)> {
+pub fn annotations<'a>(
+    ctx: &ParseContext,
+    input: &'a str,
+) -> ParseResult<'a, (Option<IdentifiedAnnotation>, Vec<Annotation>)> {
     alt((
         map(
             pair(
                 delimited(ws, identified_annotation, ws),
-                many0(delimited(ws, property_annotation, ws)),
+                many0(delimited(ws, |i| property_annotation(ctx, i), ws)),
             ),
             |(identified, properties)| (Some(identified), properties),
         ),
         map(
-            many1(delimited(ws, property_annotation, ws)),
+            many1(delimited(ws, |i| property_annotation(ctx, i), ws)),
             |properties| (None, properties),
         ),
     ))(input)
@@ -93,7 +152,8 @@ mod tests {
 
     #[test]
     fn test_annotation_key_only() {
-        let (remaining, ann) = property_annotation("@deprecated").unwrap();
+        let (remaining, ann) =
+            property_annotation(&ParseContext::default(), "@deprecated").unwrap();
         assert_eq!(ann.key, "deprecated");
         assert_eq!(ann.value, pattern_core::Value::VBoolean(true));
         assert_eq!(remaining, "");
@@ -101,7 +161,8 @@ mod tests {
 
     #[test]
     fn test_annotation_with_value() {
-        let (remaining, ann) = property_annotation("@since(v1_0)").unwrap();
+        let (remaining, ann) =
+            property_annotation(&ParseContext::default(), "@since(v1_0)").unwrap();
         assert_eq!(ann.key, "since");
         assert_eq!(ann.value, pattern_core::Value::VString("v1_0".to_string()));
         assert_eq!(remaining, "");
@@ -109,12 +170,92 @@ mod tests {
 
     #[test]
     fn test_annotation_with_whitespace() {
-        let (remaining, ann) = property_annotation("@key( value )").unwrap();
+        let (remaining, ann) =
+            property_annotation(&ParseContext::default(), "@key( value )").unwrap();
         assert_eq!(ann.key, "key");
         assert_eq!(ann.value, pattern_core::Value::VString("value".to_string()));
         assert_eq!(remaining, "");
     }
 
+    #[test]
+    fn test_annotation_with_typed_values() {
+        let (_, ann) = property_annotation(&ParseContext::default(), "@depth(2)").unwrap();
+        assert_eq!(ann.value, pattern_core::Value::VInteger(2));
+
+        let (_, ann) = property_annotation(&ParseContext::default(), "@tags([\"a\", 1])").unwrap();
+        assert_eq!(
+            ann.value,
+            pattern_core::Value::VArray(vec![
+                pattern_core::Value::VString("a".to_string()),
+                pattern_core::Value::VInteger(1),
+            ])
+        );
+
+        let (remaining, ann) = property_annotation(
+            &ParseContext::default(),
+            "@source({system: \"db\", timestamp: 2024, via: etl})",
+        )
+        .unwrap();
+        assert_eq!(ann.key, "source");
+        let pattern_core::Value::VMap(map) = ann.value else {
+            panic!("expected map value, got {:?}", ann.value);
+        };
+        assert_eq!(
+            map.get("system"),
+            Some(&pattern_core::Value::VString("db".to_string()))
+        );
+        assert_eq!(
+            map.get("timestamp"),
+            Some(&pattern_core::Value::VInteger(2024))
+        );
+        // Nested symbols are normalized like top-level ones
+        assert_eq!(
+            map.get("via"),
+            Some(&pattern_core::Value::VString("etl".to_string()))
+        );
+        assert_eq!(remaining, "");
+    }
+
+    #[test]
+    fn test_annotation_with_argument_list() {
+        use pattern_core::Value;
+
+        let (remaining, ann) =
+            property_annotation(&ParseContext::default(), "@source(\"db\", timestamp: 2024)")
+                .unwrap();
+        assert_eq!(remaining, "");
+        let Value::VArray(values) = ann.value else {
+            panic!("expected array value, got {:?}", ann.value);
+        };
+        assert_eq!(values[0], Value::VString("db".to_string()));
+        let Value::VMap(named) = &values[1] else {
+            panic!("expected named arguments map, got {:?}", values[1]);
+        };
+        assert_eq!(named.get("timestamp"), Some(&Value::VInteger(2024)));
+
+        let (_, ann) = property_annotation(
+            &ParseContext::default(),
+            "@source(system: db, timestamp: 2024)",
+        )
+        .unwrap();
+        assert_eq!(
+            ann.value,
+            property_annotation(
+                &ParseContext::default(),
+                "@source({system: db, timestamp: 2024})"
+            )
+            .unwrap()
+            .1
+            .value
+        );
+
+        let (_, ann) = property_annotation(&ParseContext::default(), "@tags(a, 1, )").unwrap();
+        assert_eq!(
+            ann.value,
+            Value::VArray(vec![Value::VString("a".to_string()), Value::VInteger(1)])
+        );
+    }
+
     #[test]
     fn test_identified_annotation_with_identity_and_label() {
         let (remaining, ann) = identified_annotation("@@p:L").unwrap();
@@ -134,7 +275,7 @@ mod tests {
     #[test]
     fn test_annotations_accept_identified_then_properties() {
         let (remaining, (identified, properties)) =
-            annotations("@@p:L @since(v1_0) @deprecated").unwrap();
+            annotations(&ParseContext::default(), "@@p:L @since(v1_0) @deprecated").unwrap();
         assert!(identified.is_some());
         assert_eq!(properties.len(), 2);
         assert_eq!(remaining, "");
@@ -163,11 +304,14 @@ fn normalize_annotation_value(value: pattern_core::Value) -> pattern_core::Value
         pattern_core::Value::VTaggedString { tag, content } => {
             pattern_core::Value::VTaggedString { tag, content }
         }
-        pattern_core::Value::VMap(map) => {
-            pattern_core::Value::VString(pattern_core::Value::VMap(map).to_string())
-        }
+        pattern_core::Value::VMap(map) => pattern_core::Value::VMap(
+            map.into_iter()
+                .map(|(key, value)| (key, normalize_annotation_value(value)))
+                .collect(),
+        ),
         pattern_core::Value::VMeasurement { unit, value } => {
             pattern_core::Value::VString(format!("{value}{unit}"))
         }
+        pattern_core::Value::VNull => pattern_core::Value::VNull,
     }
 }
@@ -4,10 +4,12 @@ use super::types::{Location, ParseResult, Span};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_until},
+    character::complete::char,
     character::complete::multispace1,
-    combinator::{recognize, rest, value},
-    multi::many0,
-    sequence::{delimited, pair},
+    combinator::{map, opt, recognize, rest, value},
+    error::{VerboseError, VerboseErrorKind},
+    multi::{many0, separated_list1},
+    sequence::{delimited, pair, terminated},
     Parser,
 };
 
@@ -16,8 +18,17 @@ pub fn ws(input: &str) -> ParseResult<'_, ()> {
     value((), many0(alt((value((), multispace1), comment))))(input)
 }
 
-/// Parse a comment: // text until newline
+/// Parse a comment: `// text until newline` or `/* text */`
+///
+/// Block comments do not nest: the first `*/` closes the comment. An unclosed
+/// block comment is a failure, not just a non-match, so it is reported where
+/// it starts instead of as unexpected input.
 pub fn comment(input: &str) -> ParseResult<'_, ()> {
+    alt((line_comment, block_comment))(input)
+}
+
+/// Parse a line comment: // text until newline
+fn line_comment(input: &str) -> ParseResult<'_, ()> {
     value(
         (),
         pair(
@@ -30,6 +41,34 @@ pub fn comment(input: &str) -> ParseResult<'_, ()> {
     )(input)
 }
 
+/// Parse a block comment: /* text */
+fn block_comment(input: &str) -> ParseResult<'_, ()> {
+    let (body, _) = tag("/*")(input)?;
+    match body.find("*/") {
+        Some(end) => Ok((&body[end + 2..], ())),
+        None => Err(nom::Err::Failure(VerboseError {
+            errors: vec![(input, VerboseErrorKind::Context("Unclosed block comment"))],
+        })),
+    }
+}
+
+/// Parse a comma-separated list that may end with a trailing comma
+///
+/// `a, b` and `a, b,` both yield `[a, b]`. A comma must follow an item, so a
+/// lone `,` is not an empty list; the caller's closing delimiter will fail on it.
+pub fn comma_list0<'a, O, F>(item: F) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>>
+where
+    F: Parser<&'a str, O, nom::error::VerboseError<&'a str>>,
+{
+    map(
+        opt(terminated(
+            separated_list1(delimited(ws, char(','), ws), item),
+            opt(pair(ws, char(','))),
+        )),
+        Option::unwrap_or_default,
+    )
+}
+
 /// Wrap a parser with optional leading/trailing whitespace
 #[allow(dead_code)]
 pub fn padded<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> ParseResult<O>
@@ -97,6 +136,32 @@ mod tests {
         assert_eq!(remaining, "");
     }
 
+    #[test]
+    fn test_block_comment() {
+        let (remaining, _) = comment("/* a\n * b */rest").unwrap();
+        assert_eq!(remaining, "rest");
+        let (remaining, _) = comment("/* /* not nested */ */").unwrap();
+        assert_eq!(remaining, " */");
+        let (remaining, _) = ws("/**/ // line\n /* block */ hello").unwrap();
+        assert_eq!(remaining, "hello");
+    }
+
+    #[test]
+    fn test_unclosed_block_comment_fails() {
+        assert!(matches!(ws("  /* open"), Err(nom::Err::Failure(_))));
+        assert!(comment("/ not a comment").is_err());
+    }
+
+    #[test]
+    fn test_comma_list0() {
+        let mut parser = comma_list0(alpha1);
+        assert_eq!(parser("a, b]").unwrap(), ("]", vec!["a", "b"]));
+        assert_eq!(parser("a, b ,]").unwrap(), ("]", vec!["a", "b"]));
+        assert_eq!(parser("]").unwrap(), ("]", vec![]));
+        assert_eq!(parser(",]").unwrap(), (",]", vec![]));
+        assert_eq!(parser("a,,]").unwrap(), (",]", vec!["a"]));
+    }
+
     #[test]
     fn test_padded() {
         let mut parser = padded(tag("hello"));
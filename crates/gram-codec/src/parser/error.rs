@@ -4,7 +4,11 @@ use super::types::Location;
 use thiserror::Error;
 
 /// Errors that can occur during parsing
+///
+/// New kinds of error may be added in minor releases, so matches on this enum
+/// need a wildcard arm.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ParseError {
     /// Syntax error with location and context
     #[error("Syntax error at {location}: expected {expected}, found '{found}'")]
@@ -31,6 +35,14 @@ pub enum ParseError {
     #[error("Unmatched {delimiter} at {location}")]
     UnmatchedDelimiter { location: Location, delimiter: char },
 
+    /// A pattern has more elements than `ParseOptions::max_elements_per_node` allows
+    #[error("Resource limit exceeded at {location}: more than {limit} elements in one pattern")]
+    ResourceLimit { location: Location, limit: usize },
+
+    /// Patterns or values are nested more than `ParseOptions::max_depth` levels deep
+    #[error("Nesting too deep at {location}: more than {depth} levels")]
+    TooDeep { location: Location, depth: usize },
+
     /// Internal parser error (should not occur in production)
     #[error("Internal parser error: {message}")]
     Internal { message: String },
@@ -51,7 +63,10 @@ impl ParseError {
                 let location = Location::from_offset(input, offset);
 
                 let found = error_input.chars().take(20).collect::<String>();
-                let expected = format!("{:?}", kind);
+                let expected = match kind {
+                    nom::error::VerboseErrorKind::Context(context) => context.to_string(),
+                    other => format!("{:?}", other),
+                };
 
                 ParseError::SyntaxError {
                     location,
@@ -72,11 +87,47 @@ impl ParseError {
             ParseError::SyntaxError { location, .. }
             | ParseError::UnexpectedInput { location, .. }
             | ParseError::InvalidValue { location, .. }
-            | ParseError::UnmatchedDelimiter { location, .. } => Some(*location),
+            | ParseError::UnmatchedDelimiter { location, .. }
+            | ParseError::ResourceLimit { location, .. }
+            | ParseError::TooDeep { location, .. } => Some(*location),
             ParseError::Internal { .. } => None,
         }
     }
 
+    /// Short description of this error, used as the label at its location
+    #[cfg(feature = "diagnostics")]
+    fn label(&self) -> String {
+        match self {
+            ParseError::SyntaxError { expected, .. } => format!("expected {}", expected),
+            ParseError::UnexpectedInput { .. } => "unexpected input".to_string(),
+            ParseError::InvalidValue { kind, .. } => format!("invalid {}", kind),
+            ParseError::UnmatchedDelimiter { delimiter, .. } => {
+                format!("unmatched '{}'", delimiter)
+            }
+            ParseError::ResourceLimit { limit, .. } => {
+                format!("more than {} elements", limit)
+            }
+            ParseError::TooDeep { depth, .. } => format!("more than {} levels", depth),
+            ParseError::Internal { message } => message.clone(),
+        }
+    }
+
+    /// Build a labeled report pointing at this error's location in `source`.
+    ///
+    /// `source` must be the text that was parsed. Printing the report with
+    /// `{:?}` renders the offending line with a caret under the error; errors
+    /// without a location are reported without a label.
+    #[cfg(feature = "diagnostics")]
+    pub fn to_report(&self, source: &str) -> miette::Report {
+        let mut diagnostic = miette::MietteDiagnostic::new(self.to_string());
+        if let Some(location) = self.location() {
+            let offset = location.offset.min(source.len());
+            diagnostic =
+                diagnostic.with_label(miette::LabeledSpan::at_offset(offset, self.label()));
+        }
+        miette::Report::new(diagnostic).with_source_code(source.to_string())
+    }
+
     /// Add context to this error
     pub fn with_context(mut self, context: String) -> Self {
         if let ParseError::SyntaxError {
@@ -107,6 +158,32 @@ mod tests {
         assert_eq!(err.location().unwrap().column, 5);
     }
 
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn test_to_report_labels_error_location() {
+        let source = "(a)\n(b";
+        let err = ParseError::UnmatchedDelimiter {
+            location: Location::from_offset(source, 4),
+            delimiter: '(',
+        };
+
+        let report = err.to_report(source);
+        assert_eq!(report.to_string(), "Unmatched ( at 2:1");
+
+        let labels: Vec<_> = report.labels().expect("report has a label").collect();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].offset(), 4);
+        assert_eq!(labels[0].label(), Some("unmatched '('"));
+
+        let span = report
+            .source_code()
+            .expect("report has source")
+            .read_span(labels[0].inner(), 0, 0)
+            .unwrap();
+        assert_eq!(span.line(), 1);
+        assert_eq!(span.column(), 0);
+    }
+
     #[test]
     fn test_error_with_context() {
         let err = ParseError::SyntaxError {
@@ -15,115 +15,412 @@ pub mod value;
 
 // Re-exports
 pub use error::ParseError;
-pub use types::{Location, ParseResult};
+pub use types::{Location, ParseResult, Span};
 
-use combinators::ws;
+use combinators::{comma_list0, ws};
 use nom::{
     branch::alt,
     character::complete::char,
-    combinator::{cut, map, opt, success},
-    multi::separated_list0,
-    sequence::{delimited, pair, separated_pair},
+    combinator::{cut, map, opt, peek, success},
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    multi::{many0, many1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
 };
 use pattern_core::{Pattern, Subject};
+use std::cell::{Cell, RefCell};
+
+/// Nesting depth allowed when no other limit is given
+///
+/// Deep enough for any hand-written document while keeping the recursive
+/// descent within the 2 MiB stack of a spawned thread. A debug build takes
+/// about 12 KiB of stack per level, so a limit of 256 would still overflow
+/// that stack at around 170 levels and abort instead of failing with
+/// [`ParseError::TooDeep`]; 64 leaves headroom for the caller's own frames.
+/// Callers with more stack or deeper documents raise the limit through
+/// `ParseOptions::max_depth`.
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// Settings and running state of one parse
+///
+/// Every parser that needs a limit, a setting, or somewhere to record spans
+/// takes the context of the parse it is part of, so parses never share state
+/// and one parse may run inside another.
+#[derive(Debug)]
+pub struct ParseContext {
+    /// Element limit per pattern (`None` is unlimited)
+    max_elements_per_node: Option<usize>,
+
+    /// Nesting depth limit
+    max_depth: usize,
+
+    /// Nesting depth of the pattern being parsed
+    depth: Cell<usize>,
+
+    /// Whether subject identities may be `?name` template variables
+    template_variables: bool,
+
+    /// Label given to nodes parsed without labels
+    default_label: Option<String>,
+
+    /// Spans of the patterns parsed so far, while recording
+    spans: Option<RefCell<Vec<RawSpan>>>,
+}
+
+impl ParseContext {
+    /// A context with the given element limit
+    pub fn new(max_elements_per_node: Option<usize>) -> Self {
+        Self {
+            max_elements_per_node,
+            max_depth: DEFAULT_MAX_DEPTH,
+            depth: Cell::new(0),
+            template_variables: false,
+            default_label: None,
+            spans: None,
+        }
+    }
+
+    /// Allow patterns nested at most `max_depth` levels deep
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Accept `?name` template variables as subject identities
+    pub fn with_template_variables(mut self) -> Self {
+        self.template_variables = true;
+        self
+    }
+
+    /// Give `label` to every node parsed without labels
+    ///
+    /// Only nodes written in parentheses are labeled; pattern references such
+    /// as `a` in `[g | a]` stand for a pattern defined elsewhere and are left
+    /// as written.
+    pub fn with_default_label(mut self, label: Option<String>) -> Self {
+        self.default_label = label;
+        self
+    }
+
+    /// Record the span of every pattern parsed, for [`ParseContext::take_spans`]
+    pub fn recording_spans(mut self) -> Self {
+        self.spans = Some(RefCell::new(Vec::new()));
+        self
+    }
+
+    /// Spans of the top-level patterns parsed so far, in order
+    pub fn take_spans(&self) -> Vec<RawSpan> {
+        self.spans
+            .as_ref()
+            .map(|spans| spans.take())
+            .unwrap_or_default()
+    }
+
+    /// Convert a failure of a parse run with this context into a [`ParseError`]
+    ///
+    /// Exceeding the element limit becomes [`ParseError::ResourceLimit`] at the
+    /// offending element, and exceeding the depth limit becomes
+    /// [`ParseError::TooDeep`] at the first pattern too deep. Other failures are
+    /// converted by [`ParseError::from_nom_error`].
+    pub fn error(&self, input: &str, err: nom::Err<VerboseError<&str>>) -> ParseError {
+        let (failed_at, kind) = match &err {
+            nom::Err::Failure(e) => match e.errors.first() {
+                Some((rest, kind)) => (*rest, kind),
+                None => return ParseError::from_nom_error(input, err),
+            },
+            _ => return ParseError::from_nom_error(input, err),
+        };
+        let location = Location::from_offset(input, input.len() - failed_at.len());
+        match (kind, self.max_elements_per_node) {
+            (VerboseErrorKind::Nom(ErrorKind::TooLarge), Some(limit)) => {
+                ParseError::ResourceLimit { location, limit }
+            }
+            (VerboseErrorKind::Context(TOO_DEEP), _) => ParseError::TooDeep {
+                location,
+                depth: self.max_depth,
+            },
+            (VerboseErrorKind::Context(RESERVED_PROPERTY), _) => ParseError::InvalidValue {
+                location,
+                kind: "relationship record".to_string(),
+                reason: format!(
+                    "`{}` and `{}` are set by the arrow and quantifier",
+                    pattern_core::DIRECTION_PROPERTY,
+                    pattern_core::QUANTIFIER_PROPERTY
+                ),
+            },
+            _ => ParseError::from_nom_error(input, err),
+        }
+    }
+}
+
+impl Default for ParseContext {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+/// Source extent of a parsed pattern, as addresses into the input, with the
+/// extents of its elements in element order
+#[derive(Debug)]
+pub struct RawSpan {
+    pub start: usize,
+    pub end: usize,
+    pub elements: Vec<RawSpan>,
+}
+
+/// Wrap a pattern parser so that, while `ctx` records spans, a successful
+/// parse records its span with the spans recorded during it as elements, and a
+/// failed parse discards anything recorded during it.
+///
+/// Every parser producing a pattern is wrapped exactly once (or folds its
+/// recorded spans like [`fold_path_spans`]), so the recorded spans have the
+/// same shape as the parsed patterns.
+pub(crate) fn spanned<'a, 'c>(
+    ctx: &'c ParseContext,
+    mut parser: impl FnMut(&'a str) -> ParseResult<'a, Pattern<Subject>> + 'c,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Pattern<Subject>> + 'c {
+    move |input| {
+        let Some(spans) = &ctx.spans else {
+            return parser(input);
+        };
+        let mark = spans.borrow().len();
+        let result = parser(input);
+        let mut spans = spans.borrow_mut();
+        match &result {
+            Ok((rest, _)) => {
+                let elements = spans.split_off(mark);
+                spans.push(RawSpan {
+                    start: input.as_ptr() as usize,
+                    end: rest.as_ptr() as usize,
+                    elements,
+                });
+            }
+            Err(_) => spans.truncate(mark),
+        }
+        result
+    }
+}
+
+/// Fold the node spans recorded for a path into the nested shape of the path
+///
+/// `backward` yields, for each segment of the path, whether it was a backward
+/// arrow, whose elements the parser stores in reverse. The last `segments + 1`
+/// recorded spans are the path's nodes in source order. Each relationship
+/// spans from the first node to the end of its own target node.
+pub(crate) fn fold_path_spans(ctx: &ParseContext, backward: impl ExactSizeIterator<Item = bool>) {
+    let Some(spans) = &ctx.spans else {
+        return;
+    };
+    let mut spans = spans.borrow_mut();
+    let first = spans.len() - backward.len() - 1;
+    let mut nodes = spans.split_off(first).into_iter();
+    let Some(mut current) = nodes.next() else {
+        return;
+    };
+    for (next, backward) in nodes.zip(backward) {
+        let (start, end) = (current.start, next.end);
+        let elements = if backward {
+            vec![next, current]
+        } else {
+            vec![current, next]
+        };
+        current = RawSpan {
+            start,
+            end,
+            elements,
+        };
+    }
+    spans.push(current);
+}
+
+/// Wrap an element parser so it fails once it has produced more elements than
+/// the element limit of `ctx` allows.
+///
+/// The count starts at zero each time the wrapper is built, so building it
+/// inside a list parser applies the limit to that one list. The failure is a
+/// `nom::Err::Failure` so that `alt` does not backtrack past it.
+fn limited<'a, O>(
+    ctx: &ParseContext,
+    mut parser: impl FnMut(&'a str) -> ParseResult<'a, O>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, O> {
+    let limit = ctx.max_elements_per_node.unwrap_or(usize::MAX);
+    let mut count = 0usize;
+    move |input| {
+        let result = parser(input);
+        if result.is_ok() {
+            count += 1;
+            if count > limit {
+                return Err(too_large(input));
+            }
+        }
+        result
+    }
+}
+
+/// The failure reported by [`limited`] at the element over the limit
+fn too_large(input: &str) -> nom::Err<VerboseError<&str>> {
+    nom::Err::Failure(VerboseError {
+        errors: vec![(input, VerboseErrorKind::Nom(ErrorKind::TooLarge))],
+    })
+}
+
+/// Context of the failure reported by [`nested`]
+const TOO_DEEP: &str = "maximum nesting depth";
+
+/// Context of the failure for a relationship record holding a reserved key
+pub(crate) const RESERVED_PROPERTY: &str = "relationship record without reserved keys";
+
+/// Wrap the parser of a pattern nested inside another so it fails once the
+/// nesting depth exceeds the depth limit of `ctx`.
+///
+/// Only wrap parsers reached after the enclosing pattern has consumed some
+/// input, so the depth counts patterns actually open. Like [`limited`], the
+/// failure is a `nom::Err::Failure` so that `alt` does not backtrack past it,
+/// which also keeps the recursion bounded on adversarial input.
+fn nested<'a, 'c, O>(
+    ctx: &'c ParseContext,
+    mut parser: impl FnMut(&'a str) -> ParseResult<'a, O> + 'c,
+) -> impl FnMut(&'a str) -> ParseResult<'a, O> + 'c {
+    move |input| {
+        let depth = ctx.depth.get() + 1;
+        if depth > ctx.max_depth {
+            return Err(nom::Err::Failure(VerboseError {
+                errors: vec![(input, VerboseErrorKind::Context(TOO_DEEP))],
+            }));
+        }
+        ctx.depth.set(depth);
+        let result = parser(input);
+        ctx.depth.set(depth - 1);
+        result
+    }
+}
 
 /// Parse a pattern reference: just a bare identifier (e.g., `alice`)
-fn pattern_reference(input: &str) -> ParseResult<'_, Pattern<Subject>> {
-    map(value::unquoted_identifier, |id| {
-        Pattern::point(Subject {
-            identity: pattern_core::Symbol(id),
-            labels: std::collections::HashSet::new(),
-            properties: std::collections::HashMap::new(),
-        })
-    })(input)
+fn pattern_reference<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Pattern<Subject>> {
+    spanned(
+        ctx,
+        map(value::unquoted_identifier, |id| {
+            Pattern::point(Subject {
+                identity: pattern_core::Symbol(id),
+                labels: std::collections::HashSet::new(),
+                properties: std::collections::HashMap::new(),
+            })
+        }),
+    )(input)
 }
 
 /// Parse an element in a subject pattern: can be a full pattern or just a reference
-fn subject_element(input: &str) -> ParseResult<'_, Pattern<Subject>> {
+fn subject_element<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Pattern<Subject>> {
     alt((
-        gram_pattern,      // Try full pattern first
-        pattern_reference, // Fall back to bare identifier reference
+        |i| gram_pattern(ctx, i),      // Try full pattern first
+        |i| pattern_reference(ctx, i), // Fall back to bare identifier reference
     ))(input)
 }
 
 /// Parse a subject pattern: [subject | elements] or [subject] or []
 /// This is defined here to avoid circular dependencies
-pub fn subject_pattern(input: &str) -> ParseResult<'_, Pattern<Subject>> {
-    delimited(
-        char('['),
+///
+/// The grammar does not distinguish an empty element list from an absent one:
+/// `[subject | ]` and `[subject]` both parse to an atomic pattern, the same
+/// value as `(subject)`. Likewise `[ | ]` and `[]` are both the empty pattern.
+/// The serializer writes all of these in node form. The element list may end
+/// with a trailing comma: `[subject | a, b,]`.
+pub fn subject_pattern<'a>(
+    ctx: &ParseContext,
+    input: &'a str,
+) -> ParseResult<'a, Pattern<Subject>> {
+    spanned(
+        ctx,
         delimited(
-            ws,
-            alt((
-                // Form 1: [subject | elements]
-                map(
-                    separated_pair(
-                        subject::subject,
-                        delimited(ws, char('|'), ws),
-                        separated_list0(
-                            delimited(ws, char(','), ws),
-                            subject_element, // Can be pattern or reference
+            char('['),
+            delimited(
+                ws,
+                alt((
+                    // Form 1: [subject | elements] - also [subject | ] with no elements
+                    map(
+                        separated_pair(
+                            |i| subject::subject(ctx, i),
+                            delimited(ws, char('|'), ws),
+                            // Can be pattern or reference
+                            comma_list0(limited(ctx, nested(ctx, |i| subject_element(ctx, i)))),
                         ),
+                        |(subj, elements)| Pattern::pattern(subj, elements),
                     ),
-                    |(subj, elements)| Pattern::pattern(subj, elements),
-                ),
-                // Form 2: [subject] - just subject, no elements
-                map(subject::subject, Pattern::point),
-                // Form 3: [] - empty subject, no elements
-                map(success(()), |_| {
-                    Pattern::point(Subject {
-                        identity: pattern_core::Symbol(String::new()),
-                        labels: std::collections::HashSet::new(),
-                        properties: std::collections::HashMap::new(),
-                    })
-                }),
-            )),
-            ws,
+                    // Form 2: [subject] - just subject, no elements
+                    map(|i| subject::subject(ctx, i), Pattern::point),
+                    // Form 3: [] - empty subject, no elements
+                    map(success(()), |_| {
+                        Pattern::point(Subject {
+                            identity: pattern_core::Symbol(String::new()),
+                            labels: std::collections::HashSet::new(),
+                            properties: std::collections::HashMap::new(),
+                        })
+                    }),
+                )),
+                ws,
+            ),
+            cut(char(']')),
         ),
-        cut(char(']')),
     )(input)
 }
 
 /// Parse an annotated pattern: @key(value) pattern
-fn annotated_pattern(input: &str) -> ParseResult<'_, Pattern<Subject>> {
-    map(
-        pair(delimited(ws, annotation::annotations, ws), gram_pattern),
-        |((identified, annotations), pattern)| {
-            let mut subject = Subject {
-                identity: pattern_core::Symbol(String::new()),
-                labels: std::collections::HashSet::new(),
-                properties: std::collections::HashMap::new(),
-            };
+fn annotated_pattern<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Pattern<Subject>> {
+    spanned(
+        ctx,
+        map(
+            pair(
+                // Every annotation starts with `@`; check before trying them all
+                preceded(
+                    peek(preceded(ws, char('@'))),
+                    delimited(ws, |i| annotation::annotations(ctx, i), ws),
+                ),
+                // Trailing whitespace is left to the caller so it is not in the span
+                nested(
+                    ctx,
+                    alt((
+                        |i| annotated_pattern(ctx, i),
+                        |i| subject_pattern(ctx, i),
+                        |i| relationship::node_or_path(ctx, i),
+                    )),
+                ),
+            ),
+            |((identified, annotations), pattern)| {
+                let mut subject = Subject {
+                    identity: pattern_core::Symbol(String::new()),
+                    labels: std::collections::HashSet::new(),
+                    properties: std::collections::HashMap::new(),
+                };
 
-            if let Some(identified) = identified {
-                if let Some(identity) = identified.identity {
-                    subject.identity = pattern_core::Symbol(identity);
-                }
+                if let Some(identified) = identified {
+                    if let Some(identity) = identified.identity {
+                        subject.identity = pattern_core::Symbol(identity);
+                    }
 
-                for label in identified.labels {
-                    subject.labels.insert(label);
+                    for label in identified.labels {
+                        subject.labels.insert(label);
+                    }
                 }
-            }
 
-            for annotation in annotations {
-                subject.properties.insert(annotation.key, annotation.value);
-            }
+                for annotation in annotations {
+                    subject.properties.insert(annotation.key, annotation.value);
+                }
 
-            Pattern::pattern(subject, vec![pattern])
-        },
+                Pattern::pattern(subject, vec![pattern])
+            },
+        ),
     )(input)
 }
 
 /// Parse any gram pattern (non-top-level)
 /// Dispatch to the appropriate parser based on syntax
-/// Note: Standalone records `{}` are only valid at top-level and handled by gram_patterns
-pub fn gram_pattern(input: &str) -> ParseResult<'_, Pattern<Subject>> {
+/// Note: Standalone records `{}` are only valid at top-level and handled by gram_document
+pub fn gram_pattern<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Pattern<Subject>> {
     delimited(
         ws,
         alt((
-            annotated_pattern,          // @key(value) pattern
-            subject_pattern,            // [subject | elements]
-            relationship::path_pattern, // (a)-->(b)-->(c)
-            node::node,                 // (subject)
+            |i| annotated_pattern(ctx, i),          // @key(value) pattern
+            |i| subject_pattern(ctx, i),            // [subject | elements]
+            |i| relationship::node_or_path(ctx, i), // (a)-->(b)-->(c) or (subject)
         )),
         ws,
     )(input)
@@ -131,78 +428,101 @@ pub fn gram_pattern(input: &str) -> ParseResult<'_, Pattern<Subject>> {
 
 /// Parse multiple gram patterns (top-level)
 ///
-/// Returns all top-level patterns found in the input.
-/// If a leading record `{}` is present, it is returned as the first pattern
-/// (a bare pattern with properties but no identity/labels/elements).
-pub fn gram_patterns(input: &str) -> ParseResult<'_, Vec<Pattern<Subject>>> {
-    use nom::multi::many0;
-
-    map(
-        delimited(
-            ws,
-            pair(
-                // Optional leading record
-                opt(subject::record),
-                // All patterns
-                many0(delimited(
-                    ws,
-                    alt((
-                        annotated_pattern,
-                        subject_pattern,
-                        relationship::path_pattern,
-                        node::node,
-                    )),
-                    ws,
-                )),
-            ),
-            ws,
+/// Returns the optional leading record `{}` alongside all top-level patterns.
+/// The record is kept separate so callers can tell it apart from a node that
+/// has only properties. Several records in a row before the first pattern,
+/// such as `{a: 1} // comment` then `{b: 2}`, form one header; a key given
+/// twice takes its last value.
+pub fn gram_document<'a>(
+    ctx: &ParseContext,
+    input: &'a str,
+) -> ParseResult<'a, (Option<pattern_core::PropertyRecord>, Vec<Pattern<Subject>>)> {
+    delimited(
+        ws,
+        pair(
+            // Optional leading record, possibly written as several groups
+            opt(map(
+                many1(terminated(|i| subject::record(ctx, i), ws)),
+                |groups: Vec<pattern_core::PropertyRecord>| {
+                    groups
+                        .into_iter()
+                        .flatten()
+                        .collect::<pattern_core::PropertyRecord>()
+                },
+            )),
+            // All patterns
+            many0(limited(ctx, |i| gram_pattern(ctx, i))),
         ),
-        |(properties_opt, mut elements): (
-            Option<std::collections::HashMap<String, pattern_core::Value>>,
-            Vec<Pattern<Subject>>,
-        )| {
-            if let Some(properties) = properties_opt {
-                let header_subject = Subject {
-                    identity: pattern_core::Symbol(String::new()),
-                    labels: std::collections::HashSet::new(),
-                    properties,
-                };
-                elements.insert(0, Pattern::point(header_subject));
-            }
-            elements
-        },
+        ws,
     )(input)
 }
 
+/// Parse a gram document within the limits of `ctx`
+///
+/// The element limit applies to the elements of each subject pattern and to
+/// the top-level patterns of the document; the depth limit applies to the
+/// elements of subject patterns and to annotated patterns. Failures are converted by
+/// [`ParseContext::error`]. On success, returns the unconsumed input alongside
+/// the result of [`gram_document`].
+#[allow(clippy::type_complexity)]
+pub fn parse_document<'a>(
+    ctx: &ParseContext,
+    input: &'a str,
+) -> Result<
+    (
+        &'a str,
+        (Option<pattern_core::PropertyRecord>, Vec<Pattern<Subject>>),
+    ),
+    ParseError,
+> {
+    gram_document(ctx, input).map_err(|err| ctx.error(input, err))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_gram_pattern_node() {
-        let (remaining, pattern) = gram_pattern("(hello)").unwrap();
+        let (remaining, pattern) = gram_pattern(&ParseContext::default(), "(hello)").unwrap();
         assert_eq!(pattern.value().identity.0, "hello");
         assert_eq!(remaining, "");
     }
 
     #[test]
     fn test_gram_pattern_relationship() {
-        let (remaining, pattern) = gram_pattern("(a)-->(b)").unwrap();
+        let (remaining, pattern) = gram_pattern(&ParseContext::default(), "(a)-->(b)").unwrap();
         assert_eq!(pattern.elements().len(), 2);
         assert_eq!(remaining, "");
     }
 
     #[test]
     fn test_gram_pattern_subject_pattern() {
-        let (remaining, pattern) = gram_pattern("[team | (alice), (bob)]").unwrap();
+        let (remaining, pattern) =
+            gram_pattern(&ParseContext::default(), "[team | (alice), (bob)]").unwrap();
         assert_eq!(pattern.value().identity.0, "team");
         assert_eq!(pattern.elements().len(), 2);
         assert_eq!(remaining, "");
     }
 
     #[test]
-    fn test_gram_patterns_multiple() {
-        let (remaining, patterns) = gram_patterns("(a) (b) (c)").unwrap();
+    fn test_subject_pattern_empty_element_list() {
+        let (_, with_pipe) = subject_pattern(&ParseContext::default(), "[a:L | ]").unwrap();
+        let (_, without_pipe) = subject_pattern(&ParseContext::default(), "[a:L]").unwrap();
+        let (_, node) = node::node(&ParseContext::default(), "(a:L)").unwrap();
+        assert_eq!(with_pipe, without_pipe);
+        assert_eq!(with_pipe, node);
+
+        let (_, empty_pipe) = subject_pattern(&ParseContext::default(), "[ | ]").unwrap();
+        let (_, empty) = subject_pattern(&ParseContext::default(), "[]").unwrap();
+        assert_eq!(empty_pipe, empty);
+        assert!(empty.elements().is_empty());
+    }
+
+    #[test]
+    fn test_gram_document_multiple() {
+        let (remaining, (_, patterns)) =
+            gram_document(&ParseContext::default(), "(a) (b) (c)").unwrap();
         // Returns all 3 patterns directly
         assert_eq!(patterns.len(), 3);
         assert_eq!(patterns[0].value().identity.0, "a");
@@ -212,15 +532,16 @@ mod tests {
     }
 
     #[test]
-    fn test_gram_patterns_empty() {
-        let (remaining, patterns) = gram_patterns("").unwrap();
+    fn test_gram_document_empty() {
+        let (remaining, (_, patterns)) = gram_document(&ParseContext::default(), "").unwrap();
         assert_eq!(patterns.len(), 0);
         assert_eq!(remaining, "");
     }
 
     #[test]
-    fn test_gram_patterns_with_whitespace() {
-        let (remaining, patterns) = gram_patterns("  (a)  \n  (b)  ").unwrap();
+    fn test_gram_document_with_whitespace() {
+        let (remaining, (_, patterns)) =
+            gram_document(&ParseContext::default(), "  (a)  \n  (b)  ").unwrap();
         assert_eq!(patterns.len(), 2);
         assert_eq!(patterns[0].value().identity.0, "a");
         assert_eq!(patterns[1].value().identity.0, "b");
@@ -240,14 +561,32 @@ mod tests {
     }
 
     #[test]
-    fn test_gram_patterns_with_leading_record() {
-        let (remaining, patterns) = gram_patterns("{k:'v'} (a)").unwrap();
-        assert_eq!(patterns.len(), 2);
-        // First pattern is the bare record
-        assert_eq!(patterns[0].value().identity.0, "");
-        assert_eq!(patterns[0].value().properties.len(), 1);
-        // Second pattern is the node
-        assert_eq!(patterns[1].value().identity.0, "a");
+    fn test_parse_document_element_limit() {
+        let ctx = ParseContext::new(Some(2));
+        assert!(parse_document(&ctx, "[g | (a), (b)]").is_ok());
+
+        let err = parse_document(&ctx, "[g | (a), (b), (c)]").unwrap_err();
+        match err {
+            ParseError::ResourceLimit { location, limit } => {
+                assert_eq!(limit, 2);
+                assert_eq!(location.offset, 15);
+            }
+            other => panic!("Expected ResourceLimit, got {:?}", other),
+        }
+
+        // The limit belongs to the context, not to later parses
+        assert!(gram_document(&ParseContext::default(), "[g | (a), (b), (c)]").is_ok());
+    }
+
+    #[test]
+    fn test_gram_document_with_leading_record() {
+        let (remaining, (header, patterns)) =
+            gram_document(&ParseContext::default(), "{k:'v'} (a)").unwrap();
+        // The bare record is returned separately
+        assert_eq!(header.map(|record| record.len()), Some(1));
+        // The node is the only pattern
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].value().identity.0, "a");
         assert_eq!(remaining, "");
     }
 }
@@ -1,8 +1,10 @@
 //! Node pattern parser for gram notation
 
 use super::combinators::ws;
+use super::spanned;
 use super::subject::subject;
 use super::types::ParseResult;
+use super::ParseContext;
 use nom::{
     character::complete::char,
     combinator::{cut, map},
@@ -12,10 +14,22 @@ use pattern_core::{Pattern, Subject};
 
 /// Parse a node pattern: (subject)
 /// Node patterns have 0 elements
-pub fn node(input: &str) -> ParseResult<'_, Pattern<Subject>> {
-    map(
-        delimited(char('('), delimited(ws, subject, ws), cut(char(')'))),
-        Pattern::point,
+pub fn node<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Pattern<Subject>> {
+    spanned(
+        ctx,
+        map(
+            delimited(
+                char('('),
+                delimited(ws, |i| subject(ctx, i), ws),
+                cut(char(')')),
+            ),
+            |mut subject| {
+                if let (Some(label), true) = (&ctx.default_label, subject.labels.is_empty()) {
+                    subject.labels.insert(label.clone());
+                }
+                Pattern::point(subject)
+            },
+        ),
     )(input)
 }
 
@@ -25,7 +39,7 @@ mod tests {
 
     #[test]
     fn test_node_simple() {
-        let (remaining, pattern) = node("(hello)").unwrap();
+        let (remaining, pattern) = node(&ParseContext::default(), "(hello)").unwrap();
         assert_eq!(pattern.value().identity.0, "hello");
         assert_eq!(pattern.elements().len(), 0);
         assert_eq!(remaining, "");
@@ -33,7 +47,7 @@ mod tests {
 
     #[test]
     fn test_node_with_label() {
-        let (remaining, pattern) = node("(alice:Person)").unwrap();
+        let (remaining, pattern) = node(&ParseContext::default(), "(alice:Person)").unwrap();
         assert_eq!(pattern.value().identity.0, "alice");
         assert!(pattern.value().labels.contains("Person"));
         assert_eq!(pattern.elements().len(), 0);
@@ -42,7 +56,8 @@ mod tests {
 
     #[test]
     fn test_node_with_properties() {
-        let (remaining, pattern) = node(r#"(alice {name: "Alice"})"#).unwrap();
+        let (remaining, pattern) =
+            node(&ParseContext::default(), r#"(alice {name: "Alice"})"#).unwrap();
         assert_eq!(pattern.value().identity.0, "alice");
         assert_eq!(pattern.value().properties.len(), 1);
         assert_eq!(pattern.elements().len(), 0);
@@ -51,7 +66,11 @@ mod tests {
 
     #[test]
     fn test_node_full() {
-        let (remaining, pattern) = node(r#"(alice:Person {name: "Alice", age: 30})"#).unwrap();
+        let (remaining, pattern) = node(
+            &ParseContext::default(),
+            r#"(alice:Person {name: "Alice", age: 30})"#,
+        )
+        .unwrap();
         assert_eq!(pattern.value().identity.0, "alice");
         assert!(pattern.value().labels.contains("Person"));
         assert_eq!(pattern.value().properties.len(), 2);
@@ -61,7 +80,7 @@ mod tests {
 
     #[test]
     fn test_node_empty() {
-        let (remaining, pattern) = node("()").unwrap();
+        let (remaining, pattern) = node(&ParseContext::default(), "()").unwrap();
         assert_eq!(pattern.value().identity.0, "");
         assert_eq!(pattern.elements().len(), 0);
         assert_eq!(remaining, "");
@@ -69,7 +88,7 @@ mod tests {
 
     #[test]
     fn test_node_with_whitespace() {
-        let (remaining, pattern) = node("(  hello  )").unwrap();
+        let (remaining, pattern) = node(&ParseContext::default(), "(  hello  )").unwrap();
         assert_eq!(pattern.value().identity.0, "hello");
         assert_eq!(remaining, "");
     }
@@ -1,15 +1,21 @@
 //! Relationship and path pattern parsers for gram notation
 
 use super::combinators::ws;
+use super::fold_path_spans;
 use super::node::node;
+use super::subject::{record, subject};
 use super::types::{ArrowType, ParseResult};
+use super::{ParseContext, RESERVED_PROPERTY};
+use crate::direction::{reserved_property, Direction};
+use crate::quantifier::Quantifier;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::char,
-    combinator::{map, value as nom_value},
-    multi::many1,
-    sequence::{delimited, pair, tuple},
+    character::complete::{char, one_of, u64 as count},
+    combinator::{map, opt, peek, value as nom_value},
+    error::{VerboseError, VerboseErrorKind},
+    multi::many0,
+    sequence::{delimited, pair, preceded, tuple},
 };
 use pattern_core::{Pattern, Subject};
 
@@ -46,15 +52,21 @@ pub fn arrow(input: &str) -> ParseResult<'_, ArrowType> {
 /// Parse a relationship: (a)-->(b) or (a)-[subject]->(b)
 /// Relationships have 2 elements (both atomic nodes)
 #[allow(dead_code)]
-pub fn relationship(input: &str) -> ParseResult<'_, Pattern<Subject>> {
-    alt((relationship_with_edge_subject, relationship_simple))(input)
+pub fn relationship<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Pattern<Subject>> {
+    alt((
+        |i| relationship_with_edge_subject(ctx, i),
+        |i| relationship_simple(ctx, i),
+    ))(input)
 }
 
 /// Parse a simple relationship without edge subject: (a)-->(b)
 #[allow(dead_code)]
-fn relationship_simple(input: &str) -> ParseResult<'_, Pattern<Subject>> {
+fn relationship_simple<'a>(
+    ctx: &ParseContext,
+    input: &'a str,
+) -> ParseResult<'a, Pattern<Subject>> {
     map(
-        pair(node, pair(arrow, node)),
+        pair(|i| node(ctx, i), pair(arrow, |i| node(ctx, i))),
         |(left, (arrow_type, right))| {
             // Handle arrow directionality
             let (first, second) = if arrow_type.is_backward() {
@@ -66,11 +78,12 @@ fn relationship_simple(input: &str) -> ParseResult<'_, Pattern<Subject>> {
             };
 
             // Create empty subject for relationship
-            let empty_subject = Subject {
+            let mut empty_subject = Subject {
                 identity: pattern_core::Symbol(String::new()),
                 labels: std::collections::HashSet::new(),
                 properties: std::collections::HashMap::new(),
             };
+            empty_subject.set_direction(Direction::from(arrow_type));
 
             Pattern::pattern(empty_subject, vec![first, second])
         },
@@ -79,27 +92,33 @@ fn relationship_simple(input: &str) -> ParseResult<'_, Pattern<Subject>> {
 
 /// Parse a relationship with edge subject: (a)-[r:LABEL]->(b)
 #[allow(dead_code)]
-fn relationship_with_edge_subject(input: &str) -> ParseResult<'_, Pattern<Subject>> {
-    use super::subject::subject;
-
+fn relationship_with_edge_subject<'a>(
+    ctx: &ParseContext,
+    input: &'a str,
+) -> ParseResult<'a, Pattern<Subject>> {
     map(
         tuple((
-            node,
+            |i| node(ctx, i),
             ws,
             // Arrow left part: -, <-, ~, <~, =, <=, etc.
             arrow_left_part,
             // Edge subject in brackets
-            delimited(char('['), delimited(ws, subject, ws), char(']')),
+            delimited(
+                char('['),
+                delimited(ws, |i| edge_subject(ctx, i), ws),
+                char(']'),
+            ),
             // Arrow right part: ->, -, ~>, ~, =>, =, etc.
             arrow_right_part,
             ws,
-            node,
+            |i| node(ctx, i),
         )),
-        |(left, _, arrow_left, edge_subject, _arrow_right, _, right)| {
+        |(left, _, arrow_left, mut edge_subject, arrow_right, _, right)| {
             // Determine directionality from arrow parts
-            let is_backward = arrow_left.starts_with('<');
+            let arrow_type = determine_arrow_type(arrow_left, arrow_right);
+            edge_subject.set_direction(Direction::from(arrow_type));
 
-            let (first, second) = if is_backward {
+            let (first, second) = if arrow_type.is_backward() {
                 (right, left)
             } else {
                 (left, right)
@@ -110,6 +129,57 @@ fn relationship_with_edge_subject(input: &str) -> ParseResult<'_, Pattern<Subjec
     )(input)
 }
 
+/// Parse the subject inside a relationship bracket: `r:KNOWS*1..3 {since: 2020}`
+///
+/// Like a node subject, with an optional [`Quantifier`] between the labels and
+/// the record, which is recorded with [`Subject::set_quantifier`]. A record
+/// holding a key the arrow or quantifier sets is a failure at the subject.
+fn edge_subject<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Subject> {
+    let (mut rest, mut edge) = subject(ctx, input)?;
+    let mut found = None;
+    if edge.properties.is_empty() {
+        (rest, found) = opt(preceded(ws, quantifier))(rest)?;
+        if found.is_some() {
+            let properties;
+            (rest, properties) = opt(preceded(ws, |i| record(ctx, i)))(rest)?;
+            edge.properties = properties.unwrap_or_default();
+        }
+    }
+    if reserved_property(&edge).is_some() {
+        return Err(nom::Err::Failure(VerboseError {
+            errors: vec![(input, VerboseErrorKind::Context(RESERVED_PROPERTY))],
+        }));
+    }
+    edge.set_quantifier(found);
+    Ok((rest, edge))
+}
+
+/// Parse a hop-count quantifier: `*`, `*3`, `*1..5`, `*2..`, or `*..4`
+///
+/// A range whose minimum is greater than its maximum, such as `*3..1`, is a
+/// failure at the `*`.
+pub fn quantifier(input: &str) -> ParseResult<'_, Quantifier> {
+    let (rest, (min, range)) = preceded(
+        char('*'),
+        pair(opt(count), opt(preceded(tag(".."), opt(count)))),
+    )(input)?;
+    let quantifier = match range {
+        None => Quantifier { min, max: min },
+        Some(max) => Quantifier { min, max },
+    };
+    if let (Some(min), Some(max)) = (quantifier.min, quantifier.max) {
+        if min > max {
+            return Err(nom::Err::Failure(VerboseError {
+                errors: vec![(
+                    input,
+                    VerboseErrorKind::Context("quantifier minimum no greater than its maximum"),
+                )],
+            }));
+        }
+    }
+    Ok((rest, quantifier))
+}
+
 /// Parse left part of arrow: -, <-, ~, <~, =, <=
 fn arrow_left_part(input: &str) -> ParseResult<'_, &str> {
     alt((
@@ -148,33 +218,44 @@ fn arrow_right_part(input: &str) -> ParseResult<'_, &str> {
 
 /// Parse an arrow segment (with or without edge subject)
 /// Returns: (ArrowType, Option<Subject>, Pattern<Subject>)
-fn arrow_segment(input: &str) -> ParseResult<'_, (ArrowType, Option<Subject>, Pattern<Subject>)> {
-    alt((arrow_segment_with_edge, arrow_segment_simple))(input)
+fn arrow_segment<'a>(
+    ctx: &ParseContext,
+    input: &'a str,
+) -> ParseResult<'a, (ArrowType, Option<Subject>, Pattern<Subject>)> {
+    alt((
+        |i| arrow_segment_with_edge(ctx, i),
+        |i| arrow_segment_simple(ctx, i),
+    ))(input)
 }
 
 /// Parse simple arrow segment: --> (node)
-fn arrow_segment_simple(
-    input: &str,
-) -> ParseResult<'_, (ArrowType, Option<Subject>, Pattern<Subject>)> {
-    map(pair(arrow, node), |(arrow_type, node_pattern)| {
-        (arrow_type, None, node_pattern)
-    })(input)
+fn arrow_segment_simple<'a>(
+    ctx: &ParseContext,
+    input: &'a str,
+) -> ParseResult<'a, (ArrowType, Option<Subject>, Pattern<Subject>)> {
+    map(
+        pair(arrow, |i| node(ctx, i)),
+        |(arrow_type, node_pattern)| (arrow_type, None, node_pattern),
+    )(input)
 }
 
 /// Parse arrow segment with edge subject: -[subject]-> (node)
-fn arrow_segment_with_edge(
-    input: &str,
-) -> ParseResult<'_, (ArrowType, Option<Subject>, Pattern<Subject>)> {
-    use super::subject::subject;
-
+fn arrow_segment_with_edge<'a>(
+    ctx: &ParseContext,
+    input: &'a str,
+) -> ParseResult<'a, (ArrowType, Option<Subject>, Pattern<Subject>)> {
     map(
         tuple((
             ws,
             arrow_left_part,
-            delimited(char('['), delimited(ws, subject, ws), char(']')),
+            delimited(
+                char('['),
+                delimited(ws, |i| edge_subject(ctx, i), ws),
+                char(']'),
+            ),
             arrow_right_part,
             ws,
-            node,
+            |i| node(ctx, i),
         )),
         |(_, arrow_left, edge_subject, arrow_right, _, next_node)| {
             // Determine arrow type from parts
@@ -209,12 +290,35 @@ fn determine_arrow_type(left: &str, right: &str) -> ArrowType {
     }
 }
 
-/// Parse a path pattern: (a)-->(b)-->(c) or (a)-[:LABEL]->(b)
-/// Paths are flattened into nested structures from left to right
-pub fn path_pattern(input: &str) -> ParseResult<'_, Pattern<Subject>> {
-    map(pair(node, many1(arrow_segment)), |(first, segments)| {
-        flatten_path_with_edges(first, segments)
-    })(input)
+/// Parse a node, or a path starting with that node: (a)-->(b)-->(c) or
+/// (a)-[r:KNOWS {since: 2020}]->(b)
+///
+/// Paths are flattened into nested structures from left to right. An edge
+/// subject in brackets (identity, labels, and properties, any of which may be
+/// omitted) becomes the value of its relationship pattern; a bare arrow gives
+/// an empty subject.
+///
+/// The leading node is parsed once, and segments are only attempted when an
+/// arrow character follows. Most patterns in a typical file are plain nodes,
+/// so this avoids most of their failed parse attempts.
+pub fn node_or_path<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Pattern<Subject>> {
+    let next_segment = preceded(peek(preceded(ws, one_of("<-~="))), |i| {
+        arrow_segment(ctx, i)
+    });
+    map(
+        pair(|i| node(ctx, i), many0(next_segment)),
+        |(first, segments)| {
+            if segments.is_empty() {
+                first
+            } else {
+                fold_path_spans(
+                    ctx,
+                    segments.iter().map(|(arrow, _, _)| arrow.is_backward()),
+                );
+                flatten_path_with_edges(first, segments)
+            }
+        },
+    )(input)
 }
 
 /// Flatten path segments with optional edge subjects into nested pattern structure
@@ -235,11 +339,12 @@ fn flatten_path_with_edges(
         };
 
         // Use provided edge subject or create empty one
-        let edge_subject = edge_subject_opt.unwrap_or_else(|| Subject {
+        let mut edge_subject = edge_subject_opt.unwrap_or_else(|| Subject {
             identity: pattern_core::Symbol(String::new()),
             labels: std::collections::HashSet::new(),
             properties: std::collections::HashMap::new(),
         });
+        edge_subject.set_direction(Direction::from(arrow_type));
 
         current = Pattern::pattern(edge_subject, vec![left, right]);
     }
@@ -330,7 +435,7 @@ mod tests {
 
     #[test]
     fn test_relationship_simple() {
-        let (remaining, pattern) = relationship("(a)-->(b)").unwrap();
+        let (remaining, pattern) = relationship(&ParseContext::default(), "(a)-->(b)").unwrap();
         assert_eq!(pattern.elements().len(), 2);
         assert_eq!(pattern.elements()[0].value().identity.0, "a");
         assert_eq!(pattern.elements()[1].value().identity.0, "b");
@@ -339,7 +444,7 @@ mod tests {
 
     #[test]
     fn test_relationship_left_arrow() {
-        let (remaining, pattern) = relationship("(a)<--(b)").unwrap();
+        let (remaining, pattern) = relationship(&ParseContext::default(), "(a)<--(b)").unwrap();
         // Left arrow reverses elements
         assert_eq!(pattern.elements().len(), 2);
         assert_eq!(pattern.elements()[0].value().identity.0, "b");
@@ -349,7 +454,8 @@ mod tests {
 
     #[test]
     fn test_relationship_with_labels() {
-        let (remaining, pattern) = relationship("(alice:Person)-->(bob:Person)").unwrap();
+        let (remaining, pattern) =
+            relationship(&ParseContext::default(), "(alice:Person)-->(bob:Person)").unwrap();
         assert_eq!(pattern.elements().len(), 2);
         assert!(pattern.elements()[0].value().labels.contains("Person"));
         assert!(pattern.elements()[1].value().labels.contains("Person"));
@@ -358,7 +464,8 @@ mod tests {
 
     #[test]
     fn test_path_three_nodes() {
-        let (remaining, pattern) = path_pattern("(a)-->(b)-->(c)").unwrap();
+        let (remaining, pattern) =
+            node_or_path(&ParseContext::default(), "(a)-->(b)-->(c)").unwrap();
         assert_eq!(pattern.elements().len(), 2);
 
         // Nested structure: outer pattern has 2 elements
@@ -373,9 +480,59 @@ mod tests {
         assert_eq!(remaining, "");
     }
 
+    #[test]
+    fn test_quantifier_forms() {
+        let q = |min, max| Quantifier { min, max };
+        for (input, expected) in [
+            ("*", q(None, None)),
+            ("*3", q(Some(3), Some(3))),
+            ("*1..5", q(Some(1), Some(5))),
+            ("*..4", q(None, Some(4))),
+            ("*2..", q(Some(2), None)),
+        ] {
+            assert_eq!(quantifier(input).unwrap(), ("", expected), "{}", input);
+        }
+        assert_eq!(quantifier("*2..2").unwrap(), ("", q(Some(2), Some(2))));
+        assert!(matches!(quantifier("*3..1"), Err(nom::Err::Failure(_))));
+    }
+
+    #[test]
+    fn test_relationship_with_quantifier() {
+        let (remaining, pattern) = relationship(
+            &ParseContext::default(),
+            "(a)-[r:KNOWS*1..3 {since: 2020}]->(b)",
+        )
+        .unwrap();
+        assert_eq!(remaining, "");
+        let edge = pattern.value();
+        assert_eq!(edge.identity.0, "r");
+        assert!(edge.labels.contains("KNOWS"));
+        assert_eq!(
+            edge.properties["since"],
+            pattern_core::Value::VInteger(2020)
+        );
+        assert_eq!(
+            edge.quantifier(),
+            Some(Quantifier {
+                min: Some(1),
+                max: Some(3)
+            })
+        );
+
+        let (_, pattern) =
+            node_or_path(&ParseContext::default(), "(a)<-[ * ]-(b)-[:R]->(c)").unwrap();
+        let first = pattern.elements()[0].value();
+        assert_eq!(first.quantifier(), Some(Quantifier::default()));
+        assert_eq!(pattern.value().quantifier(), None);
+
+        // The quantifier comes before the record
+        assert!(relationship(&ParseContext::default(), "(a)-[:R {k: 1} *2]->(b)").is_err());
+    }
+
     #[test]
     fn test_path_with_mixed_arrows() {
-        let (remaining, pattern) = path_pattern("(a)-->(b)<--(c)").unwrap();
+        let (remaining, pattern) =
+            node_or_path(&ParseContext::default(), "(a)-->(b)<--(c)").unwrap();
         assert_eq!(pattern.elements().len(), 2);
         assert_eq!(remaining, "");
     }
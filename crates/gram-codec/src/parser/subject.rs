@@ -1,14 +1,15 @@
 //! Subject parsers for gram notation identifiers, labels, and records
 
-use super::combinators::ws;
+use super::combinators::{comma_list0, ws};
 use super::types::ParseResult;
-use super::value::{identifier, key_name, value_parser};
+use super::value::{identifier, key_name, label_name, unquoted_identifier, value_parser};
+use super::ParseContext;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::char,
-    combinator::{cut, map, opt},
-    multi::{separated_list0, separated_list1},
+    combinator::{cut, map, opt, recognize},
+    multi::separated_list1,
     sequence::{delimited, preceded, tuple},
 };
 use pattern_core::Subject;
@@ -18,23 +19,24 @@ use std::collections::{HashMap, HashSet};
 /// Parse a label: :Label
 #[allow(dead_code)]
 pub fn label(input: &str) -> ParseResult<'_, String> {
-    preceded(char(':'), identifier)(input)
+    preceded(char(':'), label_name)(input)
 }
 
 /// Parse multiple labels: Label1:Label2:Label3 (without leading :)
-#[allow(dead_code)]
 fn labels(input: &str) -> ParseResult<'_, Vec<String>> {
-    separated_list1(char(':'), identifier)(input)
+    separated_list1(char(':'), label_name)(input)
 }
 
 /// Parse a property record: {key: value, key2: value2}
-pub fn record(input: &str) -> ParseResult<'_, HashMap<String, Value>> {
+///
+/// A trailing comma after the last pair is allowed: `{key: value,}`.
+pub fn record<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, HashMap<String, Value>> {
     delimited(
         char('{'),
         delimited(
             ws,
             map(
-                separated_list0(delimited(ws, char(','), ws), property_pair),
+                comma_list0(|i| property_pair(ctx, i)),
                 |pairs: Vec<(String, Value)>| pairs.into_iter().collect::<HashMap<String, Value>>(),
             ),
             ws,
@@ -44,7 +46,7 @@ pub fn record(input: &str) -> ParseResult<'_, HashMap<String, Value>> {
 }
 
 /// Parse a key-value pair: key: value or key :: value (declare)
-fn property_pair(input: &str) -> ParseResult<'_, (String, Value)> {
+fn property_pair<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, (String, Value)> {
     map(
         tuple((
             delimited(ws, key_name, ws),
@@ -52,20 +54,35 @@ fn property_pair(input: &str) -> ParseResult<'_, (String, Value)> {
                 tag("::"), // Declare separator (must come before single :)
                 tag(":"),  // Regular separator
             )),
-            delimited(ws, value_parser, ws),
+            delimited(ws, |i| value_parser(ctx, i), ws),
         )),
         |(key, _separator, value)| (key, value),
     )(input)
 }
 
+/// Parse a subject identity: an identifier, or a `?name` template variable
+/// (kept with its `?`) when `ctx` accepts template variables
+fn identity<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, String> {
+    if ctx.template_variables {
+        alt((
+            map(recognize(preceded(char('?'), unquoted_identifier)), |v| {
+                v.to_string()
+            }),
+            identifier,
+        ))(input)
+    } else {
+        identifier(input)
+    }
+}
+
 /// Parse a subject: identifier:labels {record}
 /// All components are optional, but at least one must be present
-pub fn subject(input: &str) -> ParseResult<'_, Subject> {
+pub fn subject<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Subject> {
     map(
         tuple((
-            opt(identifier),
-            opt(preceded(char(':'), separated_list1(char(':'), identifier))),
-            opt(preceded(ws, record)),
+            opt(|i| identity(ctx, i)),
+            opt(preceded(char(':'), labels)),
+            opt(preceded(ws, |i| record(ctx, i))),
         )),
         |(id, label_list, props)| {
             let identity = id
@@ -108,6 +125,20 @@ mod tests {
         assert_eq!(remaining, "");
     }
 
+    #[test]
+    fn test_label_double_quoted() {
+        let (remaining, lbl) = label(r#":"Has Dash""#).unwrap();
+        assert_eq!(lbl, "Has Dash");
+        assert_eq!(remaining, "");
+    }
+
+    #[test]
+    fn test_labels_mixed_quoting() {
+        let (remaining, lbls) = labels(r#"Person:"Has-Dash":`Role Label`"#).unwrap();
+        assert_eq!(lbls, vec!["Person", "Has-Dash", "Role Label"]);
+        assert_eq!(remaining, "");
+    }
+
     #[test]
     fn test_labels_multiple() {
         let (remaining, lbls) = labels("Person:User").unwrap();
@@ -117,7 +148,8 @@ mod tests {
 
     #[test]
     fn test_property_pair() {
-        let (remaining, (key, val)) = property_pair(r#"name: "Alice""#).unwrap();
+        let (remaining, (key, val)) =
+            property_pair(&ParseContext::default(), r#"name: "Alice""#).unwrap();
         assert_eq!(key, "name");
         match val {
             Value::VString(s) => assert_eq!(s, "Alice"),
@@ -128,7 +160,8 @@ mod tests {
 
     #[test]
     fn test_property_pair_backtick_key() {
-        let (remaining, (key, val)) = property_pair(r#"`title name`: "Alpha""#).unwrap();
+        let (remaining, (key, val)) =
+            property_pair(&ParseContext::default(), r#"`title name`: "Alpha""#).unwrap();
         assert_eq!(key, "title name");
         match val {
             Value::VString(s) => assert_eq!(s, "Alpha"),
@@ -139,7 +172,8 @@ mod tests {
 
     #[test]
     fn test_property_pair_double_quoted_key() {
-        let (remaining, (key, val)) = property_pair(r#""display title": "Beta""#).unwrap();
+        let (remaining, (key, val)) =
+            property_pair(&ParseContext::default(), r#""display title": "Beta""#).unwrap();
         assert_eq!(key, "display title");
         match val {
             Value::VString(s) => assert_eq!(s, "Beta"),
@@ -150,7 +184,8 @@ mod tests {
 
     #[test]
     fn test_record() {
-        let (remaining, rec) = record(r#"{name: "Alice", age: 30}"#).unwrap();
+        let (remaining, rec) =
+            record(&ParseContext::default(), r#"{name: "Alice", age: 30}"#).unwrap();
         assert_eq!(rec.len(), 2);
         assert!(rec.contains_key("name"));
         assert!(rec.contains_key("age"));
@@ -159,14 +194,14 @@ mod tests {
 
     #[test]
     fn test_record_empty() {
-        let (remaining, rec) = record("{}").unwrap();
+        let (remaining, rec) = record(&ParseContext::default(), "{}").unwrap();
         assert_eq!(rec.len(), 0);
         assert_eq!(remaining, "");
     }
 
     #[test]
     fn test_subject_identifier_only() {
-        let (remaining, subj) = subject("hello").unwrap();
+        let (remaining, subj) = subject(&ParseContext::default(), "hello").unwrap();
         assert_eq!(subj.identity.0, "hello");
         assert!(subj.labels.is_empty());
         assert!(subj.properties.is_empty());
@@ -175,7 +210,7 @@ mod tests {
 
     #[test]
     fn test_subject_with_label() {
-        let (remaining, subj) = subject("alice:Person").unwrap();
+        let (remaining, subj) = subject(&ParseContext::default(), "alice:Person").unwrap();
         assert_eq!(subj.identity.0, "alice");
         assert!(subj.labels.contains("Person"));
         assert_eq!(remaining, "");
@@ -183,7 +218,7 @@ mod tests {
 
     #[test]
     fn test_subject_with_multiple_labels() {
-        let (remaining, subj) = subject("alice:Person:User").unwrap();
+        let (remaining, subj) = subject(&ParseContext::default(), "alice:Person:User").unwrap();
         assert_eq!(subj.identity.0, "alice");
         assert!(subj.labels.contains("Person"));
         assert!(subj.labels.contains("User"));
@@ -192,7 +227,11 @@ mod tests {
 
     #[test]
     fn test_subject_with_properties() {
-        let (remaining, subj) = subject(r#"alice {name: "Alice", age: 30}"#).unwrap();
+        let (remaining, subj) = subject(
+            &ParseContext::default(),
+            r#"alice {name: "Alice", age: 30}"#,
+        )
+        .unwrap();
         assert_eq!(subj.identity.0, "alice");
         assert_eq!(subj.properties.len(), 2);
         assert!(subj.properties.contains_key("name"));
@@ -202,7 +241,8 @@ mod tests {
 
     #[test]
     fn test_subject_full() {
-        let (remaining, subj) = subject(r#"alice:Person {name: "Alice"}"#).unwrap();
+        let (remaining, subj) =
+            subject(&ParseContext::default(), r#"alice:Person {name: "Alice"}"#).unwrap();
         assert_eq!(subj.identity.0, "alice");
         assert!(subj.labels.contains("Person"));
         assert_eq!(subj.properties.len(), 1);
@@ -211,7 +251,8 @@ mod tests {
 
     #[test]
     fn test_subject_label_and_props_no_id() {
-        let (remaining, subj) = subject(r#":Person {name: "Alice"}"#).unwrap();
+        let (remaining, subj) =
+            subject(&ParseContext::default(), r#":Person {name: "Alice"}"#).unwrap();
         assert_eq!(subj.identity.0, "");
         assert!(subj.labels.contains("Person"));
         assert_eq!(subj.properties.len(), 1);
@@ -220,7 +261,7 @@ mod tests {
 
     #[test]
     fn test_subject_properties_only() {
-        let (remaining, subj) = subject(r#"{name: "Alice"}"#).unwrap();
+        let (remaining, subj) = subject(&ParseContext::default(), r#"{name: "Alice"}"#).unwrap();
         assert_eq!(subj.identity.0, "");
         assert!(subj.labels.is_empty());
         assert_eq!(subj.properties.len(), 1);
@@ -25,15 +25,28 @@ impl Location {
     }
 
     /// Create a location from a byte offset in the input
+    ///
+    /// Lines end at `\n`. A `\r` directly before a `\n` belongs to the line
+    /// break rather than the line, so `\r\n` and `\n` endings, or a mix of
+    /// both, give the same line and column. An offset inside a `\r\n` pair is
+    /// reported just past the end of the line. Columns count characters from
+    /// 1, so `é` in `(café)` is column 5 even though it starts at byte 4.
     pub fn from_offset(input: &str, offset: usize) -> Self {
         let offset = offset.min(input.len());
         let prefix = &input[..offset];
 
         let line = prefix.matches('\n').count() + 1;
-        let column = prefix
-            .rfind('\n')
-            .map(|pos| offset - pos)
-            .unwrap_or(offset + 1);
+        let line_start = prefix.rfind('\n').map_or(0, |pos| pos + 1);
+        Self::in_line(input, line, line_start, offset)
+    }
+
+    /// Location of `offset`, given the `line` it is on and where that line starts
+    pub(crate) fn in_line(input: &str, line: usize, line_start: usize, offset: usize) -> Self {
+        let prefix = &input[line_start..offset];
+        let mut column = prefix.chars().count() + 1;
+        if prefix.ends_with('\r') && input[offset..].starts_with('\n') {
+            column -= 1;
+        }
 
         Self {
             line,
@@ -59,8 +72,9 @@ impl std::fmt::Display for Location {
 }
 
 /// Represents a span of text in the input
+///
+/// `end` is exclusive: it is the location just past the last character.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(dead_code)]
 pub struct Span {
     pub start: Location,
     pub end: Location,
@@ -68,11 +82,15 @@ pub struct Span {
 
 impl Span {
     /// Create a new span
-    #[allow(dead_code)]
     pub fn new(start: Location, end: Location) -> Self {
         Self { start, end }
     }
 
+    /// Whether the byte `offset` falls within this span
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start.offset <= offset && offset < self.end.offset
+    }
+
     /// Create a span for a single location (zero-width span)
     #[allow(dead_code)]
     pub fn single(location: Location) -> Self {
@@ -147,7 +165,6 @@ impl ArrowType {
     }
 
     /// Returns true if arrow is undirected
-    #[allow(dead_code)]
     pub fn is_undirected(&self) -> bool {
         matches!(
             self,
@@ -203,6 +220,38 @@ mod tests {
         assert_eq!(loc.column, 3);
     }
 
+    #[test]
+    fn test_location_from_offset_crlf() {
+        let lf = "(a)\n(b {k: 1})\n(c";
+        let crlf = "(a)\r\n(b {k: 1})\r\n(c";
+        let mixed = "(a)\r\n(b {k: 1})\n(c";
+
+        // Same position in each: `k` on line 2, `c` on line 3
+        for input in [lf, crlf, mixed] {
+            let loc = Location::from_offset(input, input.find('k').unwrap());
+            assert_eq!((loc.line, loc.column), (2, 5), "{:?}", input);
+            let loc = Location::from_offset(input, input.find('c').unwrap());
+            assert_eq!((loc.line, loc.column), (3, 2), "{:?}", input);
+        }
+
+        // Both bytes of `\r\n` are just past the end of line 1
+        for offset in [3, 4] {
+            let loc = Location::from_offset(crlf, offset);
+            assert_eq!((loc.line, loc.column), (1, 4));
+        }
+        assert_eq!(Location::from_offset(crlf, 5).line, 2);
+        assert_eq!(Location::from_offset(crlf, 5).column, 1);
+    }
+
+    #[test]
+    fn test_location_from_offset_multibyte() {
+        let input = "(café)\n(日本 {k: 1})";
+        let loc = Location::from_offset(input, input.find(')').unwrap());
+        assert_eq!((loc.line, loc.column, loc.offset), (1, 6, 6));
+        let loc = Location::from_offset(input, input.find('k').unwrap());
+        assert_eq!((loc.line, loc.column), (2, 6));
+    }
+
     #[test]
     fn test_arrow_type_predicates() {
         assert!(ArrowType::Right.is_forward());
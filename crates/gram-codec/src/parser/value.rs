@@ -1,17 +1,17 @@
 //! Value parsers for gram notation property values
 
-use super::combinators::ws;
+use super::combinators::{comma_list0, ws};
 use super::error::ParseError;
 use super::types::ParseResult;
+use super::{nested, ParseContext};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while, take_while1},
-    character::complete::{char, digit1},
-    combinator::{cut, map, map_res, opt, recognize, value as nom_value},
+    character::complete::{char, digit1, one_of, satisfy},
+    combinator::{cut, map, map_res, not, opt, peek, recognize, value as nom_value},
     error::VerboseError,
-    multi::{many0, separated_list0},
     number::complete::double,
-    sequence::{delimited, pair, separated_pair, tuple},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
 };
 use pattern_core::{RangeValue, Value};
 
@@ -25,6 +25,15 @@ pub fn identifier(input: &str) -> ParseResult<'_, String> {
     ))(input)
 }
 
+/// Parse a label name: an identifier or a double-quoted name
+///
+/// Labels accept the same forms as identifiers plus `"..."`, so `:"Has Dash"`
+/// and `` :`Has Dash` `` parse to the same label. The serializer always writes
+/// backticks.
+pub fn label_name(input: &str) -> ParseResult<'_, String> {
+    alt((double_quoted_string, identifier))(input)
+}
+
 /// Parse a property or map key name: symbol, backtick-quoted name, or double-quoted name
 /// Per grammar: _key_name = symbol | quoted_name | double_quoted_name
 pub fn key_name(input: &str) -> ParseResult<'_, String> {
@@ -64,74 +73,88 @@ pub fn string_value(input: &str) -> ParseResult<'_, String> {
 
 /// Parse a double-quoted string: "text"
 fn double_quoted_string(input: &str) -> ParseResult<'_, String> {
-    delimited(
-        char('"'),
-        map(
-            recognize(many0(alt((
-                nom_value((), tag("\\\\")), // \\
-                nom_value((), tag("\\\"")), // \"
-                nom_value((), tag("\\\'")), // \'
-                nom_value((), tag("\\`")),  // \`
-                nom_value((), tag("\\/")),  // \/
-                nom_value((), tag("\\n")),  // \n
-                nom_value((), tag("\\r")),  // \r
-                nom_value((), tag("\\t")),  // \t
-                nom_value((), tag("\\b")),  // \b
-                nom_value((), tag("\\f")),  // \f
-                nom_value((), take_while1(|c| c != '\\' && c != '"')),
-            )))),
-            |s: &str| unescape_string(s),
-        ),
-        cut(char('"')),
-    )(input)
+    quoted_string(input, '"')
 }
 
 /// Parse a single-quoted string: 'text'
 fn single_quoted_string(input: &str) -> ParseResult<'_, String> {
-    delimited(
-        char('\''),
-        map(
-            recognize(many0(alt((
-                nom_value((), tag("\\\\")), // \\
-                nom_value((), tag("\\\"")), // \"
-                nom_value((), tag("\\\'")), // \'
-                nom_value((), tag("\\`")),  // \`
-                nom_value((), tag("\\/")),  // \/
-                nom_value((), tag("\\n")),  // \n
-                nom_value((), tag("\\r")),  // \r
-                nom_value((), tag("\\t")),  // \t
-                nom_value((), tag("\\b")),  // \b
-                nom_value((), tag("\\f")),  // \f
-                nom_value((), take_while1(|c| c != '\\' && c != '\'')),
-            )))),
-            |s: &str| unescape_string(s),
-        ),
-        cut(char('\'')),
-    )(input)
+    quoted_string(input, '\'')
 }
 
 /// Parse a backtick-quoted string: `text`
 fn backtick_quoted_string(input: &str) -> ParseResult<'_, String> {
-    delimited(
-        char('`'),
-        map(
-            recognize(many0(alt((
-                nom_value((), tag("\\\\")), // \\
-                nom_value((), tag("\\\"")), // \"
-                nom_value((), tag("\\\'")), // \'
-                nom_value((), tag("\\`")),  // \`
-                nom_value((), tag("\\/")),  // \/
-                nom_value((), tag("\\n")),  // \n
-                nom_value((), tag("\\r")),  // \r
-                nom_value((), tag("\\t")),  // \t
-                nom_value((), tag("\\b")),  // \b
-                nom_value((), tag("\\f")),  // \f
-                nom_value((), take_while1(|c| c != '\\' && c != '`')),
-            )))),
-            |s: &str| unescape_string(s),
-        ),
-        cut(char('`')),
-    )(input)
+    quoted_string(input, '`')
+}
+
+/// Parse a string delimited by `quote`, decoding escape sequences
+///
+/// Supported escapes: `\\`, `\"`, `\'`, `` \` ``, `\/`, `\n`, `\r`, `\t`,
+/// `\b`, `\f`, and `\u{XXXX}` with one to six hex digits naming a Unicode
+/// scalar value. Any other escape is a failure located at its backslash, as is
+/// a missing closing quote.
+fn quoted_string(input: &str, quote: char) -> ParseResult<'_, String> {
+    let (mut remaining, _) = char(quote)(input)?;
+    let mut content = String::new();
+
+    loop {
+        let mut chars = remaining.chars();
+        match chars.next() {
+            Some(c) if c == quote => return Ok((chars.as_str(), content)),
+            Some('\\') => {
+                let (rest, decoded) = escape_sequence(remaining)?;
+                content.push(decoded);
+                remaining = rest;
+            }
+            Some(c) => {
+                content.push(c);
+                remaining = chars.as_str();
+            }
+            None => {
+                // EOF without closing quote
+                return Err(nom::Err::Failure(VerboseError {
+                    errors: vec![(remaining, nom::error::VerboseErrorKind::Char(quote))],
+                }));
+            }
+        }
+    }
+}
+
+/// Decode one escape sequence starting at its backslash
+fn escape_sequence(input: &str) -> ParseResult<'_, char> {
+    let invalid = || {
+        nom::Err::Failure(VerboseError {
+            errors: vec![(
+                input,
+                nom::error::VerboseErrorKind::Context("valid escape sequence"),
+            )],
+        })
+    };
+
+    let mut chars = input[1..].chars();
+    let decoded = match chars.next() {
+        Some('n') => '\n',
+        Some('r') => '\r',
+        Some('t') => '\t',
+        Some('b') => '\u{0008}',
+        Some('f') => '\u{000C}',
+        Some(c @ ('\\' | '"' | '\'' | '`' | '/')) => c,
+        Some('u') => {
+            let rest = chars.as_str();
+            let digits = rest
+                .strip_prefix('{')
+                .and_then(|r| r.find('}').map(|end| &r[..end]))
+                .filter(|d| (1..=6).contains(&d.len()) && d.chars().all(|c| c.is_ascii_hexdigit()))
+                .ok_or_else(invalid)?;
+            let c = u32::from_str_radix(digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(invalid)?;
+            // Skip `{`, the digits, and `}`
+            return Ok((&rest[digits.len() + 2..], c));
+        }
+        _ => return Err(invalid()),
+    };
+    Ok((chars.as_str(), decoded))
 }
 
 /// Parse a fenced string: ```text``` or ```tag\ntext```
@@ -179,57 +202,55 @@ fn fenced_string(input: &str) -> ParseResult<'_, String> {
     }
 }
 
-/// Unescape string escape sequences
-fn unescape_string(s: &str) -> String {
-    let mut result = String::new();
-    let mut chars = s.chars();
-
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            match chars.next() {
-                Some('n') => result.push('\n'),
-                Some('r') => result.push('\r'),
-                Some('t') => result.push('\t'),
-                Some('\\') => result.push('\\'),
-                Some('"') => result.push('"'),
-                Some('\'') => result.push('\''),
-                Some('`') => result.push('`'),
-                Some('/') => result.push('/'),
-                Some('b') => result.push('\u{0008}'),
-                Some('f') => result.push('\u{000C}'),
-                Some(c) => {
-                    result.push('\\');
-                    result.push(c);
-                }
-                None => result.push('\\'),
-            }
-        } else {
-            result.push(c);
-        }
-    }
+/// Parse a hexadecimal integer: `0xFF`, `0XFF`, `-0x10`
+///
+/// Fails if the value does not fit an `i64`.
+pub fn hex_integer(input: &str) -> ParseResult<'_, i64> {
+    map_res(
+        pair(
+            opt(char('-')),
+            preceded(
+                alt((tag("0x"), tag("0X"))),
+                take_while1(|c: char| c.is_ascii_hexdigit()),
+            ),
+        ),
+        |(sign, digits): (Option<char>, &str)| match sign {
+            Some(_) => i64::from_str_radix(&format!("-{}", digits), 16),
+            None => i64::from_str_radix(digits, 16),
+        },
+    )(input)
+}
 
-    result
+/// Parse a decimal in scientific notation: `1e3`, `6.022E23`, `-2.5e-3`
+///
+/// The exponent is required; plain decimals are parsed by [`number`]. A value
+/// too large for an `f64`, such as `1e400`, is a failure rather than infinity,
+/// which has no gram notation to be written back as.
+pub fn scientific(input: &str) -> ParseResult<'_, f64> {
+    let (rest, text) = recognize(tuple((
+        opt(char('-')),
+        digit1,
+        opt(pair(char('.'), digit1)),
+        one_of("eE"),
+        opt(one_of("+-")),
+        digit1,
+    )))(input)?;
+    match text.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok((rest, value)),
+        _ => Err(nom::Err::Failure(VerboseError {
+            errors: vec![(
+                input,
+                nom::error::VerboseErrorKind::Context("decimal within the f64 range"),
+            )],
+        })),
+    }
 }
 
 /// Parse an integer value (decimal or hexadecimal)
 pub fn integer(input: &str) -> ParseResult<'_, i64> {
     alt((
         // Hexadecimal: 0xABCD
-        map_res(
-            recognize(tuple((
-                opt(char('-')),
-                tag("0x"),
-                take_while1(|c: char| c.is_ascii_hexdigit()),
-            ))),
-            |s: &str| {
-                let s = s
-                    .strip_prefix('-')
-                    .map(|rest| (true, rest))
-                    .unwrap_or((false, s));
-                let hex_part = s.1.strip_prefix("0x").unwrap();
-                i64::from_str_radix(hex_part, 16).map(|n| if s.0 { -n } else { n })
-            },
-        ),
+        hex_integer,
         // Decimal: 123
         map_res(recognize(pair(opt(char('-')), digit1)), |s: &str| {
             s.parse::<i64>()
@@ -284,16 +305,33 @@ pub fn boolean(input: &str) -> ParseResult<'_, bool> {
     alt((nom_value(true, tag("true")), nom_value(false, tag("false"))))(input)
 }
 
+/// Parse the null literal: `null`, but not a symbol such as `nullable`
+pub fn null(input: &str) -> ParseResult<'_, ()> {
+    nom_value(
+        (),
+        terminated(
+            tag("null"),
+            not(satisfy(|c: char| {
+                c.is_alphanumeric() || matches!(c, '_' | '-' | '@' | '.')
+            })),
+        ),
+    )(input)
+}
+
 /// Parse an array of values: [value1, value2, ...]
-pub fn array(input: &str) -> ParseResult<'_, Vec<Value>> {
-    delimited(
+///
+/// Elements may be any value, including nested arrays and maps, each one a
+/// level deeper under `ctx`'s depth limit. A trailing comma is allowed.
+pub fn array<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Vec<Value>> {
+    preceded(
         char('['),
-        delimited(
-            ws,
-            separated_list0(delimited(ws, char(','), ws), value_parser),
-            ws,
+        nested(
+            ctx,
+            terminated(
+                delimited(ws, comma_list0(|i| value_parser(ctx, i)), ws),
+                cut(char(']')),
+            ),
         ),
-        cut(char(']')),
     )(input)
 }
 
@@ -430,24 +468,27 @@ fn tagged_triple_quoted(input: &str) -> ParseResult<'_, (String, String)> {
 }
 
 /// Parse a map: { key: value, key2: value2 }
-/// Same syntax as records, but used in value context
-fn map_value(input: &str) -> ParseResult<'_, Value> {
+/// Same syntax as records, including a trailing comma, but used in value context;
+/// the values are a level deeper under `ctx`'s depth limit
+fn map_value<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Value> {
     map(
-        delimited(
+        preceded(
             char('{'),
-            delimited(
-                ws,
-                separated_list0(
-                    delimited(ws, char(','), ws),
-                    separated_pair(
-                        delimited(ws, key_name, ws),
-                        char(':'),
-                        value_parser, // Recursive call for nested values
+            nested(
+                ctx,
+                terminated(
+                    delimited(
+                        ws,
+                        comma_list0(separated_pair(
+                            delimited(ws, key_name, ws),
+                            char(':'),
+                            |i| value_parser(ctx, i), // Recursive call for nested values
+                        )),
+                        ws,
                     ),
+                    cut(char('}')),
                 ),
-                ws,
             ),
-            char('}'),
         ),
         |pairs| {
             let mut map = std::collections::HashMap::new();
@@ -460,7 +501,10 @@ fn map_value(input: &str) -> ParseResult<'_, Value> {
 }
 
 /// Parse any value type
-pub fn value_parser(input: &str) -> ParseResult<'_, Value> {
+///
+/// Arrays and maps nest under `ctx`'s depth limit, so a value nested too
+/// deeply fails like a pattern nested too deeply.
+pub fn value_parser<'a>(ctx: &ParseContext, input: &'a str) -> ParseResult<'a, Value> {
     delimited(
         ws,
         alt((
@@ -472,17 +516,27 @@ pub fn value_parser(input: &str) -> ParseResult<'_, Value> {
             // String (quoted)
             map(string_value, Value::VString),
             // Map (before array, since both use braces/brackets)
-            map_value,
+            |i| map_value(ctx, i),
             // Range (before number, since it contains ..)
             map(range, Value::VRange),
+            // Hexadecimal and scientific notation (before measurement, which
+            // would read `0xFF` as 0 with unit `xFF`, and `1e3` as 1 with unit
+            // `e`). A `0x` prefix commits to hexadecimal.
+            preceded(
+                peek(tuple((opt(char('-')), char('0'), one_of("xX")))),
+                cut(map(hex_integer, Value::VInteger)),
+            ),
+            map(scientific, Value::VDecimal),
             // Measurement (before number, since it's number + letters)
             measurement,
             // Number (integer or decimal)
             number,
             // Boolean
             map(boolean, Value::VBoolean),
+            // Null (before symbol, which would read it as `VSymbol("null")`)
+            map(null, |_| Value::VNull),
             // Array
-            map(array, Value::VArray),
+            map(|i| array(ctx, i), Value::VArray),
             // Unquoted symbol (last, most permissive)
             map(unquoted_identifier, Value::VSymbol),
         )),
@@ -577,6 +631,35 @@ mod tests {
         assert_eq!(remaining, "");
     }
 
+    #[test]
+    fn test_string_escapes() {
+        assert_eq!(
+            string_value(r#""line1\nline2\t\\\"""#).unwrap(),
+            ("", "line1\nline2\t\\\"".to_string())
+        );
+        assert_eq!(
+            string_value(r#""\u{1F600}""#).unwrap(),
+            ("", "\u{1F600}".to_string())
+        );
+        assert_eq!(string_value(r"'\u{e9}'").unwrap(), ("", "é".to_string()));
+        assert_eq!(string_value(r"`a\`b`").unwrap(), ("", "a`b".to_string()));
+
+        // Unknown escapes, malformed or out-of-range code points fail at the backslash
+        for input in [
+            r#""\q""#,
+            r#""\u1F600""#,
+            r#""\u{}""#,
+            r#""\u{1234567}""#,
+            r#""\u{D800}""#,
+            r#""\u{110000}""#,
+        ] {
+            match string_value(input) {
+                Err(nom::Err::Failure(e)) => assert_eq!(e.errors[0].0, &input[1..], "{}", input),
+                other => panic!("{}: unexpected result {:?}", input, other),
+            }
+        }
+    }
+
     #[test]
     fn test_integer() {
         let (remaining, n) = integer("42").unwrap();
@@ -588,6 +671,87 @@ mod tests {
         assert_eq!(remaining, "");
     }
 
+    #[test]
+    fn test_hex_integer() {
+        assert_eq!(integer("0xFF").unwrap(), ("", 255));
+        assert_eq!(integer("0Xff").unwrap(), ("", 255));
+        assert_eq!(integer("-0x10").unwrap(), ("", -16));
+        assert_eq!(hex_integer("-0x8000000000000000").unwrap(), ("", i64::MIN));
+        assert!(hex_integer("0x8000000000000000").is_err());
+        assert!(hex_integer("0x").is_err());
+    }
+
+    #[test]
+    fn test_scientific() {
+        assert_eq!(scientific("1e3").unwrap(), ("", 1000.0));
+        assert_eq!(scientific("1.5e10").unwrap(), ("", 1.5e10));
+        assert_eq!(scientific("6.022E23").unwrap(), ("", 6.022e23));
+        assert_eq!(scientific("2.5e-3").unwrap(), ("", 2.5e-3));
+        assert_eq!(scientific("-1e+2").unwrap(), ("", -100.0));
+        assert!(scientific("1.5").is_err());
+        assert!(scientific("1e").is_err());
+        // Exponents that overflow are failures, not infinities
+        assert!(matches!(scientific("1e400"), Err(nom::Err::Failure(_))));
+        assert!(matches!(scientific("-1e400"), Err(nom::Err::Failure(_))));
+        assert_eq!(scientific("1e-400").unwrap(), ("", 0.0));
+    }
+
+    #[test]
+    fn test_value_parser_numbers() {
+        for (input, expected) in [
+            ("1e3", Value::VDecimal(1000.0)),
+            ("2.5e-3", Value::VDecimal(0.0025)),
+            ("-6.022e23", Value::VDecimal(-6.022e23)),
+            ("0xFF", Value::VInteger(255)),
+            ("-0x1f", Value::VInteger(-31)),
+            ("1.5", Value::VDecimal(1.5)),
+            ("42", Value::VInteger(42)),
+        ] {
+            assert_eq!(
+                value_parser(&ParseContext::default(), input).unwrap(),
+                ("", expected),
+                "{}",
+                input
+            );
+        }
+
+        // Units still follow plain numbers
+        assert_eq!(
+            value_parser(&ParseContext::default(), "3em").unwrap().1,
+            Value::VMeasurement {
+                unit: "em".to_string(),
+                value: 3.0
+            }
+        );
+        // A `0x` prefix without hex digits is an error, not a measurement
+        assert!(matches!(
+            value_parser(&ParseContext::default(), "0xZZ"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(matches!(
+            value_parser(&ParseContext::default(), "0xFFFFFFFFFFFFFFFFFF"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn test_value_parser_null() {
+        assert_eq!(
+            value_parser(&ParseContext::default(), "null").unwrap(),
+            ("", Value::VNull)
+        );
+        assert_eq!(
+            value_parser(&ParseContext::default(), "null, x").unwrap(),
+            (", x", Value::VNull)
+        );
+        for symbol in ["nullable", "null_", "null-x", "null.x"] {
+            assert_eq!(
+                value_parser(&ParseContext::default(), symbol).unwrap().1,
+                Value::VSymbol(symbol.to_string())
+            );
+        }
+    }
+
     #[test]
     fn test_decimal() {
         let (remaining, n) = decimal("3.14").unwrap();
@@ -612,7 +776,7 @@ mod tests {
 
     #[test]
     fn test_array() {
-        let (remaining, arr) = array(r#"["hello", 42, true]"#).unwrap();
+        let (remaining, arr) = array(&ParseContext::default(), r#"["hello", 42, true]"#).unwrap();
         assert_eq!(arr.len(), 3);
         assert_eq!(remaining, "");
 
@@ -673,7 +837,7 @@ mod tests {
 
     #[test]
     fn test_value_parser_string() {
-        let (remaining, val) = value_parser(r#""hello""#).unwrap();
+        let (remaining, val) = value_parser(&ParseContext::default(), r#""hello""#).unwrap();
         match val {
             Value::VString(s) => assert_eq!(s, "hello"),
             _ => panic!("Expected string"),
@@ -683,7 +847,7 @@ mod tests {
 
     #[test]
     fn test_value_parser_integer() {
-        let (remaining, val) = value_parser("42").unwrap();
+        let (remaining, val) = value_parser(&ParseContext::default(), "42").unwrap();
         match val {
             Value::VInteger(n) => assert_eq!(n, 42),
             _ => panic!("Expected integer"),
@@ -693,7 +857,7 @@ mod tests {
 
     #[test]
     fn test_value_parser_boolean() {
-        let (remaining, val) = value_parser("true").unwrap();
+        let (remaining, val) = value_parser(&ParseContext::default(), "true").unwrap();
         match val {
             Value::VBoolean(b) => assert_eq!(b, true),
             _ => panic!("Expected boolean"),
@@ -703,7 +867,7 @@ mod tests {
 
     #[test]
     fn test_value_parser_array() {
-        let (remaining, val) = value_parser(r#"[1, 2, 3]"#).unwrap();
+        let (remaining, val) = value_parser(&ParseContext::default(), r#"[1, 2, 3]"#).unwrap();
         match val {
             Value::VArray(arr) => assert_eq!(arr.len(), 3),
             _ => panic!("Expected array"),
@@ -713,7 +877,7 @@ mod tests {
 
     #[test]
     fn test_value_parser_range() {
-        let (remaining, val) = value_parser("0..100").unwrap();
+        let (remaining, val) = value_parser(&ParseContext::default(), "0..100").unwrap();
         match val {
             Value::VRange(range) => {
                 assert_eq!(range.lower, Some(0.0));
@@ -0,0 +1,113 @@
+//! Data profiling over parsed patterns
+//!
+//! Summaries that answer "what is in this data?" without writing a traversal,
+//! such as how often each value of a property occurs.
+//!
+//! ```rust
+//! use gram_codec::{parse_gram, value_histogram};
+//! use pattern_core::Value;
+//!
+//! let patterns = parse_gram(
+//!     "(a {country: 'NL'}) (b {country: 'NL'}) (c {country: 'BE'}) (a)-->(c)",
+//! )
+//! .unwrap();
+//! let counts = value_histogram(&patterns, "country");
+//! assert_eq!(counts[&Value::VString("NL".to_string())], 2);
+//! assert_eq!(counts[&Value::VString("BE".to_string())], 1);
+//! ```
+
+use pattern_core::{Pattern, StandardGraph, Subject, Value};
+use std::collections::HashMap;
+
+/// Count how many nodes have each value of property `key`
+///
+/// Nodes are collected as in [`StandardGraph::from_patterns`]: every node
+/// occurrence, including relationship endpoints and elements of walks,
+/// annotations, and subject patterns, is merged by identity, so a node
+/// referenced several times is counted once, with the properties of all its
+/// occurrences combined (later values win). Anonymous nodes are each counted.
+/// Nodes without `key` are not counted.
+///
+/// Values are bucketed by `Value` equality, so `VString("x")` and
+/// `VSymbol("x")` are different buckets. Decimals are bucketed by exact value:
+/// `0.0` and `-0.0` share a bucket, as do all NaNs, but values that differ
+/// only by rounding do not (see [`Value`]). Relationships, walks, and
+/// annotations themselves are not counted.
+///
+/// # Arguments
+///
+/// * `patterns` - Parsed patterns to profile
+/// * `key` - Property to count values of
+///
+/// # Returns
+///
+/// A map from each value of `key` to the number of nodes holding it
+pub fn value_histogram(patterns: &[Pattern<Subject>], key: &str) -> HashMap<Value, usize> {
+    let graph = StandardGraph::from_patterns(patterns.iter().cloned());
+    let mut counts = HashMap::new();
+    for (_, node) in graph.nodes() {
+        if let Some(value) = node.value.properties.get(key) {
+            *counts.entry(value.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_gram;
+
+    fn string(s: &str) -> Value {
+        Value::VString(s.to_string())
+    }
+
+    #[test]
+    fn test_counts_each_node_once() {
+        let patterns = parse_gram(
+            "(a:Person {country: 'NL'})-->(b {country: 'BE'}) \
+             (a)-->(c {country: 'NL'}) \
+             [g | (a), (d {country: 'BE'})] \
+             (e {other: 1})",
+        )
+        .unwrap();
+        let counts = value_histogram(&patterns, "country");
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&string("NL")], 2);
+        assert_eq!(counts[&string("BE")], 2);
+        assert!(value_histogram(&patterns, "missing").is_empty());
+    }
+
+    #[test]
+    fn test_value_kinds_are_separate_buckets() {
+        let patterns = parse_gram(
+            "(a {v: 1}) (b {v: 1.0}) (c {v: -0.0}) (d {v: 0.0}) (e {v: 'x'}) (f {v: x}) \
+             (g {v: [1, 2]}) (h {v: [1, 2]}) () ()",
+        )
+        .unwrap();
+        let counts = value_histogram(&patterns, "v");
+        assert_eq!(counts[&Value::VInteger(1)], 1);
+        assert_eq!(counts[&Value::VDecimal(1.0)], 1);
+        assert_eq!(counts[&Value::VDecimal(0.0)], 2);
+        assert_eq!(counts[&string("x")], 1);
+        assert_eq!(counts[&Value::VSymbol("x".to_string())], 1);
+        assert_eq!(
+            counts[&Value::VArray(vec![Value::VInteger(1), Value::VInteger(2)])],
+            2
+        );
+    }
+
+    #[test]
+    fn test_nan_values_share_a_bucket() {
+        let mut patterns = parse_gram("(a {v: 1.5}) (b {v: 1.5}) (c {v: 1.5})").unwrap();
+        for (p, nan) in patterns[1..].iter_mut().zip([f64::NAN, -f64::NAN]) {
+            p.value
+                .properties
+                .insert("v".to_string(), Value::VDecimal(nan));
+        }
+        let counts = value_histogram(&patterns, "v");
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&Value::VDecimal(1.5)], 1);
+        assert_eq!(counts[&Value::VDecimal(f64::NAN)], 2);
+    }
+}
@@ -251,6 +251,19 @@ fn parse_patterns_as_dicts(py: Python, input: &str) -> PyResult<PyObject> {
     loads.call1((json_array,)).map(|obj| obj.into())
 }
 
+/// Convert a gram `ParseError` into a Python `ValueError`.
+///
+/// The exception carries `line` and `column` attributes (1-indexed) giving
+/// the location where parsing failed, or `None` when the error has none.
+fn parse_error_to_py(py: Python, e: &crate::ParseError) -> PyErr {
+    let err = PyValueError::new_err(format!("Parse error: {}", e));
+    let value = err.value(py);
+    let location = e.location();
+    let _ = value.setattr("line", location.map(|l| l.line));
+    let _ = value.setattr("column", location.map(|l| l.column));
+    err
+}
+
 /// Parse gram notation, returning a Python list of AstPattern dicts.
 ///
 /// Each dict has structure `{"subject": {"identity": str, "labels": [...],
@@ -263,14 +276,13 @@ fn parse_patterns_as_dicts(py: Python, input: &str) -> PyResult<PyObject> {
 ///     list[dict]: One dict per top-level pattern
 ///
 /// Raises:
-///     ValueError: If parsing fails
+///     ValueError: If parsing fails; `line` and `column` give the location
 #[pyfunction(name = "parse")]
 fn parse_py(py: Python, input: &str) -> PyResult<PyObject> {
     if input.trim().is_empty() {
         return Ok(pyo3::types::PyList::empty(py).into());
     }
-    let patterns = crate::parse_gram(input)
-        .map_err(|e| PyValueError::new_err(format!("Parse error: {}", e)))?;
+    let patterns = crate::parse_gram(input).map_err(|e| parse_error_to_py(py, &e))?;
     let asts: Vec<AstPattern> = patterns.iter().map(AstPattern::from_pattern).collect();
     pythonize::pythonize(py, &asts)
         .map(|b| b.unbind())
@@ -300,6 +312,27 @@ fn stringify_py(py: Python, patterns_obj: Bound<'_, PyAny>) -> PyResult<String>
     crate::to_gram(&patterns).map_err(|e| PyValueError::new_err(format!("Stringify error: {}", e)))
 }
 
+/// Serialize a single AstPattern dict to gram notation.
+///
+/// Args:
+///     pattern_obj: AstPattern dict
+///
+/// Returns:
+///     str: Gram notation string
+///
+/// Raises:
+///     ValueError: If deserialization or serialization fails
+#[pyfunction(name = "serialize")]
+fn serialize_py(pattern_obj: Bound<'_, PyAny>) -> PyResult<String> {
+    let ast: AstPattern = pythonize::depythonize(&pattern_obj)
+        .map_err(|e| PyValueError::new_err(format!("Deserialization error: {}", e)))?;
+    let pattern = ast
+        .to_pattern()
+        .map_err(|e| PyValueError::new_err(format!("Conversion error: {}", e)))?;
+    crate::to_gram_pattern(&pattern)
+        .map_err(|e| PyValueError::new_err(format!("Serialize error: {}", e)))
+}
+
 /// Parse gram notation, returning `{"header": dict | None, "patterns": [...]}`
 ///
 /// Args:
@@ -309,11 +342,11 @@ fn stringify_py(py: Python, patterns_obj: Bound<'_, PyAny>) -> PyResult<String>
 ///     dict: `{"header": dict | None, "patterns": list[dict]}`
 ///
 /// Raises:
-///     ValueError: If parsing fails
+///     ValueError: If parsing fails; `line` and `column` give the location
 #[pyfunction(name = "parse_with_header")]
 fn parse_with_header_py(py: Python, input: &str) -> PyResult<PyObject> {
-    let (header, patterns) = crate::parse_gram_with_header(input)
-        .map_err(|e| PyValueError::new_err(format!("Parse error: {}", e)))?;
+    let (header, patterns) =
+        crate::parse_gram_with_header(input).map_err(|e| parse_error_to_py(py, &e))?;
     let result = ParseWithHeaderResult::from_parts(header, patterns);
     pythonize::pythonize(py, &result)
         .map(|b| b.unbind())
@@ -370,6 +403,7 @@ fn gram_codec(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // New pythonize-based FFI functions (canonical names)
     m.add_function(wrap_pyfunction!(parse_py, m)?)?;
     m.add_function(wrap_pyfunction!(stringify_py, m)?)?;
+    m.add_function(wrap_pyfunction!(serialize_py, m)?)?;
     m.add_function(wrap_pyfunction!(parse_with_header_py, m)?)?;
     m.add_function(wrap_pyfunction!(stringify_with_header_py, m)?)?;
     m.add_function(wrap_pyfunction!(gram_validate_py, m)?)?;
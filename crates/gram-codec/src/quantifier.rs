@@ -0,0 +1,34 @@
+//! Variable-length relationships
+//!
+//! A relationship bracket may carry a hop-count quantifier after its labels,
+//! as in graph query languages: `-[:KNOWS*1..3]->` matches paths of one to
+//! three `KNOWS` relationships. The parser records it on the relationship's
+//! subject under [`QUANTIFIER_PROPERTY`](pattern_core::QUANTIFIER_PROPERTY),
+//! where [`Subject::quantifier`](pattern_core::Subject::quantifier) reads it,
+//! and serialization writes it back into the bracket.
+//!
+//! ```rust
+//! use gram_codec::{parse_gram, to_gram, Quantifier};
+//!
+//! let patterns = parse_gram("(a)-[:KNOWS*2..]->(b)").unwrap();
+//! let quantifier = patterns[0].value.quantifier();
+//! assert_eq!(quantifier, Some(Quantifier { min: Some(2), max: None }));
+//! assert_eq!(to_gram(&patterns).unwrap(), "(a)-[:KNOWS*2..]->(b)");
+//! ```
+
+pub use pattern_core::Quantifier;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let q = |min, max| Quantifier { min, max }.to_string();
+        assert_eq!(q(None, None), "*");
+        assert_eq!(q(Some(3), Some(3)), "*3");
+        assert_eq!(q(Some(1), Some(5)), "*1..5");
+        assert_eq!(q(Some(2), None), "*2..");
+        assert_eq!(q(None, Some(4)), "*..4");
+    }
+}
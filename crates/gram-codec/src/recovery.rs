@@ -0,0 +1,257 @@
+//! Lossy parsing with error recovery
+//!
+//! [`parse_gram_lossy`] never fails outright. Each top-level statement that
+//! cannot be parsed is replaced by an error pattern holding the raw text, and
+//! parsing resumes at the next top-level boundary. Editors can use the result
+//! to keep outlines, highlighting, and navigation working while a document is
+//! being typed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use gram_codec::recovery::{is_error_pattern, parse_gram_lossy};
+//!
+//! let (patterns, errors) = parse_gram_lossy("(a)\n(b {k: })\n(c)");
+//! assert_eq!(patterns.len(), 3);
+//! assert!(is_error_pattern(&patterns[1]));
+//! assert_eq!(errors.len(), 1);
+//! ```
+
+use crate::parser::{self, combinators::ws, subject, ParseContext, ParseError};
+use pattern_core::{Pattern, Subject, Symbol, Value};
+use std::collections::{HashMap, HashSet};
+
+/// Identity of the placeholder pattern produced for an unparseable statement
+pub const ERROR_IDENTITY: &str = "__error__";
+
+/// Property of an error pattern holding the raw text that was skipped
+pub const ERROR_TEXT_PROPERTY: &str = "text";
+
+/// Parse gram notation, recovering from syntax errors
+///
+/// Each top-level statement that fails to parse becomes an atomic pattern with
+/// identity [`ERROR_IDENTITY`] and the skipped source text in the
+/// [`ERROR_TEXT_PROPERTY`] property, in the same position the statement would
+/// have taken. Parsing resumes at the next top-level boundary: the next `(`,
+/// `[`, or `@` outside any brackets opened by the broken statement, or the next
+/// line starting with one of those characters. A leading record is kept as the
+/// first pattern, as in [`parse_gram`](crate::parse_gram).
+///
+/// Error locations refer to the original input, so they stay valid for the
+/// whole document. When the input parses cleanly the patterns equal those from
+/// `parse_gram` and no errors are returned.
+///
+/// # Arguments
+///
+/// * `input` - Gram notation text to parse
+///
+/// # Returns
+///
+/// The parsed patterns, including error patterns, and one [`ParseError`] per
+/// unparseable statement in document order.
+pub fn parse_gram_lossy(input: &str) -> (Vec<Pattern<Subject>>, Vec<ParseError>) {
+    let ctx = ParseContext::default();
+    let mut patterns = Vec::new();
+    let mut errors = Vec::new();
+    let mut rest = input;
+
+    // A bare record is only legal as the first statement
+    if let Ok((remaining, properties)) =
+        nom::sequence::preceded(ws, |i| subject::record(&ctx, i))(input)
+    {
+        patterns.push(Pattern::point(Subject {
+            identity: Symbol(String::new()),
+            labels: HashSet::new(),
+            properties,
+        }));
+        rest = remaining;
+    }
+
+    loop {
+        rest = ws(rest).map_or(rest, |(remaining, _)| remaining);
+        if rest.is_empty() {
+            break;
+        }
+
+        match parser::gram_pattern(&ctx, rest) {
+            Ok((remaining, pattern)) => {
+                patterns.push(pattern);
+                rest = remaining;
+            }
+            Err(err) => {
+                errors.push(ctx.error(input, err));
+                let start = input.len() - rest.len();
+                let end = next_boundary(input, start);
+                patterns.push(error_pattern(input[start..end].trim_end()));
+                rest = &input[end..];
+            }
+        }
+    }
+
+    (patterns, errors)
+}
+
+/// Check if `pattern` is a placeholder produced by [`parse_gram_lossy`]
+pub fn is_error_pattern(pattern: &Pattern<Subject>) -> bool {
+    pattern.elements.is_empty() && pattern.value.identity.0 == ERROR_IDENTITY
+}
+
+fn error_pattern(text: &str) -> Pattern<Subject> {
+    Pattern::point(Subject {
+        identity: Symbol(ERROR_IDENTITY.to_string()),
+        labels: HashSet::new(),
+        properties: HashMap::from([(ERROR_TEXT_PROPERTY.to_string(), Value::VString(text.into()))]),
+    })
+}
+
+/// Offset of the next top-level statement after the broken one starting at `start`
+///
+/// Brackets, strings, and comments are tracked so that a pattern start inside
+/// the broken statement is not mistaken for a new statement. A line that starts
+/// with a pattern start always begins a new statement, so an unclosed bracket
+/// only swallows the rest of its own line.
+fn next_boundary(input: &str, start: usize) -> usize {
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut line_start = false;
+    let mut chars = input[start..].char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        if i > 0 && matches!(c, '(' | '[' | '@') && (depth <= 0 || line_start) {
+            return start + i;
+        }
+
+        match c {
+            '"' | '\'' | '`' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+            }
+            '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                chars.next();
+                let mut star = false;
+                for (_, c) in chars.by_ref() {
+                    if star && c == '/' {
+                        break;
+                    }
+                    star = c == '*';
+                }
+            }
+            _ => {}
+        }
+
+        if c == '\n' {
+            line_start = true;
+        } else if !c.is_whitespace() {
+            line_start = false;
+        }
+    }
+
+    input.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_text(pattern: &Pattern<Subject>) -> &str {
+        match pattern.value.properties.get(ERROR_TEXT_PROPERTY) {
+            Some(Value::VString(text)) => text,
+            other => panic!("not an error pattern: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_clean_input_matches_parse_gram() {
+        for input in [
+            "",
+            "(a) (b)",
+            "{k: 1} (a)-[:R]->(b)",
+            "@@p @k(1) [g | (a), (b)] // done",
+        ] {
+            let (patterns, errors) = parse_gram_lossy(input);
+            assert!(errors.is_empty(), "{}: {:?}", input, errors);
+            assert_eq!(patterns, crate::parse_gram(input).unwrap(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_broken_statement_becomes_error_pattern() {
+        let (patterns, errors) = parse_gram_lossy("(a) (b {k: }) (c)");
+        assert_eq!(patterns.len(), 3);
+        assert_eq!(patterns[0].value.identity.0, "a");
+        assert!(is_error_pattern(&patterns[1]));
+        assert_eq!(error_text(&patterns[1]), "(b {k: })");
+        assert_eq!(patterns[2].value.identity.0, "c");
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_error_locations_refer_to_original_input() {
+        let (patterns, errors) = parse_gram_lossy("(a)\n(b\n(c)\n(d e)\n(f)");
+        let ids: Vec<&str> = patterns
+            .iter()
+            .map(|p| p.value.identity.0.as_str())
+            .collect();
+        assert_eq!(ids, ["a", ERROR_IDENTITY, "c", ERROR_IDENTITY, "f"]);
+        assert_eq!(error_text(&patterns[1]), "(b");
+
+        let lines: Vec<usize> = errors.iter().map(|e| e.location().unwrap().line).collect();
+        // `(b` is reported where its `)` was expected, at the start of line 3
+        assert_eq!(lines, [3, 4]);
+    }
+
+    #[test]
+    fn test_boundary_ignores_brackets_in_strings_and_comments() {
+        let input = "[g | (a), ?, (b {s: \"(x)\"}) // (y)\n] (c)";
+        let (patterns, errors) = parse_gram_lossy(input);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(
+            error_text(&patterns[0]),
+            "[g | (a), ?, (b {s: \"(x)\"}) // (y)\n]"
+        );
+        assert_eq!(patterns[1].value.identity.0, "c");
+    }
+
+    #[test]
+    fn test_boundary_ignores_brackets_in_block_comments() {
+        let input = "[g | (a), ? /* (y)\n[z] */ ] (c)";
+        let (patterns, errors) = parse_gram_lossy(input);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(error_text(&patterns[0]), "[g | (a), ? /* (y)\n[z] */ ]");
+        assert_eq!(patterns[1].value.identity.0, "c");
+    }
+
+    #[test]
+    fn test_mid_document_record_is_recovered() {
+        let (patterns, errors) = parse_gram_lossy("{v: 1} (a) {k: 2} (b)");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(patterns.len(), 4);
+        assert!(patterns[0].value.properties.contains_key("v"));
+        assert_eq!(error_text(&patterns[2]), "{k: 2}");
+        assert_eq!(patterns[3].value.identity.0, "b");
+    }
+
+    #[test]
+    fn test_trailing_garbage() {
+        let (patterns, errors) = parse_gram_lossy("(a) -->");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(error_text(&patterns[1]), "-->");
+    }
+}
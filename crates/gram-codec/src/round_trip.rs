@@ -0,0 +1,251 @@
+//! Round-trip stability checking
+//!
+//! [`round_trip_diff`] parses gram text, serializes the patterns, parses the
+//! output again, and reports the first place where the two parses differ.
+//! Editors can use it to warn that a document will not round-trip cleanly
+//! before it is saved.
+//!
+//! # Example
+//!
+//! ```rust
+//! use gram_codec::round_trip_diff;
+//!
+//! assert_eq!(round_trip_diff("(a:Person)-->(b)").unwrap(), None);
+//! assert!(round_trip_diff("(unclosed").is_err());
+//! ```
+
+use crate::ParseError;
+use pattern_core::{Pattern, Subject};
+use std::collections::{BTreeSet, HashSet};
+
+/// Check that gram text survives parse → serialize → parse unchanged
+///
+/// # Arguments
+///
+/// * `input` - Gram notation to check
+///
+/// # Returns
+///
+/// * `Ok(None)` - The re-parsed patterns equal the original parse
+/// * `Ok(Some(diff))` - A human-readable description of the first divergence,
+///   e.g. `patterns[0].elements[1]: labels ["A"] became ["A", "B"]`, or why the
+///   serialized output could not be produced or re-parsed
+/// * `Err(ParseError)` - The input itself does not parse
+pub fn round_trip_diff(input: &str) -> Result<Option<String>, ParseError> {
+    let original = crate::parse_gram(input)?;
+
+    let serialized = match crate::to_gram(&original) {
+        Ok(serialized) => serialized,
+        Err(e) => return Ok(Some(format!("serialization failed: {}", e))),
+    };
+    let reparsed = match crate::parse_gram(&serialized) {
+        Ok(reparsed) => reparsed,
+        Err(e) => {
+            return Ok(Some(format!(
+                "serialized output does not parse: {}\n  output: {}",
+                e, serialized
+            )))
+        }
+    };
+
+    Ok(first_divergence(&original, &reparsed))
+}
+
+/// Describe the first difference between two pattern lists, in document order
+fn first_divergence(before: &[Pattern<Subject>], after: &[Pattern<Subject>]) -> Option<String> {
+    for (i, (before, after)) in before.iter().zip(after).enumerate() {
+        if let Some(diff) = compare(before, after, format!("patterns[{}]", i)) {
+            return Some(diff);
+        }
+    }
+    if before.len() != after.len() {
+        return Some(format!(
+            "document: {} patterns became {}",
+            before.len(),
+            after.len()
+        ));
+    }
+    None
+}
+
+fn compare(before: &Pattern<Subject>, after: &Pattern<Subject>, path: String) -> Option<String> {
+    let (b, a) = (&before.value, &after.value);
+
+    if b.identity != a.identity {
+        return Some(format!(
+            "{}: identity {:?} became {:?}",
+            path, b.identity.0, a.identity.0
+        ));
+    }
+    if b.labels != a.labels {
+        return Some(format!(
+            "{}: labels {:?} became {:?}",
+            path,
+            sorted_labels(&b.labels),
+            sorted_labels(&a.labels)
+        ));
+    }
+
+    // Direction and quantifier are the reserved `_direction` and `_quantifier`
+    // properties, so arrow and `*min..max` changes are reported here too
+    let keys: BTreeSet<&String> = b.properties.keys().chain(a.properties.keys()).collect();
+    for key in keys {
+        match (b.properties.get(key), a.properties.get(key)) {
+            (Some(old), Some(new)) if old != new => {
+                return Some(format!(
+                    "{}: property `{}` changed from {} to {}",
+                    path, key, old, new
+                ))
+            }
+            (Some(old), None) => {
+                return Some(format!(
+                    "{}: property `{}` ({}) was dropped",
+                    path, key, old
+                ))
+            }
+            (None, Some(new)) => {
+                return Some(format!("{}: property `{}` ({}) was added", path, key, new))
+            }
+            _ => {}
+        }
+    }
+
+    for (i, (before, after)) in before.elements.iter().zip(&after.elements).enumerate() {
+        if let Some(diff) = compare(before, after, format!("{}.elements[{}]", path, i)) {
+            return Some(diff);
+        }
+    }
+    if before.elements.len() != after.elements.len() {
+        return Some(format!(
+            "{}: {} elements became {}",
+            path,
+            before.elements.len(),
+            after.elements.len()
+        ));
+    }
+
+    None
+}
+
+fn sorted_labels(labels: &HashSet<String>) -> Vec<&String> {
+    let mut labels: Vec<&String> = labels.iter().collect();
+    labels.sort();
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pattern_core::{Direction, Quantifier, Symbol, Value};
+    use std::collections::HashMap;
+
+    fn node(id: &str, labels: &[&str]) -> Pattern<Subject> {
+        Pattern::point(Subject {
+            identity: Symbol(id.to_string()),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            properties: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_stable_inputs_have_no_diff() {
+        for input in [
+            "(a:Person {name: \"Alice\", age: 30})",
+            "(a)-[:KNOWS]->(b)",
+            "[team | (alice), (bob)]",
+            "{k: 1} (a) (b)",
+            "@@p:L @k(\"v\") (a)",
+            "(a)<-[r:KNOWS*1..3]-(b)",
+            "(a)--(b)",
+        ] {
+            assert_eq!(round_trip_diff(input).unwrap(), None, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_unparseable_input_is_an_error() {
+        assert!(round_trip_diff("(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_reports_first_nested_divergence() {
+        let before = vec![Pattern::pattern(
+            Subject {
+                identity: Symbol("g".to_string()),
+                labels: HashSet::new(),
+                properties: HashMap::new(),
+            },
+            vec![node("a", &["A"]), node("b", &[])],
+        )];
+        let mut after = before.clone();
+        after[0].elements[0].value.labels.insert("B".to_string());
+        after[0].elements[1].value.identity = Symbol("c".to_string());
+
+        assert_eq!(
+            first_divergence(&before, &after).unwrap(),
+            "patterns[0].elements[0]: labels [\"A\"] became [\"A\", \"B\"]"
+        );
+    }
+
+    #[test]
+    fn test_reports_property_changes() {
+        let before = vec![node("a", &[])];
+        let mut after = before.clone();
+        after[0]
+            .value
+            .properties
+            .insert("k".to_string(), Value::VInteger(1));
+        assert_eq!(
+            first_divergence(&before, &after).unwrap(),
+            "patterns[0]: property `k` (1) was added"
+        );
+
+        let mut changed = after.clone();
+        changed[0]
+            .value
+            .properties
+            .insert("k".to_string(), Value::VInteger(2));
+        assert_eq!(
+            first_divergence(&after, &changed).unwrap(),
+            "patterns[0]: property `k` changed from 1 to 2"
+        );
+    }
+
+    #[test]
+    fn test_reports_direction_and_quantifier_changes() {
+        let before = crate::parse_gram("(a)<-[r*1..3]-(b)").unwrap();
+
+        let mut forward = before.clone();
+        forward[0].value.set_direction(Direction::Forward);
+        assert_eq!(
+            first_divergence(&before, &forward).unwrap(),
+            "patterns[0]: property `_direction` (backward) was dropped"
+        );
+
+        let mut widened = before.clone();
+        widened[0].value.set_quantifier(Some(Quantifier {
+            min: Some(1),
+            max: Some(5),
+        }));
+        assert_eq!(
+            first_divergence(&before, &widened).unwrap(),
+            "patterns[0]: property `_quantifier` changed from 1..3 to 1..5"
+        );
+    }
+
+    #[test]
+    fn test_reports_count_differences() {
+        let before = vec![node("a", &[]), node("b", &[])];
+        assert_eq!(
+            first_divergence(&before, &before[..1]).unwrap(),
+            "document: 2 patterns became 1"
+        );
+
+        let mut after = vec![Pattern::pattern(before[0].value.clone(), vec![])];
+        after[0].elements.push(node("x", &[]));
+        assert_eq!(
+            first_divergence(&before[..1], &after).unwrap(),
+            "patterns[0]: 0 elements became 1"
+        );
+    }
+}
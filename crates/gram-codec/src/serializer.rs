@@ -1,8 +1,10 @@
 //! Serializer for Pattern structures to Gram notation
 
+use crate::direction::{reserved_property, Direction};
 use crate::{SerializeError, Value};
-use pattern_core::{Pattern, Subject};
+use pattern_core::{Pattern, PatternGraph, Subject};
 use std::collections::HashMap;
+use std::{fmt, io};
 
 /// Serialize a Pattern structure to Gram notation
 pub fn to_gram_pattern(pattern: &Pattern<Subject>) -> Result<String, SerializeError> {
@@ -11,15 +13,34 @@ pub fn to_gram_pattern(pattern: &Pattern<Subject>) -> Result<String, SerializeEr
     match format {
         GramFormat::Node => serialize_node_pattern(pattern),
         GramFormat::Relationship => serialize_relationship_pattern(pattern),
+        // Only arrow notation can carry a quantifier
+        GramFormat::SubjectPattern
+            if pattern.value.quantifier().is_some() && is_path_pattern(pattern) =>
+        {
+            serialize_path_pattern(pattern)
+        }
         GramFormat::SubjectPattern => serialize_subject_pattern(pattern),
         GramFormat::Annotation => serialize_annotation_pattern(pattern),
         GramFormat::BareRecord => serialize_record(&pattern.value.properties),
     }
 }
 
+/// Serialize a Pattern structure to Gram notation using the layout in `options`
+///
+/// With default options this is identical to [`to_gram_pattern`]. Only
+/// `indent` and `max_width` apply to a single pattern; see [`SerializeOptions`].
+pub fn to_gram_pattern_with_options(
+    pattern: &Pattern<Subject>,
+    options: &SerializeOptions,
+) -> Result<String, SerializeError> {
+    layout_pattern(pattern, options, 0, 0, true)
+}
+
 /// Serialize a sequence of patterns to gram notation.
 ///
-/// Writes each pattern in sequence, joined by newlines.
+/// Writes each pattern in sequence, joined by newlines. Only the first pattern
+/// may be written as a bare record `{...}`; later record-only patterns are
+/// written in node form `({...})` so the output re-parses.
 ///
 /// # Arguments
 ///
@@ -29,9 +50,81 @@ pub fn to_gram_pattern(pattern: &Pattern<Subject>) -> Result<String, SerializeEr
 ///
 /// * `Ok(String)` - Valid Gram notation
 pub fn to_gram(patterns: &[Pattern<Subject>]) -> Result<String, SerializeError> {
+    serialize_statements(
+        &patterns.iter().collect::<Vec<_>>(),
+        true,
+        &SerializeOptions::default(),
+    )
+}
+
+/// Write a sequence of patterns as gram notation to an I/O sink.
+///
+/// Writes exactly what [`to_gram`] returns, one statement at a time, so the
+/// whole document is never held in memory. Writes are not buffered; wrap
+/// files and sockets in a [`std::io::BufWriter`].
+///
+/// # Arguments
+///
+/// * `patterns` - Patterns to serialize
+/// * `writer` - Sink to write to
+///
+/// # Returns
+///
+/// * `Err(SerializeError::IoError)` - If writing fails; earlier statements
+///   may already have been written
+pub fn write_gram<W: io::Write>(
+    patterns: &[Pattern<Subject>],
+    mut writer: W,
+) -> Result<(), SerializeError> {
+    write_statements(patterns, |s| Ok(writer.write_all(s.as_bytes())?))
+}
+
+/// Write a Pattern structure as gram notation to an I/O sink.
+///
+/// Writes exactly what [`to_gram_pattern`] returns.
+pub fn write_gram_pattern<W: io::Write>(
+    pattern: &Pattern<Subject>,
+    writer: W,
+) -> Result<(), SerializeError> {
+    write_gram(std::slice::from_ref(pattern), writer)
+}
+
+/// Write a sequence of patterns as gram notation to a [`fmt::Write`] sink,
+/// such as a `String` or a [`fmt::Formatter`].
+///
+/// Writes exactly what [`to_gram`] returns, one statement at a time.
+pub fn write_gram_fmt<W: fmt::Write>(
+    patterns: &[Pattern<Subject>],
+    mut writer: W,
+) -> Result<(), SerializeError> {
+    write_statements(patterns, |s| Ok(writer.write_str(s)?))
+}
+
+/// Pass each statement of [`to_gram`]'s output, and the newlines between, to `write`
+fn write_statements(
+    patterns: &[Pattern<Subject>],
+    mut write: impl FnMut(&str) -> Result<(), SerializeError>,
+) -> Result<(), SerializeError> {
+    let options = SerializeOptions::default();
+    for (i, pattern) in patterns.iter().enumerate() {
+        if i > 0 {
+            write("\n")?;
+        }
+        write(&layout_pattern(pattern, &options, 0, 0, i == 0)?)?;
+    }
+    Ok(())
+}
+
+/// Serialize top-level statements, optionally allowing a leading bare record
+fn serialize_statements(
+    patterns: &[&Pattern<Subject>],
+    leading_record: bool,
+    options: &SerializeOptions,
+) -> Result<String, SerializeError> {
     patterns
         .iter()
-        .map(to_gram_pattern)
+        .enumerate()
+        .map(|(i, pattern)| layout_pattern(pattern, options, 0, 0, i == 0 && leading_record))
         .collect::<Result<Vec<_>, _>>()
         .map(|lines| lines.join("\n"))
 }
@@ -54,7 +147,11 @@ pub fn to_gram_with_header(
     patterns: &[Pattern<Subject>],
 ) -> Result<String, SerializeError> {
     let header_str = serialize_record(&header)?;
-    let patterns_str = to_gram(patterns)?;
+    let patterns_str = serialize_statements(
+        &patterns.iter().collect::<Vec<_>>(),
+        header_str.is_empty(),
+        &SerializeOptions::default(),
+    )?;
 
     if patterns_str.is_empty() {
         Ok(header_str)
@@ -65,10 +162,393 @@ pub fn to_gram_with_header(
     }
 }
 
+/// Serializes a graph as a gram document.
+///
+/// Writes every node, then every relationship, then walks, annotations, and
+/// any other patterns, each group sorted by identity. Only nodes carry their
+/// labels and properties: wherever a node appears inside another pattern it is
+/// written as a reference `(id)`. Conflicting duplicates in `pg_conflicts` are
+/// not written.
+///
+/// Parsing the output and building a graph under any policy, as
+/// [`from_patterns_with_policy`](pattern_core::from_patterns_with_policy)
+/// does, gives back the same nodes and the same relationships, walks, and
+/// annotations between them: a reference never overrides or conflicts with
+/// the node it refers to (see
+/// [`Refinable::is_reference`](pattern_core::reconcile::Refinable::is_reference)).
+/// Nodes nested in the rebuilt graph's other patterns are those references,
+/// where the original may have held copies of the nodes.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to serialize
+///
+/// # Returns
+///
+/// * `Ok(String)` - Valid Gram notation, one statement per line
+pub fn serialize_graph(graph: &PatternGraph<(), Subject>) -> Result<String, SerializeError> {
+    fn sorted<'a>(
+        patterns: impl Iterator<Item = &'a Pattern<Subject>>,
+    ) -> Vec<&'a Pattern<Subject>> {
+        let mut patterns: Vec<_> = patterns.collect();
+        patterns.sort_by(|a, b| a.value.identity.cmp(&b.value.identity));
+        patterns
+    }
+
+    let nodes = sorted(graph.pg_nodes.values()).into_iter().cloned();
+    let containers = [
+        sorted(graph.pg_relationships.values()),
+        sorted(graph.pg_walks.values()),
+        sorted(graph.pg_annotations.values()),
+        sorted(graph.pg_other.values().map(|(_, p)| p)),
+    ]
+    .into_iter()
+    .flatten()
+    .map(with_node_references);
+    let patterns: Vec<Pattern<Subject>> = nodes.chain(containers).collect();
+
+    serialize_statements(
+        &patterns.iter().collect::<Vec<_>>(),
+        false,
+        &SerializeOptions::default(),
+    )
+}
+
+/// Replace every node nested inside `pattern` with a bare reference to its identity
+fn with_node_references(pattern: &Pattern<Subject>) -> Pattern<Subject> {
+    Pattern {
+        value: pattern.value.clone(),
+        elements: pattern
+            .elements
+            .iter()
+            .map(|element| {
+                if element.elements.is_empty() {
+                    Pattern::point(Subject::from_id(element.value.identity.0.clone()))
+                } else {
+                    with_node_references(element)
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Options controlling how a sequence of patterns is written as gram notation.
+///
+/// Labels and property keys are always written in sorted order, so output is
+/// deterministic whatever the options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SerializeOptions {
+    /// Fold relationships that share an endpoint into a single path.
+    ///
+    /// When `true`, `(a)-->(b)` followed by `(b)-->(c)` is written as
+    /// `(a)-->(b)-->(c)`, and parsed paths are written back inline rather than
+    /// as `[ | (a)-->(b), (c)]`. When `false` (the default), every relationship
+    /// is written as its own statement.
+    pub path_chaining: bool,
+
+    /// How to write top-level nodes with no identity, labels, or properties.
+    pub empty_node: EmptyNodeStyle,
+
+    /// Spaces per nesting level when writing subject patterns over several lines.
+    ///
+    /// When set, the elements of a subject pattern `[subject | elements]` go on
+    /// their own lines, indented one level deeper than the pattern, with the
+    /// closing `]` on a line of its own. When `None` (the default), every
+    /// statement is written on a single line.
+    pub indent: Option<usize>,
+
+    /// Widest line, in characters, that a subject pattern is kept on.
+    ///
+    /// Only applies with `indent`: a subject pattern that fits within this width
+    /// at its position is written on one line, and only wider ones are broken.
+    /// Nodes, relationships, and paths are never broken, so lines may still
+    /// exceed the width. When `None`, every subject pattern is broken.
+    pub max_width: Option<usize>,
+}
+
+/// How [`to_gram_with_options`] writes an empty node: one with no identity,
+/// labels, properties, or elements.
+///
+/// `()`, `( )`, `[]`, and `[ | ]` all parse to the empty node, so it is always
+/// written as `()`. Only top-level statements are affected by this setting:
+/// an empty node inside another pattern (a relationship endpoint or a subject
+/// pattern element) is always written as `()`, since omitting it would change
+/// the enclosing pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmptyNodeStyle {
+    /// Write empty nodes as `()`, which parses back to the same pattern (the default).
+    #[default]
+    Parentheses,
+    /// Omit empty top-level nodes from the output.
+    Skip,
+}
+
+/// Serialize a sequence of patterns to gram notation using the given options.
+///
+/// With default options this is identical to [`to_gram`].
+///
+/// With `path_chaining` enabled, a top-level relationship whose target node is
+/// the source node of another top-level relationship is folded into a single
+/// path. Nodes are only joined when they are equal patterns with a non-empty
+/// identity, so no node content is lost and anonymous nodes are never merged.
+/// Chains start from relationships that nothing else leads into; whatever is
+/// left (cycles, branches) starts new chains in input order. Each chain is
+/// written at the position of its earliest relationship. Building a graph
+/// from the parsed output with
+/// [`from_patterns`](pattern_core::from_patterns) gives the same nodes and
+/// relationships whether or not paths are chained, since a parsed path is
+/// built as its relationships.
+///
+/// # Arguments
+///
+/// * `patterns` - Patterns to serialize
+/// * `options` - Serialization options
+///
+/// # Returns
+///
+/// * `Ok(String)` - Valid Gram notation
+pub fn to_gram_with_options(
+    patterns: &[Pattern<Subject>],
+    options: &SerializeOptions,
+) -> Result<String, SerializeError> {
+    let patterns: Vec<&Pattern<Subject>> = patterns
+        .iter()
+        .filter(|p| options.empty_node == EmptyNodeStyle::Parentheses || !is_empty_node(p))
+        .collect();
+
+    if options.path_chaining {
+        serialize_chained(&patterns, options)
+    } else {
+        serialize_statements(&patterns, true, options)
+    }
+}
+
+/// Check if pattern is the empty node: no identity, labels, properties, or elements
+fn is_empty_node(pattern: &Pattern<Subject>) -> bool {
+    pattern.elements.is_empty()
+        && pattern.value.identity.0.is_empty()
+        && pattern.value.labels.is_empty()
+        && pattern.value.properties.is_empty()
+}
+
+/// Serialize top-level statements, folding relationships into paths
+fn serialize_chained(
+    patterns: &[&Pattern<Subject>],
+    options: &SerializeOptions,
+) -> Result<String, SerializeError> {
+    // Backward relationships are written target-first, so they don't chain
+    let is_relationship: Vec<bool> = patterns
+        .iter()
+        .map(|p| {
+            select_format(p) == GramFormat::Relationship
+                && p.value.direction() != Direction::Backward
+        })
+        .collect();
+
+    // Index relationships by the identity of their source node
+    let mut by_source: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, pattern) in patterns.iter().enumerate() {
+        if is_relationship[i] && !pattern.elements[0].value.identity.0.is_empty() {
+            by_source
+                .entry(pattern.elements[0].value.identity.0.as_str())
+                .or_default()
+                .push(i);
+        }
+    }
+
+    // A relationship has a predecessor when another relationship ends where it starts
+    let mut has_predecessor = vec![false; patterns.len()];
+    for (i, pattern) in patterns.iter().enumerate() {
+        if is_relationship[i] {
+            for j in chain_candidates(&by_source, &pattern.elements[1]) {
+                if j != i && patterns[j].elements[0] == pattern.elements[1] {
+                    has_predecessor[j] = true;
+                }
+            }
+        }
+    }
+
+    let mut consumed = vec![false; patterns.len()];
+    let mut chains: Vec<Vec<usize>> = Vec::new();
+    for start_at_heads in [true, false] {
+        for i in 0..patterns.len() {
+            if !is_relationship[i] || consumed[i] || (start_at_heads && has_predecessor[i]) {
+                continue;
+            }
+            consumed[i] = true;
+            let mut chain = vec![i];
+            let mut tail = &patterns[i].elements[1];
+            while let Some(j) = chain_candidates(&by_source, tail)
+                .find(|&j| !consumed[j] && patterns[j].elements[0] == *tail)
+            {
+                consumed[j] = true;
+                chain.push(j);
+                tail = &patterns[j].elements[1];
+            }
+            chains.push(chain);
+        }
+    }
+
+    // Emit each statement at the position of its earliest pattern
+    let mut statements: Vec<(usize, String)> = Vec::with_capacity(patterns.len());
+    for chain in chains {
+        let position = chain.iter().copied().min().unwrap_or_default();
+        let mut line = serialize_node_pattern(&patterns[chain[0]].elements[0])?;
+        for i in chain {
+            line.push_str(&serialize_edge(&patterns[i].value)?);
+            line.push_str(&serialize_node_pattern(&patterns[i].elements[1])?);
+        }
+        statements.push((position, line));
+    }
+    for (i, pattern) in patterns.iter().enumerate() {
+        if !is_relationship[i] {
+            let line = if is_path_pattern(pattern) {
+                serialize_path_pattern(pattern)?
+            } else {
+                layout_pattern(pattern, options, 0, 0, i == 0)?
+            };
+            statements.push((i, line));
+        }
+    }
+    statements.sort_by_key(|(position, _)| *position);
+
+    Ok(statements
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Indices of relationships whose source node shares an identity with `node`
+fn chain_candidates<'a>(
+    by_source: &'a HashMap<&str, Vec<usize>>,
+    node: &Pattern<Subject>,
+) -> impl Iterator<Item = usize> + 'a {
+    let candidates = if node.value.identity.0.is_empty() {
+        None
+    } else {
+        by_source.get(node.value.identity.0.as_str())
+    };
+    candidates.into_iter().flatten().copied()
+}
+
+/// Check if pattern has the nested shape the parser produces for a path
+///
+/// `(a)-->(b)-->(c)` parses as `[ | [ | (a), (b)], (c)]`: the left element is
+/// itself a relationship or path, the right element is a node. A backward last
+/// segment would put the node first, so it is not written as a path.
+fn is_path_pattern(pattern: &Pattern<Subject>) -> bool {
+    pattern.elements.len() == 2
+        && pattern.elements[1].elements.is_empty()
+        && pattern.value.direction() != Direction::Backward
+        && (is_relationship_pattern(&pattern.elements[0]) || is_path_pattern(&pattern.elements[0]))
+}
+
+/// Serialize a nested path pattern inline: `(a)-->(b)-->(c)`
+fn serialize_path_pattern(pattern: &Pattern<Subject>) -> Result<String, SerializeError> {
+    let left = if is_path_pattern(&pattern.elements[0]) {
+        serialize_path_pattern(&pattern.elements[0])?
+    } else {
+        serialize_relationship_pattern(&pattern.elements[0])?
+    };
+    let edge = serialize_edge(&pattern.value)?;
+    let right = serialize_node_pattern(&pattern.elements[1])?;
+
+    Ok(format!("{}{}{}", left, edge, right))
+}
+
+/// Serialize a pattern that starts at `column` of a line indented `depth` levels
+///
+/// Without `options.indent` this is the single-line form. Otherwise subject
+/// patterns (including one an annotation applies to) that don't fit
+/// `options.max_width` are broken over several lines. `bare_record` allows a
+/// record-only pattern to be written as a bare record, as for the first
+/// statement of a document.
+fn layout_pattern(
+    pattern: &Pattern<Subject>,
+    options: &SerializeOptions,
+    depth: usize,
+    column: usize,
+    bare_record: bool,
+) -> Result<String, SerializeError> {
+    let single_line = if bare_record {
+        to_gram_pattern(pattern)?
+    } else {
+        serialize_element(pattern)?
+    };
+    let Some(indent) = options.indent else {
+        return Ok(single_line);
+    };
+    if let Some(max_width) = options.max_width {
+        if column + single_line.chars().count() <= max_width {
+            return Ok(single_line);
+        }
+    }
+
+    match select_format(pattern) {
+        GramFormat::SubjectPattern => layout_subject_pattern(pattern, options, indent, depth),
+        GramFormat::Annotation => {
+            let annotations = serialize_annotations(&pattern.value)?;
+            let element = &pattern.elements[0];
+            let element_str = if needs_bracket_form(element) {
+                layout_subject_pattern(element, options, indent, depth)?
+            } else {
+                let column = column + annotations.chars().count() + 1;
+                layout_pattern(element, options, depth, column, false)?
+            };
+            Ok(format!("{} {}", annotations, element_str))
+        }
+        _ => Ok(single_line),
+    }
+}
+
+/// Write a subject pattern with each element on its own line, one level deeper
+fn layout_subject_pattern(
+    pattern: &Pattern<Subject>,
+    options: &SerializeOptions,
+    indent: usize,
+    depth: usize,
+) -> Result<String, SerializeError> {
+    let subject_str = serialize_subject(&pattern.value)?;
+    let inner = " ".repeat(indent * (depth + 1));
+
+    let elements_str = pattern
+        .elements
+        .iter()
+        .map(|element| {
+            let element_str = layout_pattern(element, options, depth + 1, inner.len(), false)?;
+            Ok(format!("{}{}", inner, element_str))
+        })
+        .collect::<Result<Vec<_>, SerializeError>>()?
+        .join(",\n");
+
+    Ok(format!(
+        "[{} |\n{}\n{}]",
+        subject_str,
+        elements_str,
+        " ".repeat(indent * depth)
+    ))
+}
+
+/// Serialize a pattern anywhere a bare record is not allowed
+///
+/// A bare record `{...}` is only valid as the leading record of a document.
+/// Elsewhere, a pattern with no elements, identity, or labels is written in
+/// node form `({...})`, which parses back to the same pattern.
+fn serialize_element(pattern: &Pattern<Subject>) -> Result<String, SerializeError> {
+    match select_format(pattern) {
+        GramFormat::BareRecord => serialize_node_pattern(pattern),
+        _ => to_gram_pattern(pattern),
+    }
+}
+
 /// Format types for gram notation serialization
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum GramFormat {
     /// Node pattern: `(subject)` - 0 elements
+    ///
+    /// `[subject]` and `[subject | ]` parse to the same atomic pattern as
+    /// `(subject)`, so they are all written in this normalized form.
     Node,
     /// Relationship pattern: `(left)-->(right)` - 2 atomic elements
     Relationship,
@@ -174,23 +654,71 @@ fn serialize_relationship_pattern(pattern: &Pattern<Subject>) -> Result<String,
         ));
     }
 
-    let left = serialize_node_pattern(&pattern.elements[0])?;
-    let right = serialize_node_pattern(&pattern.elements[1])?;
+    let (source, target) = (&pattern.elements[0], &pattern.elements[1]);
+    let (left, right) = if pattern.value.direction() == Direction::Backward {
+        (target, source)
+    } else {
+        (source, target)
+    };
+    let edge = serialize_edge(&pattern.value)?;
+
+    Ok(format!(
+        "{}{}{}",
+        serialize_node_pattern(left)?,
+        edge,
+        serialize_node_pattern(right)?
+    ))
+}
 
-    // Serialize the edge (relationship) subject if present
-    let edge = if pattern.value.identity.0.is_empty()
-        && pattern.value.labels.is_empty()
-        && pattern.value.properties.is_empty()
+/// Serialize the arrow between two nodes: `-->` or `-[edge]->`
+///
+/// The arrow follows the subject's [`Direction`]. For `Backward` the caller
+/// writes the target node first. A
+/// [`Quantifier`] is written after the labels, as in `-[:KNOWS*1..3]->`.
+/// Neither is written as a property of the edge, so an edge whose reserved
+/// property holds something else cannot be written.
+fn serialize_edge(subject: &Subject) -> Result<String, SerializeError> {
+    let (left, right) = match subject.direction() {
+        Direction::Forward => ("-", "->"),
+        Direction::Backward => ("<-", "-"),
+        Direction::Undirected => ("-", "-"),
+    };
+    let quantifier = subject.quantifier();
+    let mut edge = subject.clone();
+    edge.set_direction(Direction::Forward);
+    edge.set_quantifier(None);
+    if let Some(key) = reserved_property(&edge) {
+        return Err(SerializeError::invalid_value(
+            "relationship property",
+            format!(
+                "`{}` holds {}, which the arrow cannot express",
+                key, edge.properties[key]
+            ),
+        ));
+    }
+
+    if edge.identity.0.is_empty()
+        && edge.labels.is_empty()
+        && edge.properties.is_empty()
+        && quantifier.is_none()
     {
         // Empty edge: (a)-->(b)
-        String::new()
+        Ok(format!("{}{}", left, right))
     } else {
         // Edge with labels/properties: (a)-[:KNOWS {since: 2020}]->(b)
-        let edge_str = serialize_subject(&pattern.value)?;
-        format!("[{}]", edge_str)
-    };
-
-    Ok(format!("{}-{}->{}", left, edge, right))
+        let Some(quantifier) = quantifier else {
+            let edge_str = serialize_subject(&edge)?;
+            return Ok(format!("{}[{}]{}", left, edge_str, right));
+        };
+        let properties = std::mem::take(&mut edge.properties);
+        let mut edge_str = serialize_subject(&edge)?;
+        edge_str.push_str(&quantifier.to_string());
+        if !properties.is_empty() {
+            edge_str.push(' ');
+            edge_str.push_str(&serialize_record(&properties)?);
+        }
+        Ok(format!("{}[{}]{}", left, edge_str, right))
+    }
 }
 
 /// Serialize as subject pattern: `[subject | elements]`
@@ -200,7 +728,7 @@ fn serialize_subject_pattern(pattern: &Pattern<Subject>) -> Result<String, Seria
     let elements_str = pattern
         .elements
         .iter()
-        .map(to_gram_pattern)
+        .map(serialize_element)
         .collect::<Result<Vec<_>, _>>()?
         .join(", ");
 
@@ -215,17 +743,40 @@ fn serialize_annotation_pattern(pattern: &Pattern<Subject>) -> Result<String, Se
         ));
     }
 
+    let annotations = serialize_annotations(&pattern.value)?;
+    let element = &pattern.elements[0];
+    let element_str = if needs_bracket_form(element) {
+        serialize_subject_pattern(element)?
+    } else {
+        serialize_element(element)?
+    };
+
+    Ok(format!("{} {}", annotations, element_str))
+}
+
+/// Check if an annotated element must be written as `[subject | element]`
+///
+/// An element that starts with `@key(value)` would have its annotations
+/// absorbed into the enclosing annotation sequence.
+fn needs_bracket_form(element: &Pattern<Subject>) -> bool {
+    select_format(element) == GramFormat::Annotation
+        && element.value.identity.0.is_empty()
+        && element.value.labels.is_empty()
+}
+
+/// Serialize the annotations of an annotation pattern: `@@id:Label @key(value)`
+fn serialize_annotations(subject: &Subject) -> Result<String, SerializeError> {
     let mut annotations = Vec::new();
 
-    if !pattern.value.identity.0.is_empty() || !pattern.value.labels.is_empty() {
+    if !subject.identity.0.is_empty() || !subject.labels.is_empty() {
         let mut identified = String::from("@@");
 
-        if !pattern.value.identity.0.is_empty() {
-            identified.push_str(&quote_identifier(&pattern.value.identity.0));
+        if !subject.identity.0.is_empty() {
+            identified.push_str(&quote_identifier(&subject.identity.0));
         }
 
-        if !pattern.value.labels.is_empty() {
-            let mut labels: Vec<_> = pattern.value.labels.iter().collect();
+        if !subject.labels.is_empty() {
+            let mut labels: Vec<_> = subject.labels.iter().collect();
             labels.sort();
             for label in labels {
                 identified.push(':');
@@ -236,23 +787,16 @@ fn serialize_annotation_pattern(pattern: &Pattern<Subject>) -> Result<String, Se
         annotations.push(identified);
     }
 
-    let mut property_annotations: Vec<String> = pattern
-        .value
-        .properties
-        .iter()
-        .map(|(key, value)| {
-            let gram_value = value_from_pattern_value(value)?;
-            let value_str = gram_value.to_gram_notation();
-            Ok(format!("@{}({})", quote_identifier(key), value_str))
-        })
-        .collect::<Result<Vec<_>, SerializeError>>()?;
+    let mut props: Vec<_> = subject.properties.iter().collect();
+    props.sort_by_key(|(k, _)| *k); // Consistent ordering, by key rather than quoted text
 
-    property_annotations.sort();
-    annotations.extend(property_annotations);
-
-    let element_str = to_gram_pattern(&pattern.elements[0])?;
+    for (key, value) in props {
+        let gram_value = value_from_pattern_value(value)?;
+        let value_str = gram_value.to_gram_notation();
+        annotations.push(format!("@{}({})", quote_identifier(key), value_str));
+    }
 
-    Ok(format!("{} {}", annotations.join(" "), element_str))
+    Ok(annotations.join(" "))
 }
 
 /// Serialize a Subject (identifier + labels + properties)
@@ -345,13 +889,14 @@ fn value_from_pattern_value(value: &pattern_core::Value) -> Result<Value, Serial
             tag: tag.clone(),
             content: content.clone(),
         }),
-        pattern_core::Value::VMap(_map) => {
-            // Maps are not supported in gram notation property values
-            // They would need to be serialized as nested patterns
-            Err(SerializeError::invalid_structure(
-                "Map values not supported in gram notation properties",
-            ))
+        pattern_core::Value::VMap(map) => {
+            let entries = map
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), value_from_pattern_value(value)?)))
+                .collect::<Result<_, SerializeError>>()?;
+            Ok(Value::Map(entries))
         }
+        pattern_core::Value::VNull => Ok(Value::Null),
         pattern_core::Value::VMeasurement { .. } => {
             // Measurements are not supported in basic gram notation
             Err(SerializeError::invalid_structure(
@@ -363,7 +908,7 @@ fn value_from_pattern_value(value: &pattern_core::Value) -> Result<Value, Serial
 
 /// Quote identifier if needed (contains spaces, special chars, or starts with digit)
 /// Uses backtick quoting per grammar: identifiers, labels, and keys use quoted_name (`)
-fn quote_identifier(s: &str) -> String {
+pub(crate) fn quote_identifier(s: &str) -> String {
     if needs_quoting(s) {
         format!("`{}`", escape_backtick_string(s))
     } else {
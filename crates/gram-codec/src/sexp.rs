@@ -0,0 +1,422 @@
+//! S-expressions for comparing patterns
+//!
+//! [`Sexp`] reads S-expressions, both the tree-sitter corpus format and the
+//! compact per-pattern form [`check_equivalence`] compares against, so the
+//! same reader serves [`check_equivalence`] and the corpus tests.
+//! [`pattern_to_sexp`] writes a pattern in the compact form, for reference
+//! files and as a terse debugging view.
+//!
+//! [`check_equivalence`]: crate::check_equivalence
+
+use crate::serializer::quote_identifier;
+use crate::value::escape_string;
+use pattern_core::{Pattern, Subject, Value};
+use std::fmt;
+use thiserror::Error;
+
+/// A parsed S-expression
+///
+/// Atoms are runs of text up to whitespace or a parenthesis. Quoted text
+/// (`"..."`, `'...'`, or `` `...` ``) and bracketed values (`[...]`, `{...}`)
+/// are read as a single atom, so property values such as `[1, 2]` read back
+/// whole. Displaying a `Sexp` writes it on a single line.
+///
+/// ```rust
+/// use gram_codec::sexp::Sexp;
+///
+/// let roots = Sexp::parse("(node (identity a) (property tags [1, 2]))").unwrap();
+/// assert_eq!(roots[0].kind(), Some("node"));
+/// assert_eq!(roots[0].items()[1].items()[1], Sexp::Atom("[1, 2]".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sexp {
+    /// A single token, such as `node`, `identifier:`, or `"Alice"`
+    Atom(String),
+    /// A parenthesized list of items
+    List(Vec<Sexp>),
+}
+
+/// Error returned when an S-expression cannot be read
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid S-expression at offset {offset}: {message}")]
+pub struct SexpError {
+    /// Byte offset in the input
+    pub offset: usize,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl Sexp {
+    /// Read every top-level S-expression in `input`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Sexp>)` - The top-level S-expressions, at least one
+    /// * `Err(SexpError)` - The input is empty, unbalanced, or has text outside a list
+    pub fn parse(input: &str) -> Result<Vec<Sexp>, SexpError> {
+        let mut reader = SexpReader { input, pos: 0 };
+        let mut roots = Vec::new();
+        reader.skip_whitespace();
+        while reader.pos < input.len() {
+            if reader.peek() != Some('(') {
+                return Err(reader.error("expected `(`"));
+            }
+            roots.push(reader.read()?);
+            reader.skip_whitespace();
+        }
+        if roots.is_empty() {
+            return Err(reader.error("empty S-expression"));
+        }
+        Ok(roots)
+    }
+
+    /// The first atom of a list, which names its kind
+    pub fn kind(&self) -> Option<&str> {
+        match self {
+            Sexp::List(items) => match items.first() {
+                Some(Sexp::Atom(kind)) => Some(kind),
+                _ => None,
+            },
+            Sexp::Atom(_) => None,
+        }
+    }
+
+    /// The items of a list after its kind; empty for an atom
+    pub fn items(&self) -> &[Sexp] {
+        match self {
+            Sexp::List(items) if self.kind().is_some() => &items[1..],
+            Sexp::List(items) => items,
+            Sexp::Atom(_) => &[],
+        }
+    }
+
+    /// The text of an atom
+    pub fn as_atom(&self) -> Option<&str> {
+        match self {
+            Sexp::Atom(atom) => Some(atom),
+            Sexp::List(_) => None,
+        }
+    }
+
+    /// The compact S-expression for `pattern`; see [`pattern_to_sexp`]
+    pub fn from_pattern(pattern: &Pattern<Subject>) -> Sexp {
+        let field = |name: &str, values: Vec<String>| {
+            let mut items = vec![Sexp::Atom(name.to_string())];
+            items.extend(values.into_iter().map(Sexp::Atom));
+            Sexp::List(items)
+        };
+        let subject = &pattern.value;
+
+        let mut items = vec![Sexp::Atom(pattern_kind(pattern).to_string())];
+        if !subject.identity.0.is_empty() {
+            items.push(field(
+                "identity",
+                vec![quote_identifier(&subject.identity.0)],
+            ));
+        }
+        let mut labels: Vec<&String> = subject.labels.iter().collect();
+        labels.sort();
+        for label in labels {
+            items.push(field("label", vec![quote_identifier(label)]));
+        }
+        let mut properties: Vec<_> = subject.properties.iter().collect();
+        properties.sort_by_key(|(key, _)| *key);
+        for (key, value) in properties {
+            items.push(field(
+                "property",
+                vec![quote_identifier(key), value_text(value)],
+            ));
+        }
+        items.extend(pattern.elements.iter().map(Sexp::from_pattern));
+        Sexp::List(items)
+    }
+
+    /// The item after the field name `name:`, as in the tree-sitter format
+    /// `(node_pattern identifier: (symbol))`
+    pub fn field(&self, name: &str) -> Option<&Sexp> {
+        self.items()
+            .windows(2)
+            .find(|pair| pair[0].as_atom().and_then(|atom| atom.strip_suffix(':')) == Some(name))
+            .map(|pair| &pair[1])
+    }
+}
+
+impl fmt::Display for Sexp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sexp::Atom(atom) => f.write_str(atom),
+            Sexp::List(items) => {
+                f.write_str("(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+/// Write a pattern as a compact, single-line S-expression
+///
+/// The output is the form [`check_equivalence`] reads: a `node`,
+/// `relationship`, or `subject` list with `(identity id)`, `(label L)`, and
+/// `(property key value)` fields, then one list per element. Labels and
+/// properties are sorted, and the identity is omitted when empty, so equal
+/// patterns always write the same text.
+///
+/// [`check_equivalence`]: crate::check_equivalence
+///
+/// ```rust
+/// use gram_codec::{parse_gram, pattern_to_sexp};
+///
+/// let patterns = parse_gram("(alice:Person {age: 30})").unwrap();
+/// assert_eq!(
+///     pattern_to_sexp(&patterns[0]),
+///     "(node (identity alice) (label Person) (property age 30))"
+/// );
+/// ```
+pub fn pattern_to_sexp(pattern: &Pattern<Subject>) -> String {
+    Sexp::from_pattern(pattern).to_string()
+}
+
+/// Kind of a pattern by shape: `node`, `relationship`, or `subject`
+pub(crate) fn pattern_kind(pattern: &Pattern<Subject>) -> &'static str {
+    match pattern.elements.as_slice() {
+        [] => "node",
+        [first, second] if first.elements.is_empty() && second.elements.is_empty() => {
+            "relationship"
+        }
+        _ => "subject",
+    }
+}
+
+/// A property value as text: strings double-quoted, the rest as displayed
+pub(crate) fn value_text(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::VString(s) => {
+            out.push('"');
+            out.push_str(&escape_string(s));
+            out.push('"');
+        }
+        Value::VArray(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::VMap(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&quote_identifier(key));
+                out.push_str(": ");
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+struct SexpReader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl SexpReader<'_> {
+    fn read(&mut self) -> Result<Sexp, SexpError> {
+        if !self.eat('(') {
+            return Ok(Sexp::Atom(self.read_atom()?));
+        }
+
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => return Err(self.error("unclosed `(`")),
+                Some(')') => {
+                    self.pos += 1;
+                    return Ok(Sexp::List(items));
+                }
+                Some(_) => items.push(self.read()?),
+            }
+        }
+    }
+
+    fn read_atom(&mut self) -> Result<String, SexpError> {
+        let start = self.pos;
+        let mut depth = 0usize;
+        while let Some(c) = self.peek() {
+            match c {
+                '"' | '\'' | '`' => {
+                    self.pos += 1;
+                    self.skip_quoted(c)?;
+                    continue;
+                }
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth = depth.saturating_sub(1),
+                '(' | ')' if depth == 0 => break,
+                c if c.is_whitespace() && depth == 0 => break,
+                _ => {}
+            }
+            self.pos += c.len_utf8();
+        }
+        if depth > 0 {
+            return Err(self.error("unclosed bracket"));
+        }
+        if self.pos == start {
+            return Err(self.error("unexpected `)`"));
+        }
+        Ok(self.input[start..self.pos].to_string())
+    }
+
+    /// Skip to just past the closing `quote`, honouring backslash escapes
+    fn skip_quoted(&mut self, quote: char) -> Result<(), SexpError> {
+        let mut escaped = false;
+        while let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+            match c {
+                '\\' if !escaped => escaped = true,
+                c if c == quote && !escaped => return Ok(()),
+                _ => escaped = false,
+            }
+        }
+        Err(self.error("unclosed quote"))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, message: &str) -> SexpError {
+        SexpError {
+            offset: self.pos,
+            message: message.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_tree_sitter_format() {
+        let roots = Sexp::parse(
+            "(gram_pattern\n  root: (record)\n  (node_pattern identifier: (symbol)) (MISSING \")\"))",
+        )
+        .unwrap();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].kind(), Some("gram_pattern"));
+        assert_eq!(roots[0].field("root").and_then(Sexp::kind), Some("record"));
+        let node = &roots[0].items()[2];
+        assert_eq!(
+            node.field("identifier").and_then(Sexp::kind),
+            Some("symbol")
+        );
+        assert_eq!(
+            roots[0].items()[3].items(),
+            &[Sexp::Atom("\")\"".to_string())]
+        );
+    }
+
+    fn sexp_of(gram: &str) -> String {
+        let patterns = crate::parse_gram(gram).unwrap();
+        pattern_to_sexp(&patterns[0])
+    }
+
+    #[test]
+    fn test_pattern_to_sexp_node() {
+        assert_eq!(sexp_of("()"), "(node)");
+        assert_eq!(
+            sexp_of("(a:Person:Admin {name: 'Alice Smith', tags: [1, 2]})"),
+            "(node (identity a) (label Admin) (label Person) \
+             (property name \"Alice Smith\") (property tags [1, 2]))"
+        );
+        assert_eq!(
+            sexp_of("(`two words`:`Has Space`)"),
+            "(node (identity `two words`) (label `Has Space`))"
+        );
+    }
+
+    #[test]
+    fn test_pattern_to_sexp_relationship() {
+        assert_eq!(
+            sexp_of("(a)-[r:KNOWS {since: 2020}]->(b)"),
+            "(relationship (identity r) (label KNOWS) (property since 2020) \
+             (node (identity a)) (node (identity b)))"
+        );
+    }
+
+    #[test]
+    fn test_pattern_to_sexp_subject_pattern() {
+        assert_eq!(
+            sexp_of("[team:Team | (a), [sub | (b)], (c)-->(d)]"),
+            "(subject (identity team) (label Team) (node (identity a)) \
+             (subject (identity sub) (node (identity b))) \
+             (relationship (node (identity c)) (node (identity d))))"
+        );
+    }
+
+    #[test]
+    fn test_pattern_to_sexp_reads_back() {
+        for gram in [
+            "(a:Person {name: \"Alice\", score: 1.5})",
+            "(a)<-[:R]-(b)",
+            "[g | (a)-->(b)-->(c), (d {m: {k: 'v'}})]",
+        ] {
+            let patterns = crate::parse_gram(gram).unwrap();
+            let sexp = pattern_to_sexp(&patterns[0]);
+            assert_eq!(Sexp::parse(&sexp).unwrap()[0].to_string(), sexp);
+            assert!(crate::check_equivalence(gram, &sexp).is_ok(), "{}", sexp);
+        }
+    }
+
+    #[test]
+    fn test_read_errors() {
+        for (input, offset) in [
+            ("", 0),
+            ("(node", 5),
+            ("(node))", 6),
+            ("node", 0),
+            ("(a [1)", 6),
+        ] {
+            assert_eq!(Sexp::parse(input).unwrap_err().offset, offset, "{}", input);
+        }
+    }
+}
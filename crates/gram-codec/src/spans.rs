@@ -0,0 +1,201 @@
+//! Source spans of parsed patterns
+//!
+//! [`parse_gram_with_spans`] parses like [`parse_gram`](crate::parse_gram) and
+//! also returns, for each top-level pattern, a spine: a `Pattern<Span>` with
+//! the same shape as the parsed pattern, whose values are the source extents of
+//! the pattern and of each of its elements. Editors can use it to map a cursor
+//! position back to the pattern under it.
+//!
+//! ```rust
+//! use gram_codec::spans::parse_gram_with_spans;
+//!
+//! let input = "(alice)-[:KNOWS]->(bob)\n(café)";
+//! let parsed = parse_gram_with_spans(input).unwrap();
+//!
+//! let (knows, spine) = &parsed[0];
+//! assert_eq!(spine.value.start.offset, 0);
+//! assert_eq!(spine.value.end.offset, 23);
+//! // `bob` is the relationship's second element
+//! let cursor = input.find("bob").unwrap();
+//! let under_cursor = spine.elements.iter().position(|e| e.value.contains(cursor));
+//! assert_eq!(under_cursor, Some(1));
+//! assert_eq!(knows.elements[1].value.identity.0, "bob");
+//!
+//! // Columns count characters, not bytes
+//! let (_, spine) = &parsed[1];
+//! assert_eq!((spine.value.end.line, spine.value.end.column), (2, 7));
+//! ```
+
+use crate::parser::{combinators::ws, subject::record, ParseContext, RawSpan};
+use crate::{ParseError, ParseOptions};
+use pattern_core::{Pattern, Subject};
+
+pub use crate::parser::{Location, Span};
+
+/// Parse gram notation, returning each top-level pattern with its spans
+///
+/// The patterns are exactly those returned by [`parse_gram`](crate::parse_gram).
+/// Each comes with a spine of the same shape: the spine's value is the span
+/// of the whole pattern, from its first character to just past its last,
+/// excluding surrounding whitespace and comments, and its elements are the
+/// spines of the pattern's elements.
+///
+/// Elements that are written in the source have their own extent: a node, a
+/// bare identifier reference, a bracketed subject pattern, or the pattern an
+/// annotation applies to. A path `(a)-->(b)-->(c)` parses to nested
+/// relationships; each spans from `(a)` to the end of its own target node. A
+/// backward arrow stores its elements target-first, and the spine follows, so
+/// element spans may be out of source order. A leading record spans its
+/// braces.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(Pattern<Subject>, Pattern<Span>)>)` - Each top-level pattern with its spine
+/// * `Err(ParseError)` - As from [`parse_gram`](crate::parse_gram)
+#[allow(clippy::type_complexity)]
+pub fn parse_gram_with_spans(
+    input: &str,
+) -> Result<Vec<(Pattern<Subject>, Pattern<Span>)>, ParseError> {
+    let ctx = ParseContext::default().recording_spans();
+    let patterns = crate::parse_gram_with_context(&ctx, input, &ParseOptions::default())?;
+    let raw = ctx.take_spans();
+
+    let index = LineIndex::new(input);
+    let base = input.as_ptr() as usize;
+    let mut spines = Vec::with_capacity(patterns.len());
+
+    // The leading record is not a pattern to the parser, so it has no raw span
+    if patterns.len() > raw.len() {
+        let start = ws(input).map_or(input, |(rest, _)| rest);
+        let end = record(&ctx, start).map_or(start, |(rest, _)| rest);
+        spines.push(Pattern::point(Span::new(
+            index.locate(start.as_ptr() as usize - base),
+            index.locate(end.as_ptr() as usize - base),
+        )));
+    }
+    spines.extend(raw.into_iter().map(|span| index.spine(span, base)));
+
+    Ok(patterns.into_iter().zip(spines).collect())
+}
+
+/// Start offset of every line, for locating many offsets in one input
+struct LineIndex<'a> {
+    input: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(input: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(input.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { input, line_starts }
+    }
+
+    /// Same as [`Location::from_offset`], without rescanning earlier lines
+    fn locate(&self, offset: usize) -> Location {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        Location::in_line(self.input, line, self.line_starts[line - 1], offset)
+    }
+
+    fn spine(&self, span: RawSpan, base: usize) -> Pattern<Span> {
+        Pattern::pattern(
+            Span::new(self.locate(span.start - base), self.locate(span.end - base)),
+            span.elements
+                .into_iter()
+                .map(|element| self.spine(element, base))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_gram;
+
+    /// Source text of each span in `spine`, as a pattern of the same shape
+    fn texts<'a>(input: &'a str, spine: &Pattern<Span>) -> Pattern<&'a str> {
+        Pattern::pattern(
+            &input[spine.value.start.offset..spine.value.end.offset],
+            spine.elements.iter().map(|e| texts(input, e)).collect(),
+        )
+    }
+
+    fn shape<V>(pattern: &Pattern<V>) -> Pattern<()> {
+        Pattern::pattern((), pattern.elements.iter().map(shape).collect())
+    }
+
+    #[test]
+    fn test_spines_match_pattern_shapes() {
+        let input = "{v: 1}\n\
+                     (a:Person {k: 'x'})\n\
+                     (a)-[r:KNOWS]->(b)<--(c)\n\
+                     [team:Group | alice, (bob), [sub | (c)-->(d)], ]\n\
+                     @x(1) @y(2) [w | a]\n\
+                     /* comment */ (e) // trailing";
+        let parsed = parse_gram_with_spans(input).unwrap();
+        assert_eq!(
+            parsed.iter().map(|(p, _)| p.clone()).collect::<Vec<_>>(),
+            parse_gram(input).unwrap()
+        );
+        for (pattern, spine) in &parsed {
+            assert_eq!(shape(pattern), shape(spine));
+        }
+
+        let texts: Vec<Pattern<&str>> = parsed.iter().map(|(_, s)| texts(input, s)).collect();
+        assert_eq!(texts[0].value, "{v: 1}");
+        assert_eq!(texts[1].value, "(a:Person {k: 'x'})");
+
+        // [ | [ | (a), (b)], (c)] with the backward last segment stored target-first
+        let path = &texts[2];
+        assert_eq!(path.value, "(a)-[r:KNOWS]->(b)<--(c)");
+        assert_eq!(path.elements[0].value, "(c)");
+        assert_eq!(path.elements[1].value, "(a)-[r:KNOWS]->(b)");
+        assert_eq!(path.elements[1].elements[1].value, "(b)");
+
+        let team = &texts[3];
+        assert_eq!(
+            team.value,
+            "[team:Group | alice, (bob), [sub | (c)-->(d)], ]"
+        );
+        let elements: Vec<&str> = team.elements.iter().map(|e| e.value).collect();
+        assert_eq!(elements, ["alice", "(bob)", "[sub | (c)-->(d)]"]);
+        assert_eq!(team.elements[2].elements[0].value, "(c)-->(d)");
+
+        assert_eq!(texts[4].value, "@x(1) @y(2) [w | a]");
+        assert_eq!(texts[4].elements[0].value, "[w | a]");
+        assert_eq!(texts[5].value, "(e)");
+    }
+
+    #[test]
+    fn test_locations_match_from_offset() {
+        let input = "(café)\r\n(añu)-->(b)\n  [g | (bü)]";
+        for (_, spine) in parse_gram_with_spans(input).unwrap() {
+            let mut pending = vec![&spine];
+            while let Some(spine) = pending.pop() {
+                for location in [spine.value.start, spine.value.end] {
+                    assert_eq!(location, Location::from_offset(input, location.offset));
+                }
+                pending.extend(&spine.elements);
+            }
+        }
+
+        let parsed = parse_gram_with_spans(input).unwrap();
+        let cafe = parsed[0].1.value;
+        assert_eq!((cafe.start.column, cafe.end.column), (1, 7));
+        assert_eq!(cafe.end.offset, 7);
+        let b = parsed[1].1.elements[1].value;
+        assert_eq!((b.start.line, b.start.column), (2, 9));
+    }
+
+    #[test]
+    fn test_empty_input_and_errors() {
+        assert!(parse_gram_with_spans("").unwrap().is_empty());
+        assert!(parse_gram_with_spans("  // nothing\n").unwrap().is_empty());
+        assert!(parse_gram_with_spans("(a) (b").is_err());
+        // Recording stops with the parse
+        parse_gram("(a)").unwrap();
+        assert_eq!(parse_gram_with_spans("(a)").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,250 @@
+//! Template matching against graphs
+//!
+//! [`match_template`] parses a gram template in which subject identities may be
+//! variables (`?name`) and finds every way it matches a graph, returning the
+//! graph elements each variable is bound to.
+//!
+//! # Example
+//!
+//! ```rust
+//! use gram_codec::{match_template, FromGram};
+//! use pattern_core::StandardGraph;
+//!
+//! let graph = StandardGraph::from_gram(
+//!     "(alice:Person)-[k1:KNOWS]->(bob:Person) (bob:Person)-[l1:LIKES]->(carol)",
+//! )
+//! .unwrap();
+//!
+//! let matches = match_template(&graph.as_query(), "(?x:Person)-[:KNOWS]->(?y)").unwrap();
+//! assert_eq!(matches.len(), 1);
+//! assert_eq!(matches[0]["x"].value.identity.as_str(), "alice");
+//! assert_eq!(matches[0]["y"].value.identity.as_str(), "bob");
+//! ```
+
+use crate::parser::{ParseContext, ParseError};
+use pattern_core::{GraphQuery, Pattern, Subject, Symbol};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Variable bindings for one match, keyed by variable name without the `?`
+pub type Bindings = HashMap<String, Pattern<Subject>>;
+
+/// Error returned by [`match_template`]
+#[derive(Debug, Error)]
+pub enum TemplateError {
+    /// The template is not valid gram notation with `?name` variables
+    #[error("failed to parse template: {0}")]
+    Parse(#[from] ParseError),
+
+    /// The template parses but is not a shape that can be matched
+    #[error("unsupported template: {0}")]
+    Unsupported(String),
+}
+
+/// Match a single-hop gram template against a graph
+///
+/// The template is one node, e.g. `(?x:Person {active: true})`, or one
+/// relationship, e.g. `(?x:Person)-[?r:KNOWS]->(?y)`. Each subject in it
+/// constrains the graph element it matches:
+///
+/// - identity `?name` matches any identity and binds the element to `name`;
+///   an empty identity matches any identity without binding it; any other
+///   identity must match exactly
+/// - every label in the template must be present on the element
+/// - every property in the template must be present with an equal value
+///
+/// A variable used more than once must bind the same identity everywhere, so
+/// `(?x)-->(?x)` matches only self-loops.
+///
+/// Relationships are matched from source to target. Backward arrows are
+/// stored source-first by the parser, so `(?a)<--(?b)` matches relationships
+/// from `b` to `a`. The [`Direction`](crate::Direction) of the template's
+/// arrow is otherwise ignored: undirected (`--`) and bidirectional (`<-->`)
+/// arrows match like `-->`, whichever arrow a graph relationship was written
+/// with. As when serializing, a pattern with exactly
+/// two atomic elements is read as a relationship.
+///
+/// # Arguments
+///
+/// * `q` - Graph to match against
+/// * `template` - Gram notation for one node or relationship, with `?name` variables
+///
+/// # Returns
+///
+/// * `Ok(Vec<Bindings>)` - One entry per match, sorted by the bound identities
+///   in variable-name order
+/// * `Err(TemplateError)` - The template does not parse or is not single-hop
+pub fn match_template(
+    q: &GraphQuery<Subject>,
+    template: &str,
+) -> Result<Vec<Bindings>, TemplateError> {
+    let ctx = ParseContext::default().with_template_variables();
+    let patterns = crate::parse_gram_with_context(&ctx, template, &crate::ParseOptions::default())?;
+
+    let pattern = match patterns.as_slice() {
+        [pattern] => pattern,
+        _ => {
+            return Err(TemplateError::Unsupported(format!(
+                "expected one node or relationship, found {} patterns",
+                patterns.len()
+            )))
+        }
+    };
+
+    let mut matches = Vec::new();
+    match pattern.elements.as_slice() {
+        [] => {
+            for node in (q.query_nodes)() {
+                let mut bindings = Bindings::new();
+                if bind(&pattern.value, &node, &mut bindings) {
+                    matches.push(bindings);
+                }
+            }
+        }
+        [source, target] if source.is_atomic() && target.is_atomic() => {
+            // The arrow only orders the endpoints; it is not a property to match
+            let mut relationship = pattern.value.clone();
+            relationship.set_direction(crate::Direction::Forward);
+            for rel in (q.query_relationships)() {
+                let (Some(src), Some(tgt)) = ((q.query_source)(&rel), (q.query_target)(&rel))
+                else {
+                    continue;
+                };
+                let mut bindings = Bindings::new();
+                if bind(&relationship, &rel, &mut bindings)
+                    && bind(&source.value, &src, &mut bindings)
+                    && bind(&target.value, &tgt, &mut bindings)
+                {
+                    matches.push(bindings);
+                }
+            }
+        }
+        _ => {
+            return Err(TemplateError::Unsupported(
+                "only single-hop templates (one node or one relationship) are supported"
+                    .to_string(),
+            ))
+        }
+    }
+
+    matches.sort_by_cached_key(sort_key);
+    Ok(matches)
+}
+
+/// Match one template subject against a graph element, extending `bindings`
+fn bind(template: &Subject, element: &Pattern<Subject>, bindings: &mut Bindings) -> bool {
+    let subject = &element.value;
+
+    if !template.labels.is_subset(&subject.labels) {
+        return false;
+    }
+    if !template
+        .properties
+        .iter()
+        .all(|(key, value)| subject.properties.get(key) == Some(value))
+    {
+        return false;
+    }
+
+    let identity = template.identity.as_str();
+    match identity.strip_prefix('?') {
+        Some(name) => match bindings.get(name) {
+            Some(bound) => bound.value.identity == subject.identity,
+            None => {
+                bindings.insert(name.to_string(), element.clone());
+                true
+            }
+        },
+        None => identity.is_empty() || identity == subject.identity.as_str(),
+    }
+}
+
+fn sort_key(bindings: &Bindings) -> Vec<(String, Symbol)> {
+    let mut key: Vec<(String, Symbol)> = bindings
+        .iter()
+        .map(|(name, element)| (name.clone(), element.value.identity.clone()))
+        .collect();
+    key.sort();
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FromGram;
+    use pattern_core::StandardGraph;
+
+    fn graph() -> GraphQuery<Subject> {
+        StandardGraph::from_gram(
+            "(alice:Person {age: 30})-[k1:KNOWS]->(bob:Person) \
+             (bob:Person)-[k2:KNOWS]->(carol:Robot) \
+             (carol:Robot)-[k3:KNOWS]->(carol:Robot) \
+             (bob:Person)-[l1:LIKES]->(alice:Person {age: 30})",
+        )
+        .unwrap()
+        .as_query()
+    }
+
+    fn ids(matches: &[Bindings], var: &str) -> Vec<String> {
+        matches
+            .iter()
+            .map(|b| b[var].value.identity.as_str().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_node_template() {
+        let q = graph();
+        let matches = match_template(&q, "(?p:Person)").unwrap();
+        assert_eq!(ids(&matches, "p"), ["alice", "bob"]);
+
+        let matches = match_template(&q, "(?p {age: 30})").unwrap();
+        assert_eq!(ids(&matches, "p"), ["alice"]);
+    }
+
+    #[test]
+    fn test_relationship_template_binds_all_variables() {
+        let matches = match_template(&graph(), "(?x:Person)-[?r:KNOWS]->(?y)").unwrap();
+        assert_eq!(ids(&matches, "x"), ["alice", "bob"]);
+        assert_eq!(ids(&matches, "r"), ["k1", "k2"]);
+        assert_eq!(ids(&matches, "y"), ["bob", "carol"]);
+    }
+
+    #[test]
+    fn test_concrete_and_anonymous_subjects() {
+        let q = graph();
+        let matches = match_template(&q, "(bob)-->(?y)").unwrap();
+        assert_eq!(ids(&matches, "y"), ["alice", "carol"]);
+        assert_eq!(matches[0].len(), 1);
+
+        let matches = match_template(&q, "(?a)<-[:LIKES]-()").unwrap();
+        assert_eq!(ids(&matches, "a"), ["alice"]);
+    }
+
+    #[test]
+    fn test_repeated_variable_must_be_consistent() {
+        let matches = match_template(&graph(), "(?x)-[:KNOWS]->(?x)").unwrap();
+        assert_eq!(ids(&matches, "x"), ["carol"]);
+    }
+
+    #[test]
+    fn test_variables_only_enabled_for_templates() {
+        assert!(crate::parse_gram("(?x)").is_err());
+        assert!(match_template(&graph(), "(?x").is_err());
+    }
+
+    #[test]
+    fn test_unsupported_templates() {
+        let q = graph();
+        for template in ["(?a)-->(?b)-->(?c)", "(?a) (?b)", ""] {
+            assert!(
+                matches!(
+                    match_template(&q, template),
+                    Err(TemplateError::Unsupported(_))
+                ),
+                "{}",
+                template
+            );
+        }
+    }
+}
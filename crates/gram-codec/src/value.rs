@@ -1,5 +1,6 @@
 //! Value enum for heterogeneous property types in Gram notation
 
+use std::collections::BTreeMap;
 use std::fmt;
 
 /// Represents all possible value types in Gram notation property records.
@@ -33,6 +34,14 @@ pub enum Value {
     /// Tagged string with format identifier
     /// Example: `"""markdown # Heading"""`
     TaggedString { tag: String, content: String },
+
+    /// Map of values keyed by name (keys kept sorted for stable output)
+    /// Example: `{system: "db", timestamp: 2024}`
+    Map(BTreeMap<String, Value>),
+
+    /// Explicit null
+    /// Example: `null`
+    Null,
 }
 
 impl Value {
@@ -131,6 +140,20 @@ impl Value {
                     format!("{}`{}`", tag, escaped)
                 }
             }
+            Value::Map(entries) => {
+                let items: Vec<String> = entries
+                    .iter()
+                    .map(|(key, value)| {
+                        format!(
+                            "{}: {}",
+                            crate::serializer::quote_identifier(key),
+                            value.to_gram_notation()
+                        )
+                    })
+                    .collect();
+                format!("{{{}}}", items.join(", "))
+            }
+            Value::Null => "null".to_string(),
         }
     }
 
@@ -144,6 +167,8 @@ impl Value {
             Value::Array(_) => "array",
             Value::Range { .. } => "range",
             Value::TaggedString { .. } => "tagged string",
+            Value::Map(_) => "map",
+            Value::Null => "null",
         }
     }
 
@@ -187,6 +212,8 @@ impl PartialEq for Value {
                     content: c2,
                 },
             ) => t1 == t2 && c1 == c2,
+            (Value::Map(a), Value::Map(b)) => a == b,
+            (Value::Null, Value::Null) => true,
             _ => false,
         }
     }
@@ -195,12 +222,24 @@ impl PartialEq for Value {
 // Helper Functions
 
 /// Escape special characters in strings
+///
+/// Produces only escapes the parser's quoted-string decoder accepts, so
+/// any string survives a serialize/parse round trip.
 pub(crate) fn escape_string(s: &str) -> String {
-    s.replace('\\', "\\\\")
-        .replace('"', "\\\"")
-        .replace('\n', "\\n")
-        .replace('\t', "\\t")
-        .replace('\r', "\\r")
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\u{0008}' => escaped.push_str("\\b"),
+            '\u{000C}' => escaped.push_str("\\f"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
 }
 
 /// Format decimal to avoid unnecessary trailing zeros while distinguishing from integers
@@ -275,6 +314,21 @@ mod tests {
         assert_eq!(Value::Array(vec![]).to_gram_notation(), "[]");
     }
 
+    #[test]
+    fn test_map_value_serialization() {
+        let v = Value::Map(BTreeMap::from([
+            ("timestamp".to_string(), Value::Integer(2024)),
+            ("system".to_string(), Value::String("db".to_string())),
+            ("first name".to_string(), Value::Boolean(true)),
+        ]));
+        // Keys are sorted and quoted when needed
+        assert_eq!(
+            v.to_gram_notation(),
+            "{`first name`: true, system: \"db\", timestamp: 2024}"
+        );
+        assert_eq!(Value::Map(BTreeMap::new()).to_gram_notation(), "{}");
+    }
+
     #[test]
     fn test_range_value_serialization() {
         let v = Value::Range {
@@ -372,6 +426,8 @@ mod tests {
         assert_eq!(escape_string("line1\nline2"), "line1\\nline2");
         assert_eq!(escape_string("tab\there"), "tab\\there");
         assert_eq!(escape_string("back\\slash"), "back\\\\slash");
+        assert_eq!(escape_string("feed\u{000C}\u{0008}"), "feed\\f\\b");
+        assert_eq!(escape_string("🎉 \r"), "🎉 \\r");
     }
 
     #[test]
@@ -120,6 +120,43 @@ pub fn round_trip(input: &str) -> Result<String, JsValue> {
     crate::to_gram(&patterns).map_err(|e| JsValue::from_str(&format!("Serialize error: {}", e)))
 }
 
+/// Check that gram notation survives parse → serialize → parse unchanged
+///
+/// Lets an editor warn that a document will not round-trip cleanly before it
+/// is saved. Input that does not parse is reported as not round-tripping.
+///
+/// # Arguments
+///
+/// * `input` - Gram notation to check
+///
+/// # Returns
+///
+/// An object `{ ok: boolean, diff?: string }`. When `ok` is `false`, `diff`
+/// describes the first divergence, e.g.
+/// `patterns[0].elements[1]: labels ["A"] became ["A", "B"]`.
+#[wasm_bindgen(js_name = roundTripCheck)]
+pub fn round_trip_check(input: &str) -> JsValue {
+    let diff = match crate::round_trip_diff(input) {
+        Ok(diff) => diff,
+        Err(e) => Some(format!("Parse error: {}", e)),
+    };
+
+    let result = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(
+        &result,
+        &JsValue::from_str("ok"),
+        &JsValue::from_bool(diff.is_none()),
+    );
+    if let Some(diff) = diff {
+        let _ = js_sys::Reflect::set(
+            &result,
+            &JsValue::from_str("diff"),
+            &JsValue::from_str(&diff),
+        );
+    }
+    result.into()
+}
+
 /// Parse gram notation to AST (JavaScript-friendly)
 ///
 /// Returns a single pattern as a JavaScript object.
@@ -1,6 +1,10 @@
 //! Arrow type and relationship pattern tests for Phase 5
 
-use gram_codec::{parse_gram_notation, to_gram_pattern};
+use gram_codec::{
+    normalize_relationship_directions, parse_gram_notation, to_gram, to_gram_pattern,
+    to_gram_with_options, Direction, ParseError, SerializeError, SerializeOptions,
+};
+use pattern_core::Value;
 
 #[test]
 fn test_right_arrow_simple() {
@@ -97,6 +101,178 @@ fn test_round_trip_left_arrow() {
     assert!(serialized.contains("(a)"));
 }
 
+#[test]
+fn test_backward_relationships_are_stored_forward() {
+    // Backward arrows are normalized at parse time: elements are always
+    // [source, target], so the backward form is the same edge as the forward
+    // one, apart from the recorded direction
+    let pairs = [
+        ("(b)<--(a)", "(a)-->(b)"),
+        ("(b)<==(a)", "(a)==>(b)"),
+        ("(b)<~~(a)", "(a)~~>(b)"),
+        (
+            "(b:Person)<-[r:KNOWS {since: 2020}]-(a:Person)",
+            "(a:Person)-[r:KNOWS {since: 2020}]->(b:Person)",
+        ),
+    ];
+
+    for (backward, forward) in pairs {
+        let mut backward_patterns = parse_gram_notation(backward).unwrap();
+        let forward_patterns = parse_gram_notation(forward).unwrap();
+        assert_eq!(
+            backward_patterns[0].value.direction(),
+            Direction::Backward,
+            "{}",
+            backward
+        );
+        assert_eq!(
+            forward_patterns[0].value.direction(),
+            Direction::Forward,
+            "{}",
+            forward
+        );
+        backward_patterns[0].value.set_direction(Direction::Forward);
+        assert_eq!(backward_patterns, forward_patterns, "{}", backward);
+    }
+}
+
+#[test]
+fn test_normalize_relationship_directions() {
+    for (backward, forward) in [
+        ("(b)<--(a)", "(a)-->(b)"),
+        ("(b)<==(a)", "(a)==>(b)"),
+        ("(b)<~~(a)", "(a)~~>(b)"),
+        (
+            "(b)<-[r:KNOWS {since: 2020}]-(a)",
+            "(a)-[r:KNOWS {since: 2020}]->(b)",
+        ),
+    ] {
+        let mut patterns = parse_gram_notation(backward).unwrap();
+        normalize_relationship_directions(&mut patterns);
+        assert_eq!(
+            patterns[0].value.direction(),
+            Direction::Forward,
+            "{}",
+            backward
+        );
+        assert_eq!(
+            patterns[0].elements[0].value.identity.0, "a",
+            "{}",
+            backward
+        );
+        assert_eq!(
+            patterns,
+            parse_gram_notation(forward).unwrap(),
+            "{}",
+            backward
+        );
+        assert_eq!(
+            to_gram(&patterns).unwrap(),
+            to_gram(&parse_gram_notation(forward).unwrap()).unwrap(),
+            "{} should serialize with a forward arrow",
+            backward
+        );
+    }
+
+    // Nested relationships are normalized too; undirected ones are kept
+    let mut nested = parse_gram_notation("[g | (b)<--(a), (c)--(d)]").unwrap();
+    normalize_relationship_directions(&mut nested);
+    assert_eq!(nested[0].elements[0].value.direction(), Direction::Forward);
+    assert_eq!(
+        nested[0].elements[1].value.direction(),
+        Direction::Undirected
+    );
+    assert_eq!(to_gram(&nested).unwrap(), "[g | (a)-->(b), (c)--(d)]");
+}
+
+#[test]
+fn test_round_trip_arrow_directions() {
+    for gram in [
+        "(a)-->(b)",
+        "(a)<--(b)",
+        "(a)--(b)",
+        "(a)-[:OWNS]->(b)",
+        "(a)<-[:OWNS]-(b)",
+        "(a)-[:OWNS]-(b)",
+        "(a)<-[r:KNOWS {since: 2020}]-(b)",
+    ] {
+        let parsed = parse_gram_notation(gram).unwrap();
+        assert_eq!(to_gram(&parsed).unwrap(), gram);
+        assert_eq!(
+            parse_gram_notation(&to_gram(&parsed).unwrap()).unwrap(),
+            parsed
+        );
+    }
+
+    let undirected = parse_gram_notation("(a)-[:R]-(b)").unwrap();
+    assert_eq!(undirected[0].value.direction(), Direction::Undirected);
+    assert_eq!(undirected[0].elements[0].value.identity.0, "a");
+    assert_eq!(
+        undirected[0].value.properties.get("_direction"),
+        Some(&Value::VSymbol("undirected".to_string()))
+    );
+}
+
+#[test]
+fn test_relationship_records_cannot_set_reserved_keys() {
+    // The arrow and quantifier own these keys, so writing them in the record
+    // is an error rather than a value the arrow would replace
+    for gram in [
+        "(a)-[r {_direction: backward}]->(b)",
+        "(a)<-[r {_direction: forward}]-(b)",
+        r#"(a)-[{_direction: "backward"}]->(b)"#,
+        "(a)-[:KNOWS {_quantifier: 1..3}]->(b)",
+        "(a)-[:KNOWS*2 {_quantifier: 1..3}]->(b)",
+        "(a)-->(b)-[{_direction: undirected}]-(c)",
+    ] {
+        match parse_gram_notation(gram) {
+            Err(ParseError::InvalidValue { kind, .. }) => {
+                assert_eq!(kind, "relationship record", "{}", gram)
+            }
+            other => panic!("{}: expected InvalidValue, got {:?}", gram, other),
+        }
+    }
+
+    // Elsewhere they are ordinary properties
+    let gram = r#"(a {_direction: "backward"})"#;
+    let node = parse_gram_notation(gram).unwrap();
+    assert_eq!(
+        node[0].value.properties["_direction"],
+        Value::VString("backward".to_string())
+    );
+    assert_eq!(to_gram(&node).unwrap(), gram);
+}
+
+#[test]
+fn test_reserved_keys_the_arrow_cannot_express_are_not_serialized() {
+    let mut relationship = parse_gram_notation("(a)-[r]->(b)").unwrap();
+    relationship[0].value.properties.insert(
+        "_direction".to_string(),
+        Value::VString("backward".to_string()),
+    );
+    assert!(matches!(
+        to_gram(&relationship),
+        Err(SerializeError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn test_backward_relationships_are_not_chained() {
+    let patterns = parse_gram_notation("(a)-->(b) (c)<--(b) (b)-->(e) (c)--(d)").unwrap();
+    let options = SerializeOptions {
+        path_chaining: true,
+        ..Default::default()
+    };
+    let serialized = to_gram_with_options(&patterns, &options).unwrap();
+    assert_eq!(serialized, "(a)-->(b)-->(e)\n(c)<--(b)\n(c)--(d)");
+
+    // Parsed paths keep each segment's arrow
+    let path = parse_gram_notation("(a)<--(b)-->(c)--(d)").unwrap();
+    let serialized = to_gram_with_options(&path, &options).unwrap();
+    assert_eq!(serialized, "(a)<--(b)-->(c)--(d)");
+    assert_eq!(parse_gram_notation(&serialized).unwrap(), path);
+}
+
 #[test]
 fn test_round_trip_bidirectional() {
     let original = "(a)<-->(b)";
@@ -4,6 +4,8 @@
 //! tree-sitter-gram test corpus, validating that the nom parser produces
 //! semantically equivalent results.
 
+#[cfg(feature = "parallel-corpus")]
+use rayon::prelude::*;
 use std::path::{Path, PathBuf};
 
 pub mod parser;
@@ -79,6 +81,61 @@ impl CorpusTest {
             },
         }
     }
+
+    /// Build a variant of this test with `//` comments woven into the input.
+    ///
+    /// The input is wrapped in a leading comment line and a trailing comment
+    /// with no final newline. When the input has no string literals, a comment
+    /// line is also inserted after every line; inputs with strings are only
+    /// wrapped, since a comment line inside a multi-line string would change
+    /// its content. The expected S-expression is unchanged.
+    pub fn with_comments(&self) -> CorpusTest {
+        let body = if self.input.contains(['"', '\'', '`']) {
+            self.input.clone()
+        } else {
+            self.input
+                .lines()
+                .map(|line| format!("{}\n// after: {}", line, line.trim()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        CorpusTest {
+            name: format!("{} (with comments)", self.name),
+            input: format!("// corpus: {}\n{}\n// end of input", self.name, body),
+            ..self.clone()
+        }
+    }
+
+    /// Run the commented variant of this test.
+    ///
+    /// Passes when the commented input parses to exactly the same patterns as
+    /// the original input and those patterns still match the expected
+    /// S-expression.
+    pub fn run_with_comments(&self) -> CorpusTestResult {
+        use gram_codec::parse_gram;
+
+        if self.is_error {
+            return CorpusTestResult::SkippedExpectedError;
+        }
+
+        let commented = self.with_comments();
+        let result = commented.run();
+        if !result.is_pass() {
+            return result;
+        }
+
+        match (parse_gram(&self.input), parse_gram(&commented.input)) {
+            (Ok(plain), Ok(with_comments)) if plain != with_comments => {
+                CorpusTestResult::Mismatch {
+                    expected: format!("{:?}", plain),
+                    actual: format!("{:?}", with_comments),
+                    message: "Comments changed the parsed patterns".to_string(),
+                }
+            }
+            _ => result,
+        }
+    }
 }
 
 /// Result of running a corpus test
@@ -201,4 +258,48 @@ impl CorpusTestSuite {
             .map(|test| (test.clone(), test.run()))
             .collect()
     }
+
+    /// Run the commented variant of every test and return results
+    pub fn run_with_comments(&self) -> Vec<(CorpusTest, CorpusTestResult)> {
+        self.tests
+            .iter()
+            .map(|test| (test.with_comments(), test.run_with_comments()))
+            .collect()
+    }
+
+    /// Run all tests in parallel and return results sorted by source file and
+    /// line
+    ///
+    /// Tests are independent, so the results are the same as [`Self::run`];
+    /// only the order differs, and it does not depend on scheduling or on the
+    /// order the corpus files were loaded in. Requires the `parallel-corpus`
+    /// feature.
+    #[cfg(feature = "parallel-corpus")]
+    pub fn run_parallel(&self) -> Vec<(CorpusTest, CorpusTestResult)> {
+        let mut results: Vec<_> = self
+            .tests
+            .par_iter()
+            .map(|test| (test.clone(), test.run()))
+            .collect();
+
+        results.sort_by(|(a, _), (b, _)| (&a.source_file, a.line).cmp(&(&b.source_file, b.line)));
+        results
+    }
+
+    /// Run all tests in order, calling `progress(completed, total)` after each one
+    pub fn run_with_progress(
+        &self,
+        progress: impl Fn(usize, usize),
+    ) -> Vec<(CorpusTest, CorpusTestResult)> {
+        let total = self.tests.len();
+        self.tests
+            .iter()
+            .enumerate()
+            .map(|(i, test)| {
+                let result = test.run();
+                progress(i + 1, total);
+                (test.clone(), result)
+            })
+            .collect()
+    }
 }
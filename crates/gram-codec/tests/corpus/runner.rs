@@ -158,6 +158,12 @@ pub fn run_suite(suite: &CorpusTestSuite) -> CorpusTestReport {
     CorpusTestReport::from_results(results)
 }
 
+/// Run a corpus test suite with comments woven into every input and generate a report
+pub fn run_suite_with_comments(suite: &CorpusTestSuite) -> CorpusTestReport {
+    let results = suite.run_with_comments();
+    CorpusTestReport::from_results(results)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,4 +229,83 @@ mod tests {
         assert_eq!(stats.failed, 0);
         assert_eq!(stats.pass_rate, 100.0);
     }
+
+    #[test]
+    fn test_run_suite_with_comments() {
+        let mut suite = CorpusTestSuite::new(PathBuf::from("corpus"));
+        suite.tests.push(CorpusTest::new(
+            "Two Patterns".to_string(),
+            PathBuf::from("test.txt"),
+            1,
+            "()-->()\n()".to_string(),
+            "(gram_pattern\n  (relationship_pattern)\n  (node_pattern))".to_string(),
+            false,
+        ));
+        suite.tests.push(CorpusTest::new(
+            "String Property".to_string(),
+            PathBuf::from("test.txt"),
+            5,
+            "({note: \"// not a comment\"})".to_string(),
+            "(gram_pattern\n  (node_pattern\n    (record\n      (record_property))))".to_string(),
+            false,
+        ));
+
+        let commented = suite.tests[0].with_comments();
+        assert_eq!(
+            commented.input,
+            "// corpus: Two Patterns\n()-->()\n// after: ()-->()\n()\n// after: ()\n// end of input"
+        );
+
+        let report = run_suite_with_comments(&suite);
+        assert_eq!(report.stats.total, 2);
+        assert_eq!(report.stats.failed, 0, "{:?}", report.results);
+    }
+
+    fn node_test(name: &str, file: &str, line: usize, input: &str) -> CorpusTest {
+        CorpusTest::new(
+            name.to_string(),
+            PathBuf::from(file),
+            line,
+            input.to_string(),
+            "(gram_pattern\n  (node_pattern))".to_string(),
+            false,
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "parallel-corpus")]
+    fn test_run_parallel_sorts_by_file_and_line() {
+        let mut suite = CorpusTestSuite::new(PathBuf::from("corpus"));
+        suite.tests.push(node_test("B 20", "b.txt", 20, "()"));
+        suite.tests.push(node_test("A 9", "a.txt", 9, "(x"));
+        suite.tests.push(node_test("B 3", "b.txt", 3, "()"));
+        suite.tests.push(node_test("A 12", "a.txt", 12, "()"));
+
+        let results = suite.run_parallel();
+        let names: Vec<&str> = results.iter().map(|(t, _)| t.name.as_str()).collect();
+        assert_eq!(names, ["A 9", "A 12", "B 3", "B 20"]);
+
+        let stats = CorpusTestStats::from_results(&results);
+        let sequential = CorpusTestStats::from_results(&suite.run());
+        assert_eq!(
+            (stats.passed, stats.failed),
+            (sequential.passed, sequential.failed)
+        );
+        assert_eq!(stats.failed, 1);
+    }
+
+    #[test]
+    fn test_run_with_progress_reports_each_test() {
+        use std::cell::RefCell;
+
+        let mut suite = CorpusTestSuite::new(PathBuf::from("corpus"));
+        suite.tests.push(node_test("One", "test.txt", 1, "()"));
+        suite.tests.push(node_test("Two", "test.txt", 5, "()"));
+
+        let calls = RefCell::new(Vec::new());
+        let results = suite.run_with_progress(|done, total| calls.borrow_mut().push((done, total)));
+        assert_eq!(calls.into_inner(), [(1, 2), (2, 2)]);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, r)| r.is_pass()));
+    }
 }
@@ -47,6 +47,38 @@ fn test_corpus_conformance() {
     }
 }
 
+#[test]
+fn test_corpus_conformance_with_comments() {
+    let corpus_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../external/tree-sitter-gram/test/corpus");
+
+    let suite = CorpusTestSuite::load(&corpus_path).expect("Failed to load corpus test suite");
+
+    // Comments are skipped by the parser: every case that passes plainly must
+    // also pass, with identical patterns, once comments are woven in
+    let plain = runner::run_suite(&suite);
+    let commented = runner::run_suite_with_comments(&suite);
+
+    commented.print_summary();
+
+    let regressions: Vec<_> = plain
+        .results
+        .iter()
+        .zip(commented.results.iter())
+        .filter(|((_, plain), (_, commented))| plain.is_pass() && !commented.is_pass())
+        .map(|(_, (test, result))| (test.clone(), result.clone()))
+        .collect();
+
+    if !regressions.is_empty() {
+        runner::CorpusTestReport::from_results(regressions.clone()).print_failures();
+    }
+    assert!(
+        regressions.is_empty(),
+        "{} corpus tests fail only when comments are added",
+        regressions.len()
+    );
+}
+
 #[test]
 fn test_load_corpus_suite() {
     let corpus_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -0,0 +1,65 @@
+//! Bulk edit integration tests
+
+use gram_codec::{parse_gram, set_property_where, to_gram, with_property_where};
+use pattern_core::Value;
+
+fn migrated(patterns: &[gram_codec::Pattern<gram_codec::Subject>]) -> Vec<String> {
+    fn collect(p: &gram_codec::Pattern<gram_codec::Subject>, out: &mut Vec<String>) {
+        if p.value.properties.get("migrated") == Some(&Value::VBoolean(true)) {
+            out.push(p.value.identity.0.clone());
+        }
+        for e in &p.elements {
+            collect(e, out);
+        }
+    }
+    let mut out = Vec::new();
+    for p in patterns {
+        collect(p, &mut out);
+    }
+    out
+}
+
+#[test]
+fn test_set_property_where_tags_matching_nodes() {
+    let mut patterns = parse_gram("(a:Person) (b:Company) (c:Person)").unwrap();
+    set_property_where(
+        &mut patterns,
+        |s| s.labels.contains("Person"),
+        "migrated",
+        Value::VBoolean(true),
+    );
+    assert_eq!(migrated(&patterns), vec!["a", "c"]);
+}
+
+#[test]
+fn test_set_property_where_recurses_into_elements() {
+    let mut patterns =
+        parse_gram("(a:Person)-[r:Person]->(b) [g:Person | (c:Person), @@x (d:Person)]").unwrap();
+    set_property_where(
+        &mut patterns,
+        |s| s.labels.contains("Person"),
+        "migrated",
+        Value::VBoolean(true),
+    );
+    assert_eq!(migrated(&patterns), vec!["r", "a", "g", "c", "d"]);
+}
+
+#[test]
+fn test_set_property_where_overwrites_existing_value() {
+    let mut patterns = parse_gram("(a:Person {migrated: false})").unwrap();
+    set_property_where(&mut patterns, |_| true, "migrated", Value::VBoolean(true));
+    assert_eq!(to_gram(&patterns).unwrap(), "(a:Person {migrated: true})");
+}
+
+#[test]
+fn test_with_property_where_leaves_input_unchanged() {
+    let patterns = parse_gram("(a:Person)-->(b:Company)").unwrap();
+    let updated = with_property_where(
+        &patterns,
+        |s| s.labels.contains("Company"),
+        "migrated",
+        Value::VBoolean(true),
+    );
+    assert!(migrated(&patterns).is_empty());
+    assert_eq!(migrated(&updated), vec!["b"]);
+}
@@ -0,0 +1,148 @@
+//! Document merge integration tests
+
+use gram_codec::{merge_gram, parse_gram, MergeError};
+use pattern_core::reconcile::ReconciliationPolicy;
+use pattern_core::{Subject, Value};
+
+fn node<'a>(patterns: &'a [gram_codec::Pattern<Subject>], id: &str) -> &'a Subject {
+    &patterns
+        .iter()
+        .find(|p| p.elements.is_empty() && p.value.identity.0 == id)
+        .unwrap_or_else(|| panic!("no node {id}"))
+        .value
+}
+
+#[test]
+fn merge_combines_properties_of_shared_node() {
+    let a = r#"(alice:Person {name: "Alice", age: 30})"#;
+    let b = r#"(alice:Employee {age: 31, team: "core"})
+(alice)-[:KNOWS]->(bob)"#;
+
+    let merged = merge_gram(a, b, &ReconciliationPolicy::merge_builder().build()).unwrap();
+    let patterns = parse_gram(&merged).unwrap();
+
+    let alice = node(&patterns, "alice");
+    assert!(alice.labels.contains("Person"));
+    assert!(alice.labels.contains("Employee"));
+    assert_eq!(alice.properties.get("name"), Some(&Value::from("Alice")));
+    assert_eq!(alice.properties.get("age"), Some(&Value::VInteger(31)));
+    assert_eq!(alice.properties.get("team"), Some(&Value::from("core")));
+    node(&patterns, "bob");
+
+    // alice, bob, and the relationship between them
+    assert_eq!(patterns.len(), 3);
+    assert!(merged.contains("(alice)-[_anon1:KNOWS]->(bob)"), "{merged}");
+}
+
+#[test]
+fn merge_is_stable_when_merged_again() {
+    let policy = ReconciliationPolicy::merge_builder().build();
+    let merged = merge_gram("(a {x: 1})-->(b)", "(a {y: 2})", &policy).unwrap();
+    assert_eq!(merge_gram(&merged, "", &policy).unwrap(), merged);
+}
+
+#[test]
+fn anonymous_relationships_are_kept_apart() {
+    let merged = merge_gram(
+        "(a)-->()",
+        "(b)-->()",
+        &ReconciliationPolicy::merge_builder().build(),
+    )
+    .unwrap();
+    let patterns = parse_gram(&merged).unwrap();
+    let relationships: Vec<_> = patterns.iter().filter(|p| p.elements.len() == 2).collect();
+    assert_eq!(relationships.len(), 2, "{merged}");
+    assert_eq!(relationships[0].elements[0].value.identity.0, "a");
+    assert_eq!(relationships[1].elements[0].value.identity.0, "b");
+}
+
+#[test]
+fn strict_lists_conflicting_identities() {
+    let a = "(alice {name: \"Alice\"})\n(bob {name: \"Bob\"})\n(carol)";
+    let b = "(bob {name: \"Robert\"})\n(alice {name: \"Alicia\"})\n(carol)";
+
+    let err = merge_gram(a, b, &ReconciliationPolicy::Strict).unwrap_err();
+    match &err {
+        MergeError::Conflict(ids) => assert_eq!(ids, &["alice", "bob"]),
+        other => panic!("expected a conflict, got {other:?}"),
+    }
+    assert_eq!(err.to_string(), "conflicting definitions for alice, bob");
+}
+
+#[test]
+fn strict_accepts_identical_definitions() {
+    let merged = merge_gram(
+        "(alice:Person)",
+        "(alice:Person)",
+        &ReconciliationPolicy::Strict,
+    );
+    assert_eq!(merged.unwrap(), "(alice:Person)");
+}
+
+#[test]
+fn strict_accepts_references_to_defined_nodes() {
+    let doc = "(alice:Person)\n(alice)-[:KNOWS]->(bob)";
+    let merged = merge_gram(doc, "", &ReconciliationPolicy::Strict).unwrap();
+    assert!(merged.contains("(alice:Person)"), "{merged}");
+    assert!(merged.contains("(alice)-[_anon1:KNOWS]->(bob)"), "{merged}");
+}
+
+#[test]
+fn last_write_wins_keeps_data_behind_references() {
+    let merged = merge_gram(
+        "(alice:Person {age: 30})",
+        "(alice)-[:KNOWS]->(bob)",
+        &ReconciliationPolicy::LastWriteWins,
+    )
+    .unwrap();
+    let patterns = parse_gram(&merged).unwrap();
+    let alice = node(&patterns, "alice");
+    assert!(alice.labels.contains("Person"), "{merged}");
+    assert_eq!(alice.properties.get("age"), Some(&Value::VInteger(30)));
+}
+
+#[test]
+fn merging_own_output_is_stable_under_every_policy() {
+    let a = "(alice:Person {age: 30})\n(bob:Person)\n(carol)";
+    let b = "(alice)-[:KNOWS]->(bob)\n(bob)-[:KNOWS]->(carol:Person)\n(carol)-->()";
+    for policy in [
+        ReconciliationPolicy::LastWriteWins,
+        ReconciliationPolicy::FirstWriteWins,
+        ReconciliationPolicy::Strict,
+        ReconciliationPolicy::merge_builder().build(),
+    ] {
+        let merged = merge_gram(a, b, &policy).unwrap();
+        assert_eq!(
+            merge_gram(&merged, "", &policy).unwrap(),
+            merged,
+            "{policy:?}"
+        );
+    }
+}
+
+#[test]
+fn headers_are_merged_with_second_winning() {
+    let merged = merge_gram(
+        "{version: 1, source: \"a\"}\n(x)",
+        "{version: 2}\n(y)",
+        &ReconciliationPolicy::LastWriteWins,
+    )
+    .unwrap();
+    let (header, patterns) = gram_codec::parse_gram_with_header(&merged).unwrap();
+    let header = header.expect("merged header");
+    assert_eq!(header.get("version"), Some(&Value::VInteger(2)));
+    assert_eq!(header.get("source"), Some(&Value::from("a")));
+    assert_eq!(patterns.len(), 2);
+}
+
+#[test]
+fn parse_errors_name_the_document() {
+    let err = merge_gram("(a)", "(b", &ReconciliationPolicy::LastWriteWins).unwrap_err();
+    assert!(matches!(
+        err,
+        MergeError::Parse {
+            document: "second",
+            ..
+        }
+    ));
+}
@@ -1,6 +1,10 @@
 //! Parser integration tests
 
-use gram_codec::{parse_gram_notation, parse_single_pattern};
+use gram_codec::{
+    parse_gram_notation, parse_gram_with_header, parse_gram_with_limits, parse_gram_with_options,
+    parse_single_pattern, parse_to_ast, parse_to_ast_with_options, parse_value, to_gram, Direction,
+    DocumentMode, ParseError, ParseOptions, DEFAULT_MAX_DEPTH,
+};
 use pattern_core::Value;
 
 #[test]
@@ -75,6 +79,65 @@ fn test_parse_with_comments() {
     assert_eq!(patterns.len(), 1);
 }
 
+/// Identities of the top-level patterns parsed from `input`
+fn top_level_ids(input: &str) -> Vec<String> {
+    parse_gram_notation(input)
+        .unwrap_or_else(|e| panic!("{:?}: {}", input, e))
+        .iter()
+        .map(|p| p.value.identity.0.clone())
+        .collect()
+}
+
+#[test]
+fn test_parse_comments_around_patterns() {
+    assert_eq!(
+        top_level_ids("(alice) // the protagonist\n(bob)"),
+        ["alice", "bob"]
+    );
+    assert_eq!(
+        top_level_ids("/* cast */ (alice) /* and\n friend */ (bob) // end"),
+        ["alice", "bob"]
+    );
+    assert_eq!(top_level_ids("(alice)\n/* trailing */\n"), ["alice"]);
+    assert_eq!(top_level_ids("// only a comment"), Vec::<String>::new());
+}
+
+#[test]
+fn test_parse_comments_inside_patterns() {
+    let patterns = parse_gram_notation(
+        "(a:Person /* labels */ {name: 'A' /* value */, // key\n age: 3})\
+         /* arrow */ -[:KNOWS]-> /* target */ (b) \
+         [g | /* first */ a, // second\n b /* end */]",
+    )
+    .unwrap();
+    assert_eq!(patterns.len(), 2);
+    assert_eq!(patterns[0].elements[0].value.properties.len(), 2);
+    assert!(patterns[0].value.labels.contains("KNOWS"));
+    assert_eq!(patterns[1].elements.len(), 2);
+
+    // Comment markers inside strings are text
+    let patterns = parse_gram_notation("(a {s: '/* not a comment */ // nor this'})").unwrap();
+    assert_eq!(
+        patterns[0].value.properties["s"],
+        Value::VString("/* not a comment */ // nor this".to_string())
+    );
+}
+
+#[test]
+fn test_parse_unclosed_block_comment_error() {
+    let err = parse_gram_notation("(alice)\n(bob) /* never closed\n(carol)").unwrap_err();
+    match &err {
+        ParseError::SyntaxError {
+            location, expected, ..
+        } => {
+            assert_eq!((location.line, location.column), (2, 7));
+            assert_eq!(expected, "Unclosed block comment");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert!(err.to_string().contains("Unclosed block comment"));
+}
+
 #[test]
 fn test_parse_single_pattern_success() {
     let result = parse_single_pattern("(hello)");
@@ -130,6 +193,60 @@ fn test_parse_relationship_squiggle() {
     assert_eq!(patterns.len(), 1);
 }
 
+/// Subject of the single relationship in `input`
+fn relationship_subject(input: &str) -> pattern_core::Subject {
+    let patterns = parse_gram_notation(input).unwrap();
+    assert_eq!(patterns.len(), 1);
+    let rel = &patterns[0];
+    assert_eq!(rel.elements.len(), 2);
+    assert_eq!(rel.elements[0].value.identity.0, "a");
+    assert_eq!(rel.elements[1].value.identity.0, "b");
+    rel.value.clone()
+}
+
+#[test]
+fn test_parse_relationship_subject() {
+    let bare = relationship_subject("(a)-->(b)");
+    assert_eq!(bare.identity.0, "");
+    assert!(bare.labels.is_empty());
+    assert!(bare.properties.is_empty());
+
+    let labeled = relationship_subject("(a)-[:KNOWS]->(b)");
+    assert_eq!(labeled.identity.0, "");
+    assert_eq!(labeled.labels.len(), 1);
+    assert!(labeled.labels.contains("KNOWS"));
+    assert!(labeled.properties.is_empty());
+
+    let named = relationship_subject("(a)-[r]->(b)");
+    assert_eq!(named.identity.0, "r");
+    assert!(named.labels.is_empty());
+    assert!(named.properties.is_empty());
+
+    let full = relationship_subject("(a)-[r:KNOWS {k:1}]->(b)");
+    assert_eq!(full.identity.0, "r");
+    assert!(full.labels.contains("KNOWS"));
+    assert_eq!(full.properties.len(), 1);
+    assert_eq!(full.properties["k"], Value::VInteger(1));
+}
+
+#[test]
+fn test_parse_relationship_properties_match_node_properties() {
+    let record = "{since: 2020, weight: 0.8, name: 'x', tags: [1, 2], at: 1..3}";
+    let rel = relationship_subject(&format!("(a)-[r:KNOWS {}]->(b)", record));
+    let node = parse_gram_notation(&format!("(n {})", record)).unwrap();
+    assert_eq!(rel.properties, node[0].value.properties);
+    assert_eq!(rel.properties["since"], Value::VInteger(2020));
+    assert_eq!(rel.properties["weight"], Value::VDecimal(0.8));
+
+    // Backward arrows keep the subject, apart from the recorded direction, and
+    // swap the endpoints.
+    let mut patterns = parse_gram_notation(&format!("(b)<-[r:KNOWS {}]-(a)", record)).unwrap();
+    assert_eq!(patterns[0].value.direction(), Direction::Backward);
+    patterns[0].value.set_direction(Direction::Forward);
+    assert_eq!(patterns[0].value, rel);
+    assert_eq!(patterns[0].elements[0].value.identity.0, "a");
+}
+
 #[test]
 fn test_parse_annotated_pattern_with_symbol_value() {
     let result = parse_gram_notation("@type(node) (a)");
@@ -175,3 +292,488 @@ fn test_parse_multiple_annotations() {
     );
     assert_eq!(patterns[0].elements[0].value.identity.0, "a");
 }
+
+fn default_label(label: &str) -> ParseOptions {
+    ParseOptions {
+        default_label: Some(label.to_string()),
+        ..ParseOptions::default()
+    }
+}
+
+#[test]
+fn test_parse_options_default_leaves_nodes_unlabeled() {
+    let patterns = parse_gram_with_options("(a) (b:Person)", &ParseOptions::default()).unwrap();
+    assert!(patterns[0].value.labels.is_empty());
+    assert_eq!(patterns, parse_gram_notation("(a) (b:Person)").unwrap());
+}
+
+#[test]
+fn test_parse_options_default_label_applies_to_unlabeled_nodes() {
+    let patterns = parse_gram_with_options("(a) (b:Person)", &default_label("Thing")).unwrap();
+    assert_eq!(patterns.len(), 2);
+    assert!(patterns[0].value.labels.contains("Thing"));
+    assert_eq!(patterns[1].value.labels.len(), 1);
+    assert!(patterns[1].value.labels.contains("Person"));
+}
+
+#[test]
+fn test_parse_options_default_label_applies_to_relationship_endpoints() {
+    let patterns = parse_gram_with_options("(a)-[r]->(b:Person)", &default_label("Thing")).unwrap();
+    let rel = &patterns[0];
+    assert!(rel.value.labels.is_empty());
+    assert!(rel.elements[0].value.labels.contains("Thing"));
+    assert!(!rel.elements[1].value.labels.contains("Thing"));
+}
+
+#[test]
+fn test_parse_options_default_label_skips_containers_and_header() {
+    let patterns =
+        parse_gram_with_options("{k: 1} [g | (a), (b)] ({v: 2})", &default_label("Thing")).unwrap();
+    assert_eq!(patterns.len(), 3);
+    // Leading record is untouched
+    assert!(patterns[0].value.labels.is_empty());
+    // Subject pattern itself is untouched, its elements are labeled
+    assert!(patterns[1].value.labels.is_empty());
+    assert!(patterns[1]
+        .elements
+        .iter()
+        .all(|e| e.value.labels.contains("Thing")));
+    // A properties-only node after the header is still a node
+    assert!(patterns[2].value.labels.contains("Thing"));
+}
+
+#[test]
+fn test_parse_options_default_label_skips_pattern_references() {
+    let patterns =
+        parse_gram_with_options("(a:Person) [g | a, (b)]", &default_label("Thing")).unwrap();
+    assert_eq!(patterns.len(), 2);
+    assert_eq!(patterns[0].value.labels.len(), 1);
+    assert!(patterns[0].value.labels.contains("Person"));
+    // The reference to `a` is left as written, the inline node is labeled
+    let elements = &patterns[1].elements;
+    assert_eq!(elements[0].value.identity.0, "a");
+    assert!(elements[0].value.labels.is_empty());
+    assert!(elements[1].value.labels.contains("Thing"));
+}
+
+#[test]
+fn test_parse_options_default_label_is_serialized() {
+    let patterns = parse_gram_with_options("(a)-->(b)", &default_label("Thing")).unwrap();
+    assert_eq!(to_gram(&patterns).unwrap(), "(a:Thing)-->(b:Thing)");
+}
+
+fn max_elements(limit: usize) -> ParseOptions {
+    ParseOptions {
+        max_elements_per_node: Some(limit),
+        ..ParseOptions::default()
+    }
+}
+
+#[test]
+fn test_parse_options_max_elements_allows_patterns_at_limit() {
+    let patterns = parse_gram_with_options("[g | (a), (b), (c)] (d)", &max_elements(3)).unwrap();
+    assert_eq!(patterns.len(), 2);
+    assert_eq!(patterns[0].elements.len(), 3);
+}
+
+#[test]
+fn test_parse_options_max_elements_rejects_subject_pattern() {
+    let err = parse_gram_with_options("[g | (a), (b), (c)]", &max_elements(2)).unwrap_err();
+    match err {
+        ParseError::ResourceLimit { location, limit } => {
+            assert_eq!(limit, 2);
+            // Reported at the first element over the limit
+            assert_eq!(location.column, 16);
+        }
+        other => panic!("Expected ResourceLimit, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_options_max_elements_applies_per_node() {
+    // Each subject pattern is counted separately, including nested ones
+    let input = "[g | [h | (a), (b)], [i | (c), (d)]]";
+    assert!(parse_gram_with_options(input, &max_elements(2)).is_ok());
+
+    let nested = "[g | [h | (a), (b), (c)]]";
+    assert!(matches!(
+        parse_gram_with_options(nested, &max_elements(2)),
+        Err(ParseError::ResourceLimit { .. })
+    ));
+}
+
+#[test]
+fn test_parse_options_max_elements_rejects_top_level_patterns() {
+    // The leading record is not counted
+    assert!(parse_gram_with_options("{k: 1} (a) (b)", &max_elements(2)).is_ok());
+    assert!(matches!(
+        parse_gram_with_options("(a) (b) (c)", &max_elements(2)),
+        Err(ParseError::ResourceLimit { limit: 2, .. })
+    ));
+}
+
+#[test]
+fn test_parse_options_max_elements_unlimited_by_default() {
+    let input = format!(
+        "[g | {}]",
+        (0..1000)
+            .map(|i| format!("(n{})", i))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let patterns = parse_gram_with_options(&input, &ParseOptions::default()).unwrap();
+    assert_eq!(patterns[0].elements.len(), 1000);
+}
+
+fn document_mode(mode: DocumentMode) -> ParseOptions {
+    ParseOptions {
+        document_mode: mode,
+        ..ParseOptions::default()
+    }
+}
+
+#[test]
+fn test_parse_options_always_wrap_returns_one_document() {
+    let options = document_mode(DocumentMode::AlwaysWrap);
+
+    let patterns = parse_gram_with_options("{k: 1} (a) (b)", &options).unwrap();
+    assert_eq!(patterns.len(), 1);
+    let document = &patterns[0];
+    assert!(document.value.identity.0.is_empty());
+    assert_eq!(
+        document.value.properties.get("k"),
+        Some(&Value::VInteger(1))
+    );
+    assert_eq!(document.elements.len(), 2);
+
+    // A single pattern and empty input are wrapped too
+    let patterns = parse_gram_with_options("(a)", &options).unwrap();
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].elements[0].value.identity.0, "a");
+
+    let patterns = parse_gram_with_options("  ", &options).unwrap();
+    assert_eq!(patterns.len(), 1);
+    assert!(patterns[0].elements.is_empty());
+}
+
+#[test]
+fn test_parse_options_never_wrap_matches_parse_gram() {
+    let options = document_mode(DocumentMode::NeverWrap);
+    for input in ["", "(a)", "{k: 1}", "{k: 1} (a) (b)"] {
+        assert_eq!(
+            parse_gram_with_options(input, &options).unwrap(),
+            parse_gram_notation(input).unwrap(),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_parse_to_ast_with_options_document_modes() {
+    let input = "{k: 1} (a) (b)";
+
+    let auto = parse_to_ast_with_options(input, &ParseOptions::default()).unwrap();
+    assert_eq!(auto, [parse_to_ast(input).unwrap()]);
+
+    let wrapped =
+        parse_to_ast_with_options(input, &document_mode(DocumentMode::AlwaysWrap)).unwrap();
+    assert_eq!(wrapped, auto);
+
+    let raw = parse_to_ast_with_options(input, &document_mode(DocumentMode::NeverWrap)).unwrap();
+    let ids: Vec<&str> = raw.iter().map(|p| p.subject.identity.as_str()).collect();
+    assert_eq!(ids, ["", "a", "b"]);
+
+    // Auto returns a lone pattern as-is; AlwaysWrap still wraps it
+    let auto = parse_to_ast_with_options("(a)", &ParseOptions::default()).unwrap();
+    assert_eq!(auto[0].subject.identity, "a");
+    let wrapped =
+        parse_to_ast_with_options("(a)", &document_mode(DocumentMode::AlwaysWrap)).unwrap();
+    assert_eq!(wrapped[0].subject.identity, "");
+    assert_eq!(wrapped[0].elements[0].subject.identity, "a");
+}
+
+#[test]
+fn test_parse_record_trailing_comma() {
+    let with_comma = parse_gram_notation("(a {k: 1, s: \"x\",})").unwrap();
+    assert_eq!(
+        with_comma,
+        parse_gram_notation("(a {k: 1, s: \"x\"})").unwrap()
+    );
+
+    let spaced = parse_gram_notation("{k: 1 , }\n(a)").unwrap();
+    assert_eq!(
+        spaced[0].value.properties.get("k"),
+        Some(&Value::VInteger(1))
+    );
+
+    // A comma must follow a property
+    assert!(parse_gram_notation("(a {,})").is_err());
+    assert!(parse_gram_notation("(a {k: 1,,})").is_err());
+}
+
+#[test]
+fn test_parse_element_list_trailing_comma() {
+    let with_comma = parse_gram_notation("[g | (a), b,]").unwrap();
+    assert_eq!(with_comma, parse_gram_notation("[g | (a), b]").unwrap());
+    assert_eq!(with_comma[0].elements.len(), 2);
+
+    // A comma must follow an element
+    assert!(parse_gram_notation("[g | ,]").is_err());
+    assert!(parse_gram_notation("[g | (a),,]").is_err());
+}
+
+#[test]
+fn test_serializer_omits_trailing_commas() {
+    let patterns = parse_gram_notation("[g {k: 1,} | (a), (b),]").unwrap();
+    let gram = to_gram(&patterns).unwrap();
+    assert!(!gram.contains(",}") && !gram.contains(",]"), "{}", gram);
+    assert_eq!(parse_gram_notation(&gram).unwrap(), patterns);
+}
+
+#[test]
+fn test_error_location_with_crlf_line_endings() {
+    let lf = "(a)\n(b {k: 1})\n(c {k: })\n(d)";
+    let crlf = lf.replace('\n', "\r\n");
+    let mixed = "(a)\r\n(b {k: 1})\n(c {k: })\r\n(d)";
+
+    let expected = parse_gram_notation(lf).unwrap_err().location().unwrap();
+    assert_eq!(expected.line, 3);
+    assert_eq!(expected.column, 5);
+
+    for input in [crlf.as_str(), mixed] {
+        let location = parse_gram_notation(input).unwrap_err().location().unwrap();
+        assert_eq!(
+            (location.line, location.column),
+            (expected.line, expected.column),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_parse_scientific_and_hex_properties() {
+    let patterns =
+        parse_gram_notation("(a {avogadro: 6.022e23, small: 2.5e-3, k: 1e3, mask: 0xFF})").unwrap();
+    let props = &patterns[0].value.properties;
+    assert_eq!(props["avogadro"], Value::VDecimal(6.022e23));
+    assert_eq!(props["small"], Value::VDecimal(0.0025));
+    assert_eq!(props["k"], Value::VDecimal(1000.0));
+    assert_eq!(props["mask"], Value::VInteger(255));
+
+    // Written back in plain notation, the values read back the same
+    let reparsed = parse_gram_notation(&to_gram(&patterns).unwrap()).unwrap();
+    assert_eq!(reparsed, patterns);
+}
+
+#[test]
+fn test_parse_overflowing_exponent_is_error() {
+    for input in ["(a {x: 1e400})", "(a {x: -1e400})"] {
+        let err = parse_gram_notation(input).unwrap_err();
+        assert_eq!(err.location().map(|l| l.column), Some(8), "{}", input);
+        assert!(
+            err.to_string().contains("decimal within the f64 range"),
+            "{}",
+            err
+        );
+    }
+}
+
+#[test]
+fn test_parse_inverted_quantifier_is_error() {
+    let err = parse_gram_notation("(a)-[:KNOWS*3..1]->(b)").unwrap_err();
+    assert_eq!(err.location().map(|l| l.column), Some(12));
+    assert!(
+        err.to_string()
+            .contains("quantifier minimum no greater than its maximum"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_parse_invalid_hex_property_is_error() {
+    let err = parse_gram_notation("(a {mask: 0xZZ})").unwrap_err();
+    assert_eq!(err.location().map(|l| l.column), Some(13));
+}
+
+#[test]
+fn test_parse_node_labels() {
+    let labels = |input: &str| {
+        let mut labels: Vec<String> = parse_gram_notation(input).unwrap()[0]
+            .value
+            .labels
+            .iter()
+            .cloned()
+            .collect();
+        labels.sort();
+        labels
+    };
+
+    assert!(labels("(a)").is_empty());
+    assert_eq!(labels("(a:Person)"), ["Person"]);
+    assert_eq!(
+        labels("(a:Person:Employee:Manager)"),
+        ["Employee", "Manager", "Person"]
+    );
+    assert_eq!(
+        labels("(:Person:Employee:Manager {k: 1})"),
+        ["Employee", "Manager", "Person"]
+    );
+    // Repeated labels collapse
+    assert_eq!(labels("(a:Person:Person)"), ["Person"]);
+
+    let patterns = parse_gram_notation("(a:Person:Employee:Manager)").unwrap();
+    assert_eq!(to_gram(&patterns).unwrap(), "(a:Employee:Manager:Person)");
+}
+
+#[test]
+fn test_parse_null_property() {
+    let patterns = parse_gram_notation("(a {x: null, list: [1, null], m: {y: null}})").unwrap();
+    let props = &patterns[0].value.properties;
+    assert_eq!(props["x"], Value::VNull);
+    assert_eq!(
+        props["list"],
+        Value::VArray(vec![Value::VInteger(1), Value::VNull])
+    );
+
+    // Present-but-null survives a round trip, distinct from an absent property
+    let gram = to_gram(&patterns).unwrap();
+    assert_eq!(gram, "(a {list: [1, null], m: {y: null}, x: null})");
+    assert_eq!(parse_gram_notation(&gram).unwrap(), patterns);
+    assert!(!parse_gram_notation("(a)").unwrap()[0]
+        .value
+        .properties
+        .contains_key("x"));
+}
+
+#[test]
+fn test_parse_unicode_escape_in_string() {
+    let patterns = parse_gram_notation(r#"(a {face: "\u{1F600}", text: "two\nlines"})"#).unwrap();
+    let props = &patterns[0].value.properties;
+    assert_eq!(props["face"], Value::VString("😀".to_string()));
+    assert_eq!(props["text"], Value::VString("two\nlines".to_string()));
+}
+
+#[test]
+fn test_parse_invalid_escape_error() {
+    let err = parse_gram_notation(r#"(a {text: "ok \q"})"#).unwrap_err();
+    match &err {
+        ParseError::SyntaxError {
+            location, expected, ..
+        } => {
+            assert_eq!((location.line, location.column), (1, 15));
+            assert_eq!(expected, "valid escape sequence");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_with_limits_reports_first_pattern_too_deep() {
+    assert_eq!(parse_gram_with_limits("[a|[b|(c)]]", 2).unwrap().len(), 1);
+    match parse_gram_with_limits("[a|[b|[c|(d)]]]", 2).unwrap_err() {
+        ParseError::TooDeep { location, depth } => {
+            assert_eq!(depth, 2);
+            // `(d)` is the element three levels deep
+            assert_eq!((location.line, location.column), (1, 10));
+        }
+        other => panic!("Expected TooDeep, got {:?}", other),
+    }
+    // An annotated pattern is one level deep
+    assert!(parse_gram_with_limits("(c)", 0).is_ok());
+    assert!(matches!(
+        parse_gram_with_limits("@a(1) @b(2) (c)", 0),
+        Err(ParseError::TooDeep { depth: 0, .. })
+    ));
+}
+
+#[test]
+fn test_parse_deeply_nested_input_fails_cleanly() {
+    let brackets = "[".repeat(100_000);
+    assert!(parse_gram_notation(&brackets).is_err());
+
+    let nested = format!("{}(x){}", "[a | ".repeat(100_000), "]".repeat(100_000));
+    match parse_gram_notation(&nested).unwrap_err() {
+        ParseError::TooDeep { depth, .. } => assert_eq!(depth, DEFAULT_MAX_DEPTH),
+        other => panic!("Expected TooDeep, got {:?}", other),
+    }
+
+    // The default limit itself is reachable
+    let deepest = format!(
+        "{}(x){}",
+        "[a | ".repeat(DEFAULT_MAX_DEPTH),
+        "]".repeat(DEFAULT_MAX_DEPTH)
+    );
+    assert!(parse_gram_notation(&deepest).is_ok());
+}
+
+#[test]
+fn test_parse_deeply_nested_values_fail_cleanly() {
+    let arrays = format!("({{k: {}1{}}})", "[".repeat(5000), "]".repeat(5000));
+    let maps = format!("({{k: {}1{}}})", "{k: ".repeat(5000), "}".repeat(5000));
+    let annotation = format!("@k({}1{}) (a)", "[".repeat(5000), "]".repeat(5000));
+    let header = format!("{{k: {}1{}}}", "[".repeat(5000), "]".repeat(5000));
+    for input in [&arrays, &maps, &annotation, &header] {
+        match parse_gram_notation(input).unwrap_err() {
+            ParseError::TooDeep { depth, .. } => assert_eq!(depth, DEFAULT_MAX_DEPTH),
+            other => panic!("Expected TooDeep, got {:?}", other),
+        }
+    }
+    assert!(matches!(
+        parse_value(&format!("{}1{}", "[".repeat(5000), "]".repeat(5000))),
+        Err(ParseError::TooDeep { .. })
+    ));
+
+    // Values share the limit with the patterns containing them
+    assert!(parse_gram_with_limits("({k: [[1]]})", 2).is_ok());
+    assert!(matches!(
+        parse_gram_with_limits("[a | ({k: [1]})]", 1),
+        Err(ParseError::TooDeep { depth: 1, .. })
+    ));
+}
+
+#[test]
+fn test_parse_with_header_only_header() {
+    let (header, patterns) = parse_gram_with_header("{version: 2, name: 'demo'}").unwrap();
+    let header = header.unwrap();
+    assert_eq!(header["version"], Value::VInteger(2));
+    assert_eq!(header["name"], Value::VString("demo".to_string()));
+    assert!(patterns.is_empty());
+
+    // An empty record is still a header
+    let (header, patterns) = parse_gram_with_header("{}").unwrap();
+    assert_eq!(header.map(|h| h.len()), Some(0));
+    assert!(patterns.is_empty());
+}
+
+#[test]
+fn test_parse_with_header_and_patterns() {
+    let input =
+        "// file header\n{version: 2} /* more */ {owner: 'ops', version: 3}\n(a)-->(b)\n(c)";
+    let (header, patterns) = parse_gram_with_header(input).unwrap();
+    let header = header.unwrap();
+    // Groups merge; a repeated key takes its last value
+    assert_eq!(header.len(), 2);
+    assert_eq!(header["version"], Value::VInteger(3));
+    assert_eq!(header["owner"], Value::VString("ops".to_string()));
+    assert_eq!(patterns.len(), 2);
+    assert_eq!(patterns[0].elements.len(), 2);
+
+    // parse_gram still reports the merged header as a bare first pattern
+    let patterns = parse_gram_notation(input).unwrap();
+    assert_eq!(patterns.len(), 3);
+    assert_eq!(patterns[0].value.properties.len(), 2);
+}
+
+#[test]
+fn test_parse_with_header_without_header() {
+    for input in ["", "  // nothing here", "(a) (b)", "[{k: 1}]", "({k: 1})"] {
+        let (header, patterns) = parse_gram_with_header(input).unwrap();
+        assert!(header.is_none(), "{}", input);
+        assert_eq!(patterns, parse_gram_notation(input).unwrap(), "{}", input);
+    }
+    // A record after the first pattern is not a header
+    assert!(parse_gram_with_header("(a) {k: 1}").is_err());
+}
@@ -54,6 +54,37 @@ fn test_round_trip_node_with_multiple_labels() {
     assert_round_trip_equivalent("(alice:Person:User)");
 }
 
+#[test]
+fn test_round_trip_quoted_labels() {
+    let patterns = parse_gram(r#"(a:"Has Dash")"#).unwrap();
+    assert!(patterns[0].value.labels.contains("Has Dash"));
+    assert_canonical_output(r#"(a:"Has Dash")"#, "(a:`Has Dash`)");
+    assert_canonical_output(r#"(a:"Has-Dash")"#, "(a:Has-Dash)");
+
+    for input in [
+        "(a:`Has Dash`)",
+        "(a:`Has Dash`:Person)",
+        "(a)-[:`Works For`]->(b)",
+        "[g:`Team Label` | (a)]",
+        "@@:`Annotated Label` (a)",
+        r#"@@:"Annotated Label" (a)"#,
+    ] {
+        assert_round_trip_equivalent(input);
+    }
+}
+
+#[test]
+fn test_round_trip_label_with_escaped_characters() {
+    let label = "tick ` quote \" slash \\ line\n";
+    let pattern = gram_codec::Pattern::point(gram_codec::Subject {
+        identity: pattern_core::Symbol("a".to_string()),
+        labels: [label.to_string()].into_iter().collect(),
+        properties: Default::default(),
+    });
+    let gram = to_gram(std::slice::from_ref(&pattern)).unwrap();
+    assert_eq!(parse_gram(&gram).unwrap(), [pattern], "{}", gram);
+}
+
 #[test]
 fn test_round_trip_node_with_properties() {
     assert_round_trip_equivalent("(alice {name: \"Alice\"})");
@@ -106,11 +137,58 @@ fn test_annotation_output_prefers_annotated_form() {
     assert_canonical_output("[p:L {k: \"v\"} | (a)]", "@@p:L @k(\"v\") (a)");
 }
 
+#[test]
+fn test_round_trip_annotation_with_map_value() {
+    assert_canonical_output(
+        "@source({timestamp: 2024, system: \"db\"}) (a)",
+        "@source({system: \"db\", timestamp: 2024}) (a)",
+    );
+    assert_round_trip_equivalent("@@p @source({system: \"db\", tags: [1, 2]}) (a)");
+    assert_round_trip_equivalent("@meta({origin: {system: \"db\"}}) (a)");
+}
+
+#[test]
+fn test_round_trip_annotation_with_argument_list() {
+    assert_canonical_output(
+        "@source(\"db\", timestamp: 2024) (a)",
+        "@source([\"db\", {timestamp: 2024}]) (a)",
+    );
+    assert_canonical_output(
+        "@source(system: \"db\", timestamp: 2024) (a)",
+        "@source({system: \"db\", timestamp: 2024}) (a)",
+    );
+    assert_round_trip_equivalent("@source(\"db\", timestamp: 2024) (a)");
+}
+
+#[test]
+fn test_round_trip_map_property_value() {
+    assert_canonical_output("(a {m: {y: 2, x: 1}})", "(a {m: {x: 1, y: 2}})");
+}
+
 #[test]
 fn test_bare_annotation_canonicalizes_to_true() {
     assert_canonical_output("@deprecated (old_node)", "@deprecated(true) (old_node)");
 }
 
+#[test]
+fn test_empty_element_list_normalizes_to_node() {
+    // `[s | ]` and `[s]` are the same atomic pattern as `(s)`
+    assert_round_trip_equivalent("[a:L {k: 1} | ]");
+    assert_canonical_output("[a:L {k: 1} | ]", "(a:L {k: 1})");
+    assert_canonical_output("[a:L {k: 1}]", "(a:L {k: 1})");
+    assert_canonical_output("[ | ]", "()");
+    assert_canonical_output("[]", "()");
+}
+
+#[test]
+fn test_record_only_subject_round_trips_after_first_statement() {
+    // A bare record is only legal as the leading record of a document
+    assert_round_trip_equivalent("(a) [{k: 1} | ]");
+    assert_canonical_output("(a) [{k: 1} | ]", "(a)\n({k: 1})");
+    assert_canonical_output("[g | (a), [{k: 1}], (b)]", "[g | (a), ({k: 1}), (b)]");
+    assert_canonical_output("{k: 1} (a)", "{k: 1}\n(a)");
+}
+
 #[test]
 fn test_round_trip_multiple_patterns() {
     assert_round_trip_equivalent("(a) (b) (c)");
@@ -164,6 +242,24 @@ fn test_round_trip_nested_subject() {
     assert_round_trip_equivalent("[outer | [inner | (a), (b)], (c)]");
 }
 
+#[test]
+fn test_round_trip_deeply_nested_subject_patterns() {
+    assert_round_trip_equivalent("[a | [b | [c | (d)]]]");
+    assert_round_trip_equivalent("[a | [b | [c | [d | (e)]]]]");
+    assert_round_trip_equivalent("[a | [b | [c | (d), (e), (f)], (g)], (h)]");
+    assert_round_trip_equivalent("[a:L {k: 1} | [b:M | [c | (x)-->(y)], (z)]]");
+    assert_round_trip_equivalent("[a | [ | [ | (d)]]]");
+}
+
+#[test]
+fn test_nested_property_only_annotation_is_bracketed() {
+    // `@@a @k(1) @@c (d)` would parse `@k(1)` as a property of `a`
+    assert_canonical_output("[a | [{k: 1} | [c | (d)]]]", "@@a [{k: 1} | @@c (d)]");
+    assert_round_trip_equivalent("[a | [{k: 1} | [c | (d)]]]");
+    assert_round_trip_equivalent("[{k: 1} | [{j: 2} | (x)]]");
+    assert_round_trip_equivalent("[a | [{k: 1} | [{j: 2} | [c | (d)]]]]");
+}
+
 /// Test that whitespace differences don't affect semantic equivalence
 #[test]
 fn test_round_trip_whitespace_normalization() {
@@ -213,3 +309,192 @@ fn test_round_trip_idempotent() {
         "Patterns should be identical after stabilization"
     );
 }
+
+#[test]
+fn test_round_trip_nested_maps_and_arrays() {
+    use pattern_core::Value;
+    use std::collections::HashMap;
+
+    let input = r#"(a {address: {city: "Berlin", geo: {lat: 52.52, tags: [{k: 1}, []]}, zip: 10115}, mixed: [1, "two", true, [3.5, false]]})"#;
+    assert_round_trip_equivalent(input);
+    assert_round_trip_equivalent(
+        "(a {\n  m: {\n    inner: {x: 1}, // comment\n  },\n  list: [\n    1,\n    2\n  ]\n})",
+    );
+
+    let patterns = parse_gram(input).unwrap();
+    let props = &patterns[0].value.properties;
+    assert_eq!(
+        props["mixed"],
+        Value::VArray(vec![
+            Value::VInteger(1),
+            Value::VString("two".to_string()),
+            Value::VBoolean(true),
+            Value::VArray(vec![Value::VDecimal(3.5), Value::VBoolean(false)]),
+        ])
+    );
+    let Value::VMap(address) = &props["address"] else {
+        panic!("address is not a map: {:?}", props["address"]);
+    };
+    assert_eq!(address["zip"], Value::VInteger(10115));
+    let Value::VMap(geo) = &address["geo"] else {
+        panic!("geo is not a map: {:?}", address["geo"]);
+    };
+    assert_eq!(
+        geo["tags"],
+        Value::VArray(vec![
+            Value::VMap(HashMap::from([("k".to_string(), Value::VInteger(1))])),
+            Value::VArray(vec![]),
+        ])
+    );
+}
+
+#[test]
+fn test_round_trip_variable_length_relationships() {
+    for input in [
+        "(a)-[*]->(b)",
+        "(a)-[*3]->(b)",
+        "(a)-[:KNOWS*1..5]->(b)",
+        "(a)-[r*..4]->(b)",
+        "(a)<-[:KNOWS*2.. {since: 2020}]-(b)",
+    ] {
+        assert_round_trip_equivalent(input);
+        assert_canonical_output(input, input);
+    }
+    assert_round_trip_equivalent("(a)-[*1..2]-(b)-[:R*3]->(c)");
+    assert_canonical_output("(a)-[ :KNOWS *1..3 ]->(b)", "(a)-[:KNOWS*1..3]->(b)");
+    assert_canonical_output("(a)-[*..]->(b)", "(a)-[*]->(b)");
+
+    // The quantifier is kept as the `_quantifier` property, which the
+    // bracket owns, so the record may not also write it
+    assert!(parse_gram("(a)-[r {_quantifier: 1..3}]->(b)").is_err());
+}
+
+// ============================================================================
+// PatternGraph round trip
+// ============================================================================
+
+#[test]
+fn test_round_trip_graph_social() {
+    use gram_codec::serialize_graph;
+    use pattern_core::graph::StandardGraph;
+
+    let input = r#"
+        (alice:Person {name: "Alice", age: 34})
+        (bob:Person {name: "Bob"})
+        (carol:Person:Admin {name: "Carol"})
+        (acme:Company {name: "Acme"})
+        (alice)-[k1:KNOWS {since: 2019}]->(bob)
+        (bob)-[k2:KNOWS]->(carol)
+        (alice)-[w1:WORKS_AT]->(acme)
+        (acme)<-[w2:WORKS_AT]-(carol)
+        [intro:Introduction | (alice)-[k1]->(bob), (bob)-[k2]->(carol)]
+        @@note:Memo (carol)
+    "#;
+
+    let graph1 = StandardGraph::from_patterns(parse_gram(input).expect("input parses"));
+    let gram = serialize_graph(graph1.as_pattern_graph()).expect("graph serializes");
+    let graph2 = StandardGraph::from_patterns(
+        parse_gram(&gram).unwrap_or_else(|e| panic!("output does not parse: {}\n{}", e, gram)),
+    );
+
+    let (g1, g2) = (graph1.as_pattern_graph(), graph2.as_pattern_graph());
+    assert_eq!(g1.pg_nodes.len(), 4);
+    assert_eq!(g1.pg_relationships.len(), 4);
+    assert_eq!(g1.pg_walks.len(), 1);
+    assert_eq!(g1.pg_annotations.len(), 1);
+    assert_eq!(g2.pg_nodes, g1.pg_nodes);
+    assert_eq!(g2.pg_relationships, g1.pg_relationships);
+    assert_eq!(g2.pg_walks, g1.pg_walks);
+    assert_eq!(g2.pg_annotations, g1.pg_annotations);
+
+    // Node content is written once; everything else refers to nodes by identity.
+    assert_eq!(gram.matches("Alice").count(), 1);
+    assert!(gram.contains("(alice)-[k1:KNOWS {since: 2019}]->(bob)"));
+    assert!(gram.contains("(acme)<-[w2:WORKS_AT]-(carol)"));
+    assert!(gram.lines().next().unwrap().starts_with("(acme:Company"));
+}
+
+#[test]
+fn test_round_trip_graph_under_every_policy() {
+    use gram_codec::serialize_graph;
+    use pattern_core::reconcile::ReconciliationPolicy;
+    use pattern_core::{canonical_classifier, from_patterns_with_policy, Pattern, PatternGraph};
+    use pattern_core::{Subject, Symbol};
+    use std::collections::HashMap;
+
+    /// `p` with every node nested inside it replaced by the graph's node
+    fn resolved(graph: &PatternGraph<(), Subject>, p: &Pattern<Subject>) -> Pattern<Subject> {
+        if p.elements.is_empty() {
+            return graph.pg_nodes.get(&p.value.identity).unwrap_or(p).clone();
+        }
+        Pattern {
+            value: p.value.clone(),
+            elements: p.elements.iter().map(|e| resolved(graph, e)).collect(),
+        }
+    }
+    fn contents(
+        graph: &PatternGraph<(), Subject>,
+        map: &HashMap<Symbol, Pattern<Subject>>,
+    ) -> HashMap<Symbol, Pattern<Subject>> {
+        map.iter()
+            .map(|(id, p)| (id.clone(), resolved(graph, p)))
+            .collect()
+    }
+
+    let inputs = [
+        "(a:Person {k: 1})-[r:KNOWS]->(b:Person)",
+        "(alice:Person {age: 30})\n(alice)-[r:KNOWS]->(bob)\n[w | (alice)-[r:KNOWS]->(bob)]\n@@n:Memo (bob)",
+    ];
+    let policies = [
+        ("LastWriteWins", ReconciliationPolicy::LastWriteWins),
+        ("Strict", ReconciliationPolicy::Strict),
+        ("FirstWriteWins", ReconciliationPolicy::FirstWriteWins),
+        ("Merge", ReconciliationPolicy::merge_builder().build()),
+    ];
+    let classifier = canonical_classifier();
+    for input in inputs {
+        for (name, policy) in &policies {
+            let build = |gram: &str| {
+                let patterns = parse_gram(gram).unwrap_or_else(|e| panic!("{}\n{}", e, gram));
+                from_patterns_with_policy(&classifier, policy, patterns)
+            };
+            let g1 = build(input);
+            let gram = serialize_graph(&g1).expect("graph serializes");
+            let g2 = build(&gram);
+
+            assert!(g1.pg_conflicts.is_empty(), "{}: {}", name, input);
+            assert!(g2.pg_conflicts.is_empty(), "{}: {}", name, gram);
+            assert_eq!(g2.pg_nodes, g1.pg_nodes, "{}: {}", name, gram);
+            for (m1, m2) in [
+                (&g1.pg_relationships, &g2.pg_relationships),
+                (&g1.pg_walks, &g2.pg_walks),
+                (&g1.pg_annotations, &g2.pg_annotations),
+            ] {
+                assert_eq!(contents(&g2, m2), contents(&g1, m1), "{}: {}", name, gram);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_round_trip_string_escapes() {
+    use gram_codec::to_gram_pattern;
+    use pattern_core::{Pattern, Subject};
+
+    let text = "She said \"hi\"\\ back\nline\ttab\rreturn 'single' `tick` \u{8}\u{c} 🎉";
+    let pattern = Pattern::point(
+        Subject::build("a")
+            .property("name", text)
+            .property("odd \"key\"\\", "ok")
+            .done(),
+    );
+
+    let gram = to_gram_pattern(&pattern).unwrap();
+    assert!(gram.contains(r#"name: "She said \"hi\"\\ back\nline\ttab\rreturn"#));
+    assert!(gram.contains(r"\b\f 🎉"));
+    let parsed = parse_gram(&gram).unwrap_or_else(|e| panic!("re-parse of {} failed: {}", gram, e));
+    assert_eq!(parsed, vec![pattern]);
+
+    assert_round_trip_equivalent(r#"(a {name: "She said \"hi\"\\ \n\t\r 🎉"})"#);
+    assert_round_trip_equivalent(r#"(a {name: 'it\'s "quoted"'})"#);
+}
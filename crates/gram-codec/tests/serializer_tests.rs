@@ -1,7 +1,13 @@
 //! Serializer integration tests
 
-use gram_codec::{parse_gram_notation, to_gram, to_gram_pattern, to_gram_with_header};
-use pattern_core::{Pattern, Subject, Symbol};
+use gram_codec::{
+    parse_gram_notation, serialize_graph, to_gram, to_gram_pattern, to_gram_pattern_with_options,
+    to_gram_with_header, to_gram_with_options, write_gram, write_gram_fmt, write_gram_pattern,
+    EmptyNodeStyle, SerializeError, SerializeOptions,
+};
+use pattern_core::{
+    canonical_classifier, from_patterns, Pattern, Subject, Symbol, ANONYMOUS_ID_PREFIX,
+};
 use std::collections::{HashMap, HashSet};
 
 // Helper function to create a Subject with identifier
@@ -253,3 +259,425 @@ fn test_to_gram_standard_separator() {
     let result = gram_codec::to_gram(&patterns).unwrap();
     assert_eq!(result, "(a)\n(b)");
 }
+
+// ============================================================================
+// Path chaining
+// ============================================================================
+
+fn relationship(edge: Subject, left: &str, right: &str) -> Pattern<Subject> {
+    Pattern::pattern(
+        edge,
+        vec![
+            Pattern::point(subject_with_id(left)),
+            Pattern::point(subject_with_id(right)),
+        ],
+    )
+}
+
+fn labeled_edge(label: &str) -> Subject {
+    let mut edge = empty_subject();
+    edge.labels.insert(label.to_string());
+    edge
+}
+
+// The graph a document rebuilds to, written out so graphs compare as text.
+// Identities generated for anonymous patterns depend on where the patterns
+// are written, so they are left out.
+fn reparsed_graph(gram: &str) -> Vec<String> {
+    let patterns = parse_gram_notation(gram).unwrap();
+    let graph = serialize_graph(&from_patterns(&canonical_classifier(), patterns)).unwrap();
+    let mut lines: Vec<String> = graph
+        .lines()
+        .map(|line| {
+            let mut rest = line;
+            let mut kept = String::new();
+            while let Some(at) = rest.find(ANONYMOUS_ID_PREFIX) {
+                kept.push_str(&rest[..at]);
+                rest = rest[at + ANONYMOUS_ID_PREFIX.len()..]
+                    .trim_start_matches(|c: char| c.is_ascii_digit());
+            }
+            kept.push_str(rest);
+            kept
+        })
+        .collect();
+    lines.sort();
+    lines
+}
+
+fn chained() -> SerializeOptions {
+    SerializeOptions {
+        path_chaining: true,
+        ..SerializeOptions::default()
+    }
+}
+
+#[test]
+fn test_default_options_match_to_gram() {
+    let patterns = vec![
+        relationship(empty_subject(), "a", "b"),
+        relationship(empty_subject(), "b", "c"),
+    ];
+
+    let result = to_gram_with_options(&patterns, &SerializeOptions::default()).unwrap();
+    assert_eq!(result, to_gram(&patterns).unwrap());
+    assert_eq!(result, "(a)-->(b)\n(b)-->(c)");
+}
+
+#[test]
+fn test_path_chaining_folds_shared_endpoint() {
+    let patterns = vec![
+        relationship(labeled_edge("R1"), "a", "b"),
+        relationship(labeled_edge("R2"), "b", "c"),
+    ];
+
+    let result = to_gram_with_options(&patterns, &chained()).unwrap();
+    assert_eq!(result, "(a)-[:R1]->(b)-[:R2]->(c)");
+}
+
+#[test]
+fn test_path_chaining_starts_from_chain_head() {
+    // Listed out of order: the chain still starts at `a`
+    let patterns = vec![
+        relationship(empty_subject(), "c", "d"),
+        relationship(empty_subject(), "b", "c"),
+        relationship(empty_subject(), "a", "b"),
+    ];
+
+    let result = to_gram_with_options(&patterns, &chained()).unwrap();
+    assert_eq!(result, "(a)-->(b)-->(c)-->(d)");
+}
+
+#[test]
+fn test_path_chaining_branches_and_cycles() {
+    let patterns = vec![
+        relationship(empty_subject(), "a", "b"),
+        relationship(empty_subject(), "b", "c"),
+        relationship(empty_subject(), "b", "d"),
+        relationship(empty_subject(), "x", "y"),
+        relationship(empty_subject(), "y", "x"),
+    ];
+
+    let result = to_gram_with_options(&patterns, &chained()).unwrap();
+    assert_eq!(result, "(a)-->(b)-->(c)\n(b)-->(d)\n(x)-->(y)-->(x)");
+}
+
+#[test]
+fn test_path_chaining_keeps_differing_nodes_separate() {
+    // `b` carries a label in one statement only, so folding would lose it
+    let mut labeled_b = Pattern::point(subject_with_id("b"));
+    labeled_b.value.labels.insert("Person".to_string());
+    let patterns = vec![
+        Pattern::pattern(
+            empty_subject(),
+            vec![Pattern::point(subject_with_id("a")), labeled_b],
+        ),
+        relationship(empty_subject(), "b", "c"),
+    ];
+
+    let result = to_gram_with_options(&patterns, &chained()).unwrap();
+    assert_eq!(result, "(a)-->(b:Person)\n(b)-->(c)");
+}
+
+#[test]
+fn test_path_chaining_never_joins_anonymous_nodes() {
+    let patterns = vec![
+        relationship(empty_subject(), "a", ""),
+        relationship(empty_subject(), "", "c"),
+    ];
+
+    let result = to_gram_with_options(&patterns, &chained()).unwrap();
+    assert_eq!(result, "(a)-->()\n()-->(c)");
+}
+
+#[test]
+fn test_path_chaining_preserves_other_patterns_in_place() {
+    let patterns = vec![
+        Pattern::point(subject_with_id("n")),
+        relationship(empty_subject(), "a", "b"),
+        Pattern::point(subject_with_id("m")),
+        relationship(empty_subject(), "b", "c"),
+    ];
+
+    let result = to_gram_with_options(&patterns, &chained()).unwrap();
+    assert_eq!(result, "(n)\n(a)-->(b)-->(c)\n(m)");
+}
+
+#[test]
+fn test_path_chaining_writes_parsed_paths_inline() {
+    let input = "(a)-[:R1]->(b)-[:R2]->(c)-->(d)";
+    let parsed = parse_gram_notation(input).unwrap();
+
+    let result = to_gram_with_options(&parsed, &chained()).unwrap();
+    assert_eq!(result, input);
+    assert_eq!(parse_gram_notation(&result).unwrap(), parsed);
+}
+
+#[test]
+fn test_chained_and_separate_reparse_to_same_graph() {
+    let edge = |id: &str, label: &str| {
+        let mut edge = labeled_edge(label);
+        edge.identity = Symbol(id.to_string());
+        edge
+    };
+    let patterns = vec![
+        relationship(edge("k1", "KNOWS"), "alice", "bob"),
+        relationship(edge("k2", "KNOWS"), "bob", "carol"),
+        relationship(edge("l1", "LIKES"), "carol", "alice"),
+        relationship(edge("l2", "LIKES"), "bob", "dave"),
+    ];
+
+    let separate = to_gram_with_options(&patterns, &SerializeOptions::default()).unwrap();
+    let inline = to_gram_with_options(&patterns, &chained()).unwrap();
+    assert_ne!(separate, inline);
+
+    let graph = from_patterns(
+        &canonical_classifier(),
+        parse_gram_notation(&inline).unwrap(),
+    );
+    assert_eq!(graph.pg_nodes.len(), 4);
+    assert_eq!(graph.pg_relationships.len(), 4);
+    assert_eq!(reparsed_graph(&inline), reparsed_graph(&separate));
+
+    // Anonymous relationships rebuild to the same relationships too
+    let patterns = vec![
+        relationship(labeled_edge("KNOWS"), "a", "b"),
+        relationship(labeled_edge("KNOWS"), "b", "c"),
+        relationship(empty_subject(), "c", "d"),
+    ];
+    let separate = to_gram_with_options(&patterns, &SerializeOptions::default()).unwrap();
+    let inline = to_gram_with_options(&patterns, &chained()).unwrap();
+    assert_eq!(inline, "(a)-[:KNOWS]->(b)-[:KNOWS]->(c)-->(d)");
+    assert_eq!(reparsed_graph(&inline), reparsed_graph(&separate));
+}
+
+// ============================================================================
+// Empty node style
+// ============================================================================
+
+fn skip_empty() -> SerializeOptions {
+    SerializeOptions {
+        empty_node: EmptyNodeStyle::Skip,
+        ..SerializeOptions::default()
+    }
+}
+
+#[test]
+fn test_empty_nodes_written_as_parentheses_by_default() {
+    let patterns = parse_gram_notation("() (a) ()").unwrap();
+    let result = to_gram_with_options(&patterns, &SerializeOptions::default()).unwrap();
+    assert_eq!(result, "()\n(a)\n()");
+    assert_eq!(parse_gram_notation(&result).unwrap(), patterns);
+}
+
+#[test]
+fn test_empty_node_forms_reparse_consistently() {
+    for input in [
+        "()",
+        "( )",
+        "[]",
+        "[ | ]",
+        "() ()",
+        "[g | (), (a)]",
+        "()-->()",
+    ] {
+        let patterns = parse_gram_notation(input).unwrap();
+        let result = to_gram_with_options(&patterns, &SerializeOptions::default()).unwrap();
+        assert_eq!(parse_gram_notation(&result).unwrap(), patterns, "{}", input);
+    }
+}
+
+#[test]
+fn test_skip_omits_empty_top_level_nodes() {
+    let patterns = parse_gram_notation("() (a) [] (b)").unwrap();
+    let result = to_gram_with_options(&patterns, &skip_empty()).unwrap();
+    assert_eq!(result, "(a)\n(b)");
+
+    let only_empty = parse_gram_notation("() ()").unwrap();
+    assert_eq!(
+        to_gram_with_options(&only_empty, &skip_empty()).unwrap(),
+        ""
+    );
+}
+
+#[test]
+fn test_skip_keeps_nested_empty_nodes() {
+    let patterns = parse_gram_notation("[g | (), (a), ()] ()-->() ()").unwrap();
+    let result = to_gram_with_options(&patterns, &skip_empty()).unwrap();
+    assert_eq!(result, "[g | (), (a), ()]\n()-->()");
+    assert_eq!(parse_gram_notation(&result).unwrap(), patterns[..2]);
+}
+
+#[test]
+fn test_skip_with_path_chaining() {
+    let patterns = parse_gram_notation("(a)-->(b) () (b)-->(c)").unwrap();
+    let options = SerializeOptions {
+        path_chaining: true,
+        empty_node: EmptyNodeStyle::Skip,
+        ..SerializeOptions::default()
+    };
+    assert_eq!(
+        to_gram_with_options(&patterns, &options).unwrap(),
+        "(a)-->(b)-->(c)"
+    );
+}
+
+// ============================================================================
+// Indented layout
+// ============================================================================
+
+fn indented(indent: usize, max_width: Option<usize>) -> SerializeOptions {
+    SerializeOptions {
+        indent: Some(indent),
+        max_width,
+        ..SerializeOptions::default()
+    }
+}
+
+#[test]
+fn test_indent_puts_each_element_on_its_own_line() {
+    let patterns = parse_gram_notation(
+        "[team:Group {k: 1} | alice, (bob)-->(carol), [sub | (d), (e), (f)]] (g)",
+    )
+    .unwrap();
+    let result = to_gram_with_options(&patterns, &indented(2, None)).unwrap();
+    assert_eq!(
+        result,
+        "[team:Group {k: 1} |\n  (alice),\n  (bob)-->(carol),\n  [sub |\n    (d),\n    (e),\n    (f)\n  ]\n]\n(g)"
+    );
+    assert_eq!(parse_gram_notation(&result).unwrap(), patterns);
+}
+
+#[test]
+fn test_max_width_keeps_narrow_patterns_on_one_line() {
+    let patterns = parse_gram_notation("[outer | [a | (x), (y), (w)], [b | (z)]]").unwrap();
+    assert_eq!(
+        to_gram_with_options(&patterns, &indented(4, Some(80))).unwrap(),
+        "[outer | [a | (x), (y), (w)], @@b (z)]"
+    );
+    let result = to_gram_with_options(&patterns, &indented(4, Some(30))).unwrap();
+    assert_eq!(result, "[outer |\n    [a | (x), (y), (w)],\n    @@b (z)\n]");
+    assert_eq!(parse_gram_notation(&result).unwrap(), patterns);
+}
+
+#[test]
+fn test_indent_breaks_annotated_subject_patterns() {
+    for input in ["@k(1) [g | (a), (b), (c)]", "@k(1) [ | @j(2) (a)]"] {
+        let patterns = parse_gram_notation(input).unwrap();
+        let result = to_gram_with_options(&patterns, &indented(2, None)).unwrap();
+        assert!(result.contains("|\n  "), "{}", result);
+        assert_eq!(parse_gram_notation(&result).unwrap(), patterns, "{}", input);
+    }
+}
+
+#[test]
+fn test_layout_without_indent_is_single_line() {
+    let patterns = parse_gram_notation("{v: 1} [g | (a), (b)] (a)-->(b)").unwrap();
+    let options = SerializeOptions {
+        max_width: Some(5),
+        ..SerializeOptions::default()
+    };
+    assert_eq!(
+        to_gram_with_options(&patterns, &options).unwrap(),
+        to_gram(&patterns).unwrap()
+    );
+    for pattern in &patterns {
+        assert_eq!(
+            to_gram_pattern_with_options(pattern, &SerializeOptions::default()).unwrap(),
+            to_gram_pattern(pattern).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_indent_with_path_chaining() {
+    let patterns = parse_gram_notation("(a)-->(b) (b)-->(c) [g | (a), (c), (d)]").unwrap();
+    let options = SerializeOptions {
+        path_chaining: true,
+        ..indented(2, None)
+    };
+    assert_eq!(
+        to_gram_with_options(&patterns, &options).unwrap(),
+        "(a)-->(b)-->(c)\n[g |\n  (a),\n  (c),\n  (d)\n]"
+    );
+}
+
+// ============================================================================
+// Streaming to a writer
+// ============================================================================
+
+#[test]
+fn test_write_gram_matches_to_gram() {
+    for input in [
+        "",
+        "(a)",
+        "{v: 1} (a:Person {name: 'Alice'})-[:KNOWS]->(b) [g | (a), (b), (c)] @k(1) (d)",
+        "(a) ({v: 1}) (b)<--(c)",
+    ] {
+        let patterns = parse_gram_notation(input).unwrap();
+        let expected = to_gram(&patterns).unwrap();
+
+        let mut bytes = Vec::new();
+        write_gram(&patterns, &mut bytes).unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), expected, "{}", input);
+
+        let mut text = String::new();
+        write_gram_fmt(&patterns, &mut text).unwrap();
+        assert_eq!(text, expected, "{}", input);
+    }
+
+    let pattern = &parse_gram_notation("{v: 1}").unwrap()[0];
+    let mut bytes = Vec::new();
+    write_gram_pattern(pattern, &mut bytes).unwrap();
+    assert_eq!(bytes, to_gram_pattern(pattern).unwrap().into_bytes());
+}
+
+#[test]
+fn test_write_gram_reports_write_failures() {
+    struct Full;
+    impl std::io::Write for Full {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "disk full"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let patterns = parse_gram_notation("(a) (b)").unwrap();
+    let err = write_gram(&patterns, Full).unwrap_err();
+    assert!(matches!(err, SerializeError::IoError { ref message } if message == "disk full"));
+}
+
+#[test]
+fn test_serialize_labels_and_properties_in_sorted_order() {
+    let build = || {
+        let mut subject = subject_with_id("a");
+        for label in ["Person", "Admin", "Employee", "Zebra", "Manager"] {
+            subject.labels.insert(label.to_string());
+        }
+        for (i, key) in ["name", "age", "zip", "city", "email", "b key"]
+            .iter()
+            .enumerate()
+        {
+            subject
+                .properties
+                .insert(key.to_string(), pattern_core::Value::VInteger(i as i64));
+        }
+        subject
+    };
+
+    let node = Pattern::point(build());
+    let first = to_gram_pattern(&node).unwrap();
+    // A fresh subject gets fresh hash-map iteration order.
+    assert_eq!(to_gram_pattern(&Pattern::point(build())).unwrap(), first);
+    assert_eq!(
+        first,
+        "(a:Admin:Employee:Manager:Person:Zebra {age: 1, `b key`: 5, city: 3, email: 4, name: 0, zip: 2})"
+    );
+
+    let annotation = Pattern::pattern(build(), vec![Pattern::point(subject_with_id("x"))]);
+    assert_eq!(
+        to_gram_pattern(&annotation).unwrap(),
+        "@@a:Admin:Employee:Manager:Person:Zebra @age(1) @`b key`(5) @city(3) @email(4) @name(0) @zip(2) (x)"
+    );
+}
@@ -1,6 +1,7 @@
 //! Comprehensive value type tests for Phase 5
 
-use gram_codec::{parse_gram_notation, to_gram_pattern};
+use gram_codec::{parse_gram_notation, parse_value, to_gram_pattern, ParseError};
+use pattern_core::{RangeValue, Value};
 
 #[test]
 fn test_parse_integer_values() {
@@ -208,3 +209,66 @@ fn test_multiple_properties_mixed_types() {
     assert_eq!(patterns.len(), 1);
     assert_eq!(patterns[0].value.properties.len(), 6);
 }
+
+#[test]
+fn test_parse_value_literals() {
+    assert_eq!(parse_value("42").unwrap(), Value::VInteger(42));
+    assert_eq!(parse_value("-2.5").unwrap(), Value::VDecimal(-2.5));
+    assert_eq!(parse_value("true").unwrap(), Value::VBoolean(true));
+    assert_eq!(
+        parse_value("'single'").unwrap(),
+        Value::VString("single".to_string())
+    );
+    assert_eq!(
+        parse_value("active").unwrap(),
+        Value::VSymbol("active".to_string())
+    );
+    assert_eq!(
+        parse_value("[1, \"a\"]").unwrap(),
+        Value::VArray(vec![Value::VInteger(1), Value::VString("a".to_string())])
+    );
+    assert_eq!(
+        parse_value("1..10").unwrap(),
+        Value::VRange(RangeValue {
+            lower: Some(1.0),
+            upper: Some(10.0),
+        })
+    );
+}
+
+#[test]
+fn test_parse_value_matches_property_value() {
+    for literal in [
+        "{x: 1, y: [true]}",
+        "5kg",
+        "date`2024-01-01`",
+        "\"esc\\\"aped\"",
+    ] {
+        let patterns = parse_gram_notation(&format!("({{k: {}}})", literal)).unwrap();
+        assert_eq!(
+            parse_value(literal).unwrap(),
+            patterns[0].value.properties["k"],
+            "{}",
+            literal
+        );
+    }
+}
+
+#[test]
+fn test_parse_value_tolerates_surrounding_whitespace() {
+    assert_eq!(parse_value("  \n 7 \t\n").unwrap(), Value::VInteger(7));
+    assert_eq!(parse_value("7 // seven").unwrap(), Value::VInteger(7));
+}
+
+#[test]
+fn test_parse_value_rejects_trailing_content() {
+    match parse_value("1 2") {
+        Err(ParseError::UnexpectedInput { location, snippet }) => {
+            assert_eq!(location.column, 3);
+            assert_eq!(snippet, "2");
+        }
+        other => panic!("expected UnexpectedInput, got {:?}", other),
+    }
+    assert!(parse_value("").is_err());
+    assert!(parse_value("(a)").is_err());
+}
@@ -322,6 +322,7 @@ fn render_value(value: &Value) -> String {
         Value::VInteger(value) => value.to_string(),
         Value::VDecimal(value) => gram_codec::Value::Decimal(*value).to_gram_notation(),
         Value::VBoolean(value) => value.to_string(),
+        Value::VNull => "null".to_string(),
         Value::VString(value) => gram_codec::Value::String(value.clone()).to_gram_notation(),
         Value::VSymbol(value) => gram_codec::Value::String(value.clone()).to_gram_notation(),
         Value::VTaggedString { tag, content } => gram_codec::Value::TaggedString {
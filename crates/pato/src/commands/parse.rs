@@ -500,6 +500,7 @@ fn render_value_lines(value: &pattern_core::Value, indent: usize) -> Vec<String>
         pattern_core::Value::VInteger(_) => vec![format!("{}(integer)", spaces(indent))],
         pattern_core::Value::VDecimal(_) => vec![format!("{}(decimal)", spaces(indent))],
         pattern_core::Value::VBoolean(_) => vec![format!("{}(boolean_literal)", spaces(indent))],
+        pattern_core::Value::VNull => vec![format!("{}(null)", spaces(indent))],
         pattern_core::Value::VArray(values) => {
             let mut lines = vec![format!("{}(array", spaces(indent))];
             for value in values {
@@ -14,11 +14,12 @@
 //!
 //! An edge with `INFINITY` cost in a given direction is impassable in that direction.
 
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 
 use crate::graph::graph_classifier::{GraphClass, GraphClassifier, GraphValue};
 use crate::graph::graph_query::{GraphQuery, TraversalDirection, TraversalWeight};
 use crate::pattern::Pattern;
+use crate::subject::{Subject, Symbol};
 
 // ============================================================================
 // Private helper: reachable_neighbors
@@ -39,10 +40,44 @@ fn reachable_neighbors<V>(
 ) -> Vec<(Pattern<V>, f64)>
 where
     V: GraphValue + Clone,
+{
+    let mut neighbors = Vec::new();
+    for_each_reachable(q, weight, node, |neighbor, _rel, cost| {
+        neighbors.push((neighbor, cost))
+    });
+    neighbors
+}
+
+/// Like [`reachable_neighbors`], but also returns the relationship traversed
+/// to reach each neighbor.
+fn reachable_edges<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    node: &Pattern<V>,
+) -> Vec<(Pattern<V>, Pattern<V>)>
+where
+    V: GraphValue + Clone,
+{
+    let mut edges = Vec::new();
+    for_each_reachable(q, weight, node, |neighbor, rel, _cost| {
+        edges.push((neighbor, rel.clone()))
+    });
+    edges
+}
+
+/// Calls `f(neighbor, relationship, cost)` for each reachable neighbor of `node`.
+#[inline]
+fn for_each_reachable<V, F>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    node: &Pattern<V>,
+    mut f: F,
+) where
+    V: GraphValue + Clone,
+    F: FnMut(Pattern<V>, &Pattern<V>, f64),
 {
     let node_id = node.value.identify();
     let rels = (q.query_incident_rels)(node);
-    let mut neighbors = Vec::new();
 
     for rel in rels {
         let src = (q.query_source)(&rel);
@@ -54,7 +89,7 @@ where
                 let fwd = weight(&rel, TraversalDirection::Forward);
                 if fwd.is_finite() {
                     if let Some(t) = tgt.clone() {
-                        neighbors.push((t, fwd));
+                        f(t, &rel, fwd);
                     }
                 }
             }
@@ -66,12 +101,49 @@ where
                 let bwd = weight(&rel, TraversalDirection::Backward);
                 if bwd.is_finite() {
                     if let Some(s) = src.clone() {
-                        neighbors.push((s, bwd));
+                        f(s, &rel, bwd);
                     }
                 }
             }
         }
     }
+}
+
+/// Returns every node that can step to `node` under `weight`: the mirror
+/// image of [`reachable_neighbors`], used to search backwards from a target.
+fn reaching_neighbors<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    node: &Pattern<V>,
+) -> Vec<Pattern<V>>
+where
+    V: GraphValue + Clone,
+{
+    let node_id = node.value.identify();
+    let mut neighbors = Vec::new();
+
+    for rel in (q.query_incident_rels)(node) {
+        let src = (q.query_source)(&rel);
+        let tgt = (q.query_target)(&rel);
+
+        // Forward into node: node is the target → the source reaches it
+        if let (Some(s), Some(t)) = (&src, &tgt) {
+            if t.value.identify() == node_id
+                && weight(&rel, TraversalDirection::Forward).is_finite()
+            {
+                neighbors.push(s.clone());
+            }
+        }
+
+        // Backward into node: node is the source → the target reaches it
+        if let (Some(s), Some(t)) = (&src, &tgt) {
+            if s.value.identify() == node_id
+                && weight(&rel, TraversalDirection::Backward).is_finite()
+            {
+                neighbors.push(t.clone());
+            }
+        }
+    }
 
     neighbors
 }
@@ -136,6 +208,72 @@ where
     result
 }
 
+/// Breadth-first traversal from `start`, recording the relationship used to
+/// reach each node.
+///
+/// Returns the same nodes in the same order as [`bfs`], each paired with the
+/// relationship traversed to reach it (`None` for the start node). The
+/// relationships form a spanning tree of the reachable nodes.
+pub fn bfs_with_edges<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    start: &Pattern<V>,
+) -> Vec<(Pattern<V>, Option<Pattern<V>>)>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let mut visited: HashSet<V::Id> = HashSet::new();
+    let mut queue = VecDeque::new();
+    let mut result = Vec::new();
+
+    visited.insert(start.value.identify().clone());
+    queue.push_back((start.clone(), None));
+
+    while let Some((current, via)) = queue.pop_front() {
+        for (neighbor, rel) in reachable_edges(q, weight, &current) {
+            if visited.insert(neighbor.value.identify().clone()) {
+                queue.push_back((neighbor, Some(rel)));
+            }
+        }
+        result.push((current, via));
+    }
+
+    result
+}
+
+/// Depth-first traversal from `start`, recording the relationship used to
+/// reach each node.
+///
+/// Returns the same nodes in the same order as [`dfs`], each paired with the
+/// relationship traversed to reach it (`None` for the start node).
+pub fn dfs_with_edges<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    start: &Pattern<V>,
+) -> Vec<(Pattern<V>, Option<Pattern<V>>)>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let mut visited: HashSet<V::Id> = HashSet::new();
+    let mut stack = vec![(start.clone(), None)];
+    let mut result = Vec::new();
+
+    while let Some((current, via)) = stack.pop() {
+        if visited.insert(current.value.identify().clone()) {
+            for (neighbor, rel) in reachable_edges(q, weight, &current) {
+                if !visited.contains(neighbor.value.identify()) {
+                    stack.push((neighbor, Some(rel)));
+                }
+            }
+            result.push((current, via));
+        }
+    }
+
+    result
+}
+
 // ============================================================================
 // Path algorithms
 // ============================================================================
@@ -155,63 +293,245 @@ where
     V: GraphValue + Clone,
     V::Id: Clone + Eq + std::hash::Hash + Ord,
 {
-    // Same-node case
-    if from.value.identify() == to.value.identify() {
+    best_first_path(q, weight, from, to, &|_| 0.0, &|_, _| true)
+}
+
+/// Find the minimum-cost path from `from` to `to` using A* search.
+///
+/// `heuristic` estimates the remaining cost from a node to `to` and must be
+/// non-negative. With an admissible heuristic (one that never overestimates,
+/// e.g. straight-line distance between node coordinates) the result is an
+/// optimal path, the same cost as [`shortest_path`], while typically exploring
+/// fewer nodes. A heuristic of `0.0` everywhere is exactly Dijkstra.
+///
+/// - Same node: returns `Some(vec![node])` immediately.
+/// - No path: returns `None`.
+/// - Uses `f64::INFINITY` cost to mark impassable edges.
+pub fn a_star<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    from: &Pattern<V>,
+    to: &Pattern<V>,
+    heuristic: impl Fn(&Pattern<V>) -> f64,
+) -> Option<Vec<Pattern<V>>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    best_first_path(q, weight, from, to, &heuristic, &|_, _| true)
+}
+
+/// Find a path with the fewest hops from `from` to `to`, searching from both
+/// ends at once.
+///
+/// Relationship costs are ignored except that `f64::INFINITY` marks an edge as
+/// impassable, so on unit-weight graphs the path has the same length as
+/// [`shortest_path`]. Each round expands one full BFS level of whichever
+/// frontier is smaller, forwards from `from` or backwards from `to`, and the
+/// search stops in the round the two meet. On large graphs this explores far
+/// fewer nodes than a single-direction search.
+///
+/// - Same node: returns `Some(vec![node])` immediately.
+/// - No path: returns `None`.
+pub fn bidirectional_bfs<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    from: &Pattern<V>,
+    to: &Pattern<V>,
+) -> Option<Vec<Pattern<V>>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let from_id = from.value.identify().clone();
+    let to_id = to.value.identify().clone();
+    if from_id == to_id {
         return Some(vec![from.clone()]);
     }
 
-    // dist[id] = best known cost from `from` to node with that id
-    let mut dist: HashMap<V::Id, f64> = HashMap::new();
-    // prev[id] = predecessor node on the best-known path
-    let mut prev: HashMap<V::Id, Pattern<V>> = HashMap::new();
+    // Per side: id → (hops from that side's endpoint, next node towards it)
+    let mut fwd: HashMap<V::Id, (usize, Option<Pattern<V>>)> = HashMap::new();
+    let mut bwd: HashMap<V::Id, (usize, Option<Pattern<V>>)> = HashMap::new();
+    fwd.insert(from_id, (0, None));
+    bwd.insert(to_id, (0, None));
+    let mut fwd_frontier = vec![from.clone()];
+    let mut bwd_frontier = vec![to.clone()];
+
+    while !fwd_frontier.is_empty() && !bwd_frontier.is_empty() {
+        let forward = fwd_frontier.len() <= bwd_frontier.len();
+        let (frontier, seen, other) = if forward {
+            (&mut fwd_frontier, &mut fwd, &bwd)
+        } else {
+            (&mut bwd_frontier, &mut bwd, &fwd)
+        };
+
+        // Finish the whole level before stopping, keeping the shortest meeting
+        let mut meeting: Option<(usize, V::Id, Pattern<V>)> = None;
+        let mut next = Vec::new();
+        for node in std::mem::take(frontier) {
+            let hops = seen[node.value.identify()].0 + 1;
+            let neighbors = if forward {
+                reachable_neighbors(q, weight, &node)
+                    .into_iter()
+                    .map(|(n, _)| n)
+                    .collect()
+            } else {
+                reaching_neighbors(q, weight, &node)
+            };
+            for neighbor in neighbors {
+                let nid = neighbor.value.identify().clone();
+                if seen.contains_key(&nid) {
+                    continue;
+                }
+                seen.insert(nid.clone(), (hops, Some(node.clone())));
+                if let Some((other_hops, _)) = other.get(&nid) {
+                    let total = hops + other_hops;
+                    let better = match &meeting {
+                        None => true,
+                        Some((best, best_id, _)) => (total, &nid) < (*best, best_id),
+                    };
+                    if better {
+                        meeting = Some((total, nid.clone(), neighbor.clone()));
+                    }
+                }
+                next.push(neighbor);
+            }
+        }
 
-    let from_id = from.value.identify().clone();
-    dist.insert(from_id.clone(), 0.0);
+        if let Some((_, _, middle)) = meeting {
+            let walk_back = |side: &HashMap<V::Id, (usize, Option<Pattern<V>>)>| {
+                let mut nodes = Vec::new();
+                let mut cur = side[middle.value.identify()].1.clone();
+                while let Some(node) = cur {
+                    cur = side[node.value.identify()].1.clone();
+                    nodes.push(node);
+                }
+                nodes
+            };
+            let mut path = walk_back(&fwd);
+            path.reverse();
+            path.push(middle.clone());
+            path.extend(walk_back(&bwd));
+            return Some(path);
+        }
+        *frontier = next;
+    }
 
-    // Priority queue: (cost_bits_for_ordering, node_id) → node
-    // For non-negative finite f64, the IEEE 754 bit pattern preserves ordering.
-    let mut pq: BTreeMap<(u64, V::Id), Pattern<V>> = BTreeMap::new();
-    pq.insert((0u64, from_id.clone()), from.clone());
+    None
+}
+
+/// Shared path search behind [`shortest_path`], [`a_star`], and
+/// [`k_shortest_paths`].
+///
+/// Runs [`single_source_search`] from `from` until `to` is reached, then
+/// follows the predecessors back.
+fn best_first_path<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    from: &Pattern<V>,
+    to: &Pattern<V>,
+    heuristic: &dyn Fn(&Pattern<V>) -> f64,
+    step_allowed: &dyn Fn(&V::Id, &V::Id) -> bool,
+) -> Option<Vec<Pattern<V>>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let search = single_source_search(q, weight, from, Some(to), heuristic, step_allowed);
+    let node = search.reached?;
+
+    let mut path = vec![node.clone()];
+    let mut cur_id = node.value.identify().clone();
+    while let Some(p) = search.prev.get(&cur_id) {
+        path.push(p.clone());
+        cur_id = p.value.identify().clone();
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Result of [`single_source_search`].
+struct Search<V: GraphValue> {
+    /// dist[id] = best known cost from the source to the node with that id
+    dist: HashMap<V::Id, f64>,
+    /// prev[id] = predecessor node on the best-known path
+    prev: HashMap<V::Id, Pattern<V>>,
+    /// The target, if it was reached
+    reached: Option<Pattern<V>>,
+}
 
-    while let Some(((cost_bits, uid), node)) = pq.pop_first() {
-        let cost = f64::from_bits(cost_bits);
+/// Single-source shortest-path search (Dijkstra, or A* given a heuristic).
+///
+/// Nodes are expanded in order of `cost + heuristic(node)`, starting from
+/// `source` at cost 0. Edges of infinite cost are not taken, nor are steps
+/// from one node to the next that `step_allowed` rejects. The search stops
+/// once `target` is expanded; without a target it runs until every reachable
+/// node has its minimum cost in `dist`.
+fn single_source_search<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    source: &Pattern<V>,
+    target: Option<&Pattern<V>>,
+    heuristic: &dyn Fn(&Pattern<V>) -> f64,
+    step_allowed: &dyn Fn(&V::Id, &V::Id) -> bool,
+) -> Search<V>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let mut search = Search {
+        dist: HashMap::new(),
+        prev: HashMap::new(),
+        reached: None,
+    };
+    let target_id = target.map(|t| t.value.identify());
 
+    let source_id = source.value.identify().clone();
+    search.dist.insert(source_id.clone(), 0.0);
+
+    // Priority queue: (priority_bits_for_ordering, node_id) → (cost, node)
+    // For non-negative finite f64, the IEEE 754 bit pattern preserves ordering.
+    let mut pq: BTreeMap<(u64, V::Id), (f64, Pattern<V>)> = BTreeMap::new();
+    pq.insert(
+        (heuristic(source).to_bits(), source_id),
+        (0.0, source.clone()),
+    );
+
+    while let Some(((_, uid), (cost, node))) = pq.pop_first() {
         // Skip stale entries
-        if let Some(&best) = dist.get(&uid) {
+        if let Some(&best) = search.dist.get(&uid) {
             if cost > best {
                 continue;
             }
         }
 
         // Reached destination
-        if uid == *to.value.identify() {
-            let mut path = vec![node.clone()];
-            let mut cur_id = uid.clone();
-            while let Some(p) = prev.get(&cur_id) {
-                path.push(p.clone());
-                cur_id = p.value.identify().clone();
-            }
-            path.reverse();
-            return Some(path);
+        if target_id == Some(&uid) {
+            search.reached = Some(node);
+            return search;
         }
 
         for (neighbor, edge_cost) in reachable_neighbors(q, weight, &node) {
             if !edge_cost.is_finite() {
                 continue;
             }
-            let new_cost = cost + edge_cost;
             let nid = neighbor.value.identify().clone();
+            if !step_allowed(&uid, &nid) {
+                continue;
+            }
+            let new_cost = cost + edge_cost;
 
-            let should_update = dist.get(&nid).map(|&d| new_cost < d).unwrap_or(true);
+            let should_update = search.dist.get(&nid).map(|&d| new_cost < d).unwrap_or(true);
             if should_update {
-                dist.insert(nid.clone(), new_cost);
-                prev.insert(nid.clone(), node.clone());
-                pq.insert((new_cost.to_bits(), nid), neighbor);
+                search.dist.insert(nid.clone(), new_cost);
+                search.prev.insert(nid.clone(), node.clone());
+                let priority = new_cost + heuristic(&neighbor);
+                pq.insert((priority.to_bits(), nid), (new_cost, neighbor));
             }
         }
     }
 
-    None
+    search
 }
 
 /// Returns `true` if a path exists from `from` to `to`.
@@ -290,6 +610,107 @@ fn all_paths_dfs<V>(
     }
 }
 
+/// The `k` minimum-cost simple paths from `from` to `to` (Yen's algorithm).
+///
+/// Paths come in ascending cost order, ties broken by node identities, and
+/// no two paths visit the same sequence of nodes. Fewer than `k` are returned
+/// when fewer simple paths exist. Unlike [`all_paths`], only `k` shortest-path
+/// searches per returned path are needed, so this stays usable on dense graphs.
+///
+/// - Same node: returns `vec![vec![node]]` (for `k > 0`).
+/// - No path: returns an empty `Vec`.
+/// - Uses `f64::INFINITY` cost to mark impassable edges.
+pub fn k_shortest_paths<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    from: &Pattern<V>,
+    to: &Pattern<V>,
+    k: usize,
+) -> Vec<Vec<Pattern<V>>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let ids = |path: &[Pattern<V>]| -> Vec<V::Id> {
+        path.iter().map(|n| n.value.identify().clone()).collect()
+    };
+
+    let mut found: Vec<Vec<Pattern<V>>> = Vec::new();
+    if k == 0 {
+        return found;
+    }
+    let Some(first) = best_first_path(q, weight, from, to, &|_| 0.0, &|_, _| true) else {
+        return found;
+    };
+    let mut seen: HashSet<Vec<V::Id>> = HashSet::from([ids(&first)]);
+    found.push(first);
+
+    // Candidate paths keyed by (cost bits, node identities): cheapest first
+    let mut candidates = BTreeMap::new();
+
+    while found.len() < k {
+        let last = found.last().expect("at least one path found");
+        let last_ids = ids(last);
+
+        // Branch off the last path at each of its nodes in turn
+        for i in 0..last.len() - 1 {
+            let spur_id = &last_ids[i];
+            let root_ids = &last_ids[..=i];
+
+            // Steps out of the spur node already taken by a path with this root
+            let used: HashSet<V::Id> = found
+                .iter()
+                .map(|p| ids(p))
+                .filter(|p| p.len() > i + 1 && p[..=i] == *root_ids)
+                .map(|p| p[i + 1].clone())
+                .collect();
+            let step_allowed = |a: &V::Id, b: &V::Id| {
+                if root_ids[..i].contains(b) {
+                    return false;
+                }
+                a != spur_id || !used.contains(b)
+            };
+
+            let Some(spur_path) = best_first_path(q, weight, &last[i], to, &|_| 0.0, &step_allowed)
+            else {
+                continue;
+            };
+            let mut path = last[..i].to_vec();
+            path.extend(spur_path);
+            let path_ids = ids(&path);
+            if seen.insert(path_ids.clone()) {
+                let cost = path_cost(q, weight, &path);
+                candidates.insert((cost.to_bits(), path_ids), path);
+            }
+        }
+
+        let Some((_, next)) = candidates.pop_first() else {
+            break;
+        };
+        found.push(next);
+    }
+
+    found
+}
+
+/// Total cost of walking `path`, taking the cheapest traversable relationship
+/// for each step.
+fn path_cost<V>(q: &GraphQuery<V>, weight: &TraversalWeight<V>, path: &[Pattern<V>]) -> f64
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    path.windows(2)
+        .map(|step| {
+            reachable_neighbors(q, weight, &step[0])
+                .into_iter()
+                .filter(|(neighbor, _)| neighbor.value.identify() == step[1].value.identify())
+                .map(|(_, cost)| cost)
+                .fold(f64::INFINITY, f64::min)
+        })
+        .sum()
+}
+
 // ============================================================================
 // Boolean queries
 // ============================================================================
@@ -361,22 +782,120 @@ where
     components
 }
 
-/// Topological sort using iterative DFS post-order with cycle detection.
+/// Partition the graph into strongly connected components (Tarjan's algorithm).
+///
+/// Two nodes share a component when each is reachable from the other by
+/// following relationships from source to target. Every node belongs to
+/// exactly one component, so a DAG yields one singleton per node.
+///
+/// - Ignores `TraversalWeight` — uses relationship endpoint order only.
+/// - Iterative: deep graphs cannot overflow the call stack.
+///
+/// Components come in the order Tarjan's algorithm completes them: each one
+/// precedes every component with a relationship into it. Nodes and their
+/// successors are visited in `V::Id` order and nodes within a component are
+/// sorted by `V::Id`, so the same graph always yields the same result.
+pub fn strongly_connected_components<V>(q: &GraphQuery<V>) -> Vec<Vec<Pattern<V>>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let mut by_id: BTreeMap<V::Id, Pattern<V>> = (q.query_nodes)()
+        .into_iter()
+        .map(|n| (n.value.identify().clone(), n))
+        .collect();
+
+    // Forward adjacency, one entry per relationship
+    let mut successors: HashMap<V::Id, Vec<V::Id>> = HashMap::new();
+    for rel in (q.query_relationships)() {
+        let (Some(src), Some(tgt)) = ((q.query_source)(&rel), (q.query_target)(&rel)) else {
+            continue;
+        };
+        let sid = src.value.identify().clone();
+        let tid = tgt.value.identify().clone();
+        successors.entry(sid.clone()).or_default().push(tid.clone());
+        by_id.entry(sid).or_insert(src);
+        by_id.entry(tid).or_insert(tgt);
+    }
+    for ids in successors.values_mut() {
+        ids.sort();
+    }
+
+    let mut index: HashMap<V::Id, usize> = HashMap::new();
+    let mut lowlink: HashMap<V::Id, usize> = HashMap::new();
+    let mut stack: Vec<V::Id> = Vec::new();
+    let mut on_stack: HashSet<V::Id> = HashSet::new();
+    let mut components: Vec<Vec<Pattern<V>>> = Vec::new();
+
+    for root in by_id.keys() {
+        if index.contains_key(root) {
+            continue;
+        }
+
+        // Explicit call stack: (node, position of its next successor to visit)
+        let mut call: Vec<(V::Id, usize)> = vec![(root.clone(), 0)];
+
+        while let Some((v, i)) = call.last().cloned() {
+            if !index.contains_key(&v) {
+                let n = index.len();
+                index.insert(v.clone(), n);
+                lowlink.insert(v.clone(), n);
+                stack.push(v.clone());
+                on_stack.insert(v.clone());
+            }
+
+            if let Some(w) = successors.get(&v).and_then(|s| s.get(i)) {
+                call.last_mut().expect("frame for v").1 += 1;
+                if !index.contains_key(w) {
+                    call.push((w.clone(), 0));
+                } else if on_stack.contains(w) {
+                    let low = lowlink[&v].min(index[w]);
+                    lowlink.insert(v, low);
+                }
+                continue;
+            }
+
+            // All successors of v are done: return to the caller
+            call.pop();
+            if let Some((u, _)) = call.last() {
+                let low = lowlink[u].min(lowlink[&v]);
+                lowlink.insert(u.clone(), low);
+            }
+            if lowlink[&v] == index[&v] {
+                let mut ids = Vec::new();
+                loop {
+                    let w = stack.pop().expect("v is on the stack");
+                    on_stack.remove(&w);
+                    let done = w == v;
+                    ids.push(w);
+                    if done {
+                        break;
+                    }
+                }
+                ids.sort();
+                components.push(ids.iter().map(|id| by_id[id].clone()).collect());
+            }
+        }
+    }
+
+    components
+}
+
+/// Topological sort with cycle detection (Kahn's algorithm).
 ///
 /// - Returns `Some(order)` if the graph is a DAG.
 /// - Returns `None` if a directed cycle is detected.
 /// - Ignores `TraversalWeight` — uses relationship endpoint order only.
+///
+/// The order is deterministic: whenever several nodes are ready, the one with
+/// the smallest `V::Id` comes first. The result is therefore the
+/// lexicographically-smallest valid topological order (by identity), and the
+/// same DAG always yields the same sequence regardless of `query_nodes` order.
 pub fn topological_sort<V>(q: &GraphQuery<V>) -> Option<Vec<Pattern<V>>>
 where
     V: GraphValue + Clone,
     V::Id: Clone + Eq + std::hash::Hash + Ord,
 {
-    let nodes = (q.query_nodes)();
-
-    let mut in_stack: HashSet<V::Id> = HashSet::new();
-    let mut done: HashSet<V::Id> = HashSet::new();
-    let mut result: Vec<Pattern<V>> = Vec::new();
-
     // Returns forward neighbors (rels where node is the source)
     let forward_neighbors = |node: &Pattern<V>| -> Vec<Pattern<V>> {
         let rels = (q.query_incident_rels)(node);
@@ -393,46 +912,48 @@ where
             .collect()
     };
 
-    for start in &nodes {
-        if done.contains(start.value.identify()) {
-            continue;
-        }
-
-        let start_id = start.value.identify().clone();
-        in_stack.insert(start_id);
-        let neighbors = forward_neighbors(start);
-        // Stack: (node, its_forward_neighbors, current_neighbor_index)
-        let mut stack: Vec<(Pattern<V>, Vec<Pattern<V>>, usize)> =
-            vec![(start.clone(), neighbors, 0)];
-
-        while !stack.is_empty() {
-            let cur_idx = stack.last().unwrap().2;
-            let neighbors_len = stack.last().unwrap().1.len();
+    let mut by_id: BTreeMap<V::Id, Pattern<V>> = (q.query_nodes)()
+        .into_iter()
+        .map(|n| (n.value.identify().clone(), n))
+        .collect();
 
-            if cur_idx < neighbors_len {
-                let neighbor = stack.last().unwrap().1[cur_idx].clone();
-                stack.last_mut().unwrap().2 += 1;
+    // Forward adjacency (one entry per relationship) and in-degree per node
+    let mut successors: HashMap<V::Id, Vec<V::Id>> = HashMap::new();
+    let mut in_degree: HashMap<V::Id, usize> = HashMap::new();
+    for node in by_id.values().cloned().collect::<Vec<_>>() {
+        let nid = node.value.identify().clone();
+        in_degree.entry(nid.clone()).or_insert(0);
+        for neighbor in forward_neighbors(&node) {
+            let tid = neighbor.value.identify().clone();
+            *in_degree.entry(tid.clone()).or_insert(0) += 1;
+            successors.entry(nid.clone()).or_default().push(tid.clone());
+            by_id.entry(tid).or_insert(neighbor);
+        }
+    }
 
-                let nid = neighbor.value.identify().clone();
-                if in_stack.contains(&nid) {
-                    return None; // Back edge — cycle detected
-                }
-                if !done.contains(&nid) {
-                    in_stack.insert(nid);
-                    let next_neighbors = forward_neighbors(&neighbor);
-                    stack.push((neighbor, next_neighbors, 0));
-                }
-            } else {
-                let (node, _, _) = stack.pop().unwrap();
-                let nid = node.value.identify().clone();
-                in_stack.remove(&nid);
-                done.insert(nid);
-                result.push(node);
+    // Ready set ordered by identity: always emit the smallest ready node next
+    let mut ready: BTreeSet<V::Id> = in_degree
+        .iter()
+        .filter(|(_, &d)| d == 0)
+        .map(|(id, _)| id.clone())
+        .collect();
+    let mut result: Vec<Pattern<V>> = Vec::with_capacity(by_id.len());
+
+    while let Some(id) = ready.pop_first() {
+        for tid in successors.get(&id).into_iter().flatten() {
+            let d = in_degree.get_mut(tid).expect("successor has an in-degree");
+            *d -= 1;
+            if *d == 0 {
+                ready.insert(tid.clone());
             }
         }
+        result.push(by_id[&id].clone());
     }
 
-    result.reverse();
+    // Any node left unemitted lies on (or behind) a cycle
+    if result.len() < by_id.len() {
+        return None;
+    }
     Some(result)
 }
 
@@ -447,7 +968,524 @@ where
     topological_sort(q).is_none()
 }
 
-// ============================================================================
+/// Enumerate every elementary directed cycle (Johnson's algorithm).
+///
+/// Each cycle is an ordered node list starting at its smallest `V::Id` and
+/// following relationships from source to target; the closing step back to
+/// the first node is implied. A self-loop `A→A` yields `[A]`, and a DAG yields
+/// an empty `Vec`. Parallel relationships do not produce duplicate cycles.
+///
+/// - Ignores `TraversalWeight` — uses relationship endpoint order only.
+/// - Cycles are ordered by their first node, then by successor `V::Id` order,
+///   so the same graph always yields the same result.
+///
+/// The number of cycles can be exponential in the size of the graph — use only
+/// on small graphs or bounded subgraphs. Time is `O((n + e)(c + 1))` for `c`
+/// cycles.
+pub fn find_cycles<V>(q: &GraphQuery<V>) -> Vec<Vec<Pattern<V>>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let mut by_id: BTreeMap<V::Id, Pattern<V>> = (q.query_nodes)()
+        .into_iter()
+        .map(|n| (n.value.identify().clone(), n))
+        .collect();
+
+    // Forward and reverse adjacency, deduplicated and sorted
+    let mut successors: HashMap<V::Id, BTreeSet<V::Id>> = HashMap::new();
+    let mut predecessors: HashMap<V::Id, BTreeSet<V::Id>> = HashMap::new();
+    for rel in (q.query_relationships)() {
+        let (Some(src), Some(tgt)) = ((q.query_source)(&rel), (q.query_target)(&rel)) else {
+            continue;
+        };
+        let sid = src.value.identify().clone();
+        let tid = tgt.value.identify().clone();
+        successors
+            .entry(sid.clone())
+            .or_default()
+            .insert(tid.clone());
+        predecessors
+            .entry(tid.clone())
+            .or_default()
+            .insert(sid.clone());
+        by_id.entry(sid).or_insert(src);
+        by_id.entry(tid).or_insert(tgt);
+    }
+
+    let mut cycles: Vec<Vec<V::Id>> = Vec::new();
+    for start in by_id.keys() {
+        // Cycles through `start` that avoid smaller ids stay inside the strongly
+        // connected component of `start` in the subgraph of ids >= `start`.
+        let reach = |adj: &HashMap<V::Id, BTreeSet<V::Id>>| {
+            let mut seen: HashSet<V::Id> = HashSet::from([start.clone()]);
+            let mut queue = vec![start.clone()];
+            while let Some(v) = queue.pop() {
+                for w in adj.get(&v).into_iter().flatten() {
+                    if w >= start && seen.insert(w.clone()) {
+                        queue.push(w.clone());
+                    }
+                }
+            }
+            seen
+        };
+        let forward = reach(&successors);
+        let component: HashSet<V::Id> = reach(&predecessors)
+            .into_iter()
+            .filter(|v| forward.contains(v))
+            .collect();
+
+        let mut search = CycleSearch {
+            start,
+            successors: &successors,
+            component: &component,
+            blocked: HashSet::new(),
+            blocked_by: HashMap::new(),
+            path: Vec::new(),
+            cycles: &mut cycles,
+        };
+        search.circuit(start);
+    }
+
+    cycles
+        .into_iter()
+        .map(|ids| ids.iter().map(|id| by_id[id].clone()).collect())
+        .collect()
+}
+
+/// State for one start node of [`find_cycles`].
+struct CycleSearch<'a, Id> {
+    start: &'a Id,
+    successors: &'a HashMap<Id, BTreeSet<Id>>,
+    component: &'a HashSet<Id>,
+    blocked: HashSet<Id>,
+    /// blocked_by[w] = nodes to unblock once `w` is unblocked
+    blocked_by: HashMap<Id, HashSet<Id>>,
+    path: Vec<Id>,
+    cycles: &'a mut Vec<Vec<Id>>,
+}
+
+impl<Id: Clone + Eq + std::hash::Hash + Ord> CycleSearch<'_, Id> {
+    /// Extends the path through `v`; returns `true` if a cycle was closed below it.
+    fn circuit(&mut self, v: &Id) -> bool {
+        let successors = self.successors;
+        let next: Vec<&Id> = successors
+            .get(v)
+            .into_iter()
+            .flatten()
+            .filter(|w| self.component.contains(*w))
+            .collect();
+
+        let mut found = false;
+        self.path.push(v.clone());
+        self.blocked.insert(v.clone());
+        for &w in &next {
+            if w == self.start {
+                self.cycles.push(self.path.clone());
+                found = true;
+            } else if !self.blocked.contains(w) && self.circuit(w) {
+                found = true;
+            }
+        }
+        if found {
+            self.unblock(v);
+        } else {
+            for &w in &next {
+                self.blocked_by
+                    .entry(w.clone())
+                    .or_default()
+                    .insert(v.clone());
+            }
+        }
+        self.path.pop();
+        found
+    }
+
+    fn unblock(&mut self, v: &Id) {
+        self.blocked.remove(v);
+        for w in self.blocked_by.remove(v).unwrap_or_default() {
+            if self.blocked.contains(&w) {
+                self.unblock(&w);
+            }
+        }
+    }
+}
+
+/// Longest directed path in a DAG, counting relationships.
+///
+/// - Returns `None` if a directed cycle is detected.
+/// - Returns `Some(vec![])` for an empty graph, and a single node when there
+///   are no relationships.
+/// - Ignores `TraversalWeight` — every relationship counts 1, followed from
+///   source to target. See [`longest_path_weighted`] for costed edges.
+///
+/// Ties are broken by [`topological_sort`] order, so the same DAG always
+/// yields the same path.
+pub fn longest_path<V>(q: &GraphQuery<V>) -> Option<Vec<Pattern<V>>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    longest_path_by(q, |_| 1.0)
+}
+
+/// Longest directed path in a DAG, summing traversal costs.
+///
+/// Like [`longest_path`], but each relationship contributes its
+/// `TraversalDirection::Forward` cost under `weight`, which is the critical
+/// path when costs are task durations. Relationships with an infinite
+/// forward cost are not followed. Returns `None` if a directed cycle is
+/// detected.
+pub fn longest_path_weighted<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+) -> Option<Vec<Pattern<V>>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    longest_path_by(q, |rel| weight(rel, TraversalDirection::Forward))
+}
+
+/// Shared dynamic program behind [`longest_path`] and [`longest_path_weighted`].
+///
+/// Relaxes every forward relationship in topological order, keeping the
+/// best-cost predecessor of each node, then walks back from the node with
+/// the greatest cost.
+fn longest_path_by<V>(
+    q: &GraphQuery<V>,
+    cost: impl Fn(&Pattern<V>) -> f64,
+) -> Option<Vec<Pattern<V>>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let order = topological_sort(q)?;
+
+    // Every node can start a path, so costs begin at zero
+    let mut best: HashMap<V::Id, f64> = order
+        .iter()
+        .map(|n| (n.value.identify().clone(), 0.0))
+        .collect();
+    let mut pred: HashMap<V::Id, Pattern<V>> = HashMap::new();
+    let mut end: Option<(&Pattern<V>, f64)> = None;
+
+    for node in &order {
+        let nid = node.value.identify();
+        let node_cost = best[nid];
+        if end.map_or(true, |(_, c)| node_cost > c) {
+            end = Some((node, node_cost));
+        }
+
+        for rel in (q.query_incident_rels)(node) {
+            let is_source = (q.query_source)(&rel).is_some_and(|s| s.value.identify() == nid);
+            let Some(target) = (q.query_target)(&rel).filter(|_| is_source) else {
+                continue;
+            };
+            let c = cost(&rel);
+            if !c.is_finite() {
+                continue;
+            }
+            let tid = target.value.identify().clone();
+            let candidate = node_cost + c;
+            if candidate > best[&tid] {
+                best.insert(tid.clone(), candidate);
+                pred.insert(tid, node.clone());
+            }
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut current = end.map(|(node, _)| node.clone());
+    while let Some(node) = current {
+        current = pred.get(node.value.identify()).cloned();
+        path.push(node);
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Nodes with no incident relationships.
+///
+/// Returns every node whose `query_degree` is 0, in `query_nodes` order. These
+/// are exactly the singleton components of [`connected_components`], but found
+/// without any traversal.
+pub fn isolated_nodes<V>(q: &GraphQuery<V>) -> Vec<Pattern<V>>
+where
+    V: GraphValue + Clone,
+{
+    (q.query_nodes)()
+        .into_iter()
+        .filter(|node| (q.query_degree)(node) == 0)
+        .collect()
+}
+
+/// Groups of parallel relationships: those sharing endpoints and type labels.
+///
+/// Two relationships are parallel when they have the same source identity, the
+/// same target identity, and the same label set. Direction matters unless
+/// `undirected` is `true`: `A→B` and `B→A` are parallel only in undirected mode.
+/// Self-loops are compared like any other relationship, so two loops on the same
+/// node with the same labels are parallel. Relationships whose source or target
+/// cannot be resolved are ignored.
+///
+/// Only groups with more than one relationship are returned. Groups are ordered
+/// by endpoints and labels, and each group is ordered by relationship identity,
+/// so the result is deterministic.
+pub fn parallel_edges(q: &GraphQuery<Subject>, undirected: bool) -> Vec<Vec<Pattern<Subject>>> {
+    let mut groups: BTreeMap<(Symbol, Symbol, Vec<String>), Vec<Pattern<Subject>>> =
+        BTreeMap::new();
+
+    for rel in (q.query_relationships)() {
+        let (Some(src), Some(tgt)) = ((q.query_source)(&rel), (q.query_target)(&rel)) else {
+            continue;
+        };
+        let mut ends = (src.value.identity, tgt.value.identity);
+        if undirected && ends.1 < ends.0 {
+            ends = (ends.1, ends.0);
+        }
+        let mut labels: Vec<String> = rel.value.labels.iter().cloned().collect();
+        labels.sort();
+
+        groups
+            .entry((ends.0, ends.1, labels))
+            .or_default()
+            .push(rel);
+    }
+
+    groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort_by(|a, b| a.value.identity.cmp(&b.value.identity));
+            group
+        })
+        .collect()
+}
+
+/// Articulation points: nodes whose removal disconnects part of the graph.
+///
+/// Treats the graph as undirected regardless of relationship direction: a
+/// relationship connects its endpoints if it is traversable under `weight`
+/// in either direction. Self-loops never matter. Iterative DFS low-link
+/// search, so deep graphs cannot overflow the call stack.
+///
+/// Returned in `V::Id` order.
+pub fn articulation_points<V>(q: &GraphQuery<V>, weight: &TraversalWeight<V>) -> Vec<Pattern<V>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    low_link_search(q, weight).0
+}
+
+/// Bridges: relationships whose removal disconnects their endpoints.
+///
+/// Treats the graph as undirected regardless of relationship direction, like
+/// [`articulation_points`]. A relationship with a parallel twin is never a
+/// bridge, and neither is a self-loop.
+///
+/// Returned in `V::Id` order of the relationships.
+pub fn bridges<V>(q: &GraphQuery<V>, weight: &TraversalWeight<V>) -> Vec<Pattern<V>>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    low_link_search(q, weight).1
+}
+
+/// Shared search behind [`articulation_points`] and [`bridges`].
+///
+/// Runs Hopcroft–Tarjan DFS over the undirected graph, returning the
+/// articulation points and bridges, each sorted by identity.
+fn low_link_search<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+) -> (Vec<Pattern<V>>, Vec<Pattern<V>>)
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let mut by_id: BTreeMap<V::Id, Pattern<V>> = (q.query_nodes)()
+        .into_iter()
+        .map(|n| (n.value.identify().clone(), n))
+        .collect();
+    let mut rels: Vec<(V::Id, V::Id, Pattern<V>)> = Vec::new();
+    for rel in (q.query_relationships)() {
+        let (Some(src), Some(tgt)) = ((q.query_source)(&rel), (q.query_target)(&rel)) else {
+            continue;
+        };
+        if !weight(&rel, TraversalDirection::Forward).is_finite()
+            && !weight(&rel, TraversalDirection::Backward).is_finite()
+        {
+            continue;
+        }
+        let sid = src.value.identify().clone();
+        let tid = tgt.value.identify().clone();
+        by_id.entry(sid.clone()).or_insert(src);
+        by_id.entry(tid.clone()).or_insert(tgt);
+        rels.push((sid, tid, rel));
+    }
+
+    // Nodes are numbered in identity order; adjacency holds (neighbor, relationship)
+    let nodes: Vec<Pattern<V>> = by_id.into_values().collect();
+    let index: HashMap<V::Id, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.value.identify().clone(), i))
+        .collect();
+    let mut adjacency: Vec<Vec<(usize, usize)>> = vec![Vec::new(); nodes.len()];
+    for (e, (sid, tid, _)) in rels.iter().enumerate() {
+        let (s, t) = (index[sid], index[tid]);
+        if s != t {
+            adjacency[s].push((t, e));
+            adjacency[t].push((s, e));
+        }
+    }
+
+    let mut disc: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut low: Vec<usize> = vec![0; nodes.len()];
+    let mut is_cut = vec![false; nodes.len()];
+    let mut bridge_rels: Vec<usize> = Vec::new();
+    let mut time = 0;
+
+    for root in 0..nodes.len() {
+        if disc[root].is_some() {
+            continue;
+        }
+        disc[root] = Some(time);
+        low[root] = time;
+        time += 1;
+        let mut root_children = 0;
+
+        // Explicit call stack: (node, relationship it was reached by, next adjacency position)
+        let mut call: Vec<(usize, Option<usize>, usize)> = vec![(root, None, 0)];
+        while let Some(&(v, via, i)) = call.last() {
+            if let Some(&(w, e)) = adjacency[v].get(i) {
+                call.last_mut().expect("frame for v").2 += 1;
+                if via == Some(e) {
+                    continue;
+                }
+                match disc[w] {
+                    Some(d) => low[v] = low[v].min(d),
+                    None => {
+                        disc[w] = Some(time);
+                        low[w] = time;
+                        time += 1;
+                        call.push((w, Some(e), 0));
+                    }
+                }
+                continue;
+            }
+
+            // All neighbors of v are done: return to its parent
+            call.pop();
+            let Some(&(u, _, _)) = call.last() else {
+                continue;
+            };
+            low[u] = low[u].min(low[v]);
+            let disc_u = disc[u].expect("parent is discovered");
+            if low[v] > disc_u {
+                bridge_rels.push(via.expect("child was reached by a relationship"));
+            }
+            if u == root {
+                root_children += 1;
+            } else if low[v] >= disc_u {
+                is_cut[u] = true;
+            }
+        }
+        if root_children > 1 {
+            is_cut[root] = true;
+        }
+    }
+
+    let cut_nodes = nodes
+        .into_iter()
+        .zip(is_cut)
+        .filter_map(|(node, cut)| cut.then_some(node))
+        .collect();
+    let mut bridge_patterns: Vec<Pattern<V>> =
+        bridge_rels.into_iter().map(|e| rels[e].2.clone()).collect();
+    bridge_patterns.sort_by(|a, b| a.value.identify().cmp(b.value.identify()));
+    (cut_nodes, bridge_patterns)
+}
+
+/// Two-colors the graph if it is bipartite (BFS coloring).
+///
+/// Treats the graph as undirected regardless of relationship direction: a
+/// relationship connects its endpoints if it is traversable under `weight`
+/// in either direction. Returns `Some((left, right))` so that every such
+/// relationship joins a `left` node to a `right` node, or `None` as soon as an
+/// odd cycle is found. A traversable self-loop is an odd cycle.
+///
+/// Each connected component is colored on its own, starting from its smallest
+/// `V::Id`, which goes in `left`; isolated nodes are in `left` too. Both sides
+/// are returned in `V::Id` order.
+#[allow(clippy::type_complexity)]
+pub fn is_bipartite<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+) -> Option<(Vec<Pattern<V>>, Vec<Pattern<V>>)>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let mut by_id: BTreeMap<V::Id, Pattern<V>> = (q.query_nodes)()
+        .into_iter()
+        .map(|n| (n.value.identify().clone(), n))
+        .collect();
+    let mut adjacency: HashMap<V::Id, Vec<V::Id>> = HashMap::new();
+    for rel in (q.query_relationships)() {
+        let (Some(src), Some(tgt)) = ((q.query_source)(&rel), (q.query_target)(&rel)) else {
+            continue;
+        };
+        if !weight(&rel, TraversalDirection::Forward).is_finite()
+            && !weight(&rel, TraversalDirection::Backward).is_finite()
+        {
+            continue;
+        }
+        let sid = src.value.identify().clone();
+        let tid = tgt.value.identify().clone();
+        if sid == tid {
+            return None;
+        }
+        adjacency.entry(sid.clone()).or_default().push(tid.clone());
+        adjacency.entry(tid.clone()).or_default().push(sid.clone());
+        by_id.entry(sid).or_insert(src);
+        by_id.entry(tid).or_insert(tgt);
+    }
+
+    // side[id] = false for left, true for right
+    let mut side: HashMap<V::Id, bool> = HashMap::new();
+    for root in by_id.keys() {
+        if side.contains_key(root) {
+            continue;
+        }
+        side.insert(root.clone(), false);
+        let mut queue = VecDeque::from([root.clone()]);
+        while let Some(v) = queue.pop_front() {
+            let color = side[&v];
+            for w in adjacency.get(&v).into_iter().flatten() {
+                match side.get(w) {
+                    Some(&c) if c == color => return None,
+                    Some(_) => {}
+                    None => {
+                        side.insert(w.clone(), !color);
+                        queue.push_back(w.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    let (right, left): (Vec<_>, Vec<_>) = by_id.into_iter().partition(|(id, _)| side[id]);
+    Some((
+        left.into_iter().map(|(_, n)| n).collect(),
+        right.into_iter().map(|(_, n)| n).collect(),
+    ))
+}
+
+// ============================================================================
 // Spanning
 // ============================================================================
 
@@ -522,6 +1560,57 @@ where
         .collect()
 }
 
+/// BFS spanning tree from `root`, rebuilt as a nested pattern for hierarchical display.
+///
+/// Each reachable node appears exactly once, as an element of the node that first
+/// discovered it during breadth-first traversal; children keep BFS discovery order,
+/// which follows the order of `query_incident_rels`.
+/// Node patterns are rebuilt from their values, so a node's own elements are not
+/// carried over. Relationships are not represented: non-tree edges (those reaching
+/// an already-visited node) are simply omitted, and unreachable nodes do not appear.
+///
+/// The root is always the returned pattern, with no elements if it has no
+/// reachable neighbors.
+pub fn spanning_tree_pattern<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    root: &Pattern<V>,
+) -> Pattern<V>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    // BFS order with the index of each node's parent; the order doubles as the queue.
+    let mut order: Vec<(Pattern<V>, Option<usize>)> = vec![(root.clone(), None)];
+    let mut visited: HashSet<V::Id> = HashSet::new();
+    visited.insert(root.value.identify().clone());
+
+    let mut current = 0;
+    while current < order.len() {
+        for (neighbor, _cost) in reachable_neighbors(q, weight, &order[current].0) {
+            if visited.insert(neighbor.value.identify().clone()) {
+                order.push((neighbor, Some(current)));
+            }
+        }
+        current += 1;
+    }
+
+    // Children always follow their parent in BFS order, so build bottom-up.
+    let mut children: Vec<Vec<Pattern<V>>> = (0..order.len()).map(|_| Vec::new()).collect();
+    let mut tree = None;
+    for (i, (node, parent)) in order.into_iter().enumerate().rev() {
+        let mut elements = std::mem::take(&mut children[i]);
+        elements.reverse();
+        let subtree = Pattern::pattern(node.value, elements);
+        match parent {
+            Some(p) => children[p].push(subtree),
+            None => tree = Some(subtree),
+        }
+    }
+
+    tree.expect("root is always first in BFS order")
+}
+
 /// Path-compression union-find: returns the root of the component containing `x`.
 fn uf_find<Id>(parent: &mut HashMap<Id, Id>, x: Id) -> Id
 where
@@ -567,12 +1656,15 @@ where
 
 /// Betweenness centrality using the Brandes BFS algorithm (unnormalized).
 ///
-/// Returns the unnormalized betweenness score for each node. To normalize for
-/// an undirected graph with `n` nodes, divide by `(n-1)(n-2)/2`.
+/// Returns the unnormalized betweenness score for each node. Scores are summed
+/// over ordered source/target pairs, so under an undirected weight every path
+/// is counted once in each direction. Use [`betweenness_centrality_normalized`]
+/// for scores in `[0, 1]`.
 ///
 /// Uses the `weight` function to determine which edges are traversable (finite
 /// cost = reachable). All reachable edges are treated as unit-weight for the
-/// shortest-path counting phase.
+/// shortest-path counting phase; use [`weighted_betweenness_centrality`] to
+/// count shortest paths by cost.
 pub fn betweenness_centrality<V>(
     q: &GraphQuery<V>,
     weight: &TraversalWeight<V>,
@@ -656,6 +1748,291 @@ where
     betweenness
 }
 
+/// Betweenness centrality over minimum-cost paths (Brandes with Dijkstra, unnormalized).
+///
+/// Like [`betweenness_centrality`], but a path is shortest when its total cost
+/// under `weight` is minimal rather than when it has the fewest hops, so a
+/// node on a cheap detour scores for the pairs whose expensive direct edge it
+/// bypasses. Paths tie only when their costs are exactly equal. Costs must be
+/// non-negative; edges of infinite cost are not traversable. Scores are summed
+/// over ordered source/target pairs, as in [`betweenness_centrality`].
+pub fn weighted_betweenness_centrality<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+) -> HashMap<V::Id, f64>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let nodes = (q.query_nodes)();
+    let mut betweenness: HashMap<V::Id, f64> = nodes
+        .iter()
+        .map(|n| (n.value.identify().clone(), 0.0))
+        .collect();
+
+    for s in &nodes {
+        let s_id = s.value.identify().clone();
+
+        // Dijkstra phase: settle nodes in order of cost, counting the
+        // minimum-cost paths to each and remembering their predecessors
+        let mut stack: Vec<V::Id> = Vec::new();
+        let mut pred: HashMap<V::Id, Vec<V::Id>> = HashMap::new();
+        let mut sigma: HashMap<V::Id, f64> = HashMap::from([(s_id.clone(), 1.0)]);
+        let mut dist: HashMap<V::Id, f64> = HashMap::from([(s_id.clone(), 0.0)]);
+        let mut settled: HashSet<V::Id> = HashSet::new();
+
+        // For non-negative finite f64, the IEEE 754 bit pattern preserves ordering
+        let mut pq: BTreeMap<(u64, V::Id), Pattern<V>> = BTreeMap::new();
+        pq.insert((0.0f64.to_bits(), s_id.clone()), s.clone());
+
+        while let Some(((_, v_id), v)) = pq.pop_first() {
+            if !settled.insert(v_id.clone()) {
+                continue;
+            }
+            stack.push(v_id.clone());
+            let v_dist = dist[&v_id];
+            let v_sigma = sigma[&v_id];
+
+            for (w, cost) in reachable_neighbors(q, weight, &v) {
+                let w_id = w.value.identify().clone();
+                if settled.contains(&w_id) {
+                    continue;
+                }
+                let new_dist = v_dist + cost;
+                match dist.get(&w_id) {
+                    Some(&d) if new_dist > d => {}
+                    Some(&d) if new_dist == d => {
+                        *sigma.get_mut(&w_id).unwrap() += v_sigma;
+                        pred.get_mut(&w_id).unwrap().push(v_id.clone());
+                    }
+                    _ => {
+                        dist.insert(w_id.clone(), new_dist);
+                        sigma.insert(w_id.clone(), v_sigma);
+                        pred.insert(w_id.clone(), vec![v_id.clone()]);
+                        pq.insert((new_dist.to_bits(), w_id), w);
+                    }
+                }
+            }
+        }
+
+        // Back-propagation in reverse order of cost
+        let mut delta: HashMap<V::Id, f64> = HashMap::new();
+        while let Some(w_id) = stack.pop() {
+            let w_delta = delta.get(&w_id).copied().unwrap_or(0.0);
+            for v_id in pred.get(&w_id).into_iter().flatten() {
+                let coeff = sigma[v_id] / sigma[&w_id] * (1.0 + w_delta);
+                *delta.entry(v_id.clone()).or_insert(0.0) += coeff;
+            }
+            if w_id != s_id {
+                *betweenness.get_mut(&w_id).unwrap() += w_delta;
+            }
+        }
+    }
+
+    betweenness
+}
+
+/// Betweenness centrality normalized to `[0, 1]`.
+///
+/// Divides each score from [`betweenness_centrality`] by `(n-1)(n-2)`, the
+/// number of ordered pairs of other nodes. Because the unnormalized scores
+/// count ordered pairs, this is the correct factor under any `weight`: for a
+/// directed weight it is the usual directed normalization, and for an
+/// undirected weight it equals dividing the once-per-pair score by
+/// `(n-1)(n-2)/2`. Graphs with fewer than three nodes score 0.0 for every node.
+pub fn betweenness_centrality_normalized<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+) -> HashMap<V::Id, f64>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let mut scores = betweenness_centrality(q, weight);
+    let n = scores.len();
+    let pairs = if n > 2 {
+        ((n - 1) * (n - 2)) as f64
+    } else {
+        0.0
+    };
+    for score in scores.values_mut() {
+        *score = if pairs > 0.0 { *score / pairs } else { 0.0 };
+    }
+    scores
+}
+
+/// Harmonic centrality for all nodes (unnormalized).
+///
+/// The score of node `v` is the sum of `1 / d(v, u)` over every other node `u`,
+/// where `d` is the minimum path cost under `weight`. Unreachable nodes
+/// contribute 0, so the measure stays well-defined on disconnected graphs.
+/// Nodes at zero cost are skipped. To normalize, divide by `n - 1`.
+pub fn harmonic_centrality<V>(q: &GraphQuery<V>, weight: &TraversalWeight<V>) -> HashMap<V::Id, f64>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let nodes = (q.query_nodes)();
+    let mut result = HashMap::new();
+
+    for node in &nodes {
+        let score: f64 = other_node_costs(q, weight, node)
+            .into_iter()
+            .map(|d| 1.0 / d)
+            .sum();
+        result.insert(node.value.identify().clone(), score);
+    }
+
+    result
+}
+
+/// Closeness centrality for all nodes (Wasserman–Faust).
+///
+/// For node `v` reaching `r` other nodes at total minimum cost `s` under
+/// `weight`, the score is `(r / s) * (r / (n - 1))`. The second factor scales
+/// down nodes that reach only part of the graph, so scores stay comparable on
+/// disconnected graphs. Nodes that reach nothing score 0.0. Costs are measured
+/// outward from `v`; pass [`directed_reverse`](crate::graph::graph_query::directed_reverse)
+/// for closeness by incoming paths.
+pub fn closeness_centrality<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+) -> HashMap<V::Id, f64>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let nodes = (q.query_nodes)();
+    let others = nodes.len().saturating_sub(1) as f64;
+    let mut result = HashMap::new();
+
+    for node in &nodes {
+        let costs = other_node_costs(q, weight, node);
+        let reached = costs.len() as f64;
+        let total: f64 = costs.into_iter().sum();
+        let score = if total > 0.0 {
+            (reached / total) * (reached / others)
+        } else {
+            0.0
+        };
+        result.insert(node.value.identify().clone(), score);
+    }
+
+    result
+}
+
+/// PageRank for all nodes.
+///
+/// Rank flows from each node to the neighbors it can reach under `weight`,
+/// split evenly across its traversable relationships; edge costs are ignored.
+/// Nodes with no traversable relationships spread their rank over every node.
+/// `damping` is the probability of following a relationship rather than
+/// jumping to a random node; 0.85 is the conventional choice. Iterates until
+/// the total change falls below `1e-10` or 100 iterations have run. Scores sum
+/// to 1.0.
+pub fn pagerank<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    damping: f64,
+) -> HashMap<V::Id, f64>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    pagerank_with_iterations(q, weight, damping, 100)
+}
+
+/// PageRank for all nodes, running at most `max_iterations` iterations.
+///
+/// Like [`pagerank`], which allows 100, but with a caller-chosen cap; it still
+/// stops early once the total change falls below `1e-10`. With a cap of 0
+/// every node keeps the uniform starting score `1/n`. Scores sum to 1.0.
+pub fn pagerank_with_iterations<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    damping: f64,
+    max_iterations: usize,
+) -> HashMap<V::Id, f64>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    const TOLERANCE: f64 = 1e-10;
+
+    let mut nodes = (q.query_nodes)();
+    nodes.sort_by(|a, b| a.value.identify().cmp(b.value.identify()));
+    let n = nodes.len();
+    if n == 0 {
+        return HashMap::new();
+    }
+
+    let index: HashMap<V::Id, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.value.identify().clone(), i))
+        .collect();
+    let out_links: Vec<Vec<usize>> = nodes
+        .iter()
+        .map(|node| {
+            let mut links: Vec<usize> = reachable_neighbors(q, weight, node)
+                .into_iter()
+                .filter_map(|(neighbor, _cost)| index.get(neighbor.value.identify()).copied())
+                .collect();
+            links.sort_unstable();
+            links
+        })
+        .collect();
+
+    let mut rank = vec![1.0 / n as f64; n];
+    for _ in 0..max_iterations {
+        let dangling: f64 = (0..n)
+            .filter(|&i| out_links[i].is_empty())
+            .map(|i| rank[i])
+            .sum();
+        let base = (1.0 - damping) / n as f64 + damping * dangling / n as f64;
+
+        let mut next = vec![base; n];
+        for (i, links) in out_links.iter().enumerate() {
+            let share = damping * rank[i] / links.len() as f64;
+            for &j in links {
+                next[j] += share;
+            }
+        }
+
+        let change: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+
+    nodes
+        .into_iter()
+        .zip(rank)
+        .map(|(node, score)| (node.value.identify().clone(), score))
+        .collect()
+}
+
+/// Minimum costs from `source` to every other reachable node, in ascending
+/// order so that sums over them do not depend on hash order.
+fn other_node_costs<V>(
+    q: &GraphQuery<V>,
+    weight: &TraversalWeight<V>,
+    source: &Pattern<V>,
+) -> Vec<f64>
+where
+    V: GraphValue + Clone,
+    V::Id: Clone + Eq + std::hash::Hash + Ord,
+{
+    let mut costs: Vec<f64> = single_source_search(q, weight, source, None, &|_| 0.0, &|_, _| true)
+        .dist
+        .into_values()
+        .filter(|&d| d > 0.0)
+        .collect();
+    costs.sort_by(f64::total_cmp);
+    costs
+}
+
 // ============================================================================
 // Context query helpers
 // ============================================================================
@@ -0,0 +1,53 @@
+//! Order-insensitive canonical form of a pattern.
+//!
+//! [`Pattern::canonical`] sorts every element list by identity so that two
+//! patterns differing only in element order compare equal afterwards.
+
+use crate::graph::graph_classifier::GraphValue;
+use crate::pattern::Pattern;
+
+impl<V: GraphValue + Clone> Pattern<V> {
+    /// Returns a copy of this pattern with every element list sorted, so that
+    /// `a.canonical() == b.canonical()` tests equality ignoring element order.
+    ///
+    /// Elements are canonicalized first and then ordered by the identities of
+    /// their values in pre-order: their own identity, then those of their
+    /// elements. Ordering needs `V::Id: Ord`, which [`GraphValue`] requires.
+    /// Elements whose identities all tie (anonymous subjects sharing `""`, for
+    /// instance) but that differ in other fields keep their relative order, so
+    /// reorderings of such siblings are not recognized as equal.
+    ///
+    /// Element order is meaningful for relationships and walks, where it gives
+    /// direction and sequence; canonicalize only patterns whose elements are
+    /// sets, such as co-members of a group.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pattern_core::{Pattern, Subject};
+    ///
+    /// let team = |ids: [&str; 3]| {
+    ///     Pattern::pattern(
+    ///         Subject::from_id("team"),
+    ///         ids.iter().map(|id| Pattern::point(Subject::from_id(*id))).collect(),
+    ///     )
+    /// };
+    /// let a = team(["alice", "bob", "carol"]);
+    /// let b = team(["carol", "alice", "bob"]);
+    /// assert_ne!(a, b);
+    /// assert_eq!(a.canonical(), b.canonical());
+    /// ```
+    pub fn canonical(&self) -> Pattern<V> {
+        let mut elements: Vec<Pattern<V>> = self.elements.iter().map(Pattern::canonical).collect();
+        elements.sort_by_cached_key(|elem| {
+            elem.values()
+                .into_iter()
+                .map(|v| v.identify().clone())
+                .collect::<Vec<V::Id>>()
+        });
+        Pattern {
+            value: self.value.clone(),
+            elements,
+        }
+    }
+}
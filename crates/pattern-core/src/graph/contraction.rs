@@ -0,0 +1,111 @@
+//! Contracting a group of nodes into one.
+//!
+//! [`contract_nodes`] replaces a set of nodes with a single node whose subject
+//! is the merge of theirs, and rewires every relationship that touched the
+//! group to the new node. This is the usual way to collapse duplicates found
+//! by entity resolution, or to summarize a cluster as one node.
+
+use std::collections::HashSet;
+
+use crate::graph::graph_query::GraphQuery;
+use crate::pattern::Pattern;
+use crate::reconcile::{Mergeable, SubjectMergeStrategy};
+use crate::subject::{Subject, Symbol};
+
+/// What [`contract_nodes`] does with relationships between two grouped nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntraGroupEdges {
+    /// Remove them from the contracted graph.
+    Drop,
+    /// Keep them as self-loops on the contracted node.
+    SelfLoop,
+}
+
+/// Merge the nodes in `group` into one node identified by `new_id`.
+///
+/// The contracted node's subject is built by folding
+/// [`Subject::merge`](Mergeable::merge) with `merge` over the grouped nodes in
+/// `group` order, then giving the result the identity `new_id`. If a node
+/// outside `group` already has identity `new_id`, it is merged in last, so the
+/// result never holds two nodes with the same identity. Identities in `group`
+/// that are not nodes of `q` are ignored; if none are, the graph is returned
+/// unchanged.
+///
+/// Every relationship with an endpoint in the group is rewired to the
+/// contracted node, keeping its own subject and direction. Relationships with
+/// both endpoints in the group are handled according to `intra_group`.
+/// Relationships from different grouped nodes to the same neighbor stay
+/// separate, as parallel relationships.
+///
+/// # Returns
+///
+/// The contracted graph as patterns: every node in `query_nodes` order, with
+/// the contracted node in place of the first grouped node, followed by every
+/// relationship as a two-element pattern in `query_relationships` order.
+/// Walks and annotations are not included.
+pub fn contract_nodes(
+    q: &GraphQuery<Subject>,
+    group: &[Symbol],
+    new_id: Symbol,
+    merge: &SubjectMergeStrategy,
+    intra_group: IntraGroupEdges,
+) -> Vec<Pattern<Subject>> {
+    let nodes = (q.query_nodes)();
+
+    let mut members: Vec<&Symbol> = group.iter().collect();
+    members.push(&new_id);
+    let mut grouped: HashSet<Symbol> = HashSet::new();
+    let mut merged: Option<Subject> = None;
+    for id in members {
+        if grouped.contains(id) {
+            continue;
+        }
+        if let Some(node) = nodes.iter().find(|n| &n.value.identity == id) {
+            grouped.insert(id.clone());
+            merged = Some(match merged {
+                Some(acc) => Subject::merge(merge, acc, node.value.clone()),
+                None => node.value.clone(),
+            });
+        }
+    }
+
+    let Some(mut merged) = merged else {
+        let mut patterns = nodes;
+        patterns.extend((q.query_relationships)());
+        return patterns;
+    };
+    merged.identity = new_id;
+    let contracted = Pattern::point(merged);
+
+    let mut patterns = Vec::new();
+    let mut placed = false;
+    for node in nodes {
+        if !grouped.contains(&node.value.identity) {
+            patterns.push(node);
+        } else if !placed {
+            patterns.push(contracted.clone());
+            placed = true;
+        }
+    }
+
+    let rewire = |endpoint: Pattern<Subject>| {
+        if grouped.contains(&endpoint.value.identity) {
+            (contracted.clone(), true)
+        } else {
+            (endpoint, false)
+        }
+    };
+    for rel in (q.query_relationships)() {
+        let (Some(source), Some(target)) = ((q.query_source)(&rel), (q.query_target)(&rel)) else {
+            continue;
+        };
+        let (source, source_grouped) = rewire(source);
+        let (target, target_grouped) = rewire(target);
+        if source_grouped && target_grouped && intra_group == IntraGroupEdges::Drop {
+            continue;
+        }
+        patterns.push(Pattern::pattern(rel.value, vec![source, target]));
+    }
+
+    patterns
+}
@@ -0,0 +1,226 @@
+//! Structural differences between two patterns of subjects.
+//!
+//! [`diff_patterns`] flattens both patterns with [`Pattern::to_edge_list`] and
+//! compares the results by identity, producing a changelog of
+//! [`PatternChange`]s whose `Display` form reads one change per line.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::pattern::Pattern;
+use crate::subject::{Subject, Symbol, Value};
+
+/// One difference reported by [`diff_patterns`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternChange {
+    /// A node present only in the new pattern
+    AddedNode(Subject),
+    /// A node present only in the old pattern
+    RemovedNode(Subject),
+    /// A relationship present only in the new pattern
+    AddedRelationship {
+        /// Identity of the source node
+        source: Symbol,
+        /// Identity of the target node
+        target: Symbol,
+        /// The relationship's subject
+        relationship: Subject,
+    },
+    /// A relationship present only in the old pattern
+    RemovedRelationship {
+        /// Identity of the source node
+        source: Symbol,
+        /// Identity of the target node
+        target: Symbol,
+        /// The relationship's subject
+        relationship: Subject,
+    },
+    /// A label gained by a node or relationship present in both patterns
+    AddedLabel {
+        /// Identity of the node or relationship
+        id: Symbol,
+        /// The label
+        label: String,
+    },
+    /// A label lost by a node or relationship present in both patterns
+    RemovedLabel {
+        /// Identity of the node or relationship
+        id: Symbol,
+        /// The label
+        label: String,
+    },
+    /// A property set, removed, or given a new value on a node or
+    /// relationship present in both patterns
+    ChangedProperty {
+        /// Identity of the node or relationship
+        id: Symbol,
+        /// The property key
+        key: String,
+        /// The old value, or `None` if the property is new
+        old: Option<Value>,
+        /// The new value, or `None` if the property was removed
+        new: Option<Value>,
+    },
+}
+
+impl fmt::Display for PatternChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternChange::AddedNode(node) => write!(f, "+ ({})", node),
+            PatternChange::RemovedNode(node) => write!(f, "- ({})", node),
+            PatternChange::AddedRelationship {
+                source,
+                target,
+                relationship,
+            } => write!(f, "+ ({})-[{}]->({})", source, relationship, target),
+            PatternChange::RemovedRelationship {
+                source,
+                target,
+                relationship,
+            } => write!(f, "- ({})-[{}]->({})", source, relationship, target),
+            PatternChange::AddedLabel { id, label } => write!(f, "+ {}:{}", id, label),
+            PatternChange::RemovedLabel { id, label } => write!(f, "- {}:{}", id, label),
+            PatternChange::ChangedProperty { id, key, old, new } => match (old, new) {
+                (Some(old), Some(new)) => write!(f, "~ {}.{}: {} -> {}", id, key, old, new),
+                (None, Some(new)) => write!(f, "+ {}.{}: {}", id, key, new),
+                (Some(old), None) => write!(f, "- {}.{}: {}", id, key, old),
+                (None, None) => write!(f, "~ {}.{}", id, key),
+            },
+        }
+    }
+}
+
+/// Lists the changes that turn `old` into `new`.
+///
+/// Both patterns are flattened as [`Pattern::to_edge_list`] does. Nodes are
+/// matched by identity. Relationships are matched by identity together with
+/// their endpoints, so moving a relationship to other nodes reports it as
+/// removed and added again; a relationship repeated within one pattern counts
+/// once. A node or relationship present on both sides reports its label
+/// changes and then its property changes, keys in sorted order.
+///
+/// Changes to nodes come first, in order of identity, then changes to
+/// relationships, in order of identity, source, and target. Anonymous nodes
+/// share the empty identity and are compared as one node; give them
+/// identities with [`assign_identities`](crate::assign_identities) first to
+/// keep them apart.
+///
+/// # Examples
+///
+/// ```
+/// use pattern_core::{diff_patterns, Pattern, Subject};
+///
+/// let old = Pattern::point(Subject::build("a").property("name", "Alice").done());
+/// let new = Pattern::point(Subject::build("a").property("name", "Alicia").done());
+///
+/// let changes = diff_patterns(&old, &new);
+/// assert_eq!(changes.len(), 1);
+/// assert_eq!(changes[0].to_string(), r#"~ a.name: "Alice" -> "Alicia""#);
+/// ```
+pub fn diff_patterns(old: &Pattern<Subject>, new: &Pattern<Subject>) -> Vec<PatternChange> {
+    let (old_nodes, old_edges) = old.to_edge_list();
+    let (new_nodes, new_edges) = new.to_edge_list();
+    let mut changes = Vec::new();
+
+    let by_id = |n: Subject| (n.identity.clone(), n);
+    diff_by_key(
+        old_nodes.into_iter().map(by_id).collect(),
+        new_nodes.into_iter().map(by_id).collect(),
+        &mut changes,
+        |_, node| PatternChange::RemovedNode(node),
+        |_, node| PatternChange::AddedNode(node),
+    );
+
+    let by_key = |(source, target, rel): (Symbol, Symbol, Subject)| {
+        ((rel.identity.clone(), source, target), rel)
+    };
+    diff_by_key(
+        old_edges.into_iter().map(by_key).collect(),
+        new_edges.into_iter().map(by_key).collect(),
+        &mut changes,
+        |(_, source, target), relationship| PatternChange::RemovedRelationship {
+            source,
+            target,
+            relationship,
+        },
+        |(_, source, target), relationship| PatternChange::AddedRelationship {
+            source,
+            target,
+            relationship,
+        },
+    );
+
+    changes
+}
+
+/// Appends the changes between subjects matched by key, in key order.
+fn diff_by_key<K: Ord>(
+    old: BTreeMap<K, Subject>,
+    new: BTreeMap<K, Subject>,
+    changes: &mut Vec<PatternChange>,
+    removed: impl Fn(K, Subject) -> PatternChange,
+    added: impl Fn(K, Subject) -> PatternChange,
+) {
+    let mut old = old.into_iter().peekable();
+    let mut new = new.into_iter().peekable();
+    loop {
+        let order = match (old.peek(), new.peek()) {
+            (Some((o, _)), Some((n, _))) => o.cmp(n),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+        match order {
+            Ordering::Less => {
+                let (key, subject) = old.next().unwrap();
+                changes.push(removed(key, subject));
+            }
+            Ordering::Greater => {
+                let (key, subject) = new.next().unwrap();
+                changes.push(added(key, subject));
+            }
+            Ordering::Equal => {
+                let (_, old_subject) = old.next().unwrap();
+                let (_, new_subject) = new.next().unwrap();
+                diff_subjects(&old_subject, &new_subject, changes);
+            }
+        }
+    }
+}
+
+/// Appends the label and property changes between two subjects of one identity.
+fn diff_subjects(old: &Subject, new: &Subject, changes: &mut Vec<PatternChange>) {
+    let id = &new.identity;
+    let old_labels: BTreeSet<&String> = old.labels.iter().collect();
+    let new_labels: BTreeSet<&String> = new.labels.iter().collect();
+    changes.extend(
+        new_labels
+            .difference(&old_labels)
+            .map(|label| PatternChange::AddedLabel {
+                id: id.clone(),
+                label: (*label).clone(),
+            }),
+    );
+    changes.extend(
+        old_labels
+            .difference(&new_labels)
+            .map(|label| PatternChange::RemovedLabel {
+                id: id.clone(),
+                label: (*label).clone(),
+            }),
+    );
+
+    let keys: BTreeSet<&String> = old.properties.keys().chain(new.properties.keys()).collect();
+    for key in keys {
+        let (old_value, new_value) = (old.properties.get(key), new.properties.get(key));
+        if old_value != new_value {
+            changes.push(PatternChange::ChangedProperty {
+                id: id.clone(),
+                key: key.clone(),
+                old: old_value.cloned(),
+                new: new_value.cloned(),
+            });
+        }
+    }
+}
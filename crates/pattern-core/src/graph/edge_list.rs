@@ -0,0 +1,131 @@
+//! Flattening a pattern into a node list and an edge list.
+//!
+//! [`Pattern::to_edge_list`] turns a pattern of subjects into the plain
+//! adjacency form most graph libraries and visualization tools accept:
+//! distinct nodes, and `(source, target, relationship)` triples.
+
+use std::collections::HashMap;
+
+use crate::graph::graph_classifier::{classify_by_shape, GraphClass};
+use crate::pattern::Pattern;
+use crate::subject::{Subject, Symbol};
+
+impl Pattern<Subject> {
+    /// Returns the distinct nodes of this pattern and its relationships as
+    /// `(source_id, target_id, relationship)` triples.
+    ///
+    /// Each pattern is classified as [`classify_by_shape`] does:
+    ///
+    /// - a node is added to the node list;
+    /// - a relationship adds both endpoints and an edge from its first element
+    ///   to its second;
+    /// - a walk adds each of its relationships;
+    /// - an annotation adds what its single element adds.
+    ///
+    /// Any other pattern with two elements, the second of them a node, is read
+    /// as a relationship from the node its first element ends at. This is the
+    /// nested form the gram parser produces for a path such as
+    /// `(a)-->(b)-->(c)`, whose outer pattern is the relationship `(b)-->(c)`
+    /// with the path `(a)-->(b)` as its source. Every other pattern adds only
+    /// what its elements add; its own subject appears in neither list.
+    ///
+    /// Nodes are listed in order of first appearance and are distinct by
+    /// identity. Repeated occurrences of a node contribute their labels and
+    /// any properties not already set, so `(a)-->(b), (b:Person)` yields a
+    /// `b` labeled `Person`. Anonymous nodes all share the empty identity;
+    /// give them identities with [`assign_identities`](crate::assign_identities)
+    /// first to keep them apart. Edges are listed in order of appearance,
+    /// including repeats.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pattern_core::{Pattern, Subject, Symbol};
+    ///
+    /// let node = |id: &str| Pattern::point(Subject::from_id(id));
+    /// let knows = Pattern::pattern(Subject::from_id("r1"), vec![node("a"), node("b")]);
+    ///
+    /// let (nodes, edges) = knows.to_edge_list();
+    /// assert_eq!(nodes.len(), 2);
+    /// assert_eq!(edges[0].0, Symbol::from("a"));
+    /// assert_eq!(edges[0].1, Symbol::from("b"));
+    /// ```
+    pub fn to_edge_list(&self) -> (Vec<Subject>, Vec<(Symbol, Symbol, Subject)>) {
+        let mut list = EdgeList::default();
+        list.add(self);
+        (list.nodes, list.edges)
+    }
+}
+
+/// Nodes and edges collected by [`Pattern::to_edge_list`].
+#[derive(Default)]
+struct EdgeList {
+    nodes: Vec<Subject>,
+    index: HashMap<Symbol, usize>,
+    edges: Vec<(Symbol, Symbol, Subject)>,
+}
+
+impl EdgeList {
+    /// Adds `p` and returns the identity of the node it ends at, if it is a
+    /// node or a relationship.
+    fn add(&mut self, p: &Pattern<Subject>) -> Option<Symbol> {
+        match classify_by_shape(p) {
+            GraphClass::GNode => Some(self.node(&p.value)),
+            GraphClass::GRelationship => {
+                let source = self.node(&p.elements[0].value);
+                Some(self.edge(source, &p.elements[1], &p.value))
+            }
+            GraphClass::GWalk | GraphClass::GAnnotation => {
+                for element in &p.elements {
+                    self.add(element);
+                }
+                None
+            }
+            GraphClass::GOther(()) => match p.elements.as_slice() {
+                [source, target] if target.elements.is_empty() => match self.add(source) {
+                    Some(source) => Some(self.edge(source, target, &p.value)),
+                    None => {
+                        self.node(&target.value);
+                        None
+                    }
+                },
+                elements => {
+                    for element in elements {
+                        self.add(element);
+                    }
+                    None
+                }
+            },
+        }
+    }
+
+    /// Adds an edge from `source` to the node `target` and returns the
+    /// target's identity.
+    fn edge(&mut self, source: Symbol, target: &Pattern<Subject>, rel: &Subject) -> Symbol {
+        let target = self.node(&target.value);
+        self.edges.push((source, target.clone(), rel.clone()));
+        target
+    }
+
+    /// Adds `subject` as a node, merging it into an earlier node with the same
+    /// identity, and returns its identity.
+    fn node(&mut self, subject: &Subject) -> Symbol {
+        match self.index.get(&subject.identity) {
+            Some(&i) => {
+                let node = &mut self.nodes[i];
+                node.labels.extend(subject.labels.iter().cloned());
+                for (key, value) in &subject.properties {
+                    node.properties
+                        .entry(key.clone())
+                        .or_insert_with(|| value.clone());
+                }
+            }
+            None => {
+                self.index
+                    .insert(subject.identity.clone(), self.nodes.len());
+                self.nodes.push(subject.clone());
+            }
+        }
+        subject.identity.clone()
+    }
+}
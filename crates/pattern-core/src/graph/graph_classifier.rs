@@ -2,7 +2,9 @@
 //!
 //! Ported from `Pattern.Graph.GraphClassifier` in the Haskell reference implementation.
 
+use std::fmt;
 use std::hash::Hash;
+use std::str::FromStr;
 
 use crate::pattern::Pattern;
 use crate::subject::{Subject, Symbol};
@@ -15,6 +17,32 @@ use crate::subject::{Subject, Symbol};
 pub trait GraphValue {
     type Id: Ord + Clone + Hash;
     fn identify(&self) -> &Self::Id;
+
+    /// Replaces this value's identity.
+    ///
+    /// Used by [`assign_identities`](crate::assign_identities) to give
+    /// anonymous nodes distinct identities. The default leaves the value
+    /// unchanged, so anonymous values of such a type keep sharing one identity.
+    fn assign_identity(&mut self, _id: Self::Id) {}
+
+    /// The labels this value carries.
+    ///
+    /// [`PatternGraph`](crate::PatternGraph) indexes its nodes by these labels
+    /// to answer [`GraphQuery::query_nodes_by_label`](crate::GraphQuery::query_nodes_by_label). The
+    /// default has none, so nodes of such a type are never found by label.
+    fn node_labels(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Whether this relationship value was written with a backward arrow.
+    ///
+    /// A path written through such a relationship continues from its first
+    /// element rather than its second, which
+    /// [`from_patterns_with_policy`](crate::from_patterns_with_policy) needs
+    /// to split a parsed path into relationships. The default is `false`.
+    fn is_backward(&self) -> bool {
+        false
+    }
 }
 
 impl GraphValue for Subject {
@@ -23,6 +51,18 @@ impl GraphValue for Subject {
     fn identify(&self) -> &Symbol {
         &self.identity
     }
+
+    fn assign_identity(&mut self, id: Symbol) {
+        self.identity = id;
+    }
+
+    fn node_labels(&self) -> Vec<&str> {
+        self.labels.iter().map(String::as_str).collect()
+    }
+
+    fn is_backward(&self) -> bool {
+        self.direction() == crate::Direction::Backward
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -55,6 +95,66 @@ impl<Extra> GraphClass<Extra> {
     }
 }
 
+impl<Extra> GraphClass<Extra> {
+    /// The lowercase class name: `"node"`, `"relationship"`, `"annotation"`,
+    /// `"walk"`, or `"other"`.
+    ///
+    /// These are the strings used by the WASM `GraphClass` constants.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GraphClass::GNode => "node",
+            GraphClass::GRelationship => "relationship",
+            GraphClass::GAnnotation => "annotation",
+            GraphClass::GWalk => "walk",
+            GraphClass::GOther(_) => "other",
+        }
+    }
+}
+
+/// Writes the class name; the `GOther` payload is not shown.
+impl<Extra> fmt::Display for GraphClass<Extra> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Parses a class name written by `Display`; `"other"` yields `GOther(Extra::default())`.
+impl<Extra: Default> FromStr for GraphClass<Extra> {
+    type Err = ParseGraphClassError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "node" => Ok(GraphClass::GNode),
+            "relationship" => Ok(GraphClass::GRelationship),
+            "annotation" => Ok(GraphClass::GAnnotation),
+            "walk" => Ok(GraphClass::GWalk),
+            "other" => Ok(GraphClass::GOther(Extra::default())),
+            _ => Err(ParseGraphClassError {
+                input: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown graph class name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGraphClassError {
+    /// The string that was not a class name
+    pub input: String,
+}
+
+impl fmt::Display for ParseGraphClassError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown graph class {:?}: expected one of \"node\", \"relationship\", \"annotation\", \"walk\", \"other\"",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for ParseGraphClassError {}
+
 // -----------------------------------------------------------------------------
 // GraphClassifier struct
 // -----------------------------------------------------------------------------
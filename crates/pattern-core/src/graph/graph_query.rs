@@ -8,7 +8,7 @@
 //! over a graph. Algorithms operate against this interface, not against any specific
 //! backing representation. This enables the same algorithm code to run against
 //! `PatternGraph`, database-backed stores, or any other structure that can produce
-//! the nine required closures.
+//! the ten required closures.
 //!
 //! # Structural Invariants
 //!
@@ -20,11 +20,14 @@
 //! 5. `query_node_by_id(n.value.identify()) = Some(n)` for all `n ∈ query_nodes()`
 //! 6. `query_relationship_by_id(r.value.identify()) = Some(r)` for all `r ∈ query_relationships()`
 //! 7. `query_containers` returns only **direct** containers — not transitive containment
+//! 8. `query_nodes_by_label(l)` returns, in identity order, the `n ∈ query_nodes()` whose
+//!    [`GraphValue::node_labels`] include `l`
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::graph::graph_classifier::GraphValue;
 use crate::pattern::Pattern;
+use crate::subject::{Subject, Value};
 
 // ============================================================================
 // TraversalDirection
@@ -116,7 +119,7 @@ pub fn directed_reverse<V: Send + Sync + 'static>() -> TraversalWeight<V> {
 // GraphQuery struct (Rc default; Arc under thread-safe feature)
 // ============================================================================
 
-/// Portable graph query interface: a struct of nine closures.
+/// Portable graph query interface: a struct of ten closures.
 ///
 /// All graph algorithms operate against `GraphQuery<V>`, not against any specific
 /// backing representation. Cloning is cheap — it increments reference counts only.
@@ -124,7 +127,7 @@ pub fn directed_reverse<V: Send + Sync + 'static>() -> TraversalWeight<V> {
 /// # Construction
 ///
 /// Use [`crate::from_pattern_graph`] to wrap a [`crate::PatternGraph`], or build
-/// manually by providing all nine closure fields.
+/// manually by providing all ten closure fields.
 ///
 /// # Thread Safety
 ///
@@ -151,6 +154,8 @@ pub struct GraphQuery<V: GraphValue> {
     pub query_relationship_by_id: std::rc::Rc<dyn Fn(&V::Id) -> Option<Pattern<V>>>,
     /// Returns all direct containers of the given element (relationships, walks, annotations).
     pub query_containers: std::rc::Rc<dyn Fn(&Pattern<V>) -> Vec<Pattern<V>>>,
+    /// Returns the nodes carrying the given label, sorted by identity.
+    pub query_nodes_by_label: std::rc::Rc<dyn Fn(&str) -> Vec<Pattern<V>>>,
 }
 
 #[cfg(feature = "thread-safe")]
@@ -175,6 +180,8 @@ pub struct GraphQuery<V: GraphValue> {
         std::sync::Arc<dyn Fn(&V::Id) -> Option<Pattern<V>> + Send + Sync>,
     /// Returns all direct containers of the given element (relationships, walks, annotations).
     pub query_containers: std::sync::Arc<dyn Fn(&Pattern<V>) -> Vec<Pattern<V>> + Send + Sync>,
+    /// Returns the nodes carrying the given label, sorted by identity.
+    pub query_nodes_by_label: std::sync::Arc<dyn Fn(&str) -> Vec<Pattern<V>> + Send + Sync>,
 }
 
 // ============================================================================
@@ -194,6 +201,7 @@ impl<V: GraphValue> Clone for GraphQuery<V> {
             query_node_by_id: std::rc::Rc::clone(&self.query_node_by_id),
             query_relationship_by_id: std::rc::Rc::clone(&self.query_relationship_by_id),
             query_containers: std::rc::Rc::clone(&self.query_containers),
+            query_nodes_by_label: std::rc::Rc::clone(&self.query_nodes_by_label),
         }
     }
 }
@@ -211,6 +219,7 @@ impl<V: GraphValue> Clone for GraphQuery<V> {
             query_node_by_id: std::sync::Arc::clone(&self.query_node_by_id),
             query_relationship_by_id: std::sync::Arc::clone(&self.query_relationship_by_id),
             query_containers: std::sync::Arc::clone(&self.query_containers),
+            query_nodes_by_label: std::sync::Arc::clone(&self.query_nodes_by_label),
         }
     }
 }
@@ -231,6 +240,7 @@ impl<V: GraphValue> Clone for GraphQuery<V> {
 /// - `query_node_by_id(i)` — base lookup; returns `None` if result doesn't satisfy predicate
 /// - `query_relationship_by_id(i)` — base lookup; returns `None` if result doesn't satisfy predicate
 /// - `query_containers(p)` — base containers filtered by predicate
+/// - `query_nodes_by_label(l)` — base nodes with the label, filtered by predicate
 ///
 /// Rc and Arc variants are intentionally separate (no macro): only one is compiled per build,
 /// and Rust does not abstract over Rc/Arc here without macros or runtime indirection.
@@ -305,6 +315,11 @@ where
         base_cont(element).into_iter().filter(|c| inc7(c)).collect()
     });
 
+    let inc8 = Rc::clone(&include);
+    let base_label = Rc::clone(&base.query_nodes_by_label);
+    let query_nodes_by_label =
+        Rc::new(move |label: &str| base_label(label).into_iter().filter(|n| inc8(n)).collect());
+
     GraphQuery {
         query_nodes,
         query_relationships,
@@ -315,6 +330,7 @@ where
         query_node_by_id,
         query_relationship_by_id,
         query_containers,
+        query_nodes_by_label,
     }
 }
 
@@ -390,6 +406,11 @@ where
         base_cont(element).into_iter().filter(|c| inc7(c)).collect()
     });
 
+    let inc8 = Arc::clone(&include);
+    let base_label = Arc::clone(&base.query_nodes_by_label);
+    let query_nodes_by_label =
+        Arc::new(move |label: &str| base_label(label).into_iter().filter(|n| inc8(n)).collect());
+
     GraphQuery {
         query_nodes,
         query_relationships,
@@ -400,6 +421,38 @@ where
         query_node_by_id,
         query_relationship_by_id,
         query_containers,
+        query_nodes_by_label,
+    }
+}
+
+impl GraphQuery<Subject> {
+    /// Returns the nodes carrying `label` in any letter case, sorted by identity.
+    ///
+    /// Matches as [`Subject::has_label_ignore_case`] does; the returned nodes
+    /// keep their labels as stored.
+    pub fn query_nodes_by_label_ignore_case(&self, label: &str) -> Vec<Pattern<Subject>> {
+        let mut nodes: Vec<Pattern<Subject>> = (self.query_nodes)()
+            .into_iter()
+            .filter(|n| n.value.has_label_ignore_case(label))
+            .collect();
+        nodes.sort_by(|a, b| a.value.identity.cmp(&b.value.identity));
+        nodes
+    }
+
+    /// Returns the nodes whose `key` property satisfies `pred`, sorted by identity.
+    ///
+    /// Nodes without the property are skipped; `pred` is never called for them.
+    pub fn query_nodes_where(
+        &self,
+        key: &str,
+        pred: &dyn Fn(&Value) -> bool,
+    ) -> Vec<Pattern<Subject>> {
+        let mut nodes: Vec<Pattern<Subject>> = (self.query_nodes)()
+            .into_iter()
+            .filter(|n| n.value.properties.get(key).is_some_and(pred))
+            .collect();
+        nodes.sort_by(|a, b| a.value.identity.cmp(&b.value.identity));
+        nodes
     }
 }
 
@@ -465,6 +518,7 @@ where
         query_node_by_id: base.query_node_by_id,
         query_relationship_by_id: base.query_relationship_by_id,
         query_containers: base.query_containers,
+        query_nodes_by_label: base.query_nodes_by_label,
     }
 }
 
@@ -511,5 +565,215 @@ where
         query_node_by_id: base.query_node_by_id,
         query_relationship_by_id: base.query_relationship_by_id,
         query_containers: base.query_containers,
+        query_nodes_by_label: base.query_nodes_by_label,
+    }
+}
+
+// ============================================================================
+// overlay combinator
+// ============================================================================
+
+/// Concatenate two result lists, keeping only the first element seen for each identity.
+fn union_by_id<V: GraphValue>(first: Vec<Pattern<V>>, second: Vec<Pattern<V>>) -> Vec<Pattern<V>> {
+    let mut seen: HashSet<V::Id> = HashSet::new();
+    first
+        .into_iter()
+        .chain(second)
+        .filter(|p| seen.insert(p.value.identify().clone()))
+        .collect()
+}
+
+/// Combine two `GraphQuery<V>` values into one that answers over the union of both graphs.
+///
+/// Intended for layering temporary or computed elements (e.g. inferred relationships)
+/// over a base graph without building a new [`crate::PatternGraph`]. Nothing is copied:
+/// every query consults `base` and `extra` on each call.
+///
+/// # Precedence
+///
+/// **`base` wins.** When the same identity appears in both graphs, results carry the
+/// `base` pattern and the `extra` one is dropped. Lists keep `base` results first, in
+/// base order, followed by the remaining `extra` results.
+///
+/// - `query_nodes` / `query_relationships` / `query_containers(p)` — union by identity
+/// - `query_incident_rels(n)` — union by identity of both graphs' incident rels
+/// - `query_degree(n)` — length of the combined incident rels
+/// - `query_node_by_id(i)` / `query_relationship_by_id(i)` — `base` lookup, then `extra`
+/// - `query_nodes_by_label(l)` — `base` nodes with the label, plus `extra` ones whose identity
+///   `base` lacks, sorted by identity
+/// - `query_source(r)` / `query_target(r)` — from the graph that owns `r` (`base` first),
+///   resolved to the combined node with that identity
+///
+/// Relationships in `extra` may connect nodes that exist only in `base`.
+#[cfg(not(feature = "thread-safe"))]
+#[allow(clippy::type_complexity)]
+pub fn overlay<V>(base: GraphQuery<V>, extra: GraphQuery<V>) -> GraphQuery<V>
+where
+    V: GraphValue + Clone + 'static,
+{
+    use std::rc::Rc;
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_nodes = Rc::new(move || union_by_id((b.query_nodes)(), (e.query_nodes)()));
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_relationships =
+        Rc::new(move || union_by_id((b.query_relationships)(), (e.query_relationships)()));
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_incident_rels: Rc<dyn Fn(&Pattern<V>) -> Vec<Pattern<V>>> =
+        Rc::new(move |node: &Pattern<V>| {
+            union_by_id((b.query_incident_rels)(node), (e.query_incident_rels)(node))
+        });
+
+    let inc = Rc::clone(&query_incident_rels);
+    let query_degree = Rc::new(move |node: &Pattern<V>| inc(node).len());
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_node_by_id: Rc<dyn Fn(&V::Id) -> Option<Pattern<V>>> =
+        Rc::new(move |id: &V::Id| (b.query_node_by_id)(id).or_else(|| (e.query_node_by_id)(id)));
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_relationship_by_id = Rc::new(move |id: &V::Id| {
+        (b.query_relationship_by_id)(id).or_else(|| (e.query_relationship_by_id)(id))
+    });
+
+    let (b, e, nbi) = (base.clone(), extra.clone(), Rc::clone(&query_node_by_id));
+    let query_source = Rc::new(move |rel: &Pattern<V>| {
+        let owner = if (b.query_relationship_by_id)(rel.value.identify()).is_some() {
+            &b
+        } else {
+            &e
+        };
+        (owner.query_source)(rel).map(|s| nbi(s.value.identify()).unwrap_or(s))
+    });
+
+    let (b, e, nbi) = (base.clone(), extra.clone(), Rc::clone(&query_node_by_id));
+    let query_target = Rc::new(move |rel: &Pattern<V>| {
+        let owner = if (b.query_relationship_by_id)(rel.value.identify()).is_some() {
+            &b
+        } else {
+            &e
+        };
+        (owner.query_target)(rel).map(|t| nbi(t.value.identify()).unwrap_or(t))
+    });
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_nodes_by_label = Rc::new(move |label: &str| {
+        let mut nodes = (b.query_nodes_by_label)(label);
+        nodes.extend(
+            (e.query_nodes_by_label)(label)
+                .into_iter()
+                .filter(|n| (b.query_node_by_id)(n.value.identify()).is_none()),
+        );
+        nodes.sort_by(|x, y| x.value.identify().cmp(y.value.identify()));
+        nodes
+    });
+
+    let query_containers = Rc::new(move |element: &Pattern<V>| {
+        union_by_id(
+            (base.query_containers)(element),
+            (extra.query_containers)(element),
+        )
+    });
+
+    GraphQuery {
+        query_nodes,
+        query_relationships,
+        query_incident_rels,
+        query_source,
+        query_target,
+        query_degree,
+        query_node_by_id,
+        query_relationship_by_id,
+        query_containers,
+        query_nodes_by_label,
+    }
+}
+
+#[cfg(feature = "thread-safe")]
+#[allow(clippy::type_complexity)]
+pub fn overlay<V>(base: GraphQuery<V>, extra: GraphQuery<V>) -> GraphQuery<V>
+where
+    V: GraphValue + Clone + Send + Sync + 'static,
+    V::Id: Send + Sync + 'static,
+{
+    use std::sync::Arc;
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_nodes = Arc::new(move || union_by_id((b.query_nodes)(), (e.query_nodes)()));
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_relationships =
+        Arc::new(move || union_by_id((b.query_relationships)(), (e.query_relationships)()));
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_incident_rels: Arc<dyn Fn(&Pattern<V>) -> Vec<Pattern<V>> + Send + Sync> =
+        Arc::new(move |node: &Pattern<V>| {
+            union_by_id((b.query_incident_rels)(node), (e.query_incident_rels)(node))
+        });
+
+    let inc = Arc::clone(&query_incident_rels);
+    let query_degree = Arc::new(move |node: &Pattern<V>| inc(node).len());
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_node_by_id: Arc<dyn Fn(&V::Id) -> Option<Pattern<V>> + Send + Sync> =
+        Arc::new(move |id: &V::Id| (b.query_node_by_id)(id).or_else(|| (e.query_node_by_id)(id)));
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_relationship_by_id = Arc::new(move |id: &V::Id| {
+        (b.query_relationship_by_id)(id).or_else(|| (e.query_relationship_by_id)(id))
+    });
+
+    let (b, e, nbi) = (base.clone(), extra.clone(), Arc::clone(&query_node_by_id));
+    let query_source = Arc::new(move |rel: &Pattern<V>| {
+        let owner = if (b.query_relationship_by_id)(rel.value.identify()).is_some() {
+            &b
+        } else {
+            &e
+        };
+        (owner.query_source)(rel).map(|s| nbi(s.value.identify()).unwrap_or(s))
+    });
+
+    let (b, e, nbi) = (base.clone(), extra.clone(), Arc::clone(&query_node_by_id));
+    let query_target = Arc::new(move |rel: &Pattern<V>| {
+        let owner = if (b.query_relationship_by_id)(rel.value.identify()).is_some() {
+            &b
+        } else {
+            &e
+        };
+        (owner.query_target)(rel).map(|t| nbi(t.value.identify()).unwrap_or(t))
+    });
+
+    let (b, e) = (base.clone(), extra.clone());
+    let query_nodes_by_label = Arc::new(move |label: &str| {
+        let mut nodes = (b.query_nodes_by_label)(label);
+        nodes.extend(
+            (e.query_nodes_by_label)(label)
+                .into_iter()
+                .filter(|n| (b.query_node_by_id)(n.value.identify()).is_none()),
+        );
+        nodes.sort_by(|x, y| x.value.identify().cmp(y.value.identify()));
+        nodes
+    });
+
+    let query_containers = Arc::new(move |element: &Pattern<V>| {
+        union_by_id(
+            (base.query_containers)(element),
+            (extra.query_containers)(element),
+        )
+    });
+
+    GraphQuery {
+        query_nodes,
+        query_relationships,
+        query_incident_rels,
+        query_source,
+        query_target,
+        query_degree,
+        query_node_by_id,
+        query_relationship_by_id,
+        query_containers,
+        query_nodes_by_label,
     }
 }
@@ -91,14 +91,7 @@ where
 {
     use std::rc::Rc;
 
-    let rc_graph = Rc::new(PatternGraph {
-        pg_nodes: graph.pg_nodes.clone(),
-        pg_relationships: graph.pg_relationships.clone(),
-        pg_walks: graph.pg_walks.clone(),
-        pg_annotations: graph.pg_annotations.clone(),
-        pg_other: graph.pg_other.clone(),
-        pg_conflicts: graph.pg_conflicts.clone(),
-    });
+    let rc_graph = Rc::new(graph.clone());
     let view_query = crate::pattern_graph::from_pattern_graph(rc_graph);
     let view_elements = view_elements_from_graph(classifier, graph);
     GraphView {
@@ -119,14 +112,7 @@ where
 {
     use std::sync::Arc;
 
-    let arc_graph = Arc::new(PatternGraph {
-        pg_nodes: graph.pg_nodes.clone(),
-        pg_relationships: graph.pg_relationships.clone(),
-        pg_walks: graph.pg_walks.clone(),
-        pg_annotations: graph.pg_annotations.clone(),
-        pg_other: graph.pg_other.clone(),
-        pg_conflicts: graph.pg_conflicts.clone(),
-    });
+    let arc_graph = Arc::new(graph.clone());
     let view_query = crate::pattern_graph::from_pattern_graph(arc_graph);
     let view_elements = view_elements_from_graph(classifier, graph);
     GraphView {
@@ -178,7 +164,7 @@ where
         + PartialEq
         + Clone
         + 'static,
-    Extra: 'static,
+    Extra: Clone + 'static,
 {
     crate::pattern_graph::from_patterns_with_policy(
         classifier,
@@ -1,4 +1,9 @@
 pub mod algorithms;
+mod canonical;
+pub mod centrality;
+pub mod contraction;
+mod diff;
+mod edge_list;
 pub mod graph_classifier;
 pub mod graph_query;
 pub mod graph_view;
@@ -6,17 +11,25 @@ pub mod standard;
 pub mod transform;
 
 pub use algorithms::{
-    all_paths, betweenness_centrality, bfs, connected_components, degree_centrality, dfs,
-    has_cycle, has_path, is_connected, is_neighbor, minimum_spanning_tree, query_annotations_of,
-    query_co_members, query_walks_containing, shortest_path, topological_sort,
+    a_star, all_paths, articulation_points, betweenness_centrality,
+    betweenness_centrality_normalized, bfs, bfs_with_edges, bidirectional_bfs, bridges,
+    closeness_centrality, connected_components, degree_centrality, dfs, dfs_with_edges,
+    find_cycles, harmonic_centrality, has_cycle, has_path, is_bipartite, is_connected, is_neighbor,
+    isolated_nodes, k_shortest_paths, longest_path, longest_path_weighted, minimum_spanning_tree,
+    pagerank, pagerank_with_iterations, parallel_edges, query_annotations_of, query_co_members,
+    query_walks_containing, shortest_path, spanning_tree_pattern, strongly_connected_components,
+    topological_sort, weighted_betweenness_centrality,
 };
+pub use centrality::{annotate_centrality, CentralityMeasure, DEFAULT_CENTRALITY_PROPERTY};
+pub use contraction::{contract_nodes, IntraGroupEdges};
+pub use diff::{diff_patterns, PatternChange};
 pub use graph_classifier::{
     canonical_classifier, classify_by_shape, from_test_node, GraphClass, GraphClassifier,
-    GraphValue,
+    GraphValue, ParseGraphClassError,
 };
 pub use graph_query::{
-    directed, directed_reverse, frame_query, memoize_incident_rels, undirected, GraphQuery,
-    TraversalDirection, TraversalWeight,
+    directed, directed_reverse, frame_query, memoize_incident_rels, overlay, undirected,
+    GraphQuery, TraversalDirection, TraversalWeight,
 };
 pub use graph_view::{from_graph_lens, from_pattern_graph, materialize, GraphView};
 pub use standard::StandardGraph;
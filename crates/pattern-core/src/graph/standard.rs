@@ -10,7 +10,7 @@ use crate::graph::graph_classifier::{canonical_classifier, GraphValue};
 use crate::graph::graph_query::GraphQuery;
 use crate::graph::graph_view::GraphView;
 use crate::pattern::Pattern;
-use crate::pattern_graph::PatternGraph;
+use crate::pattern_graph::{write, PatternGraph};
 use crate::subject::{Subject, Symbol};
 
 /// A concrete, ergonomic graph type wrapping `PatternGraph<(), Subject>`.
@@ -55,9 +55,7 @@ impl StandardGraph {
     /// The subject becomes an atomic pattern (no elements). If a node with the
     /// same identity already exists, it is replaced (last-write-wins).
     pub fn add_node(&mut self, subject: Subject) -> &mut Self {
-        let id = subject.identity.clone();
-        let pattern = Pattern::point(subject);
-        self.inner.pg_nodes.insert(id, pattern);
+        self.inner.put_node(Pattern::point(subject));
         self
     }
 
@@ -81,7 +79,7 @@ impl StandardGraph {
 
         let id = subject.identity.clone();
         let pattern = Pattern::pattern(subject, vec![source_pattern, target_pattern]);
-        self.inner.pg_relationships.insert(id, pattern);
+        write(&mut self.inner.pg_relationships).insert(id, pattern);
         self
     }
 
@@ -101,7 +99,7 @@ impl StandardGraph {
 
         let id = subject.identity.clone();
         let pattern = Pattern::pattern(subject, rel_patterns);
-        self.inner.pg_walks.insert(id, pattern);
+        write(&mut self.inner.pg_walks).insert(id, pattern);
         self
     }
 
@@ -119,15 +117,13 @@ impl StandardGraph {
         } else {
             // Insert placeholder into pg_nodes for consistency with add_relationship
             let placeholder = Self::make_placeholder_node(element_id);
-            self.inner
-                .pg_nodes
-                .insert(element_id.clone(), placeholder.clone());
+            self.inner.put_node(placeholder.clone());
             placeholder
         };
 
         let id = subject.identity.clone();
         let pattern = Pattern::pattern(subject, vec![element_pattern]);
-        self.inner.pg_annotations.insert(id, pattern);
+        write(&mut self.inner.pg_annotations).insert(id, pattern);
         self
     }
 
@@ -353,30 +349,14 @@ impl StandardGraph {
     /// Creates a GraphQuery from this graph.
     #[cfg(not(feature = "thread-safe"))]
     pub fn as_query(&self) -> GraphQuery<Subject> {
-        use std::rc::Rc;
-        let graph = Rc::new(PatternGraph {
-            pg_nodes: self.inner.pg_nodes.clone(),
-            pg_relationships: self.inner.pg_relationships.clone(),
-            pg_walks: self.inner.pg_walks.clone(),
-            pg_annotations: self.inner.pg_annotations.clone(),
-            pg_other: self.inner.pg_other.clone(),
-            pg_conflicts: self.inner.pg_conflicts.clone(),
-        });
+        let graph = std::rc::Rc::new(self.inner.clone());
         crate::pattern_graph::from_pattern_graph(graph)
     }
 
     /// Creates a GraphQuery from this graph.
     #[cfg(feature = "thread-safe")]
     pub fn as_query(&self) -> GraphQuery<Subject> {
-        use std::sync::Arc;
-        let graph = Arc::new(PatternGraph {
-            pg_nodes: self.inner.pg_nodes.clone(),
-            pg_relationships: self.inner.pg_relationships.clone(),
-            pg_walks: self.inner.pg_walks.clone(),
-            pg_annotations: self.inner.pg_annotations.clone(),
-            pg_other: self.inner.pg_other.clone(),
-            pg_conflicts: self.inner.pg_conflicts.clone(),
-        });
+        let graph = std::sync::Arc::new(self.inner.clone());
         crate::pattern_graph::from_pattern_graph(graph)
     }
 
@@ -403,7 +383,7 @@ impl StandardGraph {
             node.clone()
         } else {
             let placeholder = Self::make_placeholder_node(id);
-            self.inner.pg_nodes.insert(id.clone(), placeholder.clone());
+            self.inner.put_node(placeholder.clone());
             placeholder
         }
     }
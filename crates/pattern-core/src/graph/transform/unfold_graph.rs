@@ -54,7 +54,7 @@ where
         + PartialEq
         + Clone
         + 'static,
-    Extra: 'static,
+    Extra: Clone + 'static,
 {
     let patterns: Vec<Pattern<V>> = seeds.into_iter().flat_map(expand).collect();
     from_patterns_with_policy(classifier, policy, patterns)
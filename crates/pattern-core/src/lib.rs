@@ -52,7 +52,7 @@
 //! For direct pattern manipulation without the graph layer:
 //!
 //! ```rust
-//! use pattern_core::{Pattern, Subject, Symbol, Value};
+//! use pattern_core::{Direction, Pattern, Subject, Symbol, Value};
 //! use std::collections::{HashSet, HashMap};
 //!
 //! // Create an atomic pattern (special case)
@@ -218,26 +218,37 @@ pub mod python;
 pub mod wasm;
 
 pub use graph::{
-    all_paths, betweenness_centrality, bfs, canonical_classifier, classify_by_shape,
-    connected_components, degree_centrality, dfs, directed, directed_reverse, filter_graph,
-    fold_graph, frame_query, from_graph_lens, from_pattern_graph, from_test_node, has_cycle,
-    has_path, is_connected, is_neighbor, map_all_graph, map_graph, map_with_context, materialize,
-    memoize_incident_rels, minimum_spanning_tree, para_graph, para_graph_fixed,
-    query_annotations_of, query_co_members, query_walks_containing, shortest_path,
-    topological_sort, undirected, unfold_graph, CategoryMappers, GraphClass, GraphClassifier,
-    GraphQuery, GraphValue, GraphView, StandardGraph, Substitution, TraversalDirection,
-    TraversalWeight,
+    a_star, all_paths, annotate_centrality, articulation_points, betweenness_centrality,
+    betweenness_centrality_normalized, bfs, bfs_with_edges, bidirectional_bfs, bridges,
+    canonical_classifier, classify_by_shape, closeness_centrality, connected_components,
+    contract_nodes, degree_centrality, dfs, dfs_with_edges, diff_patterns, directed,
+    directed_reverse, filter_graph, find_cycles, fold_graph, frame_query, from_graph_lens,
+    from_pattern_graph, from_test_node, harmonic_centrality, has_cycle, has_path, is_bipartite,
+    is_connected, is_neighbor, isolated_nodes, k_shortest_paths, longest_path,
+    longest_path_weighted, map_all_graph, map_graph, map_with_context, materialize,
+    memoize_incident_rels, minimum_spanning_tree, overlay, pagerank, pagerank_with_iterations,
+    para_graph, para_graph_fixed, parallel_edges, query_annotations_of, query_co_members,
+    query_walks_containing, shortest_path, spanning_tree_pattern, strongly_connected_components,
+    topological_sort, undirected, unfold_graph, weighted_betweenness_centrality, CategoryMappers,
+    CentralityMeasure, GraphClass, GraphClassifier, GraphQuery, GraphValue, GraphView,
+    IntraGroupEdges, ParseGraphClassError, PatternChange, StandardGraph, Substitution,
+    TraversalDirection, TraversalWeight, DEFAULT_CENTRALITY_PROPERTY,
 };
 pub use pattern::{unfold, Pattern, StructureAnalysis, ValidationError, ValidationRules};
 pub use pattern_graph::{
-    from_pattern_graph as graph_query_from_pattern_graph, from_patterns, from_patterns_with_policy,
-    merge as pg_merge, merge_with_policy as pg_merge_with_policy, PatternGraph,
+    assign_identities, from_pattern_graph as graph_query_from_pattern_graph, from_patterns,
+    from_patterns_with_policy, match_subgraph, merge as pg_merge,
+    merge_with_policy as pg_merge_with_policy, PatternGraph, PatternGraphBuilder, SharedMap,
+    ANONYMOUS_ID_PREFIX,
 };
 pub use reconcile::{
-    ElementMergeStrategy, HasIdentity, LabelMerge, Mergeable, PropertyMerge, ReconciliationPolicy,
-    Refinable, SubjectMergeStrategy,
+    ElementMergeStrategy, HasIdentity, LabelMerge, MergeFn, MergePolicyBuilder, Mergeable,
+    PropertyMerge, ReconciliationPolicy, Refinable, SubjectMergeStrategy,
+};
+pub use subject::{
+    Direction, MeasurementError, PropertyRecord, Quantifier, RangeValue, Subject, SubjectBuilder,
+    Symbol, Value, DIRECTION_PROPERTY, QUANTIFIER_PROPERTY,
 };
-pub use subject::{PropertyRecord, RangeValue, Subject, SubjectBuilder, Symbol, Value};
 
 // Re-export comonad operations for convenient access
 // These are defined in pattern::comonad and pattern::comonad_helpers modules
@@ -374,7 +385,7 @@ impl Combinable for () {
 /// # Examples
 ///
 /// ```rust
-/// use pattern_core::{Subject, Symbol, Combinable};
+/// use pattern_core::{Direction, Subject, Symbol, Combinable};
 /// use std::collections::{HashMap, HashSet};
 ///
 /// let s1 = Subject {
@@ -212,10 +212,29 @@ pub struct StructureAnalysis {
     pub element_counts: Vec<usize>,
     /// Identified structural patterns (e.g., "linear", "tree", "balanced")
     pub nesting_patterns: Vec<String>,
+    /// Largest number of elements of any one node
+    pub max_branching: usize,
+    /// Mean number of elements of the nodes that have elements (0.0 if none do)
+    pub avg_branching: f64,
+    /// Number of nodes without elements
+    pub leaf_count: usize,
     /// Human-readable summary of structure
     pub summary: String,
 }
 
+/// Element counts gathered while analyzing structure
+#[derive(Default)]
+struct Branching {
+    /// Nodes without elements
+    leaves: usize,
+    /// Nodes with elements
+    parents: usize,
+    /// Total elements of all parents
+    elements: usize,
+    /// Largest element count of any node
+    max: usize,
+}
+
 impl<V: fmt::Debug> Pattern<V> {
     fn fmt_debug_with_depth(
         &self,
@@ -904,6 +923,63 @@ impl<V> Pattern<V> {
         }
     }
 
+    /// Collects the atomic subpatterns (those with no elements), including self.
+    ///
+    /// Leaves are returned in depth-first pre-order, the same order as
+    /// [`filter`](Pattern::filter). An atomic pattern's only leaf is itself.
+    /// Equivalent to `filter(|p| p.is_atomic())` without the predicate call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pattern_core::Pattern;
+    ///
+    /// // A relationship's leaves are its endpoint nodes
+    /// let rel = Pattern::pattern("knows", vec![
+    ///     Pattern::point("alice"),
+    ///     Pattern::point("bob"),
+    /// ]);
+    /// let leaves = rel.leaves();
+    /// assert_eq!(leaves.len(), 2);
+    /// assert_eq!(leaves[0].value, "alice");
+    /// assert_eq!(leaves[1].value, "bob");
+    ///
+    /// let atom = Pattern::point("solo");
+    /// assert_eq!(atom.leaves(), vec![&atom]);
+    /// ```
+    pub fn leaves(&self) -> Vec<&Pattern<V>> {
+        let mut result = Vec::new();
+        self.collect_leaves(&mut result);
+        result
+    }
+
+    /// Collects the values of the atomic subpatterns, in the order of [`leaves`](Pattern::leaves).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pattern_core::Pattern;
+    ///
+    /// let pattern = Pattern::pattern(1, vec![
+    ///     Pattern::point(2),
+    ///     Pattern::pattern(3, vec![Pattern::point(4)]),
+    /// ]);
+    /// assert_eq!(pattern.leaf_values(), vec![&2, &4]);
+    /// ```
+    pub fn leaf_values(&self) -> Vec<&V> {
+        self.leaves().into_iter().map(|p| &p.value).collect()
+    }
+
+    /// Internal helper to recursively collect atomic subpatterns in pre-order.
+    fn collect_leaves<'a>(&'a self, result: &mut Vec<&'a Pattern<V>>) {
+        if self.elements.is_empty() {
+            result.push(self);
+        }
+        for element in &self.elements {
+            element.collect_leaves(result);
+        }
+    }
+
     /// Finds the first subpattern (including self) that satisfies a predicate.
     ///
     /// This method performs a depth-first pre-order traversal of the pattern structure
@@ -1977,6 +2053,8 @@ impl<V> Pattern<V> {
     /// - Depth distribution: Count of nodes at each depth level
     /// - Element counts: Maximum element count at each level (for pattern identification)
     /// - Nesting patterns: Identified structural patterns
+    /// - Branching: Maximum and average element count of non-leaf nodes, and
+    ///   the number of leaves
     /// - Summary: Human-readable text summary
     ///
     /// # Examples
@@ -2001,8 +2079,14 @@ impl<V> Pattern<V> {
     pub fn analyze_structure(&self) -> StructureAnalysis {
         let mut depth_distribution = Vec::new();
         let mut element_counts = Vec::new();
+        let mut branching = Branching::default();
 
-        self.analyze_recursive(0, &mut depth_distribution, &mut element_counts);
+        self.analyze_recursive(
+            0,
+            &mut depth_distribution,
+            &mut element_counts,
+            &mut branching,
+        );
 
         // Trim trailing zeros from element_counts (leaf levels with 0 elements)
         // According to spec: atomic pattern should have [], 2-level tree should have [count], not [count, 0]
@@ -2013,14 +2097,28 @@ impl<V> Pattern<V> {
         // Identify nesting patterns
         let nesting_patterns = self.identify_nesting_patterns(&depth_distribution, &element_counts);
 
+        let avg_branching = if branching.parents == 0 {
+            0.0
+        } else {
+            branching.elements as f64 / branching.parents as f64
+        };
+
         // Generate summary
-        let summary =
-            self.generate_summary(&depth_distribution, &element_counts, &nesting_patterns);
+        let summary = self.generate_summary(
+            &depth_distribution,
+            &element_counts,
+            &nesting_patterns,
+            branching.max,
+            avg_branching,
+        );
 
         StructureAnalysis {
             depth_distribution,
             element_counts,
             nesting_patterns,
+            max_branching: branching.max,
+            avg_branching,
+            leaf_count: branching.leaves,
             summary,
         }
     }
@@ -2032,6 +2130,7 @@ impl<V> Pattern<V> {
         current_depth: usize,
         depth_distribution: &mut Vec<usize>,
         element_counts: &mut Vec<usize>,
+        branching: &mut Branching,
     ) {
         // Ensure vectors are large enough
         while depth_distribution.len() <= current_depth {
@@ -2053,9 +2152,22 @@ impl<V> Pattern<V> {
             element_counts[current_depth] = current_count;
         }
 
+        if current_count == 0 {
+            branching.leaves += 1;
+        } else {
+            branching.parents += 1;
+            branching.elements += current_count;
+            branching.max = branching.max.max(current_count);
+        }
+
         // Recursively analyze elements
         for element in &self.elements {
-            element.analyze_recursive(current_depth + 1, depth_distribution, element_counts);
+            element.analyze_recursive(
+                current_depth + 1,
+                depth_distribution,
+                element_counts,
+                branching,
+            );
         }
     }
 
@@ -2115,6 +2227,8 @@ impl<V> Pattern<V> {
         depth_distribution: &[usize],
         _element_counts: &[usize], // Reserved for future use in summary generation
         nesting_patterns: &[String],
+        max_branching: usize,
+        avg_branching: f64,
     ) -> String {
         let total_nodes: usize = depth_distribution.iter().sum();
         let max_depth = depth_distribution.len().saturating_sub(1);
@@ -2124,13 +2238,23 @@ impl<V> Pattern<V> {
             &nesting_patterns[0]
         };
 
+        let branching_desc = if max_branching == 0 {
+            "no branching".to_string()
+        } else {
+            format!(
+                "branching up to {} (average {:.1})",
+                max_branching, avg_branching
+            )
+        };
+
         format!(
-            "Pattern with {} level{}, {} node{}, {}-like structure",
+            "Pattern with {} level{}, {} node{}, {}-like structure, {}",
             max_depth + 1,
             if max_depth == 0 { "" } else { "s" },
             total_nodes,
             if total_nodes == 1 { "" } else { "s" },
-            pattern_desc
+            pattern_desc,
+            branching_desc
         )
     }
 
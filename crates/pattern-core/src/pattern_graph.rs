@@ -2,44 +2,172 @@
 //!
 //! Ported from `Pattern.PatternGraph` in the Haskell reference implementation.
 //! Patterns are routed into six typed collections by a `GraphClassifier`.
-//! Duplicate identities are resolved via `ReconciliationPolicy`.
+//! Duplicate identities are resolved via `ReconciliationPolicy`, applied the
+//! same way to every collection (nodes, relationships, walks, annotations, and
+//! other); irreconcilable duplicates are recorded in `pg_conflicts`.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use crate::graph::graph_classifier::{GraphClass, GraphClassifier, GraphValue};
 use crate::graph::graph_query::GraphQuery;
 use crate::pattern::Pattern;
 use crate::reconcile::{HasIdentity, Mergeable, ReconciliationPolicy, Refinable};
-use crate::subject::Symbol;
+use crate::subject::{Subject, Symbol};
 
 // -----------------------------------------------------------------------------
 // PatternGraph struct
 // -----------------------------------------------------------------------------
 
+/// One collection of a [`PatternGraph`], shared between graphs until written.
+///
+/// Uses `Rc` by default; enable the `thread-safe` feature to use `Arc`.
+#[cfg(not(feature = "thread-safe"))]
+pub type SharedMap<K, T> = std::rc::Rc<HashMap<K, T>>;
+
+#[cfg(feature = "thread-safe")]
+pub type SharedMap<K, T> = std::sync::Arc<HashMap<K, T>>;
+
+/// Returns `map` for writing, first copying it if another graph shares it.
+#[cfg(not(feature = "thread-safe"))]
+pub(crate) fn write<K: Clone, T: Clone>(map: &mut SharedMap<K, T>) -> &mut HashMap<K, T> {
+    std::rc::Rc::make_mut(map)
+}
+
+#[cfg(feature = "thread-safe")]
+pub(crate) fn write<K: Clone, T: Clone>(map: &mut SharedMap<K, T>) -> &mut HashMap<K, T> {
+    std::sync::Arc::make_mut(map)
+}
+
 /// Materialized graph container with six typed collections, each keyed by identity.
+///
+/// Each collection sits behind its own [`SharedMap`], so cloning a graph is
+/// cheap and a clone only copies a collection once it changes.
+///
+/// Nodes are also indexed by label (see [`GraphValue::node_labels`]). Every
+/// function in this module and [`StandardGraph`](crate::graph::StandardGraph)
+/// keeps the index in step; code that writes `pg_nodes` directly bypasses it,
+/// so label queries will not see nodes added that way.
 pub struct PatternGraph<Extra, V: GraphValue> {
-    pub pg_nodes: HashMap<V::Id, Pattern<V>>,
-    pub pg_relationships: HashMap<V::Id, Pattern<V>>,
-    pub pg_walks: HashMap<V::Id, Pattern<V>>,
-    pub pg_annotations: HashMap<V::Id, Pattern<V>>,
-    pub pg_other: HashMap<V::Id, (Extra, Pattern<V>)>,
-    pub pg_conflicts: HashMap<V::Id, Vec<Pattern<V>>>,
+    pub pg_nodes: SharedMap<V::Id, Pattern<V>>,
+    pub pg_relationships: SharedMap<V::Id, Pattern<V>>,
+    pub pg_walks: SharedMap<V::Id, Pattern<V>>,
+    pub pg_annotations: SharedMap<V::Id, Pattern<V>>,
+    pub pg_other: SharedMap<V::Id, (Extra, Pattern<V>)>,
+    pub pg_conflicts: SharedMap<V::Id, Vec<Pattern<V>>>,
+    /// Node identities by label, each set in identity order.
+    label_index: SharedMap<String, BTreeSet<V::Id>>,
 }
 
 impl<Extra, V: GraphValue> PatternGraph<Extra, V> {
     /// Returns an empty graph with all six maps empty.
     pub fn empty() -> Self {
         PatternGraph {
-            pg_nodes: HashMap::new(),
-            pg_relationships: HashMap::new(),
-            pg_walks: HashMap::new(),
-            pg_annotations: HashMap::new(),
-            pg_other: HashMap::new(),
-            pg_conflicts: HashMap::new(),
+            pg_nodes: Default::default(),
+            pg_relationships: Default::default(),
+            pg_walks: Default::default(),
+            pg_annotations: Default::default(),
+            pg_other: Default::default(),
+            pg_conflicts: Default::default(),
+            label_index: Default::default(),
+        }
+    }
+
+    /// Inserts node `p`, replacing any node with its identity.
+    pub(crate) fn put_node(&mut self, p: Pattern<V>)
+    where
+        V: Clone,
+    {
+        let id = p.value.identify().clone();
+        let old = self.labels_of(&id);
+        write(&mut self.pg_nodes).insert(id.clone(), p);
+        self.reindex_node(&id, old);
+    }
+
+    /// The labels of node `id`, or none if there is no such node.
+    fn labels_of(&self, id: &V::Id) -> BTreeSet<String> {
+        self.pg_nodes
+            .get(id)
+            .map(|n| {
+                n.value
+                    .node_labels()
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Moves node `id` in the label index from the labels it had, `old`, to
+    /// the ones it has now. Leaves the index unwritten when they are the same.
+    fn reindex_node(&mut self, id: &V::Id, old: BTreeSet<String>) {
+        let new = self.labels_of(id);
+        if old == new {
+            return;
+        }
+        let index = write(&mut self.label_index);
+        for label in old.difference(&new) {
+            if let Some(ids) = index.get_mut(label) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    index.remove(label);
+                }
+            }
+        }
+        for label in new.difference(&old) {
+            index.entry(label.clone()).or_default().insert(id.clone());
+        }
+    }
+
+    /// The nodes carrying `label`, in identity order.
+    fn indexed_nodes(&self, label: &str) -> impl Iterator<Item = &Pattern<V>> + '_ {
+        self.label_index
+            .get(label)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.pg_nodes.get(id))
+    }
+}
+
+impl<Extra, V: GraphValue> Clone for PatternGraph<Extra, V> {
+    /// Shares all six maps and the label index with `self`; nothing is copied
+    /// until one is written.
+    fn clone(&self) -> Self {
+        PatternGraph {
+            pg_nodes: self.pg_nodes.clone(),
+            pg_relationships: self.pg_relationships.clone(),
+            pg_walks: self.pg_walks.clone(),
+            pg_annotations: self.pg_annotations.clone(),
+            pg_other: self.pg_other.clone(),
+            pg_conflicts: self.pg_conflicts.clone(),
+            label_index: self.label_index.clone(),
         }
     }
 }
 
+impl<Extra> PatternGraph<Extra, Subject> {
+    /// Returns the nodes carrying `label`, sorted by identity.
+    ///
+    /// Reads the label index, so only the matching nodes are visited.
+    pub fn query_nodes_by_label(&self, label: &str) -> Vec<Pattern<Subject>> {
+        self.indexed_nodes(label).cloned().collect()
+    }
+
+    /// Returns the nodes carrying `label` in any letter case, sorted by identity.
+    ///
+    /// Matches as [`Subject::has_label_ignore_case`] does; the returned nodes
+    /// keep their labels as stored.
+    pub fn query_nodes_by_label_ignore_case(&self, label: &str) -> Vec<Pattern<Subject>> {
+        let mut nodes: Vec<Pattern<Subject>> = self
+            .pg_nodes
+            .values()
+            .filter(|n| n.value.has_label_ignore_case(label))
+            .cloned()
+            .collect();
+        nodes.sort_by(|a, b| a.value.identity.cmp(&b.value.identity));
+        nodes
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Trait bounds alias (used throughout)
 // -----------------------------------------------------------------------------
@@ -61,35 +189,118 @@ fn two_occurrences<V: Clone>(existing: &Pattern<V>, incoming: Pattern<V>) -> Pat
 }
 
 // -----------------------------------------------------------------------------
-// Private insert functions
+// Shared reconciliation
 // -----------------------------------------------------------------------------
 
-fn insert_node<Extra, V>(
+/// An entry of one typed collection: a pattern, or for `pg_other` a pattern
+/// with its classifier payload.
+trait Entry<V> {
+    fn pattern(&self) -> &Pattern<V>;
+    fn into_pattern(self) -> Pattern<V>;
+    /// Replaces the pattern, keeping anything else the entry carries.
+    fn with_pattern(self, p: Pattern<V>) -> Self;
+}
+
+impl<V> Entry<V> for Pattern<V> {
+    fn pattern(&self) -> &Pattern<V> {
+        self
+    }
+
+    fn into_pattern(self) -> Pattern<V> {
+        self
+    }
+
+    fn with_pattern(self, p: Pattern<V>) -> Self {
+        p
+    }
+}
+
+impl<Extra, V> Entry<V> for (Extra, Pattern<V>) {
+    fn pattern(&self) -> &Pattern<V> {
+        &self.1
+    }
+
+    fn into_pattern(self) -> Pattern<V> {
+        self.1
+    }
+
+    fn with_pattern(self, p: Pattern<V>) -> Self {
+        (self.0, p)
+    }
+}
+
+/// Inserts `entry` into one typed collection, reconciling it with any
+/// existing entry of the same identity under `policy`.
+///
+/// All five collections go through this function, so every collection
+/// resolves collisions the same way. A reference (see
+/// [`Refinable::is_reference`]) defers to whatever the collection holds: an
+/// incoming reference leaves the existing entry alone, and an incoming entry
+/// replaces an existing reference, under every policy. When reconciliation
+/// fails (e.g. `Strict` with differing content), the existing entry is kept
+/// and the incoming pattern is recorded in `conflicts`. A merged `pg_other`
+/// entry keeps the classifier payload of the existing one.
+fn reconcile_into<V, T>(
     policy: &ReconciliationPolicy<V::MergeStrategy>,
-    p: Pattern<V>,
-    mut g: PatternGraph<Extra, V>,
-) -> PatternGraph<Extra, V>
-where
+    collection: &mut SharedMap<Symbol, T>,
+    conflicts: &mut SharedMap<Symbol, Vec<Pattern<V>>>,
+    entry: T,
+) where
     V: GraphValue<Id = Symbol> + HasIdentity<V, Symbol> + Mergeable + Refinable + PartialEq + Clone,
+    T: Entry<V> + Clone,
 {
-    let i = V::identity(&p.value).clone();
-    match g.pg_nodes.remove(&i) {
+    let collection = write(collection);
+    let i = V::identity(&entry.pattern().value).clone();
+    match collection.remove(&i) {
         None => {
-            g.pg_nodes.insert(i, p);
+            collection.insert(i, entry);
+        }
+        Some(existing) if is_reference(entry.pattern()) => {
+            collection.insert(i, existing);
+        }
+        Some(existing) if is_reference(existing.pattern()) => {
+            collection.insert(i, entry);
         }
         Some(existing) => {
-            let synthetic = two_occurrences(&existing, p.clone());
+            let synthetic = two_occurrences(existing.pattern(), entry.pattern().clone());
             match crate::reconcile::reconcile(policy, &synthetic) {
                 Err(_) => {
-                    g.pg_nodes.insert(i.clone(), existing);
-                    g.pg_conflicts.entry(i).or_default().push(p);
+                    collection.insert(i.clone(), existing);
+                    write(conflicts)
+                        .entry(i)
+                        .or_default()
+                        .push(entry.into_pattern());
                 }
                 Ok(merged) => {
-                    g.pg_nodes.insert(i, merged);
+                    collection.insert(i, existing.with_pattern(merged));
                 }
             }
         }
     }
+}
+
+/// True when `p` only refers to its identity: no elements, and a value that
+/// [`Refinable::is_reference`] accepts.
+fn is_reference<V: Refinable>(p: &Pattern<V>) -> bool {
+    p.elements.is_empty() && V::is_reference(&p.value)
+}
+
+// -----------------------------------------------------------------------------
+// Private insert functions
+// -----------------------------------------------------------------------------
+
+fn insert_node<Extra, V>(
+    policy: &ReconciliationPolicy<V::MergeStrategy>,
+    p: Pattern<V>,
+    mut g: PatternGraph<Extra, V>,
+) -> PatternGraph<Extra, V>
+where
+    V: GraphValue<Id = Symbol> + HasIdentity<V, Symbol> + Mergeable + Refinable + PartialEq + Clone,
+{
+    let id = p.value.identify().clone();
+    let old = g.labels_of(&id);
+    reconcile_into(policy, &mut g.pg_nodes, &mut g.pg_conflicts, p);
+    g.reindex_node(&id, old);
     g
 }
 
@@ -107,7 +318,7 @@ where
         + PartialEq
         + Clone
         + 'static,
-    Extra: 'static,
+    Extra: Clone + 'static,
 {
     // Merge endpoint nodes first.
     let g1 = if p.elements.len() == 2 {
@@ -119,25 +330,8 @@ where
         g
     };
 
-    let i = V::identity(&p.value).clone();
     let mut g2 = g1;
-    match g2.pg_relationships.remove(&i) {
-        None => {
-            g2.pg_relationships.insert(i, p);
-        }
-        Some(existing) => {
-            let synthetic = two_occurrences(&existing, p.clone());
-            match crate::reconcile::reconcile(policy, &synthetic) {
-                Err(_) => {
-                    g2.pg_relationships.insert(i.clone(), existing);
-                    g2.pg_conflicts.entry(i).or_default().push(p);
-                }
-                Ok(merged) => {
-                    g2.pg_relationships.insert(i, merged);
-                }
-            }
-        }
-    }
+    reconcile_into(policy, &mut g2.pg_relationships, &mut g2.pg_conflicts, p);
     g2
 }
 
@@ -155,7 +349,7 @@ where
         + PartialEq
         + Clone
         + 'static,
-    Extra: 'static,
+    Extra: Clone + 'static,
 {
     // Merge each component relationship (which recursively merges their nodes).
     let elements: Vec<Pattern<V>> = p.elements.clone();
@@ -163,25 +357,8 @@ where
         merge_with_policy(classifier, policy, elem, acc)
     });
 
-    let i = V::identity(&p.value).clone();
     let mut g2 = g1;
-    match g2.pg_walks.remove(&i) {
-        None => {
-            g2.pg_walks.insert(i, p);
-        }
-        Some(existing) => {
-            let synthetic = two_occurrences(&existing, p.clone());
-            match crate::reconcile::reconcile(policy, &synthetic) {
-                Err(_) => {
-                    g2.pg_walks.insert(i.clone(), existing);
-                    g2.pg_conflicts.entry(i).or_default().push(p);
-                }
-                Ok(merged) => {
-                    g2.pg_walks.insert(i, merged);
-                }
-            }
-        }
-    }
+    reconcile_into(policy, &mut g2.pg_walks, &mut g2.pg_conflicts, p);
     g2
 }
 
@@ -199,7 +376,7 @@ where
         + PartialEq
         + Clone
         + 'static,
-    Extra: 'static,
+    Extra: Clone + 'static,
 {
     // Merge the single inner element first.
     let g1 = if p.elements.len() == 1 {
@@ -209,25 +386,8 @@ where
         g
     };
 
-    let i = V::identity(&p.value).clone();
     let mut g2 = g1;
-    match g2.pg_annotations.remove(&i) {
-        None => {
-            g2.pg_annotations.insert(i, p);
-        }
-        Some(existing) => {
-            let synthetic = two_occurrences(&existing, p.clone());
-            match crate::reconcile::reconcile(policy, &synthetic) {
-                Err(_) => {
-                    g2.pg_annotations.insert(i.clone(), existing);
-                    g2.pg_conflicts.entry(i).or_default().push(p);
-                }
-                Ok(merged) => {
-                    g2.pg_annotations.insert(i, merged);
-                }
-            }
-        }
-    }
+    reconcile_into(policy, &mut g2.pg_annotations, &mut g2.pg_conflicts, p);
     g2
 }
 
@@ -239,25 +399,9 @@ fn insert_other<Extra, V>(
 ) -> PatternGraph<Extra, V>
 where
     V: GraphValue<Id = Symbol> + HasIdentity<V, Symbol> + Mergeable + Refinable + PartialEq + Clone,
+    Extra: Clone,
 {
-    let i = V::identity(&p.value).clone();
-    match g.pg_other.remove(&i) {
-        None => {
-            g.pg_other.insert(i, (extra, p));
-        }
-        Some((existing_extra, existing)) => {
-            let synthetic = two_occurrences(&existing, p.clone());
-            match crate::reconcile::reconcile(policy, &synthetic) {
-                Err(_) => {
-                    g.pg_other.insert(i.clone(), (existing_extra, existing));
-                    g.pg_conflicts.entry(i).or_default().push(p);
-                }
-                Ok(merged) => {
-                    g.pg_other.insert(i, (existing_extra, merged));
-                }
-            }
-        }
-    }
+    reconcile_into(policy, &mut g.pg_other, &mut g.pg_conflicts, (extra, p));
     g
 }
 
@@ -269,6 +413,8 @@ where
 ///
 /// Dispatches to the appropriate typed collection based on `classifier`.
 /// Sub-elements are recursively merged before the top-level pattern is inserted.
+/// A `GOther` pattern that is a path in the nested form the gram parser
+/// produces, such as `(a)-->(b)-->(c)`, is inserted as its relationships.
 pub fn merge_with_policy<Extra, V>(
     classifier: &GraphClassifier<Extra, V>,
     policy: &ReconciliationPolicy<V::MergeStrategy>,
@@ -283,17 +429,62 @@ where
         + PartialEq
         + Clone
         + 'static,
-    Extra: 'static,
+    Extra: Clone + 'static,
 {
     match (classifier.classify)(&p) {
         GraphClass::GNode => insert_node(policy, p, g),
         GraphClass::GRelationship => insert_relationship(classifier, policy, p, g),
         GraphClass::GWalk => insert_walk(classifier, policy, p, g),
         GraphClass::GAnnotation => insert_annotation(classifier, policy, p, g),
-        GraphClass::GOther(extra) => insert_other(policy, extra, p, g),
+        GraphClass::GOther(extra) => match path_relationships(&p) {
+            Some(relationships) => relationships
+                .into_iter()
+                .fold(g, |acc, r| merge_with_policy(classifier, policy, r, acc)),
+            None => insert_other(policy, extra, p, g),
+        },
     }
 }
 
+/// Splits a path in the nested form the gram parser produces into its
+/// relationships, in the order written.
+///
+/// `(a)-->(b)-->(c)` parses as the relationship `(b)-->(c)` with the path
+/// `(a)-->(b)` in place of its source node; this returns `(a)-->(b)` and
+/// `(b)-->(c)`. Returns `None` for a pattern of any other shape, including a
+/// single relationship.
+fn path_relationships<V: GraphValue + Clone>(p: &Pattern<V>) -> Option<Vec<Pattern<V>>> {
+    /// The relationships of `p` and the node it ends at as written
+    fn unfold<V: GraphValue + Clone>(p: &Pattern<V>) -> Option<(Vec<Pattern<V>>, &Pattern<V>)> {
+        let [first, second] = p.elements.as_slice() else {
+            return None;
+        };
+        // A backward arrow stores its source first, so the path continues there
+        let (rest, end) = if p.value.is_backward() {
+            (second, first)
+        } else {
+            (first, second)
+        };
+        if !end.elements.is_empty() {
+            return None;
+        }
+        if rest.elements.is_empty() {
+            return Some((vec![p.clone()], end));
+        }
+        let (mut relationships, joint) = unfold(rest)?;
+        let endpoints = if p.value.is_backward() {
+            vec![end.clone(), joint.clone()]
+        } else {
+            vec![joint.clone(), end.clone()]
+        };
+        relationships.push(Pattern::pattern(p.value.clone(), endpoints));
+        Some((relationships, end))
+    }
+
+    unfold(p)
+        .map(|(relationships, _)| relationships)
+        .filter(|relationships| relationships.len() > 1)
+}
+
 /// Inserts one pattern using `LastWriteWins` policy.
 pub fn merge<Extra, V>(
     classifier: &GraphClassifier<Extra, V>,
@@ -308,7 +499,7 @@ where
         + PartialEq
         + Clone
         + 'static,
-    Extra: 'static,
+    Extra: Clone + 'static,
 {
     merge_with_policy(classifier, &ReconciliationPolicy::LastWriteWins, p, g)
 }
@@ -326,7 +517,7 @@ where
         + PartialEq
         + Clone
         + 'static,
-    Extra: 'static,
+    Extra: Clone + 'static,
 {
     from_patterns_with_policy(classifier, &ReconciliationPolicy::LastWriteWins, patterns)
 }
@@ -337,7 +528,7 @@ where
 
 /// Wraps a `PatternGraph` in a `GraphQuery<V>`.
 ///
-/// All nine `GraphQuery` fields are implemented against the `PatternGraph` maps.
+/// All ten `GraphQuery` fields are implemented against the `PatternGraph` maps.
 ///
 /// # Complexity
 ///
@@ -347,11 +538,18 @@ where
 /// - `query_degree`: O(r) scan
 /// - `query_node_by_id` / `query_relationship_by_id`: O(1) average HashMap lookup
 /// - `query_containers`: O(r + w + a) scan of relationships, walks, annotations
+/// - `query_nodes_by_label`: O(k) over the k matching nodes, via the label index
 ///
 /// # Deferred
 ///
 /// TODO: `from_graph_lens` — deferred until `GraphLens` type is available in pattern-rs.
 ///
+/// # Sharing
+///
+/// The graph is not copied: each closure holds a clone of the `Rc`/`Arc` handle,
+/// so every query built from the same handle reads the same allocation. Patterns
+/// are cloned only when a query returns them. See also [`PatternGraph::as_query`].
+///
 /// Rc and Arc variants are intentionally separate (no macro): only one is compiled per build,
 /// trait bounds stay clear, and we avoid fragile abstraction over pointer types.
 #[cfg(not(feature = "thread-safe"))]
@@ -406,6 +604,10 @@ where
     let g6 = Rc::clone(&graph);
     let query_relationship_by_id = Rc::new(move |id: &V::Id| g6.pg_relationships.get(id).cloned());
 
+    let g8 = Rc::clone(&graph);
+    let query_nodes_by_label =
+        Rc::new(move |label: &str| g8.indexed_nodes(label).cloned().collect());
+
     let g7 = Rc::clone(&graph);
     let query_containers = Rc::new(move |element: &Pattern<V>| {
         let elem_id = element.value.identify();
@@ -448,6 +650,7 @@ where
         query_node_by_id,
         query_relationship_by_id,
         query_containers,
+        query_nodes_by_label,
     }
 }
 
@@ -502,6 +705,10 @@ where
     let g6 = Arc::clone(&graph);
     let query_relationship_by_id = Arc::new(move |id: &V::Id| g6.pg_relationships.get(id).cloned());
 
+    let g8 = Arc::clone(&graph);
+    let query_nodes_by_label =
+        Arc::new(move |label: &str| g8.indexed_nodes(label).cloned().collect());
+
     let g7 = Arc::clone(&graph);
     let query_containers = Arc::new(move |element: &Pattern<V>| {
         let elem_id = element.value.identify();
@@ -541,10 +748,61 @@ where
         query_node_by_id,
         query_relationship_by_id,
         query_containers,
+        query_nodes_by_label,
+    }
+}
+
+#[cfg(not(feature = "thread-safe"))]
+impl<Extra, V> PatternGraph<Extra, V>
+where
+    Extra: 'static,
+    V: GraphValue + Clone + 'static,
+    V::Id: Clone + Eq + std::hash::Hash + 'static,
+{
+    /// Returns a `GraphQuery` over this shared graph.
+    ///
+    /// Equivalent to `from_pattern_graph(Rc::clone(self))`. The query only holds
+    /// reference-counted handles to the graph, so any number of queries can share
+    /// one allocation; the graph itself is never copied.
+    pub fn as_query(self: &std::rc::Rc<Self>) -> GraphQuery<V> {
+        from_pattern_graph(std::rc::Rc::clone(self))
+    }
+}
+
+#[cfg(feature = "thread-safe")]
+impl<Extra, V> PatternGraph<Extra, V>
+where
+    Extra: Send + Sync + 'static,
+    V: GraphValue + Clone + Send + Sync + 'static,
+    V::Id: Clone + Eq + std::hash::Hash + Send + Sync + 'static,
+{
+    /// Returns a `GraphQuery` over this shared graph.
+    ///
+    /// Equivalent to `from_pattern_graph(Arc::clone(self))`. The query only holds
+    /// reference-counted handles to the graph, so any number of queries can share
+    /// one allocation; the graph itself is never copied.
+    pub fn as_query(self: &std::sync::Arc<Self>) -> GraphQuery<V> {
+        from_pattern_graph(std::sync::Arc::clone(self))
     }
 }
 
 /// Builds a graph from an iterable of patterns using the given policy.
+///
+/// The policy applies to every classified collection, including walks and
+/// annotations: two annotations sharing an identity are reconciled exactly like
+/// two nodes would be, and under `Strict` a differing duplicate is kept out of
+/// the collection and recorded in `pg_conflicts`.
+///
+/// A path in the nested form the gram parser produces for `(a)-->(b)-->(c)`,
+/// which `classifier` leaves as `GOther`, is built as its relationships, as
+/// [`merge_with_policy`] does, so it gives the same graph as
+/// `(a)-->(b) (b)-->(c)` apart from the identities generated for anonymous
+/// relationships.
+///
+/// Anonymous patterns are first given distinct identities by
+/// [`assign_identities`], so `(a)-->() (b)-->()` has four nodes and two
+/// relationships rather than three nodes and one relationship. Patterns with
+/// an identity are unaffected.
 pub fn from_patterns_with_policy<Extra, V>(
     classifier: &GraphClassifier<Extra, V>,
     policy: &ReconciliationPolicy<V::MergeStrategy>,
@@ -558,9 +816,488 @@ where
         + PartialEq
         + Clone
         + 'static,
-    Extra: 'static,
+    Extra: Clone + 'static,
+{
+    assign_identities(patterns.into_iter().collect())
+        .into_iter()
+        .fold(PatternGraph::empty(), |g, p| {
+            merge_with_policy(classifier, policy, p, g)
+        })
+}
+
+// -----------------------------------------------------------------------------
+// Incremental updates
+// -----------------------------------------------------------------------------
+
+impl<Extra, V> PatternGraph<Extra, V>
+where
+    V: GraphValue<Id = Symbol>
+        + HasIdentity<V, Symbol>
+        + Mergeable
+        + Refinable
+        + PartialEq
+        + Clone
+        + 'static,
+    Extra: Clone + 'static,
+{
+    /// Returns a copy of this graph with `p` merged in.
+    ///
+    /// The result equals `from_patterns_with_policy` over the original patterns
+    /// followed by `p`: sub-elements are merged first, duplicates are reconciled
+    /// under `policy`, and irreconcilable duplicates land in `pg_conflicts`.
+    /// Anonymous patterns in `p` get identities that avoid every identity
+    /// already in the graph. Only `p` is classified and reconciled, and only
+    /// the collections it changes are copied; the rest stay shared with `self`.
+    pub fn with_pattern_added(
+        &self,
+        classifier: &GraphClassifier<Extra, V>,
+        policy: &ReconciliationPolicy<V::MergeStrategy>,
+        mut p: Pattern<V>,
+    ) -> Self {
+        let mut anonymous = AnonymousIds::default();
+        anonymous.used.extend(
+            self.pg_nodes
+                .keys()
+                .chain(self.pg_relationships.keys())
+                .chain(self.pg_walks.keys())
+                .chain(self.pg_annotations.keys())
+                .chain(self.pg_other.keys())
+                .cloned(),
+        );
+        anonymous.reserve(&p);
+        anonymous.assign(&mut p);
+        merge_with_policy(classifier, policy, p, self.clone())
+    }
+
+    /// Returns a copy of this graph without the element identified by `id`.
+    ///
+    /// The element is removed from whichever collection holds it, together
+    /// with its `pg_conflicts` entry. Removal cascades to every relationship,
+    /// walk, or annotation that contains a removed element, so no relationship
+    /// is left pointing at a missing node. Endpoints of a removed relationship
+    /// stay in the graph. Removing an unknown identity returns an equal copy.
+    /// Collections that lose nothing stay shared with `self`.
+    pub fn with_pattern_removed(&self, id: &Symbol) -> Self {
+        let mut g = self.clone();
+        let mut removed: HashSet<Symbol> = HashSet::new();
+        let old = g.labels_of(id);
+        remove_shared(&mut g.pg_nodes, id);
+        g.reindex_node(id, old);
+        remove_shared(&mut g.pg_relationships, id);
+        remove_shared(&mut g.pg_walks, id);
+        remove_shared(&mut g.pg_annotations, id);
+        remove_shared(&mut g.pg_other, id);
+        removed.insert(id.clone());
+
+        // Containers may themselves be contained (a relationship inside a walk
+        // or annotation), so repeat until nothing else refers to a removed id.
+        loop {
+            let contains_removed = |p: &Pattern<V>| {
+                p.elements
+                    .iter()
+                    .any(|e| removed.contains(e.value.identify()))
+            };
+            let doomed: Vec<Symbol> = g
+                .pg_relationships
+                .iter()
+                .chain(g.pg_walks.iter())
+                .chain(g.pg_annotations.iter())
+                .filter(|(_, p)| contains_removed(p))
+                .map(|(i, _)| i.clone())
+                .collect();
+            if doomed.is_empty() {
+                break;
+            }
+            for i in doomed {
+                remove_shared(&mut g.pg_relationships, &i);
+                remove_shared(&mut g.pg_walks, &i);
+                remove_shared(&mut g.pg_annotations, &i);
+                removed.insert(i);
+            }
+        }
+
+        for i in &removed {
+            remove_shared(&mut g.pg_conflicts, i);
+        }
+        g
+    }
+}
+
+/// Removes `id` from `map`, copying the map only if it holds `id`.
+fn remove_shared<T: Clone>(map: &mut SharedMap<Symbol, T>, id: &Symbol) {
+    if map.contains_key(id) {
+        write(map).remove(id);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Subgraph matching
+// -----------------------------------------------------------------------------
+
+/// Finds every embedding of the linear `query` pattern in `graph`.
+///
+/// `query` is a node, a relationship, a walk of relationships, or a path in
+/// the nested form the gram parser produces for `(a)-->(b)-->(c)`. Each
+/// query node matches a graph node carrying all of its labels and
+/// properties; each query relationship matches a graph relationship in the
+/// same direction carrying all of its labels (the relationship type) and
+/// properties. Distinct query nodes bind distinct graph nodes, and a query
+/// identity that appears twice must bind the same node both times.
+///
+/// Each result maps query node identities to the bound graph nodes; anonymous
+/// query nodes constrain the match but are not reported, so embeddings that
+/// differ only in them appear once. Results are sorted by their bindings in
+/// identity order. Query shapes other than the ones above match nothing.
+pub fn match_subgraph<Extra>(
+    graph: &PatternGraph<Extra, Subject>,
+    query: &Pattern<Subject>,
+) -> Vec<HashMap<Symbol, Pattern<Subject>>> {
+    let mut shape = QueryShape::default();
+    if shape.add(query).is_none() {
+        return Vec::new();
+    }
+
+    let mut nodes: Vec<&Pattern<Subject>> = graph.pg_nodes.values().collect();
+    nodes.sort_by(|a, b| a.value.identity.cmp(&b.value.identity));
+    let mut rels: Vec<&Pattern<Subject>> = graph
+        .pg_relationships
+        .values()
+        .filter(|r| r.elements.len() == 2)
+        .collect();
+    rels.sort_by(|a, b| a.value.identity.cmp(&b.value.identity));
+
+    let mut search = SubgraphSearch {
+        shape: &shape,
+        graph_nodes: &graph.pg_nodes,
+        nodes,
+        rels,
+        bound: vec![None; shape.nodes.len()],
+        used_rels: HashSet::new(),
+        found: Vec::new(),
+    };
+    search.extend_rels(0);
+
+    let mut found: Vec<Vec<(Symbol, Symbol)>> = search
+        .found
+        .iter()
+        .map(|b| {
+            let mut key: Vec<(Symbol, Symbol)> = shape
+                .nodes
+                .iter()
+                .zip(b)
+                .filter(|(q, _)| !q.identity.0.is_empty())
+                .map(|(q, n)| (q.identity.clone(), n.value.identity.clone()))
+                .collect();
+            key.sort();
+            key
+        })
+        .collect();
+    found.sort();
+    found.dedup();
+    found
+        .into_iter()
+        .map(|key| {
+            key.into_iter()
+                .map(|(q, n)| {
+                    let node = graph.pg_nodes[&n].clone();
+                    (q, node)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// A linear query flattened into node slots and relationships between them.
+#[derive(Default)]
+struct QueryShape<'q> {
+    nodes: Vec<&'q Subject>,
+    /// `(relationship, source slot, target slot)`
+    rels: Vec<(&'q Subject, usize, usize)>,
+}
+
+impl<'q> QueryShape<'q> {
+    /// Adds `p` to the shape and returns the slot of its last node, or `None`
+    /// when `p` is not a supported shape.
+    fn add(&mut self, p: &'q Pattern<Subject>) -> Option<usize> {
+        match p.elements.as_slice() {
+            [] => Some(self.slot(&p.value)),
+            [source, target] if target.elements.is_empty() => {
+                // `(a)-->(b)` has an atomic source; the parser nests longer
+                // paths so the source is the path so far, ending at the
+                // relationship's source node.
+                let s = self.add(source)?;
+                let t = self.slot(&target.value);
+                self.rels.push((&p.value, s, t));
+                Some(t)
+            }
+            elements if elements.iter().all(|e| e.elements.len() == 2) => {
+                let mut last = None;
+                for e in elements {
+                    last = Some(self.add(e)?);
+                }
+                last
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the slot for a query node, sharing slots between repeated
+    /// identities; every anonymous node gets its own slot.
+    fn slot(&mut self, node: &'q Subject) -> usize {
+        if !node.identity.0.is_empty() {
+            if let Some(i) = self.nodes.iter().position(|n| n.identity == node.identity) {
+                return i;
+            }
+        }
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+}
+
+/// True when `candidate` carries every label and property of `query`.
+fn subject_fits(query: &Subject, candidate: &Subject) -> bool {
+    query.labels.is_subset(&candidate.labels)
+        && query
+            .properties
+            .iter()
+            .all(|(k, v)| candidate.properties.get(k) == Some(v))
+}
+
+/// Backtracking state for [`match_subgraph`].
+struct SubgraphSearch<'q, 'g> {
+    shape: &'q QueryShape<'q>,
+    graph_nodes: &'g HashMap<Symbol, Pattern<Subject>>,
+    nodes: Vec<&'g Pattern<Subject>>,
+    rels: Vec<&'g Pattern<Subject>>,
+    bound: Vec<Option<&'g Pattern<Subject>>>,
+    used_rels: HashSet<&'g Symbol>,
+    found: Vec<Vec<&'g Pattern<Subject>>>,
+}
+
+impl<'q, 'g> SubgraphSearch<'q, 'g> {
+    /// Binds query relationship `i` and every one after it.
+    fn extend_rels(&mut self, i: usize) {
+        let Some(&(query_rel, s, t)) = self.shape.rels.get(i) else {
+            self.extend_nodes();
+            return;
+        };
+        for r in 0..self.rels.len() {
+            let rel = self.rels[r];
+            if self.used_rels.contains(&rel.value.identity) || !subject_fits(query_rel, &rel.value)
+            {
+                continue;
+            }
+            let (Some(source), Some(target)) = (
+                self.graph_nodes.get(&rel.elements[0].value.identity),
+                self.graph_nodes.get(&rel.elements[1].value.identity),
+            ) else {
+                continue;
+            };
+            let Some(bound_s) = self.bind(s, source) else {
+                continue;
+            };
+            if let Some(bound_t) = self.bind(t, target) {
+                self.used_rels.insert(&rel.value.identity);
+                self.extend_rels(i + 1);
+                self.used_rels.remove(&rel.value.identity);
+                if bound_t {
+                    self.bound[t] = None;
+                }
+            }
+            if bound_s {
+                self.bound[s] = None;
+            }
+        }
+    }
+
+    /// Binds the query nodes that no relationship constrains.
+    fn extend_nodes(&mut self) {
+        let Some(slot) = self.bound.iter().position(|b| b.is_none()) else {
+            self.found
+                .push(self.bound.iter().flatten().copied().collect());
+            return;
+        };
+        for n in 0..self.nodes.len() {
+            let node = self.nodes[n];
+            if let Some(true) = self.bind(slot, node) {
+                self.extend_nodes();
+                self.bound[slot] = None;
+            }
+        }
+    }
+
+    /// Tries to bind `slot` to `node`. Returns `Some(true)` when the slot was
+    /// newly bound, `Some(false)` when it was already bound to `node`, and
+    /// `None` when the binding is not possible.
+    fn bind(&mut self, slot: usize, node: &'g Pattern<Subject>) -> Option<bool> {
+        match self.bound[slot] {
+            Some(b) => (b.value.identity == node.value.identity).then_some(false),
+            None => {
+                let taken = self
+                    .bound
+                    .iter()
+                    .flatten()
+                    .any(|b| b.value.identity == node.value.identity);
+                if taken || !subject_fits(self.shape.nodes[slot], &node.value) {
+                    return None;
+                }
+                self.bound[slot] = Some(node);
+                Some(true)
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Anonymous node identities
+// -----------------------------------------------------------------------------
+
+/// Prefix of the identities generated by [`assign_identities`].
+pub const ANONYMOUS_ID_PREFIX: &str = "_anon";
+
+/// Gives every anonymous pattern a distinct identity.
+///
+/// Anonymous patterns all share the empty identity, so once they are keyed by
+/// identity in a graph they would collapse into one. This replaces the empty
+/// identity of each pattern — a node, standalone or nested inside a
+/// relationship, walk, or annotation, or the relationship, walk, or annotation
+/// itself — with a fresh [`Symbol`] `_anon1`, `_anon2`, ... (see
+/// [`ANONYMOUS_ID_PREFIX`]), numbered in pre-order across `patterns` and
+/// skipping any identity already present in the input. Every occurrence of
+/// `()` or `-->` is a separate element, so two anonymous endpoints or
+/// relationships never share an identity. Patterns with a non-empty identity
+/// are left unchanged.
+pub fn assign_identities<V>(mut patterns: Vec<Pattern<V>>) -> Vec<Pattern<V>>
+where
+    V: GraphValue<Id = Symbol>,
 {
-    patterns.into_iter().fold(PatternGraph::empty(), |g, p| {
-        merge_with_policy(classifier, policy, p, g)
-    })
+    let mut ids = AnonymousIds::default();
+    for p in &patterns {
+        ids.reserve(p);
+    }
+    for p in &mut patterns {
+        ids.assign(p);
+    }
+    patterns
+}
+
+/// Supply of fresh identities for anonymous nodes.
+#[derive(Default)]
+struct AnonymousIds {
+    used: HashSet<Symbol>,
+    next: usize,
+}
+
+impl AnonymousIds {
+    /// Records every identity in `p` so it is never generated.
+    fn reserve<V: GraphValue<Id = Symbol>>(&mut self, p: &Pattern<V>) {
+        self.used.insert(p.value.identify().clone());
+        for e in &p.elements {
+            self.reserve(e);
+        }
+    }
+
+    /// Assigns a fresh identity to each anonymous pattern in `p`, in pre-order.
+    fn assign<V: GraphValue<Id = Symbol>>(&mut self, p: &mut Pattern<V>) {
+        if p.value.identify().0.is_empty() {
+            let id = self.fresh();
+            p.value.assign_identity(id);
+        }
+        for e in &mut p.elements {
+            self.assign(e);
+        }
+    }
+
+    fn fresh(&mut self) -> Symbol {
+        loop {
+            self.next += 1;
+            let id = Symbol(format!("{}{}", ANONYMOUS_ID_PREFIX, self.next));
+            if self.used.insert(id.clone()) {
+                return id;
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// PatternGraphBuilder
+// -----------------------------------------------------------------------------
+
+/// Incremental [`PatternGraph`] construction, one pattern at a time.
+///
+/// Each [`push`](Self::push) merges a pattern with the builder's classifier and
+/// policy, exactly as [`from_patterns_with_policy`] does, so pushing a sequence
+/// of patterns produces the same graph as building from the whole sequence at
+/// once. Useful when patterns arrive as a stream.
+///
+/// Anonymous patterns get identities as in [`assign_identities`], but only
+/// identities pushed so far can be avoided: if a later pattern uses a generated
+/// identity such as `_anon1`, it merges with that anonymous pattern.
+pub struct PatternGraphBuilder<Extra, V: GraphValue + Mergeable> {
+    classifier: GraphClassifier<Extra, V>,
+    policy: ReconciliationPolicy<V::MergeStrategy>,
+    graph: PatternGraph<Extra, V>,
+    anonymous: AnonymousIds,
+}
+
+impl<Extra, V> PatternGraphBuilder<Extra, V>
+where
+    V: GraphValue<Id = Symbol>
+        + HasIdentity<V, Symbol>
+        + Mergeable
+        + Refinable
+        + PartialEq
+        + Clone
+        + 'static,
+    Extra: Clone + 'static,
+{
+    /// Creates a builder with an empty graph.
+    pub fn new(
+        classifier: GraphClassifier<Extra, V>,
+        policy: ReconciliationPolicy<V::MergeStrategy>,
+    ) -> Self {
+        PatternGraphBuilder {
+            classifier,
+            policy,
+            graph: PatternGraph::empty(),
+            anonymous: AnonymousIds::default(),
+        }
+    }
+
+    /// Merges one pattern into the graph.
+    pub fn push(&mut self, mut p: Pattern<V>) -> &mut Self {
+        self.anonymous.reserve(&p);
+        self.anonymous.assign(&mut p);
+        let graph = std::mem::replace(&mut self.graph, PatternGraph::empty());
+        self.graph = merge_with_policy(&self.classifier, &self.policy, p, graph);
+        self
+    }
+
+    /// The graph built so far.
+    pub fn graph(&self) -> &PatternGraph<Extra, V> {
+        &self.graph
+    }
+
+    /// Finishes building and returns the graph.
+    pub fn build(self) -> PatternGraph<Extra, V> {
+        self.graph
+    }
+}
+
+impl<Extra, V> Extend<Pattern<V>> for PatternGraphBuilder<Extra, V>
+where
+    V: GraphValue<Id = Symbol>
+        + HasIdentity<V, Symbol>
+        + Mergeable
+        + Refinable
+        + PartialEq
+        + Clone
+        + 'static,
+    Extra: Clone + 'static,
+{
+    fn extend<I: IntoIterator<Item = Pattern<V>>>(&mut self, patterns: I) {
+        for p in patterns {
+            self.push(p);
+        }
+    }
 }
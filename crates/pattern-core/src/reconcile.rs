@@ -24,20 +24,58 @@ pub trait Mergeable {
 
     /// Merge two values. `a` is the "accumulated/existing" value, `b` is the "incoming" one.
     fn merge(strategy: &Self::MergeStrategy, a: Self, b: Self) -> Self;
+
+    /// Merge two values with a caller-supplied function, for `ReconciliationPolicy::Custom`.
+    ///
+    /// [`MergeFn`] works on subjects, so only types that can pass through one
+    /// need to override this. The default ignores `f` and keeps `b`, as
+    /// `LastWriteWins` would.
+    fn merge_with(_f: &MergeFn, _a: Self, b: Self) -> Self
+    where
+        Self: Sized,
+    {
+        b
+    }
 }
 
 /// Allows checking if one value is a "partial" version of another.
 pub trait Refinable {
     /// Returns `true` if `sub` contains a subset of the information in `sup`.
     fn is_refinement_of(sup: &Self, sub: &Self) -> bool;
+
+    /// Returns `true` if `v` carries nothing beyond its identity, so a pattern
+    /// holding it with no elements is only a reference, like `(alice)` in
+    /// `(alice)-->(bob)`.
+    ///
+    /// [`PatternGraph`](crate::PatternGraph) never lets such a reference
+    /// override or conflict with another occurrence of its identity. The
+    /// default treats no value as a reference.
+    fn is_reference(_v: &Self) -> bool {
+        false
+    }
 }
 
 // -----------------------------------------------------------------------------
 // Reconciliation Policy
 // -----------------------------------------------------------------------------
 
+/// Caller-supplied merge for `ReconciliationPolicy::Custom`.
+///
+/// Receives the accumulated subject and the incoming one and returns the merged
+/// subject. The result keeps the identity of the accumulated subject.
+///
+/// By default uses `Rc` (single-threaded). Enable the `thread-safe` feature
+/// to use `Arc` with `Send + Sync` bounds.
+#[cfg(not(feature = "thread-safe"))]
+pub type MergeFn = std::rc::Rc<dyn Fn(&Subject, &Subject) -> Subject>;
+
+#[cfg(feature = "thread-safe")]
+pub type MergeFn = std::sync::Arc<dyn Fn(&Subject, &Subject) -> Subject + Send + Sync>;
+
 /// Policy for resolving duplicate identities during reconciliation.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Two `Custom` policies are equal only when they share the same function.
+#[derive(Clone)]
 pub enum ReconciliationPolicy<S> {
     /// Keep the last occurrence of each identity.
     LastWriteWins,
@@ -47,8 +85,48 @@ pub enum ReconciliationPolicy<S> {
     Merge(ElementMergeStrategy, S),
     /// Fail if any duplicate identities have different content.
     Strict,
+    /// Fold all occurrences through a caller-supplied function, in order.
+    ///
+    /// Elements are combined as with `UnionElements`.
+    Custom(MergeFn),
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for ReconciliationPolicy<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReconciliationPolicy::LastWriteWins => f.write_str("LastWriteWins"),
+            ReconciliationPolicy::FirstWriteWins => f.write_str("FirstWriteWins"),
+            ReconciliationPolicy::Merge(elements, strategy) => f
+                .debug_tuple("Merge")
+                .field(elements)
+                .field(strategy)
+                .finish(),
+            ReconciliationPolicy::Strict => f.write_str("Strict"),
+            ReconciliationPolicy::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
 }
 
+impl<S: PartialEq> PartialEq for ReconciliationPolicy<S> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ReconciliationPolicy::LastWriteWins, ReconciliationPolicy::LastWriteWins)
+            | (ReconciliationPolicy::FirstWriteWins, ReconciliationPolicy::FirstWriteWins)
+            | (ReconciliationPolicy::Strict, ReconciliationPolicy::Strict) => true,
+            (ReconciliationPolicy::Merge(e1, s1), ReconciliationPolicy::Merge(e2, s2)) => {
+                e1 == e2 && s1 == s2
+            }
+            (ReconciliationPolicy::Custom(f1), ReconciliationPolicy::Custom(f2)) => std::ptr::eq(
+                &**f1 as *const _ as *const (),
+                &**f2 as *const _ as *const (),
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl<S: Eq> Eq for ReconciliationPolicy<S> {}
+
 /// Strategy for merging the children (elements) of a pattern.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ElementMergeStrategy {
@@ -95,6 +173,79 @@ pub fn default_subject_merge_strategy() -> SubjectMergeStrategy {
     }
 }
 
+impl ReconciliationPolicy<SubjectMergeStrategy> {
+    /// Starts a [`MergePolicyBuilder`] for a Subject `Merge` policy.
+    ///
+    /// Unset fields keep the same defaults as the WASM `merge_policy`:
+    /// `UnionElements`, `UnionLabels`, and `ShallowMerge`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pattern_core::{
+    ///     ElementMergeStrategy, LabelMerge, PropertyMerge, ReconciliationPolicy,
+    ///     SubjectMergeStrategy,
+    /// };
+    ///
+    /// let policy = ReconciliationPolicy::merge_builder()
+    ///     .labels(LabelMerge::UnionLabels)
+    ///     .properties(PropertyMerge::DeepMerge)
+    ///     .elements(ElementMergeStrategy::UnionElements)
+    ///     .build();
+    /// assert_eq!(
+    ///     policy,
+    ///     ReconciliationPolicy::Merge(
+    ///         ElementMergeStrategy::UnionElements,
+    ///         SubjectMergeStrategy {
+    ///             label_merge: LabelMerge::UnionLabels,
+    ///             property_merge: PropertyMerge::DeepMerge,
+    ///         },
+    ///     )
+    /// );
+    /// ```
+    pub fn merge_builder() -> MergePolicyBuilder {
+        MergePolicyBuilder {
+            elements: ElementMergeStrategy::UnionElements,
+            strategy: default_subject_merge_strategy(),
+        }
+    }
+}
+
+/// Fluent builder for a Subject `ReconciliationPolicy::Merge`.
+///
+/// Created via `ReconciliationPolicy::merge_builder()`. Chain `.labels()`,
+/// `.properties()`, and `.elements()` calls, then finalize with `.build()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergePolicyBuilder {
+    elements: ElementMergeStrategy,
+    strategy: SubjectMergeStrategy,
+}
+
+impl MergePolicyBuilder {
+    /// Sets how label sets are merged.
+    pub fn labels(mut self, label_merge: LabelMerge) -> Self {
+        self.strategy.label_merge = label_merge;
+        self
+    }
+
+    /// Sets how property maps are merged.
+    pub fn properties(mut self, property_merge: PropertyMerge) -> Self {
+        self.strategy.property_merge = property_merge;
+        self
+    }
+
+    /// Sets how element lists are merged.
+    pub fn elements(mut self, elements: ElementMergeStrategy) -> Self {
+        self.elements = elements;
+        self
+    }
+
+    /// Finalizes the builder and returns the `Merge` policy.
+    pub fn build(self) -> ReconciliationPolicy<SubjectMergeStrategy> {
+        ReconciliationPolicy::Merge(self.elements, self.strategy)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Subject trait implementations
 // -----------------------------------------------------------------------------
@@ -117,6 +268,13 @@ impl Mergeable for Subject {
             properties: merged_props,
         }
     }
+
+    fn merge_with(f: &MergeFn, a: Subject, b: Subject) -> Subject {
+        Subject {
+            identity: a.identity.clone(),
+            ..f(&a, &b)
+        }
+    }
 }
 
 impl Refinable for Subject {
@@ -128,6 +286,10 @@ impl Refinable for Subject {
                 .iter()
                 .all(|(k, v)| sup.properties.get(k) == Some(v))
     }
+
+    fn is_reference(v: &Subject) -> bool {
+        v.labels.is_empty() && v.properties.is_empty()
+    }
 }
 
 fn merge_labels(
@@ -174,7 +336,7 @@ pub struct ReconcileError {
 
 /// Normalizes a pattern by resolving duplicate identities according to the policy.
 ///
-/// - `LastWriteWins`, `FirstWriteWins`, `Merge` always return `Ok`.
+/// - `LastWriteWins`, `FirstWriteWins`, `Merge`, `Custom` always return `Ok`.
 /// - `Strict` returns `Err` if any duplicate identity has different content.
 pub fn reconcile<V>(
     policy: &ReconciliationPolicy<V::MergeStrategy>,
@@ -247,6 +409,19 @@ where
                 elements: merged_elements,
             }
         }
+        ReconciliationPolicy::Custom(f) => {
+            let merged_val = occurrences
+                .iter()
+                .skip(1)
+                .fold(occurrences[0].value.clone(), |acc, p| {
+                    V::merge_with(f, acc, p.value.clone())
+                });
+            let all_elements = merge_elements_union(occurrences.iter().map(|p| &p.elements));
+            Pattern {
+                value: merged_val,
+                elements: all_elements,
+            }
+        }
         ReconciliationPolicy::Strict => unreachable!("Strict handled separately"),
     }
 }
@@ -271,12 +446,13 @@ where
         ElementMergeStrategy::ReplaceElements => all.into_iter().last().unwrap_or_default(),
         ElementMergeStrategy::AppendElements => all.into_iter().flatten().collect(),
         ElementMergeStrategy::UnionElements => {
-            let mut seen: HashMap<Symbol, Pattern<V>> = HashMap::new();
-            for elem in all.into_iter().flatten() {
-                let id = V::identity(&elem.value).clone();
-                seen.entry(id).or_insert(elem);
-            }
-            seen.into_values().collect()
+            // Keep first-occurrence order: a relationship's elements are its
+            // source and target, so reordering them would flip its direction.
+            let mut seen: HashSet<Symbol> = HashSet::new();
+            all.into_iter()
+                .flatten()
+                .filter(|elem| seen.insert(V::identity(&elem.value).clone()))
+                .collect()
         }
     }
 }
@@ -3,7 +3,9 @@
 //! This module provides the Subject type and related types (Symbol, Value, RangeValue, PropertyRecord)
 //! for use as pattern values in `Pattern<Subject>`.
 
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// Symbol identifier that uniquely identifies the subject.
 ///
@@ -16,11 +18,35 @@ use std::fmt;
 /// use pattern_core::Symbol;
 ///
 /// let symbol = Symbol("n".to_string());
-/// assert_eq!(symbol.0, "n");
+/// assert_eq!(symbol.as_str(), "n");
+/// ```
+///
+/// # Equality, hashing, and ordering
+///
+/// `Symbol` is the identity type (`V::Id`) of `Subject` throughout the graph
+/// layer, where it is used as a `HashMap`/`HashSet` key and sorted to make
+/// output deterministic. `Eq`, `Hash`, and `Ord` are derived from the wrapped
+/// string, so symbols compare and order exactly as their text does
+/// (lexicographically by byte). Graph code relies on these impls; keep them
+/// consistent with one another if they are ever written by hand.
+///
+/// ```rust
+/// use pattern_core::Symbol;
+///
+/// let mut ids = vec![Symbol::from("b"), Symbol::from("B"), Symbol::from("a")];
+/// ids.sort();
+/// assert_eq!(ids, [Symbol::from("B"), Symbol::from("a"), Symbol::from("b")]);
 /// ```
 #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Symbol(pub String);
 
+impl Symbol {
+    /// The identifier text.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 impl fmt::Debug for Symbol {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Symbol").field(&self.0).finish()
@@ -49,8 +75,8 @@ impl From<String> for Symbol {
 ///
 /// Used in `Value::VRange` to represent numeric ranges with optional bounds.
 ///
-/// Note: This type only implements `PartialEq`, not `Eq`, because `f64` doesn't implement `Eq`
-/// (due to NaN != NaN).
+/// Bounds compare and hash like decimals in [`Value`]; see
+/// [Equality and hashing](Value#equality-and-hashing).
 ///
 /// # Examples
 ///
@@ -75,7 +101,7 @@ impl From<String> for Symbol {
 ///     upper: Some(10.0),
 /// };
 /// ```
-#[derive(Clone, PartialEq)]
+#[derive(Clone)]
 pub struct RangeValue {
     /// Lower bound of the range (inclusive), `None` means unbounded below
     pub lower: Option<f64>,
@@ -103,14 +129,84 @@ impl fmt::Display for RangeValue {
     }
 }
 
+impl RangeValue {
+    /// Returns `true` if `x` lies within the range.
+    ///
+    /// Both bounds are inclusive and a missing bound is unbounded on that
+    /// side, so `0.0..` contains `0.0` and every larger number. NaN is never
+    /// contained.
+    pub fn contains(&self, x: f64) -> bool {
+        !x.is_nan()
+            && !matches!(self.lower, Some(lower) if x < lower)
+            && !matches!(self.upper, Some(upper) if x > upper)
+    }
+
+    /// Returns `true` if some number lies in both ranges.
+    ///
+    /// Ranges that only touch at a shared bound overlap, since bounds are
+    /// inclusive.
+    pub fn overlaps(&self, other: &RangeValue) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the numbers in both ranges, or `None` if there are none.
+    ///
+    /// The result keeps the tighter bound on each side and is unbounded only
+    /// where both ranges are.
+    pub fn intersection(&self, other: &RangeValue) -> Option<RangeValue> {
+        let tighter = |a: Option<f64>, b: Option<f64>, pick: fn(f64, f64) -> f64| match (a, b) {
+            (Some(a), Some(b)) => Some(pick(a, b)),
+            (a, b) => a.or(b),
+        };
+        let lower = tighter(self.lower, other.lower, f64::max);
+        let upper = tighter(self.upper, other.upper, f64::min);
+        if let (Some(l), Some(u)) = (lower, upper) {
+            if l > u {
+                return None;
+            }
+        }
+        Some(RangeValue { lower, upper })
+    }
+}
+
 /// Property value types for Subject properties.
 ///
 /// `Value` is an enum that represents rich value types that can be stored in Subject properties.
 /// It supports standard types (integers, decimals, booleans, strings, symbols) and extended types
-/// (tagged strings, arrays, maps, ranges, measurements).
+/// (tagged strings, arrays, maps, ranges, measurements), and an explicit null.
 ///
-/// Note: This type only implements `PartialEq`, not `Eq`, because it contains `RangeValue`
-/// which uses `f64` (`f64` doesn't implement `Eq` due to NaN != NaN).
+/// # Equality and hashing
+///
+/// `Value` implements `Eq` and `Hash`, so it can be a `HashMap` or `HashSet`
+/// key. Arrays compare element by element and maps compare as maps, ignoring
+/// entry order; both hash structurally. Variants never equal one another:
+/// `VInteger(1)` is not `VDecimal(1.0)`, and `VString("x")` is not
+/// `VSymbol("x")`.
+///
+/// **Floats do not follow IEEE 754 equality.** Decimals, measurement values,
+/// and range bounds compare with `==`, except that every NaN equals every
+/// other NaN; this keeps `Eq` reflexive. `0.0` and `-0.0` are equal. Hashes use
+/// the bit pattern after folding `-0.0` into `0.0` and all NaNs into one NaN,
+/// so equal values always hash alike. Values that differ only by rounding
+/// (`0.1 + 0.2` versus `0.3`) are different keys.
+///
+/// # Ordering
+///
+/// `Value` implements `Ord` with a total order consistent with equality, so
+/// mixed values can be sorted and used as `BTreeMap` keys:
+///
+/// - Values of different kinds order by kind, with integers and decimals as
+///   one kind: null < numbers < booleans < strings < symbols < tagged strings
+///   < arrays < maps < ranges < measurements.
+/// - Numbers compare by exact numeric value, so `VInteger(2) < VDecimal(2.5)`.
+///   An integer and a decimal with the same value are not equal; the integer
+///   comes first: `VInteger(3) < VDecimal(3.0) < VInteger(4)`. `0.0` and
+///   `-0.0` are equal, and NaN is greater than every other number.
+/// - Strings, symbols, and tagged strings (by tag, then content) compare
+///   lexicographically by code point. Arrays compare element by element, a
+///   prefix first. Maps compare as their entries sorted by key, entry by
+///   entry. Ranges compare by lower bound, then upper bound, an unbounded end
+///   first. Measurements compare by unit, then value.
 ///
 /// # Examples
 ///
@@ -134,7 +230,7 @@ impl fmt::Display for RangeValue {
 ///     Value::VInteger(2),
 /// ]);
 /// ```
-#[derive(Clone, PartialEq)]
+#[derive(Clone)]
 pub enum Value {
     /// Integer value (i64)
     VInteger(i64),
@@ -166,6 +262,8 @@ pub enum Value {
         /// The numeric value
         value: f64,
     },
+    /// Explicit null: a property that is present but has no value
+    VNull,
 }
 
 impl fmt::Debug for Value {
@@ -189,6 +287,7 @@ impl fmt::Debug for Value {
                 .field("unit", unit)
                 .field("value", value)
                 .finish(),
+            Value::VNull => f.write_str("VNull"),
         }
     }
 }
@@ -226,10 +325,321 @@ impl fmt::Display for Value {
             }
             Value::VRange(r) => write!(f, "{}", r),
             Value::VMeasurement { unit, value } => write!(f, "{}{}", value, unit),
+            Value::VNull => write!(f, "null"),
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::VInteger(a), Value::VInteger(b)) => a == b,
+            (Value::VDecimal(a), Value::VDecimal(b)) => f64_eq(*a, *b),
+            (Value::VBoolean(a), Value::VBoolean(b)) => a == b,
+            (Value::VString(a), Value::VString(b)) => a == b,
+            (Value::VSymbol(a), Value::VSymbol(b)) => a == b,
+            (
+                Value::VTaggedString { tag, content },
+                Value::VTaggedString {
+                    tag: other_tag,
+                    content: other_content,
+                },
+            ) => tag == other_tag && content == other_content,
+            (Value::VArray(a), Value::VArray(b)) => a == b,
+            (Value::VMap(a), Value::VMap(b)) => a == b,
+            (Value::VRange(a), Value::VRange(b)) => a == b,
+            (
+                Value::VMeasurement { unit, value },
+                Value::VMeasurement {
+                    unit: other_unit,
+                    value: other_value,
+                },
+            ) => unit == other_unit && f64_eq(*value, *other_value),
+            (Value::VNull, Value::VNull) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::VInteger(i) => i.hash(state),
+            Value::VDecimal(d) => hash_f64(*d, state),
+            Value::VBoolean(b) => b.hash(state),
+            Value::VString(s) | Value::VSymbol(s) => s.hash(state),
+            Value::VTaggedString { tag, content } => {
+                tag.hash(state);
+                content.hash(state);
+            }
+            Value::VArray(items) => items.hash(state),
+            Value::VMap(map) => {
+                // Entry order is unspecified, so combine entry hashes commutatively
+                let combined = map.iter().fold(0u64, |acc, entry| {
+                    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                    entry.hash(&mut hasher);
+                    acc.wrapping_add(hasher.finish())
+                });
+                map.len().hash(state);
+                combined.hash(state);
+            }
+            Value::VRange(r) => r.hash(state),
+            Value::VMeasurement { unit, value } => {
+                unit.hash(state);
+                hash_f64(*value, state);
+            }
+            Value::VNull => {}
+        }
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::VInteger(a), Value::VInteger(b)) => a.cmp(b),
+            (Value::VDecimal(a), Value::VDecimal(b)) => f64_cmp(*a, *b),
+            (Value::VInteger(a), Value::VDecimal(b)) => int_decimal_cmp(*a, *b),
+            (Value::VDecimal(a), Value::VInteger(b)) => int_decimal_cmp(*b, *a).reverse(),
+            (Value::VBoolean(a), Value::VBoolean(b)) => a.cmp(b),
+            (Value::VNull, Value::VNull) => Ordering::Equal,
+            (Value::VString(a), Value::VString(b)) => a.cmp(b),
+            (Value::VSymbol(a), Value::VSymbol(b)) => a.cmp(b),
+            (
+                Value::VTaggedString { tag, content },
+                Value::VTaggedString {
+                    tag: other_tag,
+                    content: other_content,
+                },
+            ) => (tag, content).cmp(&(other_tag, other_content)),
+            (Value::VArray(a), Value::VArray(b)) => a.cmp(b),
+            (Value::VMap(a), Value::VMap(b)) => {
+                let mut a: Vec<_> = a.iter().collect();
+                let mut b: Vec<_> = b.iter().collect();
+                a.sort_unstable_by_key(|(k, _)| *k);
+                b.sort_unstable_by_key(|(k, _)| *k);
+                a.cmp(&b)
+            }
+            (Value::VRange(a), Value::VRange(b)) => a.cmp(b),
+            (
+                Value::VMeasurement { unit, value },
+                Value::VMeasurement {
+                    unit: other_unit,
+                    value: other_value,
+                },
+            ) => unit
+                .cmp(other_unit)
+                .then_with(|| f64_cmp(*value, *other_value)),
+            _ => self.kind_rank().cmp(&other.kind_rank()),
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Value {
+    /// Position of this value's kind in the [`Ord`] order; numbers share one
+    fn kind_rank(&self) -> u8 {
+        match self {
+            Value::VNull => 0,
+            Value::VInteger(_) | Value::VDecimal(_) => 1,
+            Value::VBoolean(_) => 2,
+            Value::VString(_) => 3,
+            Value::VSymbol(_) => 4,
+            Value::VTaggedString { .. } => 5,
+            Value::VArray(_) => 6,
+            Value::VMap(_) => 7,
+            Value::VRange(_) => 8,
+            Value::VMeasurement { .. } => 9,
         }
     }
 }
 
+impl PartialEq for RangeValue {
+    fn eq(&self, other: &Self) -> bool {
+        let bound_eq = |a: Option<f64>, b: Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => f64_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        bound_eq(self.lower, other.lower) && bound_eq(self.upper, other.upper)
+    }
+}
+
+impl Eq for RangeValue {}
+
+impl Ord for RangeValue {
+    /// Lower bound first, then upper bound; an unbounded end sorts first
+    fn cmp(&self, other: &Self) -> Ordering {
+        let bound_cmp = |a: Option<f64>, b: Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => f64_cmp(a, b),
+            (a, b) => a.is_some().cmp(&b.is_some()),
+        };
+        bound_cmp(self.lower, other.lower).then_with(|| bound_cmp(self.upper, other.upper))
+    }
+}
+
+impl PartialOrd for RangeValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for RangeValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for bound in [self.lower, self.upper] {
+            bound.is_some().hash(state);
+            if let Some(b) = bound {
+                hash_f64(b, state);
+            }
+        }
+    }
+}
+
+/// Float equality for `Value`: IEEE `==`, except that NaN equals NaN.
+fn f64_eq(a: f64, b: f64) -> bool {
+    a == b || (a.is_nan() && b.is_nan())
+}
+
+/// Float ordering consistent with [`f64_eq`]: numeric, with NaN above
+/// everything else.
+fn f64_cmp(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+/// Exact numeric comparison of an integer and a decimal; the integer comes
+/// first when they are equal, so that `Ord` agrees with `Eq`.
+fn int_decimal_cmp(a: i64, b: f64) -> Ordering {
+    if b.is_nan() {
+        return Ordering::Less;
+    }
+    match (a as f64).partial_cmp(&b) {
+        // `a as f64` may round; a decimal equal to it is integral and within
+        // ±2^63, so it converts to i128 exactly
+        Some(Ordering::Equal) | None => (a as i128).cmp(&(b as i128)).then(Ordering::Less),
+        Some(ordering) => ordering,
+    }
+}
+
+/// Float hashing consistent with [`f64_eq`]: `-0.0` hashes as `0.0`, and every
+/// NaN hashes as the canonical NaN.
+fn hash_f64<H: Hasher>(x: f64, state: &mut H) {
+    let x = if x == 0.0 {
+        0.0
+    } else if x.is_nan() {
+        f64::NAN
+    } else {
+        x
+    };
+    x.to_bits().hash(state);
+}
+
+/// Error returned by measurement arithmetic on [`Value`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeasurementError {
+    /// An operand is not a [`Value::VMeasurement`]
+    NotAMeasurement(Value),
+    /// The operands have different units
+    UnitMismatch {
+        /// Unit of the left operand
+        left: String,
+        /// Unit of the right operand
+        right: String,
+    },
+}
+
+impl fmt::Display for MeasurementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeasurementError::NotAMeasurement(v) => write!(f, "not a measurement: {}", v),
+            MeasurementError::UnitMismatch { left, right } => {
+                write!(f, "unit mismatch: {} vs {}", left, right)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MeasurementError {}
+
+impl Value {
+    /// Unit and amount of a measurement, or an error for any other value.
+    fn measurement(&self) -> Result<(&str, f64), MeasurementError> {
+        match self {
+            Value::VMeasurement { unit, value } => Ok((unit, *value)),
+            other => Err(MeasurementError::NotAMeasurement(other.clone())),
+        }
+    }
+
+    /// Combines two measurements of the same unit with `op`.
+    fn measurement_op(
+        &self,
+        other: &Value,
+        op: fn(f64, f64) -> f64,
+    ) -> Result<Value, MeasurementError> {
+        let (unit, a) = self.measurement()?;
+        let (other_unit, b) = other.measurement()?;
+        if unit != other_unit {
+            return Err(MeasurementError::UnitMismatch {
+                left: unit.to_string(),
+                right: other_unit.to_string(),
+            });
+        }
+        Ok(Value::VMeasurement {
+            unit: unit.to_string(),
+            value: op(a, b),
+        })
+    }
+
+    /// Sum of two measurements with the same unit.
+    ///
+    /// Units are compared exactly, so `kg` and `g` do not mix; use
+    /// [`convert`](Value::convert) first.
+    ///
+    /// ```rust
+    /// use pattern_core::{MeasurementError, Value};
+    ///
+    /// let kg = |value| Value::VMeasurement { unit: "kg".to_string(), value };
+    /// assert_eq!(kg(5.0).add(&kg(3.0)), Ok(kg(8.0)));
+    ///
+    /// let m = Value::VMeasurement { unit: "m".to_string(), value: 3.0 };
+    /// assert!(matches!(kg(5.0).add(&m), Err(MeasurementError::UnitMismatch { .. })));
+    /// ```
+    pub fn add(&self, other: &Value) -> Result<Value, MeasurementError> {
+        self.measurement_op(other, |a, b| a + b)
+    }
+
+    /// Difference of two measurements with the same unit.
+    pub fn sub(&self, other: &Value) -> Result<Value, MeasurementError> {
+        self.measurement_op(other, |a, b| a - b)
+    }
+
+    /// A measurement multiplied by `factor`, in the same unit.
+    pub fn scale(&self, factor: f64) -> Result<Value, MeasurementError> {
+        let (unit, value) = self.measurement()?;
+        Ok(Value::VMeasurement {
+            unit: unit.to_string(),
+            value: value * factor,
+        })
+    }
+
+    /// A measurement expressed in `to_unit`, where one unit of the original
+    /// equals `factor` of `to_unit` (e.g. `1000.0` for `kg` to `g`).
+    pub fn convert(&self, to_unit: &str, factor: f64) -> Result<Value, MeasurementError> {
+        let (_, value) = self.measurement()?;
+        Ok(Value::VMeasurement {
+            unit: to_unit.to_string(),
+            value: value * factor,
+        })
+    }
+}
+
 /// Property record type alias.
 ///
 /// A `PropertyRecord` is a map from string keys to `Value` types, storing
@@ -247,6 +657,105 @@ impl fmt::Display for Value {
 /// ```
 pub type PropertyRecord = std::collections::HashMap<String, Value>;
 
+/// Property key recording the [`Direction`] of a relationship's subject.
+///
+/// Holds the symbol `backward` or `undirected`, and is absent for forward
+/// relationships. Use [`Subject::direction`] and [`Subject::set_direction`]
+/// rather than reading it directly; the gram serializer writes it as the
+/// arrow of the relationship instead of as a property, and gram notation
+/// rejects it in a relationship's record, where the arrow would replace it.
+pub const DIRECTION_PROPERTY: &str = "_direction";
+
+/// Property key recording the [`Quantifier`] of a variable-length
+/// relationship's subject.
+///
+/// Holds a [`Value::VRange`] of whole hop counts. Use [`Subject::quantifier`]
+/// and [`Subject::set_quantifier`] rather than reading it directly; the gram
+/// serializer writes it inside the relationship bracket, as in `-[*1..3]->`,
+/// and gram notation rejects it in a relationship's record.
+pub const QUANTIFIER_PROPERTY: &str = "_quantifier";
+
+/// Direction of the arrow a relationship was written with.
+///
+/// Relationship patterns store their elements source-first whatever arrow was
+/// written, so `(b)<--(a)` and `(a)-->(b)` have the same elements. The
+/// direction of the relationship's subject, kept under
+/// [`DIRECTION_PROPERTY`], tells the two apart.
+///
+/// # Examples
+///
+/// ```rust
+/// use pattern_core::{Direction, Subject};
+///
+/// let mut owns = Subject::from_id("r");
+/// assert_eq!(owns.direction(), Direction::Forward);
+/// owns.set_direction(Direction::Backward);
+/// assert_ne!(owns, Subject::from_id("r"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Direction {
+    /// `-->`, `-[r]->`, and bidirectional arrows such as `<-->`
+    #[default]
+    Forward,
+    /// `<--`, `<-[r]-`; the elements are still stored source-first
+    Backward,
+    /// `--`, `-[r]-`
+    Undirected,
+}
+
+/// Hop-count bounds of a variable-length relationship.
+///
+/// Written inside the relationship bracket after the labels, as in graph query
+/// languages: `-[:KNOWS*1..3]->` matches paths of one to three `KNOWS`
+/// relationships. A missing bound is unbounded: `*` has neither, `*2..` has
+/// only `min`, and `*..4` has only `max`. A single count `*3` has `min` and
+/// `max` both 3.
+///
+/// # Examples
+///
+/// ```rust
+/// use pattern_core::{Quantifier, RangeValue};
+///
+/// let q = Quantifier { min: Some(1), max: Some(3) };
+/// assert_eq!(q.to_string(), "*1..3");
+/// assert_eq!(RangeValue::from(q), RangeValue { lower: Some(1.0), upper: Some(3.0) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Quantifier {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+}
+
+impl From<Quantifier> for RangeValue {
+    fn from(quantifier: Quantifier) -> RangeValue {
+        RangeValue {
+            lower: quantifier.min.map(|n| n as f64),
+            upper: quantifier.max.map(|n| n as f64),
+        }
+    }
+}
+
+impl fmt::Display for Quantifier {
+    /// Gram notation for the quantifier: `*`, `*3`, `*1..5`, `*2..`, or `*..4`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (None, None) => write!(f, "*"),
+            (Some(min), Some(max)) if min == max => write!(f, "*{}", min),
+            (min, max) => {
+                write!(f, "*")?;
+                if let Some(min) = min {
+                    write!(f, "{}", min)?;
+                }
+                write!(f, "..")?;
+                if let Some(max) = max {
+                    write!(f, "{}", max)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Self-descriptive object with identity, labels, and properties.
 ///
 /// `Subject` is designed to be the primary content type for patterns
@@ -366,6 +875,172 @@ impl fmt::Display for Subject {
 }
 
 impl Subject {
+    /// Returns true if this subject carries `label`, compared exactly.
+    ///
+    /// ```rust
+    /// use pattern_core::Subject;
+    ///
+    /// let alice = Subject::build("alice").label("Person").done();
+    /// assert!(alice.has_label("Person"));
+    /// assert!(!alice.has_label("person"));
+    /// ```
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels.contains(label)
+    }
+
+    /// The string property `key`, or `None` if it is absent or not a
+    /// [`Value::VString`].
+    ///
+    /// Symbols are not strings here; read them from `properties` directly.
+    ///
+    /// ```rust
+    /// use pattern_core::Subject;
+    ///
+    /// let alice = Subject::build("alice").property("name", "Alice").done();
+    /// assert_eq!(alice.get_str("name"), Some("Alice"));
+    /// assert_eq!(alice.get_str("age"), None);
+    /// ```
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.properties.get(key)? {
+            Value::VString(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// The integer property `key`, or `None` if it is absent or not a
+    /// [`Value::VInteger`].
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        match self.properties.get(key)? {
+            Value::VInteger(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// The decimal property `key`, or `None` if it is absent or not a
+    /// [`Value::VDecimal`].
+    ///
+    /// Integers are not widened, so `get_float` on `{n: 1}` is `None`; use
+    /// [`get_int`](Subject::get_int) for those.
+    pub fn get_float(&self, key: &str) -> Option<f64> {
+        match self.properties.get(key)? {
+            Value::VDecimal(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    /// The boolean property `key`, or `None` if it is absent or not a
+    /// [`Value::VBoolean`].
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.properties.get(key)? {
+            Value::VBoolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this subject carries `label` in any letter case.
+    ///
+    /// Labels are compared by their lowercase forms, so `Person`, `person`, and
+    /// `PERSON` all match each other. The stored labels are not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pattern_core::Subject;
+    ///
+    /// let alice = Subject::build("alice").label("Person").done();
+    /// assert!(alice.has_label_ignore_case("person"));
+    /// assert!(alice.labels.contains("Person"));
+    /// assert!(!alice.labels.contains("person"));
+    /// ```
+    pub fn has_label_ignore_case(&self, label: &str) -> bool {
+        let label = label.to_lowercase();
+        self.labels.iter().any(|l| l.to_lowercase() == label)
+    }
+
+    /// The arrow direction recorded under [`DIRECTION_PROPERTY`].
+    ///
+    /// `Forward` when the property is absent or holds anything other than
+    /// the symbol `backward` or `undirected`, so subjects that are not
+    /// relationships are always `Forward`.
+    pub fn direction(&self) -> Direction {
+        match self.properties.get(DIRECTION_PROPERTY) {
+            Some(Value::VSymbol(s)) if s == "backward" => Direction::Backward,
+            Some(Value::VSymbol(s)) if s == "undirected" => Direction::Undirected,
+            _ => Direction::Forward,
+        }
+    }
+
+    /// Records `direction` under [`DIRECTION_PROPERTY`], removing the
+    /// property for `Forward`.
+    ///
+    /// Does nothing if [`direction`](Subject::direction) already reads
+    /// `direction`, so a property value that is not a direction is kept.
+    pub fn set_direction(&mut self, direction: Direction) {
+        if self.direction() == direction {
+            return;
+        }
+        let name = match direction {
+            Direction::Forward => {
+                self.properties.remove(DIRECTION_PROPERTY);
+                return;
+            }
+            Direction::Backward => "backward",
+            Direction::Undirected => "undirected",
+        };
+        self.properties.insert(
+            DIRECTION_PROPERTY.to_string(),
+            Value::VSymbol(name.to_string()),
+        );
+    }
+
+    /// The hop-count bounds recorded under [`QUANTIFIER_PROPERTY`].
+    ///
+    /// `None` when the property is absent, or is not a range whose bounds are
+    /// whole, non-negative numbers.
+    ///
+    /// ```rust
+    /// use pattern_core::{Quantifier, Subject};
+    ///
+    /// let mut knows = Subject::from_id("r");
+    /// assert_eq!(knows.quantifier(), None);
+    /// knows.set_quantifier(Some(Quantifier { min: Some(1), max: None }));
+    /// assert_eq!(knows.quantifier(), Some(Quantifier { min: Some(1), max: None }));
+    /// ```
+    pub fn quantifier(&self) -> Option<Quantifier> {
+        let Value::VRange(range) = self.properties.get(QUANTIFIER_PROPERTY)? else {
+            return None;
+        };
+        let bound = |b: Option<f64>| match b {
+            None => Some(None),
+            Some(n) if n >= 0.0 && n.fract() == 0.0 => Some(Some(n as u64)),
+            Some(_) => None,
+        };
+        Some(Quantifier {
+            min: bound(range.lower)?,
+            max: bound(range.upper)?,
+        })
+    }
+
+    /// Records `quantifier` under [`QUANTIFIER_PROPERTY`], removing the
+    /// property for `None`.
+    ///
+    /// Does nothing if [`quantifier`](Subject::quantifier) already reads
+    /// `quantifier`, so a property value that is not a quantifier is kept.
+    pub fn set_quantifier(&mut self, quantifier: Option<Quantifier>) {
+        if self.quantifier() == quantifier {
+            return;
+        }
+        match quantifier {
+            Some(q) => {
+                self.properties
+                    .insert(QUANTIFIER_PROPERTY.to_string(), Value::VRange(q.into()));
+            }
+            None => {
+                self.properties.remove(QUANTIFIER_PROPERTY);
+            }
+        }
+    }
+
     /// Creates an identity-only Subject with no labels or properties.
     ///
     /// Useful as a reference handle when passing to methods that accept `&Subject`
@@ -421,6 +1096,90 @@ impl Subject {
     }
 }
 
+impl crate::Pattern<Subject> {
+    /// A node: an atomic pattern holding `subject`.
+    ///
+    /// ```rust
+    /// use pattern_core::{Pattern, Subject};
+    ///
+    /// let alice = Pattern::node(Subject::build("alice").label("Person").done());
+    /// assert!(alice.is_atomic());
+    /// ```
+    pub fn node(subject: impl Into<Subject>) -> crate::Pattern<Subject> {
+        crate::Pattern::point(subject.into())
+    }
+
+    /// A relationship `(source)-[subject]->(target)`: a pattern holding
+    /// `subject` with the two endpoint nodes as its elements, in the shape
+    /// gram notation parses and the graph classifiers recognize.
+    ///
+    /// ```rust
+    /// use pattern_core::{Pattern, Subject};
+    ///
+    /// let knows = Pattern::relationship(
+    ///     Subject::build("r1").label("KNOWS"),
+    ///     Subject::from_id("alice"),
+    ///     Subject::from_id("bob"),
+    /// );
+    /// assert_eq!(knows.elements[0].value.identity.0, "alice");
+    /// assert_eq!(knows.elements[1].value.identity.0, "bob");
+    /// ```
+    pub fn relationship(
+        subject: impl Into<Subject>,
+        source: impl Into<Subject>,
+        target: impl Into<Subject>,
+    ) -> crate::Pattern<Subject> {
+        crate::Pattern::pattern(subject.into(), vec![Self::node(source), Self::node(target)])
+    }
+
+    /// Transforms every Subject in the pattern, keeping its structure.
+    ///
+    /// A type-preserving counterpart to [`map`](crate::Pattern::map): `f` sees
+    /// each Subject by reference and returns its replacement, and the pattern
+    /// is borrowed rather than consumed. Identities are whatever `f` returns,
+    /// so a function that only touches labels or properties keeps them intact.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pattern_core::{Pattern, Subject};
+    ///
+    /// let pattern = Pattern::pattern(
+    ///     Subject::build("r").label("knows").done(),
+    ///     vec![
+    ///         Pattern::point(Subject::build("a").label("person").done()),
+    ///         Pattern::point(Subject::build("b").label("person").done()),
+    ///     ],
+    /// );
+    /// let upper = pattern.map_subjects(|s| Subject {
+    ///     labels: s.labels.iter().map(|l| l.to_uppercase()).collect(),
+    ///     ..s.clone()
+    /// });
+    /// assert!(upper.value.labels.contains("KNOWS"));
+    /// assert_eq!(upper.elements[1].value.identity.0, "b");
+    /// ```
+    pub fn map_subjects<F>(&self, f: F) -> crate::Pattern<Subject>
+    where
+        F: Fn(&Subject) -> Subject,
+    {
+        self.map_subjects_with(&f)
+    }
+
+    fn map_subjects_with<F>(&self, f: &F) -> crate::Pattern<Subject>
+    where
+        F: Fn(&Subject) -> Subject,
+    {
+        crate::Pattern {
+            value: f(&self.value),
+            elements: self
+                .elements
+                .iter()
+                .map(|elem| elem.map_subjects_with(f))
+                .collect(),
+        }
+    }
+}
+
 /// Fluent builder for constructing Subject values.
 ///
 /// Created via `Subject::build(identity)`. Chain `.label()` and `.property()`
@@ -432,6 +1191,29 @@ pub struct SubjectBuilder {
 }
 
 impl SubjectBuilder {
+    /// Creates a builder for an anonymous subject; set the identity with
+    /// [`identity`](SubjectBuilder::identity) or start from [`Subject::build`].
+    ///
+    /// ```rust
+    /// use pattern_core::SubjectBuilder;
+    ///
+    /// let alice = SubjectBuilder::new()
+    ///     .identity("alice")
+    ///     .label("Person")
+    ///     .property("name", "Alice")
+    ///     .build();
+    /// assert_eq!(alice.identity.0, "alice");
+    /// ```
+    pub fn new() -> Self {
+        Subject::build("")
+    }
+
+    /// Sets the identity of the subject being built, replacing any earlier one.
+    pub fn identity(mut self, identity: impl Into<String>) -> Self {
+        self.identity = Symbol(identity.into());
+        self
+    }
+
     /// Adds a label to the subject being built.
     pub fn label(mut self, label: impl Into<String>) -> Self {
         self.labels.insert(label.into());
@@ -452,6 +1234,17 @@ impl SubjectBuilder {
             properties: self.properties,
         }
     }
+
+    /// Finalizes the builder; the same as [`done`](SubjectBuilder::done).
+    pub fn build(self) -> Subject {
+        self.done()
+    }
+}
+
+impl Default for SubjectBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl From<SubjectBuilder> for Subject {
@@ -11,6 +11,10 @@
 //!
 //! See [gramref CLI Testing Guide](../../../../docs/gramref-cli-testing-guide.md) for
 //! comprehensive usage examples and integration patterns.
+//!
+//! To check gram text against a reference S-expression, either a compact
+//! gram-hs description or a `(gram_pattern ...)` tree from the shared corpus,
+//! use `gram_codec::check_equivalence`, which reports the first divergence.
 
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
@@ -196,6 +196,26 @@ impl WasmStructureAnalysis {
         arr
     }
 
+    /// Get the largest number of elements of any one node.
+    #[wasm_bindgen(getter, js_name = maxBranching)]
+    pub fn max_branching(&self) -> usize {
+        self.inner.max_branching
+    }
+
+    /// Get the mean number of elements of the nodes that have elements.
+    ///
+    /// # Returns
+    /// The average branching factor, or 0 if no node has elements
+    #[wasm_bindgen(getter, js_name = avgBranching)]
+    pub fn avg_branching(&self) -> f64 {
+        self.inner.avg_branching
+    }
+
+    /// Get the number of nodes without elements.
+    #[wasm_bindgen(getter, js_name = leafCount)]
+    pub fn leaf_count(&self) -> usize {
+        self.inner.leaf_count
+    }
     /// Get a human-readable summary of the structure.
     ///
     /// # Returns
@@ -382,8 +402,11 @@ pub fn js_object_to_value_map(obj: &JsValue) -> Result<HashMap<String, Value>, S
 /// with specific shapes.
 pub fn js_to_value(js: &JsValue) -> Result<Value, String> {
     // Check for null/undefined first
-    if js.is_null() || js.is_undefined() {
-        return Err("Cannot convert null/undefined to Value".to_string());
+    if js.is_null() {
+        return Ok(Value::VNull);
+    }
+    if js.is_undefined() {
+        return Err("Cannot convert undefined to Value".to_string());
     }
 
     // Check for boolean
@@ -499,6 +522,7 @@ pub fn value_to_js(value: &Value) -> JsValue {
         Value::VInteger(i) => JsValue::from_f64(*i as f64),
         Value::VDecimal(d) => JsValue::from_f64(*d),
         Value::VBoolean(b) => JsValue::from_bool(*b),
+        Value::VNull => JsValue::NULL,
         Value::VSymbol(s) => {
             // Return as { _type: 'symbol', value: string }
             let obj = js_sys::Object::new();
@@ -843,86 +867,63 @@ impl WasmSubject {
     }
 }
 
+thread_local! {
+    /// Prototype shared by every `WasmSubject` instance handed to JavaScript.
+    ///
+    /// Captured once from a probe instance so [`WasmSubject::is_instance`] can
+    /// compare prototypes instead of probing field names.
+    static WASM_SUBJECT_PROTOTYPE: JsValue = {
+        let probe = JsValue::from(WasmSubject::from_subject(Subject {
+            identity: Symbol(String::new()),
+            labels: std::collections::HashSet::new(),
+            properties: HashMap::new(),
+        }));
+        js_sys::Object::get_prototype_of(&probe).into()
+    };
+}
+
 // Conversion helpers for WasmSubject ↔ JsValue
 impl WasmSubject {
+    /// Returns true if `value` is a `WasmSubject` instance (not a lookalike plain object).
+    pub fn is_instance(value: &JsValue) -> bool {
+        value.is_object()
+            && WASM_SUBJECT_PROTOTYPE
+                .with(|proto| js_sys::Object::is(&js_sys::Object::get_prototype_of(value), proto))
+    }
+
     /// Convert this WasmSubject to a JsValue for use in patterns.
     ///
-    /// This allows WasmSubject instances to be stored in Pattern<JsValue>.
+    /// The value is a new `WasmSubject` instance holding a copy of this
+    /// subject, so [`Self::from_js_value`] recognizes it by prototype rather
+    /// than by any marker a plain object could also carry.
     pub fn to_js_value(&self) -> JsValue {
-        let obj = js_sys::Object::new();
-
-        // Set identity
-        js_sys::Reflect::set(
-            &obj,
-            &JsValue::from_str("identity"),
-            &JsValue::from_str(&self.inner.identity.0),
-        )
-        .expect("set identity");
-
-        // Set labels as Set
-        let labels_set = js_sys::Set::new(&JsValue::undefined());
-        for label in &self.inner.labels {
-            labels_set.add(&JsValue::from_str(label));
-        }
-        js_sys::Reflect::set(&obj, &JsValue::from_str("labels"), &labels_set).expect("set labels");
-
-        // Set properties
-        js_sys::Reflect::set(
-            &obj,
-            &JsValue::from_str("properties"),
-            &value_map_to_js(&self.inner.properties),
-        )
-        .expect("set properties");
-
-        // Add a type marker so we can identify Subjects
-        js_sys::Reflect::set(
-            &obj,
-            &JsValue::from_str("_type"),
-            &JsValue::from_str("Subject"),
-        )
-        .expect("set _type");
-
-        obj.into()
+        JsValue::from(WasmSubject {
+            inner: self.inner.clone(),
+        })
     }
 
     /// Try to convert a JsValue back to a WasmSubject.
     ///
-    /// Handles two representations:
-    /// 1. Plain JS objects with `_type: "Subject"` (from `to_js_value()`)
-    /// 2. WasmSubject WASM instances with `identity`, `labels`, `properties` getters
+    /// Only `WasmSubject` instances are accepted (checked by prototype, see
+    /// [`Self::is_instance`]); plain objects are not Subjects, whatever keys
+    /// they hold.
+    ///
+    /// A relationship's direction and quantifier are read back from the
+    /// reserved `_direction` and `_quantifier` entries of `properties`.
     ///
     /// Returns None if the JsValue is not a valid Subject representation.
     pub fn from_js_value(value: &JsValue) -> Option<Self> {
-        if !value.is_object() {
+        if !Self::is_instance(value) {
             return None;
         }
 
         let obj: &js_sys::Object = value.unchecked_ref();
 
-        // Check for type marker (plain JS object from to_js_value())
-        let type_marker = js_sys::Reflect::get(obj, &JsValue::from_str("_type")).ok();
-        let is_plain_subject = type_marker
-            .as_ref()
-            .and_then(|v| v.as_string())
-            .map(|s| s == "Subject")
-            .unwrap_or(false);
-
-        // Check if it's a WasmSubject WASM instance (has __wbg_ptr and identity getter)
-        let has_wbg_ptr = js_sys::Reflect::get(obj, &JsValue::from_str("__wbg_ptr"))
-            .ok()
-            .map(|v| !v.is_undefined() && !v.is_null())
-            .unwrap_or(false);
-
-        if !is_plain_subject && !has_wbg_ptr {
-            return None;
-        }
-
-        // Extract identity (works for both plain objects and WASM instances)
+        // Extract identity
         let identity_js = js_sys::Reflect::get(obj, &JsValue::from_str("identity")).ok()?;
         let identity = identity_js.as_string()?;
 
-        // Extract labels (works for both plain objects and WASM instances)
-        // Labels may be a JS Array or a JS Set
+        // Extract labels, which may be a JS Array or a JS Set
         let labels_js = js_sys::Reflect::get(obj, &JsValue::from_str("labels")).ok()?;
         let labels_set: std::collections::HashSet<String> = if js_sys::Array::is_array(&labels_js) {
             js_array_to_strings(&labels_js).ok()?.into_iter().collect()
@@ -938,7 +939,7 @@ impl WasmSubject {
             result
         };
 
-        // Extract properties (works for both plain objects and WASM instances)
+        // Extract properties
         let properties_js = js_sys::Reflect::get(obj, &JsValue::from_str("properties")).ok()?;
         let properties = js_object_to_value_map(&properties_js).ok()?;
 
@@ -1164,6 +1165,32 @@ impl WasmPattern {
         Ok(result)
     }
 
+    /// Create a pattern of Subjects whose values are `Subject` instances.
+    ///
+    /// Accepts a Pattern, or a plain `{ value, elements }` object, in which
+    /// every value is a `Subject` instance. The result holds fresh `Subject`
+    /// instances, so graph operations read it back exactly.
+    ///
+    /// # Arguments
+    /// * `pattern` - A pattern of Subjects
+    ///
+    /// # Returns
+    /// A new Pattern with Subject values, or throws naming the first value
+    /// that is not a Subject
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const alice = Subject.fromId("alice");
+    /// const pattern = Pattern.fromSubjectPattern({ value: alice, elements: [] });
+    /// const graph = NativePatternGraph.fromPatterns([pattern]);
+    /// ```
+    #[wasm_bindgen(js_name = fromSubjectPattern)]
+    pub fn from_js_subject_pattern(pattern: &JsValue) -> Result<WasmPattern, JsValue> {
+        js_value_to_subject_pattern_strict(pattern)
+            .map(|p| Self::from_subject_pattern(&p))
+            .map_err(|reason| JsValue::from_str(&format!("fromSubjectPattern: {}", reason)))
+    }
+
     // ========================================================================
     // Accessors (T008)
     // ========================================================================
@@ -1543,6 +1570,11 @@ impl WasmPattern {
 
     /// Check if two patterns have identical structure (same values and same tree structure).
     ///
+    /// Strings, numbers, and booleans compare by value and Subjects by content,
+    /// so two `Subject` instances with the same identity, labels, and properties
+    /// match. Other values match if they are the same value or serialize to
+    /// the same JSON.
+    ///
     /// # Arguments
     /// * `other` - Another Pattern to compare with
     ///
@@ -1583,16 +1615,20 @@ impl WasmPattern {
             if b1 != b2 {
                 return false;
             }
-        } else {
-            // For complex types, use JavaScript equality
-            let eq = js_sys::Reflect::get(&js_sys::Object::new(), &JsValue::from_str("equals"))
+        } else if let (Some(s1), Some(s2)) = (
+            WasmSubject::from_js_value(self_val),
+            WasmSubject::from_js_value(other_val),
+        ) {
+            // Compare Subjects by content, whichever representation holds them
+            if s1.inner != s2.inner {
+                return false;
+            }
+        } else if !js_sys::Object::is(self_val, other_val) {
+            // For other complex types, compare JSON serializations
+            let eq = js_sys::JSON::stringify(self_val)
                 .ok()
-                .and_then(|_| {
-                    // Fallback: convert to JSON strings and compare
-                    let s1 = js_sys::JSON::stringify(self_val).ok()?;
-                    let s2 = js_sys::JSON::stringify(other_val).ok()?;
-                    Some(s1.as_string()? == s2.as_string()?)
-                })
+                .zip(js_sys::JSON::stringify(other_val).ok())
+                .and_then(|(s1, s2)| Some(s1.as_string()? == s2.as_string()?))
                 .unwrap_or(false);
 
             if !eq {
@@ -1708,6 +1744,56 @@ impl WasmPattern {
         })
     }
 
+    /// Transform every Subject in the pattern, keeping the result usable as a Subject pattern.
+    ///
+    /// Unlike `map`, the function receives each value as a `Subject` instance and must
+    /// return a `Subject` instance. The results are stored as `Subject` instances, so graph
+    /// operations still accept the mapped pattern.
+    ///
+    /// # Arguments
+    /// * `f` - A JavaScript function that takes a Subject and returns a Subject
+    ///
+    /// # Returns
+    /// A new Pattern with transformed Subjects, or throws if any value is not a Subject
+    /// or `f` returns something other than a Subject
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const tagged = pattern.mapSubjects(s =>
+    ///     new Subject(s.identity, [...s.labels, "Reviewed"], s.properties)
+    /// );
+    /// const graph = NativePatternGraph.fromPatterns([tagged]);
+    /// ```
+    #[wasm_bindgen(js_name = mapSubjects)]
+    pub fn map_subjects(&self, f: &js_sys::Function) -> Result<WasmPattern, JsValue> {
+        self.map_subjects_recursive(f)
+    }
+
+    /// Helper for mapSubjects() - recursive implementation.
+    fn map_subjects_recursive(&self, f: &js_sys::Function) -> Result<WasmPattern, JsValue> {
+        let subject = WasmSubject::from_js_value(self.inner.value())
+            .ok_or_else(|| JsValue::from_str("mapSubjects: pattern value is not a Subject"))?;
+
+        let this = JsValue::null();
+        let result = f
+            .call1(&this, &JsValue::from(subject))
+            .map_err(|e| JsValue::from_str(&format!("Map function error: {:?}", e)))?;
+        let new_subject = WasmSubject::from_js_value(&result)
+            .ok_or_else(|| JsValue::from_str("mapSubjects: function must return a Subject"))?;
+
+        let mut new_elements = Vec::new();
+        for elem in self.inner.elements() {
+            let wasm_elem = WasmPattern {
+                inner: elem.clone(),
+            };
+            new_elements.push(wasm_elem.map_subjects_recursive(f)?.inner);
+        }
+
+        Ok(WasmPattern {
+            inner: Pattern::pattern(JsValue::from(new_subject), new_elements),
+        })
+    }
+
     /// Fold the pattern into a single value by applying a function with an accumulator.
     ///
     /// Processes values in depth-first, root-first order (pre-order traversal).
@@ -2135,6 +2221,50 @@ impl WasmPattern {
     pub fn as_pattern(&self) -> &Pattern<JsValue> {
         &self.inner
     }
+
+    /// Create a WasmPattern from a Rust Pattern<Subject>.
+    ///
+    /// Every value is stored as a `WasmSubject` instance, so converting back
+    /// with [`to_subject_pattern`](Self::to_subject_pattern) is exact and
+    /// cannot be fooled by lookalike objects. JavaScript reaches the same
+    /// conversion through `Pattern.fromSubjectPattern`.
+    ///
+    /// # Arguments
+    /// * `pattern` - A Rust Pattern<Subject>
+    ///
+    /// # Returns
+    /// A new WasmPattern with WasmSubject values
+    pub fn from_subject_pattern(pattern: &Pattern<Subject>) -> Self {
+        WasmPattern {
+            inner: Pattern {
+                value: JsValue::from(WasmSubject::from_subject(pattern.value.clone())),
+                elements: pattern
+                    .elements
+                    .iter()
+                    .map(|e| Self::from_subject_pattern(e).inner)
+                    .collect(),
+            },
+        }
+    }
+
+    /// Convert this WasmPattern back to a Rust Pattern<Subject>.
+    ///
+    /// Returns None if the value of this pattern, or of any element nested in
+    /// it, is not a Subject; dropping such an element would change the shape
+    /// of the pattern, e.g. turn a relationship into an annotation.
+    ///
+    /// # Returns
+    /// The equivalent Pattern<Subject>, or None
+    pub fn to_subject_pattern(&self) -> Option<Pattern<Subject>> {
+        let value = WasmSubject::from_js_value(&self.inner.value)?.into_subject();
+        let elements = self
+            .inner
+            .elements
+            .iter()
+            .map(|e| WasmPattern { inner: e.clone() }.to_subject_pattern())
+            .collect::<Option<Vec<_>>>()?;
+        Some(Pattern { value, elements })
+    }
 }
 
 // ============================================================================
@@ -2147,54 +2277,36 @@ impl WasmPattern {
 // All types use the `Native*` JS name prefix to distinguish WASM-backed concrete
 // classes from the pure TypeScript interfaces in @relateby/graph.
 
-use crate::graph::graph_classifier::canonical_classifier;
+use crate::graph::graph_classifier::{canonical_classifier, GraphClass};
 use crate::graph::graph_query::{directed, directed_reverse, undirected, GraphQuery};
 use crate::graph::StandardGraph;
-use crate::pattern_graph::{from_pattern_graph, from_patterns_with_policy, PatternGraph};
+use crate::pattern_graph::{
+    from_pattern_graph, from_patterns_with_policy, PatternGraph, PatternGraphBuilder,
+};
 use crate::reconcile::{
     ElementMergeStrategy, LabelMerge, PropertyMerge, ReconciliationPolicy, SubjectMergeStrategy,
 };
 
 // ---------------------------------------------------------------------------
-// Helper: convert Pattern<Subject> → WasmPattern (via JsValue encoding)
+// Helper: convert Pattern<Subject> ↔ WasmPattern (WasmSubject instance values)
 // ---------------------------------------------------------------------------
 
 fn subject_pattern_to_wasm(p: &crate::pattern::Pattern<crate::subject::Subject>) -> WasmPattern {
-    let subject_js = WasmSubject::from_subject(p.value.clone()).to_js_value();
-    let wasm_p = WasmPattern {
-        inner: crate::pattern::Pattern {
-            value: subject_js,
-            elements: p
-                .elements
-                .iter()
-                .map(|e| subject_pattern_to_wasm(e).inner)
-                .collect(),
-        },
-    };
-    wasm_p
+    WasmPattern::from_subject_pattern(p)
 }
 
 fn wasm_pattern_to_subject_pattern(
     p: &WasmPattern,
 ) -> Option<crate::pattern::Pattern<crate::subject::Subject>> {
-    let subject = WasmSubject::from_js_value(&p.inner.value)?.into_subject();
-    let elements: Vec<_> = p
-        .inner
-        .elements
-        .iter()
-        .filter_map(|e| wasm_pattern_to_subject_pattern(&WasmPattern { inner: e.clone() }))
-        .collect();
-    Some(crate::pattern::Pattern {
-        value: subject,
-        elements,
-    })
+    p.to_subject_pattern()
 }
 
 /// Convert a JsValue (which may be a serialized WasmPattern object) to Pattern<Subject>.
 ///
-/// WasmPattern objects in JS have `value` and `elements` fields. The `value` is a
-/// Subject JsValue with `_type: 'Subject'`. This function extracts the Subject and
-/// recursively converts child elements.
+/// WasmPattern objects in JS have `value` and `elements` fields. The `value` must
+/// be a WasmSubject instance. This function extracts the Subject and recursively
+/// converts child elements, returning None if any of them is not a pattern of
+/// Subjects.
 fn js_value_to_subject_pattern(
     js: &JsValue,
 ) -> Option<crate::pattern::Pattern<crate::subject::Subject>> {
@@ -2211,8 +2323,8 @@ fn js_value_to_subject_pattern(
     let elements = if js_sys::Array::is_array(&elements_js) {
         let arr: &js_sys::Array = elements_js.unchecked_ref();
         (0..arr.length())
-            .filter_map(|i| js_value_to_subject_pattern(&arr.get(i)))
-            .collect()
+            .map(|i| js_value_to_subject_pattern(&arr.get(i)))
+            .collect::<Option<Vec<_>>>()?
     } else {
         vec![]
     };
@@ -2223,6 +2335,43 @@ fn js_value_to_subject_pattern(
     })
 }
 
+/// Like [`js_value_to_subject_pattern`], but reports why a pattern (or any
+/// element nested in it) can't be converted instead of dropping it.
+fn js_value_to_subject_pattern_strict(
+    js: &JsValue,
+) -> Result<crate::pattern::Pattern<crate::subject::Subject>, String> {
+    if !js.is_object() {
+        return Err("expected a pattern object".to_string());
+    }
+
+    let value_js = js_sys::Reflect::get(js, &JsValue::from_str("value"))
+        .map_err(|_| "cannot read value".to_string())?;
+    let subject = WasmSubject::from_js_value(&value_js)
+        .ok_or_else(|| "value is not a Subject".to_string())?
+        .into_subject();
+
+    let elements_js = js_sys::Reflect::get(js, &JsValue::from_str("elements"))
+        .map_err(|_| "cannot read elements".to_string())?;
+    let elements = if js_sys::Array::is_array(&elements_js) {
+        let arr: &js_sys::Array = elements_js.unchecked_ref();
+        (0..arr.length())
+            .map(|i| {
+                js_value_to_subject_pattern_strict(&arr.get(i))
+                    .map_err(|reason| format!("elements[{}]: {}", i, reason))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    } else if elements_js.is_undefined() || elements_js.is_null() {
+        vec![]
+    } else {
+        return Err("elements is not an array".to_string());
+    };
+
+    Ok(crate::pattern::Pattern {
+        value: subject,
+        elements,
+    })
+}
+
 fn subject_pattern_to_js(p: &crate::pattern::Pattern<crate::subject::Subject>) -> JsValue {
     JsValue::from(subject_pattern_to_wasm(p))
 }
@@ -2356,12 +2505,38 @@ impl WasmReconciliationPolicy {
             }
         }
 
-        let strategy = SubjectMergeStrategy {
-            label_merge,
-            property_merge,
-        };
         WasmReconciliationPolicy {
-            inner: ReconciliationPolicy::Merge(element_strategy, strategy),
+            inner: ReconciliationPolicy::merge_builder()
+                .labels(label_merge)
+                .properties(property_merge)
+                .elements(element_strategy)
+                .build(),
+        }
+    }
+
+    /// Resolve conflicts with a JS callback.
+    ///
+    /// `merge(existing, incoming)` receives two Subject objects and returns the
+    /// merged Subject. The merged subject keeps the existing identity. If the
+    /// callback throws or returns something other than a Subject, the existing
+    /// subject is kept.
+    #[wasm_bindgen(js_name = custom)]
+    pub fn custom(merge: js_sys::Function) -> WasmReconciliationPolicy {
+        WasmReconciliationPolicy {
+            inner: ReconciliationPolicy::Custom(std::rc::Rc::new(
+                move |a: &Subject, b: &Subject| {
+                    let result = merge.call2(
+                        &JsValue::undefined(),
+                        &JsValue::from(WasmSubject::from_subject(a.clone())),
+                        &JsValue::from(WasmSubject::from_subject(b.clone())),
+                    );
+                    result
+                        .ok()
+                        .and_then(|v| WasmSubject::from_js_value(&v))
+                        .map(WasmSubject::into_subject)
+                        .unwrap_or_else(|| a.clone())
+                },
+            )),
         }
     }
 }
@@ -2382,22 +2557,36 @@ pub struct WasmPatternGraph {
     pub inner: std::rc::Rc<PatternGraph<(), Subject>>,
 }
 
+impl WasmPatternGraph {
+    /// Build a graph from already-converted patterns.
+    fn build(
+        subject_patterns: Vec<crate::pattern::Pattern<Subject>>,
+        policy: Option<WasmReconciliationPolicy>,
+    ) -> WasmPatternGraph {
+        let classifier = canonical_classifier::<Subject>();
+        let policy_inner = policy
+            .map(|p| p.inner)
+            .unwrap_or(ReconciliationPolicy::LastWriteWins);
+        let graph = from_patterns_with_policy(&classifier, &policy_inner, subject_patterns);
+
+        WasmPatternGraph {
+            inner: std::rc::Rc::new(graph),
+        }
+    }
+}
+
 #[wasm_bindgen]
 impl WasmPatternGraph {
     /// Construct a graph from an array of NativePattern instances.
     ///
     /// Patterns whose values are not Subject instances are classified as `other`.
-    /// Never throws — unrecognized patterns are silently dropped.
+    /// Never throws — unrecognized patterns are silently dropped; use
+    /// `fromPatternsStrict` to be told about them instead.
     #[wasm_bindgen(js_name = fromPatterns)]
     pub fn from_patterns(
         patterns: &js_sys::Array,
         policy: Option<WasmReconciliationPolicy>,
     ) -> WasmPatternGraph {
-        let classifier = canonical_classifier::<Subject>();
-        let policy_inner = policy
-            .map(|p| p.inner)
-            .unwrap_or(ReconciliationPolicy::LastWriteWins);
-
         let subject_patterns: Vec<crate::pattern::Pattern<Subject>> = (0..patterns.length())
             .filter_map(|i| {
                 let item = patterns.get(i);
@@ -2407,11 +2596,46 @@ impl WasmPatternGraph {
             })
             .collect();
 
-        let graph = from_patterns_with_policy(&classifier, &policy_inner, subject_patterns);
+        Self::build(subject_patterns, policy)
+    }
 
-        WasmPatternGraph {
-            inner: std::rc::Rc::new(graph),
+    /// Construct a graph, rejecting input that can't be converted.
+    ///
+    /// Unlike `fromPatterns`, which silently drops patterns whose values are not
+    /// Subject instances, this returns an Either-like value:
+    /// - Success: `{ _tag: 'Right', right: NativePatternGraph }`
+    /// - Failure: `{ _tag: 'Left', left: { index, reason } }`, where `index` is
+    ///   the position of the first unconvertible pattern in `patterns` and
+    ///   `reason` says what is wrong with it (including nested elements)
+    ///
+    /// Use it during development to catch malformed input early.
+    #[wasm_bindgen(js_name = fromPatternsStrict)]
+    pub fn from_patterns_strict(
+        patterns: &js_sys::Array,
+        policy: Option<WasmReconciliationPolicy>,
+    ) -> JsValue {
+        let mut subject_patterns = Vec::with_capacity(patterns.length() as usize);
+        for i in 0..patterns.length() {
+            match js_value_to_subject_pattern_strict(&patterns.get(i)) {
+                Ok(pattern) => subject_patterns.push(pattern),
+                Err(reason) => {
+                    // Setting a key on a fresh plain object cannot fail
+                    let error = js_sys::Object::new();
+                    let _ = js_sys::Reflect::set(
+                        &error,
+                        &JsValue::from_str("index"),
+                        &JsValue::from(i),
+                    );
+                    let _ = js_sys::Reflect::set(
+                        &error,
+                        &JsValue::from_str("reason"),
+                        &JsValue::from_str(&reason),
+                    );
+                    return either_left(error.into());
+                }
+            }
         }
+        either_right(Self::build(subject_patterns, policy).into())
     }
 
     /// Construct an empty graph.
@@ -2466,7 +2690,7 @@ impl WasmPatternGraph {
     #[wasm_bindgen(getter)]
     pub fn conflicts(&self) -> JsValue {
         let obj = js_sys::Object::new();
-        for (id, patterns) in &self.inner.pg_conflicts {
+        for (id, patterns) in self.inner.pg_conflicts.iter() {
             let arr = patterns_to_js_array(patterns);
             js_sys::Reflect::set(&obj, &JsValue::from_str(&id.0), &arr).ok();
         }
@@ -2513,6 +2737,18 @@ impl WasmPatternGraph {
         }
     }
 
+    /// Create a query handle over this graph.
+    ///
+    /// The handle shares this graph's allocation (the `Rc` is cloned, not the
+    /// graph), so many queries over one long-lived graph are cheap.
+    /// Equivalent to `WasmGraphQuery.fromPatternGraph(this)`.
+    #[wasm_bindgen(js_name = queryHandle)]
+    pub fn query_handle(&self) -> WasmGraphQuery {
+        WasmGraphQuery {
+            inner: self.inner.as_query(),
+        }
+    }
+
     /// Return patterns in bottom-up shape-class topological order.
     ///
     /// Returns null if the graph contains a cycle.
@@ -2527,6 +2763,61 @@ impl WasmPatternGraph {
     }
 }
 
+// ---------------------------------------------------------------------------
+// WasmPatternGraphBuilder
+// ---------------------------------------------------------------------------
+
+/// WASM binding for PatternGraphBuilder<(), Subject>.
+///
+/// Accumulates patterns one at a time, e.g. as they arrive over a socket,
+/// reconciling each with the builder's policy as it is pushed. `build()`
+/// returns a graph identical to `fromPatterns` on every pattern pushed so far.
+///
+/// Exported to JavaScript as `WasmPatternGraphBuilder`.
+#[wasm_bindgen]
+pub struct WasmPatternGraphBuilder {
+    #[wasm_bindgen(skip)]
+    pub inner: PatternGraphBuilder<(), Subject>,
+}
+
+#[wasm_bindgen]
+impl WasmPatternGraphBuilder {
+    /// Create an empty builder. The policy defaults to LastWriteWins, as in `fromPatterns`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(policy: Option<WasmReconciliationPolicy>) -> WasmPatternGraphBuilder {
+        let policy = policy
+            .map(|p| p.inner)
+            .unwrap_or(ReconciliationPolicy::LastWriteWins);
+        WasmPatternGraphBuilder {
+            inner: PatternGraphBuilder::new(canonical_classifier::<Subject>(), policy),
+        }
+    }
+
+    /// Add one NativePattern to the graph.
+    ///
+    /// Returns false, leaving the graph unchanged, if the value is not a
+    /// pattern of Subjects; `fromPatterns` drops such items the same way.
+    pub fn push(&mut self, pattern: &JsValue) -> bool {
+        match js_value_to_subject_pattern(pattern) {
+            Some(p) => {
+                self.inner.push(p);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Snapshot of the graph built so far. The builder can keep accepting patterns.
+    ///
+    /// The snapshot shares the builder's collections; a later push copies only
+    /// the collections it changes.
+    pub fn build(&self) -> WasmPatternGraph {
+        WasmPatternGraph {
+            inner: std::rc::Rc::new(self.inner.graph().clone()),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // WasmGraphQuery (js_name = NativeGraphQuery)
 // ---------------------------------------------------------------------------
@@ -2546,11 +2837,11 @@ pub struct WasmGraphQuery {
 #[wasm_bindgen]
 impl WasmGraphQuery {
     /// Create a query handle from a NativePatternGraph.
+    ///
+    /// Shares the graph's allocation rather than copying it.
     #[wasm_bindgen(js_name = fromPatternGraph)]
     pub fn from_pattern_graph(graph: &WasmPatternGraph) -> WasmGraphQuery {
-        WasmGraphQuery {
-            inner: from_pattern_graph(std::rc::Rc::clone(&graph.inner)),
-        }
+        graph.query_handle()
     }
 
     /// All node patterns.
@@ -2621,6 +2912,43 @@ impl WasmGraphQuery {
         }
     }
 
+    /// All nodes carrying the given label, sorted by identity.
+    #[wasm_bindgen(js_name = nodesByLabel)]
+    pub fn nodes_by_label(&self, label: &str) -> js_sys::Array {
+        patterns_to_js_array(&(self.inner.query_nodes_by_label)(label))
+    }
+
+    /// Nodes whose `key` property satisfies `predicate`, sorted by identity.
+    ///
+    /// `predicate` receives the property value (converted via `value_to_js`)
+    /// and should return a boolean. Nodes without the property are skipped.
+    /// The first exception thrown by `predicate` is returned as an error.
+    #[wasm_bindgen(js_name = nodesWhere)]
+    pub fn nodes_where(
+        &self,
+        key: &str,
+        predicate: &js_sys::Function,
+    ) -> Result<js_sys::Array, JsValue> {
+        let error: std::cell::RefCell<Option<JsValue>> = std::cell::RefCell::new(None);
+        let nodes = self.inner.query_nodes_where(key, &|v| {
+            if error.borrow().is_some() {
+                return false;
+            }
+            match predicate.call1(&JsValue::null(), &value_to_js(v)) {
+                Ok(result) => result.as_bool().unwrap_or(false),
+                Err(e) => {
+                    *error.borrow_mut() =
+                        Some(JsValue::from_str(&format!("Predicate error: {:?}", e)));
+                    false
+                }
+            }
+        });
+        match error.into_inner() {
+            Some(e) => Err(e),
+            None => Ok(patterns_to_js_array(&nodes)),
+        }
+    }
+
     /// Look up a relationship by its identity string. Returns null if not found.
     #[wasm_bindgen(js_name = relationshipById)]
     pub fn relationship_by_id(&self, identity: &str) -> JsValue {
@@ -2643,26 +2971,21 @@ impl WasmGraphQuery {
 #[wasm_bindgen]
 pub fn graph_class_constants() -> JsValue {
     let obj = js_sys::Object::new();
-    js_sys::Reflect::set(&obj, &JsValue::from_str("NODE"), &JsValue::from_str("node")).ok();
-    js_sys::Reflect::set(
-        &obj,
-        &JsValue::from_str("RELATIONSHIP"),
-        &JsValue::from_str("relationship"),
-    )
-    .ok();
-    js_sys::Reflect::set(
-        &obj,
-        &JsValue::from_str("ANNOTATION"),
-        &JsValue::from_str("annotation"),
-    )
-    .ok();
-    js_sys::Reflect::set(&obj, &JsValue::from_str("WALK"), &JsValue::from_str("walk")).ok();
-    js_sys::Reflect::set(
-        &obj,
-        &JsValue::from_str("OTHER"),
-        &JsValue::from_str("other"),
-    )
-    .ok();
+    let classes: [GraphClass<()>; 5] = [
+        GraphClass::GNode,
+        GraphClass::GRelationship,
+        GraphClass::GAnnotation,
+        GraphClass::GWalk,
+        GraphClass::GOther(()),
+    ];
+    for class in &classes {
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str(&class.name().to_uppercase()),
+            &JsValue::from_str(class.name()),
+        )
+        .ok();
+    }
     obj.into()
 }
 
@@ -2723,6 +3046,68 @@ pub fn dfs(query: &WasmGraphQuery, start: &WasmPattern, weight: JsValue) -> js_s
     patterns_to_js_array(&result)
 }
 
+/// Breadth-first search from a start node, with the relationship used to reach
+/// each node.
+///
+/// Returns `{ node, edge }` objects in BFS order; `edge` is null for the start
+/// node. Weight defaults to undirected.
+#[wasm_bindgen(js_name = bfsWithEdges)]
+pub fn bfs_with_edges(
+    query: &WasmGraphQuery,
+    start: &WasmPattern,
+    weight: JsValue,
+) -> js_sys::Array {
+    let subject_start = match wasm_pattern_to_subject_pattern(start) {
+        Some(s) => s,
+        None => return js_sys::Array::new(),
+    };
+    let w = parse_weight(&weight);
+    let result = crate::graph::algorithms::bfs_with_edges(&query.inner, &w, &subject_start);
+    traversal_edges_to_js_array(&result)
+}
+
+/// Depth-first search from a start node, with the relationship used to reach
+/// each node.
+///
+/// Returns `{ node, edge }` objects in DFS order; `edge` is null for the start
+/// node. Weight defaults to undirected.
+#[wasm_bindgen(js_name = dfsWithEdges)]
+pub fn dfs_with_edges(
+    query: &WasmGraphQuery,
+    start: &WasmPattern,
+    weight: JsValue,
+) -> js_sys::Array {
+    let subject_start = match wasm_pattern_to_subject_pattern(start) {
+        Some(s) => s,
+        None => return js_sys::Array::new(),
+    };
+    let w = parse_weight(&weight);
+    let result = crate::graph::algorithms::dfs_with_edges(&query.inner, &w, &subject_start);
+    traversal_edges_to_js_array(&result)
+}
+
+fn traversal_edges_to_js_array(
+    steps: &[(
+        crate::pattern::Pattern<crate::subject::Subject>,
+        Option<crate::pattern::Pattern<crate::subject::Subject>>,
+    )],
+) -> js_sys::Array {
+    let arr = js_sys::Array::new();
+    for (node, edge) in steps {
+        let obj = js_sys::Object::new();
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("node"),
+            &subject_pattern_to_js(node),
+        )
+        .ok();
+        let edge = edge.as_ref().map_or(JsValue::NULL, subject_pattern_to_js);
+        js_sys::Reflect::set(&obj, &JsValue::from_str("edge"), &edge).ok();
+        arr.push(&obj);
+    }
+    arr
+}
+
 /// Shortest path between two nodes.
 ///
 /// Returns null if no path exists. Weight defaults to undirected.
@@ -2748,6 +3133,68 @@ pub fn shortest_path(
     }
 }
 
+/// Shortest path between two nodes using A* search.
+///
+/// `heuristic` is called with a node and must return a non-negative estimate
+/// of the remaining cost to `end`. An exception thrown by `heuristic`, or a
+/// result that is not a number, is negative, or is NaN, is returned as an
+/// error. Returns null if no path exists. Weight defaults to undirected.
+#[wasm_bindgen(js_name = aStar)]
+pub fn a_star(
+    query: &WasmGraphQuery,
+    start: &WasmPattern,
+    end: &WasmPattern,
+    heuristic: &js_sys::Function,
+    weight: JsValue,
+) -> Result<JsValue, JsValue> {
+    let subject_start = match wasm_pattern_to_subject_pattern(start) {
+        Some(s) => s,
+        None => return Ok(JsValue::null()),
+    };
+    let subject_end = match wasm_pattern_to_subject_pattern(end) {
+        Some(e) => e,
+        None => return Ok(JsValue::null()),
+    };
+    let w = parse_weight(&weight);
+    let error = std::cell::RefCell::new(None);
+    let h = |node: &crate::pattern::Pattern<Subject>| {
+        if error.borrow().is_some() {
+            return 0.0;
+        }
+        let wasm_node = subject_pattern_to_wasm(node);
+        let estimate = match heuristic.call1(&JsValue::undefined(), &JsValue::from(wasm_node)) {
+            Ok(v) => heuristic_estimate(v.as_f64()).map_err(|m| JsValue::from_str(&m)),
+            Err(e) => Err(JsValue::from_str(&format!("Heuristic error: {:?}", e))),
+        };
+        estimate.unwrap_or_else(|e| {
+            *error.borrow_mut() = Some(e);
+            0.0
+        })
+    };
+    let path = crate::graph::algorithms::a_star(&query.inner, &w, &subject_start, &subject_end, h);
+    if let Some(e) = error.into_inner() {
+        return Err(e);
+    }
+    Ok(path.map_or(JsValue::null(), |path| {
+        JsValue::from(patterns_to_js_array(&path))
+    }))
+}
+
+/// Check a value returned by an `aStar` heuristic.
+///
+/// Accepts non-negative numbers, including infinity; rejects a missing
+/// number, negative values, and NaN with a message for the caller.
+fn heuristic_estimate(value: Option<f64>) -> Result<f64, String> {
+    match value {
+        Some(h) if h >= 0.0 => Ok(h),
+        Some(h) => Err(format!(
+            "Heuristic must return a non-negative number, got {}",
+            h
+        )),
+        None => Err("Heuristic must return a number".to_string()),
+    }
+}
+
 /// All paths between two nodes.
 ///
 /// Returns an array of path arrays. Weight defaults to undirected.
@@ -2789,6 +3236,28 @@ pub fn connected_components(query: &WasmGraphQuery, weight: JsValue) -> js_sys::
     outer
 }
 
+/// Nodes with no incident relationships.
+///
+/// Useful for spotting orphaned records after an import.
+#[wasm_bindgen(js_name = isolatedNodes)]
+pub fn isolated_nodes(query: &WasmGraphQuery) -> js_sys::Array {
+    patterns_to_js_array(&crate::graph::algorithms::isolated_nodes(&query.inner))
+}
+
+/// Groups of parallel relationships (same endpoints and labels).
+///
+/// Returns an array of arrays, each holding two or more relationship patterns.
+/// Direction matters unless `undirected` is true.
+#[wasm_bindgen(js_name = parallelEdges)]
+pub fn parallel_edges(query: &WasmGraphQuery, undirected: bool) -> js_sys::Array {
+    let groups = crate::graph::algorithms::parallel_edges(&query.inner, undirected);
+    let arr = js_sys::Array::new();
+    for group in &groups {
+        arr.push(&patterns_to_js_array(group));
+    }
+    arr
+}
+
 /// Returns true if the graph contains a directed cycle.
 #[wasm_bindgen(js_name = hasCycle)]
 pub fn has_cycle(query: &WasmGraphQuery) -> bool {
@@ -2815,6 +3284,57 @@ pub fn topological_sort(query: &WasmGraphQuery) -> JsValue {
     }
 }
 
+/// Longest directed path in a DAG.
+///
+/// Returns null if the graph has a directed cycle. Without a weight every
+/// relationship counts 1; otherwise forward traversal costs are summed.
+#[wasm_bindgen(js_name = longestPath)]
+pub fn longest_path(query: &WasmGraphQuery, weight: JsValue) -> JsValue {
+    let path = if weight.is_undefined() || weight.is_null() {
+        crate::graph::algorithms::longest_path(&query.inner)
+    } else {
+        let w = parse_weight(&weight);
+        crate::graph::algorithms::longest_path_weighted(&query.inner, &w)
+    };
+    match path {
+        Some(path) => JsValue::from(patterns_to_js_array(&path)),
+        None => JsValue::null(),
+    }
+}
+
+/// Contract the nodes with the given identities into one node `newId`.
+///
+/// Subjects are merged with the label and property strategies of a
+/// `merge` policy; any other policy (or none) uses the default merge.
+/// Relationships between grouped nodes are dropped unless `selfLoops` is true.
+/// Returns an array of NativePattern: nodes, then relationships.
+#[wasm_bindgen(js_name = contractNodes)]
+pub fn contract_nodes(
+    query: &WasmGraphQuery,
+    group: &js_sys::Array,
+    new_id: String,
+    policy: Option<WasmReconciliationPolicy>,
+    self_loops: bool,
+) -> js_sys::Array {
+    let group: Vec<Symbol> = group
+        .iter()
+        .filter_map(|id| id.as_string())
+        .map(Symbol)
+        .collect();
+    let strategy = match policy.map(|p| p.inner) {
+        Some(ReconciliationPolicy::Merge(_, strategy)) => strategy,
+        _ => crate::reconcile::default_subject_merge_strategy(),
+    };
+    let intra_group = if self_loops {
+        crate::graph::IntraGroupEdges::SelfLoop
+    } else {
+        crate::graph::IntraGroupEdges::Drop
+    };
+    let patterns =
+        crate::graph::contract_nodes(&query.inner, &group, Symbol(new_id), &strategy, intra_group);
+    patterns_to_js_array(&patterns)
+}
+
 /// Degree centrality for all nodes.
 ///
 /// Returns a JS object mapping identity strings to normalized scores.
@@ -2828,6 +3348,25 @@ pub fn degree_centrality(query: &WasmGraphQuery) -> JsValue {
     obj.into()
 }
 
+/// PageRank for all nodes, following relationships from source to target.
+///
+/// Returns a JS object mapping identity strings to scores that sum to 1.
+/// Runs at most `iterations` iterations, stopping early once scores settle.
+#[wasm_bindgen(js_name = pageRank)]
+pub fn page_rank(query: &WasmGraphQuery, damping: f64, iterations: usize) -> JsValue {
+    let scores = crate::graph::algorithms::pagerank_with_iterations(
+        &query.inner,
+        &directed::<Subject>(),
+        damping,
+        iterations,
+    );
+    let obj = js_sys::Object::new();
+    for (id, score) in &scores {
+        js_sys::Reflect::set(&obj, &JsValue::from_str(&id.0), &JsValue::from_f64(*score)).ok();
+    }
+    obj.into()
+}
+
 /// Betweenness centrality for all nodes.
 ///
 /// Returns a JS object mapping identity strings to scores.
@@ -2843,6 +3382,53 @@ pub fn betweenness_centrality(query: &WasmGraphQuery, weight: JsValue) -> JsValu
     obj.into()
 }
 
+/// Betweenness centrality for all nodes, normalized to `[0, 1]`.
+///
+/// Returns a JS object mapping identity strings to scores divided by
+/// `(n-1)(n-2)`; graphs with fewer than three nodes score 0. Weight defaults
+/// to undirected.
+#[wasm_bindgen(js_name = betweennessCentralityNormalized)]
+pub fn betweenness_centrality_normalized(query: &WasmGraphQuery, weight: JsValue) -> JsValue {
+    let w = parse_weight(&weight);
+    let scores = crate::graph::algorithms::betweenness_centrality_normalized(&query.inner, &w);
+    let obj = js_sys::Object::new();
+    for (id, score) in &scores {
+        js_sys::Reflect::set(&obj, &JsValue::from_str(&id.0), &JsValue::from_f64(*score)).ok();
+    }
+    obj.into()
+}
+
+/// Harmonic centrality for all nodes.
+///
+/// Returns a JS object mapping identity strings to scores. Unreachable nodes
+/// contribute 0, so disconnected graphs are supported. Weight defaults to undirected.
+#[wasm_bindgen(js_name = harmonicCentrality)]
+pub fn harmonic_centrality(query: &WasmGraphQuery, weight: JsValue) -> JsValue {
+    let w = parse_weight(&weight);
+    let scores = crate::graph::algorithms::harmonic_centrality(&query.inner, &w);
+    let obj = js_sys::Object::new();
+    for (id, score) in &scores {
+        js_sys::Reflect::set(&obj, &JsValue::from_str(&id.0), &JsValue::from_f64(*score)).ok();
+    }
+    obj.into()
+}
+
+/// Closeness centrality for all nodes.
+///
+/// Returns a JS object mapping identity strings to scores. A node's score is
+/// scaled by the share of other nodes it reaches, and nodes that reach nothing
+/// score 0. Weight defaults to undirected.
+#[wasm_bindgen(js_name = closenessCentrality)]
+pub fn closeness_centrality(query: &WasmGraphQuery, weight: JsValue) -> JsValue {
+    let w = parse_weight(&weight);
+    let scores = crate::graph::algorithms::closeness_centrality(&query.inner, &w);
+    let obj = js_sys::Object::new();
+    for (id, score) in &scores {
+        js_sys::Reflect::set(&obj, &JsValue::from_str(&id.0), &JsValue::from_f64(*score)).ok();
+    }
+    obj.into()
+}
+
 /// Minimum spanning tree.
 ///
 /// Returns an array of relationship patterns. Weight defaults to undirected.
@@ -3219,16 +3805,8 @@ impl WasmStandardGraph {
     /// Convert to NativePatternGraph.
     #[wasm_bindgen(js_name = asPatternGraph)]
     pub fn as_pattern_graph(&self) -> WasmPatternGraph {
-        let pg = self.inner.as_pattern_graph();
         WasmPatternGraph {
-            inner: std::rc::Rc::new(PatternGraph {
-                pg_nodes: pg.pg_nodes.clone(),
-                pg_relationships: pg.pg_relationships.clone(),
-                pg_walks: pg.pg_walks.clone(),
-                pg_annotations: pg.pg_annotations.clone(),
-                pg_other: pg.pg_other.clone(),
-                pg_conflicts: pg.pg_conflicts.clone(),
-            }),
+            inner: std::rc::Rc::new(self.inner.as_pattern_graph().clone()),
         }
     }
 
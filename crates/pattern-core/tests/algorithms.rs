@@ -3,15 +3,23 @@
 //!
 //! Corresponds to tasks T022, T022b, T027, T028, T035.
 
+use std::cell::Cell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
+use pattern_core::reconcile::default_subject_merge_strategy;
 use pattern_core::{
-    all_paths, betweenness_centrality, bfs, canonical_classifier, connected_components,
-    degree_centrality, dfs, directed, directed_reverse, from_patterns,
-    graph_query_from_pattern_graph, has_cycle, has_path, is_connected, is_neighbor,
-    minimum_spanning_tree, shortest_path, topological_sort, undirected, GraphQuery, GraphValue,
-    Pattern, PatternGraph, Subject, Symbol, TraversalDirection, TraversalWeight,
+    a_star, all_paths, annotate_centrality, articulation_points, betweenness_centrality,
+    betweenness_centrality_normalized, bfs, bfs_with_edges, bidirectional_bfs, bridges,
+    canonical_classifier, closeness_centrality, connected_components, contract_nodes,
+    degree_centrality, dfs, dfs_with_edges, directed, directed_reverse, find_cycles, from_patterns,
+    graph_query_from_pattern_graph, harmonic_centrality, has_cycle, has_path, is_bipartite,
+    is_connected, is_neighbor, isolated_nodes, k_shortest_paths, longest_path,
+    longest_path_weighted, minimum_spanning_tree, pagerank, pagerank_with_iterations,
+    parallel_edges, shortest_path, spanning_tree_pattern, strongly_connected_components,
+    topological_sort, undirected, weighted_betweenness_centrality, CentralityMeasure, GraphQuery,
+    GraphValue, IntraGroupEdges, Pattern, PatternGraph, Subject, Symbol, TraversalDirection,
+    TraversalWeight, Value, DEFAULT_CENTRALITY_PROPERTY,
 };
 
 // ============================================================================
@@ -121,6 +129,77 @@ fn dfs_visits_all_reachable_nodes() {
     assert_eq!(visited.len(), 3, "DFS from A visits all 3 nodes");
 }
 
+// ============================================================================
+// bfs_with_edges / dfs_with_edges
+// ============================================================================
+
+/// (node id, id of the relationship used to reach it) for each traversal step
+fn step_ids(
+    steps: &[(Pattern<Subject>, Option<Pattern<Subject>>)],
+) -> Vec<(String, Option<String>)> {
+    steps
+        .iter()
+        .map(|(node, edge)| {
+            (
+                node.value.identity.0.clone(),
+                edge.as_ref().map(|e| e.value.identity.0.clone()),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn bfs_with_edges_records_relationship_to_each_node() {
+    let gq = directed_chain_abc();
+    let a = (gq.query_node_by_id)(&Symbol("A".to_string())).expect("A");
+    let steps = bfs_with_edges(&gq, &directed(), &a);
+    assert_eq!(
+        step_ids(&steps),
+        [
+            ("A".to_string(), None),
+            ("B".to_string(), Some("AB".to_string())),
+            ("C".to_string(), Some("BC".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn traversal_with_edges_matches_plain_traversal() {
+    // Star around A plus a back edge C-D that must not appear in the tree
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("AC", node("A"), node("C")),
+        rel("AD", node("A"), node("D")),
+        rel("CD", node("C"), node("D")),
+    ]);
+    let a = (gq.query_node_by_id)(&Symbol("A".to_string())).expect("A");
+
+    for (steps, plain) in [
+        (
+            bfs_with_edges(&gq, &undirected(), &a),
+            bfs(&gq, &undirected(), &a),
+        ),
+        (
+            dfs_with_edges(&gq, &undirected(), &a),
+            dfs(&gq, &undirected(), &a),
+        ),
+    ] {
+        let nodes: Vec<Pattern<Subject>> = steps.iter().map(|(n, _)| n.clone()).collect();
+        assert_eq!(nodes, plain);
+        assert!(steps[0].1.is_none());
+
+        // Every later node is reached from an earlier node via one of its relationships
+        for (i, (node, edge)) in steps.iter().enumerate().skip(1) {
+            let edge = edge.as_ref().expect("non-start nodes have an edge");
+            let ends: Vec<&Symbol> = edge.elements.iter().map(|e| &e.value.identity).collect();
+            assert!(ends.contains(&&node.value.identity));
+            assert!(steps[..i]
+                .iter()
+                .any(|(earlier, _)| ends.contains(&&earlier.value.identity)));
+        }
+    }
+}
+
 // ============================================================================
 // T022: shortest_path correctness
 // ============================================================================
@@ -163,6 +242,284 @@ fn shortest_path_disconnected_returns_none() {
     assert!(path.is_none(), "no path between isolated nodes");
 }
 
+// ============================================================================
+// a_star
+// ============================================================================
+
+/// Build an undirected `n`×`n` grid whose nodes carry `x`/`y` coordinate properties.
+fn grid(n: i64) -> GraphQuery<Subject> {
+    let grid_node = |x: i64, y: i64| {
+        let mut s = subj(&format!("n{}_{}", x, y));
+        s.properties.insert("x".to_string(), Value::VInteger(x));
+        s.properties.insert("y".to_string(), Value::VInteger(y));
+        Pattern {
+            value: s,
+            elements: vec![],
+        }
+    };
+    let mut patterns = Vec::new();
+    for x in 0..n {
+        for y in 0..n {
+            if x + 1 < n {
+                let id = format!("h{}_{}", x, y);
+                patterns.push(rel(&id, grid_node(x, y), grid_node(x + 1, y)));
+            }
+            if y + 1 < n {
+                let id = format!("v{}_{}", x, y);
+                patterns.push(rel(&id, grid_node(x, y), grid_node(x, y + 1)));
+            }
+        }
+    }
+    make_gq(patterns)
+}
+
+fn coord(p: &Pattern<Subject>, key: &str) -> f64 {
+    match p.value.properties.get(key) {
+        Some(Value::VInteger(v)) => *v as f64,
+        _ => 0.0,
+    }
+}
+
+/// Undirected unit weight that counts how many edges were examined.
+fn counting_weight(count: Rc<Cell<usize>>) -> TraversalWeight<Subject> {
+    Rc::new(move |_rel: &Pattern<Subject>, _dir| {
+        count.set(count.get() + 1);
+        1.0
+    })
+}
+
+#[test]
+fn a_star_matches_shortest_path_cost_with_admissible_heuristic() {
+    let gq = grid(6);
+    let from = (gq.query_node_by_id)(&Symbol("n0_0".to_string())).expect("start");
+    let to = (gq.query_node_by_id)(&Symbol("n5_3".to_string())).expect("goal");
+    let manhattan = |p: &Pattern<Subject>| {
+        (coord(p, "x") - coord(&to, "x")).abs() + (coord(p, "y") - coord(&to, "y")).abs()
+    };
+
+    let dijkstra = shortest_path(&gq, &undirected(), &from, &to).expect("path");
+    let astar = a_star(&gq, &undirected(), &from, &to, manhattan).expect("path");
+    assert_eq!(astar.len(), dijkstra.len(), "A* path must be optimal");
+    assert_eq!(astar.first().unwrap().value.identity, from.value.identity);
+    assert_eq!(astar.last().unwrap().value.identity, to.value.identity);
+}
+
+#[test]
+fn a_star_explores_less_than_dijkstra() {
+    let gq = grid(6);
+    let from = (gq.query_node_by_id)(&Symbol("n0_0".to_string())).expect("start");
+    let to = (gq.query_node_by_id)(&Symbol("n5_0".to_string())).expect("goal");
+    let manhattan = |p: &Pattern<Subject>| {
+        (coord(p, "x") - coord(&to, "x")).abs() + (coord(p, "y") - coord(&to, "y")).abs()
+    };
+
+    let dijkstra_count = Rc::new(Cell::new(0));
+    let astar_count = Rc::new(Cell::new(0));
+    shortest_path(&gq, &counting_weight(dijkstra_count.clone()), &from, &to).expect("path");
+    a_star(
+        &gq,
+        &counting_weight(astar_count.clone()),
+        &from,
+        &to,
+        manhattan,
+    )
+    .expect("path");
+    assert!(
+        astar_count.get() < dijkstra_count.get(),
+        "A* examined {} edges, Dijkstra {}",
+        astar_count.get(),
+        dijkstra_count.get()
+    );
+}
+
+#[test]
+fn a_star_zero_heuristic_is_dijkstra() {
+    let gq = grid(4);
+    let from = (gq.query_node_by_id)(&Symbol("n0_0".to_string())).expect("start");
+    let to = (gq.query_node_by_id)(&Symbol("n3_3".to_string())).expect("goal");
+    let ids = |path: Vec<Pattern<Subject>>| -> Vec<Symbol> {
+        path.into_iter().map(|p| p.value.identity).collect()
+    };
+    assert_eq!(
+        ids(a_star(&gq, &undirected(), &from, &to, |_| 0.0).expect("path")),
+        ids(shortest_path(&gq, &undirected(), &from, &to).expect("path"))
+    );
+}
+
+#[test]
+fn a_star_respects_impassable_edges() {
+    let gq = chain_abc();
+    let a = (gq.query_node_by_id)(&Symbol("A".to_string())).expect("A");
+    let c = (gq.query_node_by_id)(&Symbol("C".to_string())).expect("C");
+    assert_eq!(
+        a_star(&gq, &directed(), &a, &c, |_| 0.0).map(|p| p.len()),
+        Some(3)
+    );
+    assert!(a_star(&gq, &directed(), &c, &a, |_| 0.0).is_none());
+    assert!(a_star(&gq, &directed_reverse(), &a, &c, |_| 0.0).is_none());
+}
+
+#[test]
+fn a_star_disconnected_returns_none() {
+    let gq = make_gq(vec![node("X"), node("Y")]);
+    let x = (gq.query_node_by_id)(&Symbol("X".to_string())).expect("X");
+    let y = (gq.query_node_by_id)(&Symbol("Y".to_string())).expect("Y");
+    assert!(a_star(&gq, &undirected(), &x, &y, |_| 0.0).is_none());
+    assert_eq!(
+        a_star(&gq, &undirected(), &x, &x, |_| 0.0).map(|p| p.len()),
+        Some(1)
+    );
+}
+
+// ============================================================================
+// k_shortest_paths
+// ============================================================================
+
+#[test]
+fn k_shortest_paths_in_ascending_cost_order() {
+    // Two near-equal routes A-B-D (2.0) and A-C-D (2.5), linked by a cheap B-C
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BD", node("B"), node("D")),
+        rel("AC", node("A"), node("C")),
+        rel("CD", node("C"), node("D")),
+        rel("BC", node("B"), node("C")),
+        rel("AD", node("A"), node("D")),
+    ]);
+    let costs: HashMap<&str, f64> = [
+        ("AB", 1.0),
+        ("BD", 1.0),
+        ("AC", 1.0),
+        ("CD", 1.5),
+        ("BC", 0.2),
+        ("AD", 5.0),
+    ]
+    .into();
+    let weight: TraversalWeight<Subject> =
+        Rc::new(move |rel: &Pattern<Subject>, _dir| costs[rel.value.identity.0.as_str()]);
+    let a = (gq.query_node_by_id)(&Symbol("A".to_string())).expect("A");
+    let d = (gq.query_node_by_id)(&Symbol("D".to_string())).expect("D");
+
+    let paths = k_shortest_paths(&gq, &weight, &a, &d, 3);
+    let ids: Vec<Vec<&str>> = paths.iter().map(|p| path_ids(p)).collect();
+    assert_eq!(
+        ids,
+        [
+            vec!["A", "B", "D"],
+            vec!["A", "C", "B", "D"],
+            vec!["A", "C", "D"]
+        ]
+    );
+    assert_eq!(paths[0], shortest_path(&gq, &weight, &a, &d).expect("path"));
+
+    // Only five simple paths exist
+    let paths = k_shortest_paths(&gq, &weight, &a, &d, 10);
+    let ids: Vec<Vec<&str>> = paths.iter().map(|p| path_ids(p)).collect();
+    assert_eq!(ids.len(), all_paths(&gq, &weight, &a, &d).len());
+    assert_eq!(ids[3], ["A", "B", "C", "D"]);
+    assert_eq!(ids[4], ["A", "D"]);
+}
+
+#[test]
+fn k_shortest_paths_edge_cases() {
+    let gq = chain_abc();
+    let a = (gq.query_node_by_id)(&Symbol("A".to_string())).expect("A");
+    let c = (gq.query_node_by_id)(&Symbol("C".to_string())).expect("C");
+    assert!(k_shortest_paths(&gq, &undirected(), &a, &c, 0).is_empty());
+    assert_eq!(k_shortest_paths(&gq, &undirected(), &a, &c, 5).len(), 1);
+    assert_eq!(
+        k_shortest_paths(&gq, &undirected(), &a, &a, 2),
+        vec![vec![a.clone()]]
+    );
+    assert!(k_shortest_paths(&gq, &directed(), &c, &a, 2).is_empty());
+}
+
+// ============================================================================
+// bidirectional_bfs
+// ============================================================================
+
+#[test]
+fn bidirectional_bfs_matches_shortest_path_length() {
+    let gq = grid(6);
+    let from = (gq.query_node_by_id)(&Symbol("n0_0".to_string())).expect("start");
+    for goal in ["n5_5", "n2_4", "n0_1", "n3_0"] {
+        let to = (gq.query_node_by_id)(&Symbol(goal.to_string())).expect("goal");
+        let expected = shortest_path(&gq, &undirected(), &from, &to).expect("path");
+        let path = bidirectional_bfs(&gq, &undirected(), &from, &to).expect("path");
+        assert_eq!(path.len(), expected.len(), "hops to {}", goal);
+        assert_eq!(path.first(), Some(&from));
+        assert_eq!(path.last(), Some(&to));
+        for pair in path.windows(2) {
+            assert!(is_neighbor(&gq, &undirected(), &pair[0], &pair[1]));
+        }
+    }
+}
+
+#[test]
+fn bidirectional_bfs_edge_cases() {
+    let gq = chain_abc();
+    let a = (gq.query_node_by_id)(&Symbol("A".to_string())).expect("A");
+    let c = (gq.query_node_by_id)(&Symbol("C".to_string())).expect("C");
+    assert_eq!(
+        bidirectional_bfs(&gq, &directed(), &a, &a),
+        Some(vec![a.clone()])
+    );
+    assert_eq!(
+        bidirectional_bfs(&gq, &directed(), &a, &c).map(|p| path_ids(&p).join("")),
+        Some("ABC".to_string())
+    );
+    assert!(bidirectional_bfs(&gq, &directed(), &c, &a).is_none());
+    assert!(bidirectional_bfs(&gq, &directed_reverse(), &a, &c).is_none());
+}
+
+#[test]
+fn bidirectional_bfs_explores_less_on_long_chain() {
+    // c0 → c1 → … → c199, each chain node with three dead-end leaves
+    let mut patterns = Vec::new();
+    for i in 0..200 {
+        if i + 1 < 200 {
+            let id = format!("c{}-c{}", i, i + 1);
+            let next = node(&format!("c{}", i + 1));
+            patterns.push(rel(&id, node(&format!("c{}", i)), next));
+        }
+        for j in 0..3 {
+            let leaf = node(&format!("leaf{}_{}", i, j));
+            patterns.push(rel(
+                &format!("l{}_{}", i, j),
+                node(&format!("c{}", i)),
+                leaf,
+            ));
+        }
+    }
+    let gq = make_gq(patterns);
+    let from = (gq.query_node_by_id)(&Symbol("c0".to_string())).expect("start");
+    let to = (gq.query_node_by_id)(&Symbol("c199".to_string())).expect("goal");
+    let counting_directed = |count: Rc<Cell<usize>>| -> TraversalWeight<Subject> {
+        Rc::new(move |_rel: &Pattern<Subject>, dir| {
+            count.set(count.get() + 1);
+            match dir {
+                TraversalDirection::Forward => 1.0,
+                TraversalDirection::Backward => f64::INFINITY,
+            }
+        })
+    };
+
+    let single_count = Rc::new(Cell::new(0));
+    let bidi_count = Rc::new(Cell::new(0));
+    let single =
+        shortest_path(&gq, &counting_directed(single_count.clone()), &from, &to).expect("path");
+    let bidi =
+        bidirectional_bfs(&gq, &counting_directed(bidi_count.clone()), &from, &to).expect("path");
+    assert_eq!(bidi.len(), 200);
+    assert_eq!(bidi, single);
+    assert!(
+        bidi_count.get() < single_count.get(),
+        "bidirectional examined {} edges, single-direction {}",
+        bidi_count.get(),
+        single_count.get()
+    );
+}
+
 // ============================================================================
 // T022: has_path
 // ============================================================================
@@ -208,6 +565,261 @@ fn connected_components_two_components() {
     assert_eq!(components.len(), 2, "two disconnected pairs → 2 components");
 }
 
+// ============================================================================
+// strongly_connected_components
+// ============================================================================
+
+fn component_ids(components: Vec<Vec<Pattern<Subject>>>) -> Vec<Vec<String>> {
+    components
+        .into_iter()
+        .map(|c| c.into_iter().map(|n| n.value.identity.0).collect())
+        .collect()
+}
+
+#[test]
+fn strongly_connected_components_cycle_is_one_component() {
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("CA", node("C"), node("A")),
+    ]);
+    assert_eq!(
+        component_ids(strongly_connected_components(&gq)),
+        vec![vec!["A", "B", "C"]]
+    );
+}
+
+#[test]
+fn strongly_connected_components_dag_is_all_singletons() {
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("AC", node("A"), node("C")),
+        rel("BD", node("B"), node("D")),
+        rel("CD", node("C"), node("D")),
+    ]);
+    // Each component precedes the components with a relationship into it
+    assert_eq!(
+        component_ids(strongly_connected_components(&gq)),
+        vec![vec!["D"], vec!["B"], vec!["C"], vec!["A"]]
+    );
+}
+
+#[test]
+fn strongly_connected_components_mixed_graph() {
+    // {A, B} cycle → {C, D, E} cycle, plus isolated X
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BA", node("B"), node("A")),
+        rel("BC", node("B"), node("C")),
+        rel("CD", node("C"), node("D")),
+        rel("DE", node("D"), node("E")),
+        rel("EC", node("E"), node("C")),
+        node("X"),
+    ]);
+    assert_eq!(
+        component_ids(strongly_connected_components(&gq)),
+        vec![vec!["C", "D", "E"], vec!["A", "B"], vec!["X"]]
+    );
+}
+
+#[test]
+fn strongly_connected_components_deep_chain_does_not_overflow() {
+    let n = 20_000;
+    let patterns = (0..n)
+        .map(|i| {
+            rel(
+                &format!("r{i:05}"),
+                node(&format!("n{i:05}")),
+                node(&format!("n{:05}", i + 1)),
+            )
+        })
+        .chain(std::iter::once(rel(
+            "back",
+            node(&format!("n{n:05}")),
+            node("n00000"),
+        )))
+        .collect();
+    let components = strongly_connected_components(&make_gq(patterns));
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].len(), n + 1);
+}
+
+// ============================================================================
+// articulation_points / bridges
+// ============================================================================
+
+fn ids_of(patterns: Vec<Pattern<Subject>>) -> Vec<String> {
+    patterns.into_iter().map(|p| p.value.identity.0).collect()
+}
+
+#[test]
+fn articulation_points_and_bridges_on_chain() {
+    let gq = chain_abc();
+    assert_eq!(ids_of(articulation_points(&gq, &undirected())), ["B"]);
+    assert_eq!(ids_of(bridges(&gq, &undirected())), ["AB", "BC"]);
+
+    // Direction is ignored: a directed weight still connects both endpoints
+    assert_eq!(ids_of(articulation_points(&gq, &directed())), ["B"]);
+    assert_eq!(ids_of(bridges(&gq, &directed_reverse())), ["AB", "BC"]);
+}
+
+#[test]
+fn articulation_points_and_bridges_on_triangle() {
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("CA", node("C"), node("A")),
+    ]);
+    assert!(articulation_points(&gq, &undirected()).is_empty());
+    assert!(bridges(&gq, &undirected()).is_empty());
+}
+
+#[test]
+fn articulation_points_and_bridges_on_joined_triangles() {
+    // Triangles A-B-C and D-E-F joined by C-D, plus a parallel pair F=G and a self-loop on G
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("CA", node("C"), node("A")),
+        rel("CD", node("C"), node("D")),
+        rel("DE", node("D"), node("E")),
+        rel("EF", node("E"), node("F")),
+        rel("FD", node("F"), node("D")),
+        rel("FG1", node("F"), node("G")),
+        rel("FG2", node("G"), node("F")),
+        rel("GG", node("G"), node("G")),
+    ]);
+    assert_eq!(
+        ids_of(articulation_points(&gq, &undirected())),
+        ["C", "D", "F"]
+    );
+    assert_eq!(ids_of(bridges(&gq, &undirected())), ["CD"]);
+}
+
+#[test]
+fn bridges_skip_impassable_relationships() {
+    // With AC impassable, the triangle is a chain A-B-C again
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("AC", node("A"), node("C")),
+    ]);
+    let weight: TraversalWeight<Subject> = Rc::new(|rel: &Pattern<Subject>, _dir| {
+        if rel.value.identity.0 == "AC" {
+            f64::INFINITY
+        } else {
+            1.0
+        }
+    });
+    assert_eq!(ids_of(articulation_points(&gq, &weight)), ["B"]);
+    assert_eq!(ids_of(bridges(&gq, &weight)), ["AB", "BC"]);
+}
+
+// ============================================================================
+// is_bipartite
+// ============================================================================
+
+#[test]
+fn is_bipartite_even_cycle() {
+    // Square A→B→C→D→A, directions mixed to show they are ignored
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("CB", node("C"), node("B")),
+        rel("CD", node("C"), node("D")),
+        rel("AD", node("A"), node("D")),
+    ]);
+    let (left, right) = is_bipartite(&gq, &directed()).expect("even cycle is bipartite");
+    assert_eq!(ids_of(left), ["A", "C"]);
+    assert_eq!(ids_of(right), ["B", "D"]);
+}
+
+#[test]
+fn is_bipartite_rejects_odd_cycles() {
+    let triangle = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("CA", node("C"), node("A")),
+    ]);
+    assert!(is_bipartite(&triangle, &undirected()).is_none());
+
+    let self_loop = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BB", node("B"), node("B")),
+    ]);
+    assert!(is_bipartite(&self_loop, &undirected()).is_none());
+
+    // An impassable edge does not close the odd cycle
+    let weight: TraversalWeight<Subject> = Rc::new(|rel: &Pattern<Subject>, _dir| {
+        if rel.value.identity.0 == "CA" {
+            f64::INFINITY
+        } else {
+            1.0
+        }
+    });
+    let (left, right) = is_bipartite(&triangle, &weight).expect("chain is bipartite");
+    assert_eq!(ids_of(left), ["A", "C"]);
+    assert_eq!(ids_of(right), ["B"]);
+}
+
+#[test]
+fn is_bipartite_checks_each_component() {
+    // Two separate paths and an isolated node: each component starts on the left
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("XY", node("X"), node("Y")),
+        rel("YZ", node("Y"), node("Z")),
+        node("M"),
+    ]);
+    let (left, right) = is_bipartite(&gq, &undirected()).expect("bipartite");
+    assert_eq!(ids_of(left), ["A", "M", "X", "Z"]);
+    assert_eq!(ids_of(right), ["B", "Y"]);
+
+    // An odd cycle in just one component is enough to fail
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("XY", node("X"), node("Y")),
+        rel("YZ", node("Y"), node("Z")),
+        rel("ZX", node("Z"), node("X")),
+    ]);
+    assert!(is_bipartite(&gq, &undirected()).is_none());
+}
+
+// ============================================================================
+// isolated_nodes
+// ============================================================================
+
+#[test]
+fn isolated_nodes_returns_only_degree_zero_nodes() {
+    let gq = make_gq(vec![rel("AB", node("A"), node("B")), node("X"), node("Y")]);
+    let mut ids: Vec<Symbol> = isolated_nodes(&gq)
+        .iter()
+        .map(|n| n.value.identity.clone())
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec![Symbol("X".to_string()), Symbol("Y".to_string())]);
+}
+
+#[test]
+fn isolated_nodes_empty_when_all_connected() {
+    let gq = chain_abc();
+    assert!(isolated_nodes(&gq).is_empty());
+}
+
+#[test]
+fn isolated_nodes_are_singleton_components() {
+    let gq = make_gq(vec![rel("AB", node("A"), node("B")), node("X")]);
+    let singletons: Vec<Symbol> = connected_components(&gq, &undirected())
+        .into_iter()
+        .filter(|c| c.len() == 1)
+        .map(|c| c[0].value.identity.clone())
+        .collect();
+    let isolated: Vec<Symbol> = isolated_nodes(&gq)
+        .iter()
+        .map(|n| n.value.identity.clone())
+        .collect();
+    assert_eq!(isolated, singletons);
+}
+
 // ============================================================================
 // T022: degree_centrality
 // ============================================================================
@@ -311,9 +923,18 @@ fn hand_built_triangle() -> GraphQuery<Subject> {
 
     let query_containers = Rc::new(|_: &Pattern<Subject>| vec![]);
 
-    GraphQuery {
-        query_nodes,
-        query_relationships,
+    let nodes_label = Rc::clone(&all_nodes);
+    let query_nodes_by_label = Rc::new(move |label: &str| {
+        nodes_label
+            .iter()
+            .filter(|n| n.value.labels.contains(label))
+            .cloned()
+            .collect()
+    });
+
+    GraphQuery {
+        query_nodes,
+        query_relationships,
         query_incident_rels,
         query_source,
         query_target,
@@ -321,6 +942,7 @@ fn hand_built_triangle() -> GraphQuery<Subject> {
         query_node_by_id,
         query_relationship_by_id,
         query_containers,
+        query_nodes_by_label,
     }
 }
 
@@ -445,6 +1067,90 @@ fn topological_sort_on_dag() {
     assert!(pos[&Symbol("C".to_string())] < pos[&Symbol("D".to_string())]);
 }
 
+#[test]
+fn topological_sort_is_lexicographically_smallest() {
+    // C→A plus independent B and D→E: the smallest ready identity always goes next
+    let gq = make_gq(vec![
+        rel("CA", node("C"), node("A")),
+        node("B"),
+        rel("DE", node("D"), node("E")),
+    ]);
+    let ids: Vec<Symbol> = topological_sort(&gq)
+        .expect("DAG")
+        .into_iter()
+        .map(|n| n.value.identity)
+        .collect();
+    let expected: Vec<Symbol> = ["B", "C", "A", "D", "E"]
+        .iter()
+        .map(|s| Symbol(s.to_string()))
+        .collect();
+    assert_eq!(ids, expected);
+}
+
+#[test]
+fn topological_sort_is_independent_of_input_order() {
+    let rels = vec![
+        rel("AB", node("A"), node("B")),
+        rel("AC", node("A"), node("C")),
+        rel("BD", node("B"), node("D")),
+        rel("CD", node("C"), node("D")),
+        node("E"),
+    ];
+    let ids = |patterns: Vec<Pattern<Subject>>| -> Vec<Symbol> {
+        topological_sort(&make_gq(patterns))
+            .expect("DAG")
+            .into_iter()
+            .map(|n| n.value.identity)
+            .collect()
+    };
+    let forward = ids(rels.clone());
+    let reversed = ids(rels.into_iter().rev().collect());
+    assert_eq!(forward, reversed);
+    assert_eq!(
+        forward,
+        ["A", "B", "C", "D", "E"]
+            .iter()
+            .map(|s| Symbol(s.to_string()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn topological_sort_is_stable_across_repeated_calls() {
+    // A wide DAG has many ready nodes at once; every graph built below gets
+    // fresh HashMap seeds, so any dependence on iteration order would show
+    let dag = || {
+        let mut patterns: Vec<Pattern<Subject>> = (0..10)
+            .map(|i| rel(&format!("r{}", i), node("root"), node(&format!("n{}", i))))
+            .collect();
+        patterns.push(rel("sink", node("n3"), node("z")));
+        patterns.push(node("isolated"));
+        patterns
+    };
+    let ids = |gq: &GraphQuery<Subject>| -> Vec<Symbol> {
+        topological_sort(gq)
+            .expect("DAG")
+            .into_iter()
+            .map(|n| n.value.identity)
+            .collect()
+    };
+
+    let gq = make_gq(dag());
+    let first = ids(&gq);
+    for _ in 0..100 {
+        assert_eq!(ids(&gq), first);
+        assert_eq!(ids(&make_gq(dag())), first);
+    }
+    assert_eq!(first[0], Symbol("isolated".to_string()));
+    assert_eq!(first[1], Symbol("root".to_string()));
+}
+
+#[test]
+fn topological_sort_self_loop_returns_none() {
+    let gq = make_gq(vec![rel("AA", node("A"), node("A")), node("B")]);
+    assert!(topological_sort(&gq).is_none());
+}
+
 #[test]
 fn topological_sort_cyclic_returns_none() {
     // Cycle: A→B→C→A
@@ -473,6 +1179,117 @@ fn has_cycle_false_on_dag() {
     assert!(!has_cycle(&gq), "linear chain A→B→C has no cycle");
 }
 
+fn path_ids(path: &[Pattern<Subject>]) -> Vec<&str> {
+    path.iter().map(|n| n.value.identity.0.as_str()).collect()
+}
+
+#[test]
+fn find_cycles_self_loop_and_dag() {
+    let self_loop = make_gq(vec![rel("AA", node("A"), node("A"))]);
+    let cycles = find_cycles(&self_loop);
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(path_ids(&cycles[0]), ["A"]);
+
+    assert!(find_cycles(&chain_abc()).is_empty());
+    let diamond = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("AC", node("A"), node("C")),
+        rel("BD", node("B"), node("D")),
+        rel("CD", node("C"), node("D")),
+    ]);
+    assert!(find_cycles(&diamond).is_empty());
+}
+
+#[test]
+fn find_cycles_enumerates_each_elementary_cycle_once() {
+    // A⇄B, B→C→A, C→C, and a parallel B→C; D hangs off the cycle
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BA", node("B"), node("A")),
+        rel("BC", node("B"), node("C")),
+        rel("BC2", node("B"), node("C")),
+        rel("CA", node("C"), node("A")),
+        rel("CC", node("C"), node("C")),
+        rel("CD", node("C"), node("D")),
+    ]);
+    let cycles = find_cycles(&gq);
+    let ids: Vec<Vec<&str>> = cycles.iter().map(|c| path_ids(c)).collect();
+    assert_eq!(ids, [vec!["A", "B"], vec!["A", "B", "C"], vec!["C"]]);
+    assert_eq!(has_cycle(&gq), !cycles.is_empty());
+}
+
+#[test]
+fn find_cycles_complete_digraph_count() {
+    // The complete digraph on 4 nodes has 6 + 8 + 6 = 20 elementary cycles
+    let ids = ["A", "B", "C", "D"];
+    let mut patterns = Vec::new();
+    for a in ids {
+        for b in ids {
+            if a != b {
+                patterns.push(rel(&format!("{}{}", a, b), node(a), node(b)));
+            }
+        }
+    }
+    let cycles = find_cycles(&make_gq(patterns));
+    assert_eq!(cycles.len(), 20);
+    let distinct: HashSet<Vec<Symbol>> = cycles
+        .iter()
+        .map(|c| c.iter().map(|n| n.value.identity.clone()).collect())
+        .collect();
+    assert_eq!(distinct.len(), 20);
+}
+
+#[test]
+fn longest_path_weighted_takes_costliest_branch() {
+    // Diamond A→{B,C}→D: A→B→D costs 1+2, A→C→D costs 2+3
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("AC", node("A"), node("C")),
+        rel("BD", node("B"), node("D")),
+        rel("CD", node("C"), node("D")),
+    ]);
+    let costs: HashMap<&str, f64> = [("AB", 1.0), ("BD", 2.0), ("AC", 2.0), ("CD", 3.0)].into();
+    let weight: TraversalWeight<Subject> = Rc::new(move |rel: &Pattern<Subject>, dir| match dir {
+        TraversalDirection::Forward => costs[rel.value.identity.0.as_str()],
+        TraversalDirection::Backward => f64::INFINITY,
+    });
+
+    let path = longest_path_weighted(&gq, &weight).expect("diamond is a DAG");
+    assert_eq!(path_ids(&path), ["A", "C", "D"]);
+
+    // Unweighted, both branches have two hops; the first in topological order wins
+    let path = longest_path(&gq).expect("diamond is a DAG");
+    assert_eq!(path_ids(&path), ["A", "B", "D"]);
+}
+
+#[test]
+fn longest_path_counts_hops() {
+    // A→D directly, and A→B→C→D; plus a disconnected E
+    let gq = make_gq(vec![
+        rel("AD", node("A"), node("D")),
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("CD", node("C"), node("D")),
+        node("E"),
+    ]);
+    let path = longest_path(&gq).expect("DAG");
+    assert_eq!(path_ids(&path), ["A", "B", "C", "D"]);
+
+    let isolated = longest_path(&make_gq(vec![node("B"), node("A")])).expect("DAG");
+    assert_eq!(path_ids(&isolated), ["A"]);
+    assert_eq!(longest_path(&make_gq(vec![])).expect("DAG"), vec![]);
+}
+
+#[test]
+fn longest_path_returns_none_on_cycle() {
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BA", node("B"), node("A")),
+    ]);
+    assert!(longest_path(&gq).is_none());
+    assert!(longest_path_weighted(&gq, &directed()).is_none());
+}
+
 #[test]
 fn all_paths_finds_all_simple_paths() {
     // Two paths from A to C: A→B→C and A→C (direct)
@@ -563,6 +1380,123 @@ fn minimum_spanning_tree_empty() {
     assert_eq!(mst.len(), 0, "no edges → empty MST");
 }
 
+// ============================================================================
+// spanning_tree_pattern
+// ============================================================================
+
+/// Render a tree pattern as `id[child, ...]` with children sorted, for compact assertions.
+fn tree_shape(p: &Pattern<Subject>) -> String {
+    if p.elements.is_empty() {
+        return p.value.identity.0.clone();
+    }
+    let mut children: Vec<String> = p.elements.iter().map(tree_shape).collect();
+    children.sort();
+    format!("{}[{}]", p.value.identity.0, children.join(", "))
+}
+
+#[test]
+fn spanning_tree_pattern_follows_bfs_discovery() {
+    // A→B, A→C, B→D, D→E, E→C: the E→C edge reaches an already-visited node
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("AC", node("A"), node("C")),
+        rel("BD", node("B"), node("D")),
+        rel("DE", node("D"), node("E")),
+        rel("EC", node("E"), node("C")),
+    ]);
+    let a = (gq.query_node_by_id)(&Symbol("A".to_string())).expect("A");
+
+    let tree = spanning_tree_pattern(&gq, &directed(), &a);
+    assert_eq!(tree_shape(&tree), "A[B[D[E]], C]");
+    assert_eq!(tree.size(), bfs(&gq, &directed(), &a).len());
+}
+
+#[test]
+fn spanning_tree_pattern_omits_cycle_edges() {
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("CA", node("C"), node("A")),
+    ]);
+    let b = (gq.query_node_by_id)(&Symbol("B".to_string())).expect("B");
+
+    assert_eq!(
+        tree_shape(&spanning_tree_pattern(&gq, &directed(), &b)),
+        "B[C[A]]"
+    );
+    assert_eq!(
+        tree_shape(&spanning_tree_pattern(&gq, &undirected(), &b)),
+        "B[A, C]"
+    );
+}
+
+#[test]
+fn spanning_tree_pattern_isolated_root_is_atomic() {
+    let gq = make_gq(vec![node("A"), rel("BC", node("B"), node("C"))]);
+    let a = (gq.query_node_by_id)(&Symbol("A".to_string())).expect("A");
+
+    let tree = spanning_tree_pattern(&gq, &undirected(), &a);
+    assert!(tree.is_atomic());
+    assert_eq!(tree.value, subj("A"));
+}
+
+// ============================================================================
+// parallel_edges
+// ============================================================================
+
+fn typed_rel(id: &str, label: &str, src: &str, tgt: &str) -> Pattern<Subject> {
+    let mut r = rel(id, node(src), node(tgt));
+    r.value.labels.insert(label.to_string());
+    r
+}
+
+fn group_ids(groups: &[Vec<Pattern<Subject>>]) -> Vec<Vec<&str>> {
+    groups
+        .iter()
+        .map(|g| g.iter().map(|r| r.value.identity.as_str()).collect())
+        .collect()
+}
+
+#[test]
+fn parallel_edges_groups_same_endpoints_and_type() {
+    let gq = make_gq(vec![
+        typed_rel("r2", "KNOWS", "A", "B"),
+        typed_rel("r1", "KNOWS", "A", "B"),
+        typed_rel("r3", "LIKES", "A", "B"),
+        typed_rel("r4", "KNOWS", "B", "C"),
+        typed_rel("r5", "KNOWS", "B", "C"),
+        typed_rel("r6", "KNOWS", "C", "D"),
+    ]);
+
+    assert_eq!(
+        group_ids(&parallel_edges(&gq, false)),
+        [vec!["r1", "r2"], vec!["r4", "r5"]]
+    );
+}
+
+#[test]
+fn parallel_edges_direction_matters_unless_undirected() {
+    let gq = make_gq(vec![
+        typed_rel("ab", "KNOWS", "A", "B"),
+        typed_rel("ba", "KNOWS", "B", "A"),
+    ]);
+
+    assert!(parallel_edges(&gq, false).is_empty());
+    assert_eq!(group_ids(&parallel_edges(&gq, true)), [vec!["ab", "ba"]]);
+}
+
+#[test]
+fn parallel_edges_self_loops() {
+    let gq = make_gq(vec![
+        typed_rel("l1", "SELF", "A", "A"),
+        typed_rel("l2", "SELF", "A", "A"),
+        typed_rel("l3", "OTHER", "A", "A"),
+    ]);
+
+    assert_eq!(group_ids(&parallel_edges(&gq, false)), [vec!["l1", "l2"]]);
+    assert_eq!(group_ids(&parallel_edges(&gq, true)), [vec!["l1", "l2"]]);
+}
+
 // ============================================================================
 // T035: betweenness_centrality (US3)
 // ============================================================================
@@ -591,6 +1525,428 @@ fn betweenness_centrality_path_graph() {
     );
 }
 
+#[test]
+fn betweenness_centrality_normalized_undirected_and_directed() {
+    // Undirected A-B-C: B lies on both orderings of (A, C), the maximum possible
+    let centrality = betweenness_centrality_normalized(&chain_abc(), &undirected());
+    assert!((centrality[&Symbol("B".to_string())] - 1.0).abs() < 1e-9);
+    assert_eq!(centrality[&Symbol("A".to_string())], 0.0);
+    assert_eq!(centrality[&Symbol("C".to_string())], 0.0);
+
+    // Directed A→B→C: B lies only on A→C, one of the two ordered pairs
+    let centrality = betweenness_centrality_normalized(&directed_chain_abc(), &directed());
+    assert!((centrality[&Symbol("B".to_string())] - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn betweenness_centrality_normalized_small_graphs_score_zero() {
+    let single = make_gq(vec![node("A")]);
+    let pair = make_gq(vec![rel("AB", node("A"), node("B"))]);
+    for gq in [single, pair] {
+        let centrality = betweenness_centrality_normalized(&gq, &undirected());
+        assert!(!centrality.is_empty());
+        assert!(centrality.values().all(|&score| score == 0.0));
+    }
+}
+
+#[test]
+fn weighted_betweenness_follows_cheaper_two_hop_route() {
+    // Undirected A-C costs 10; the detour A-B-C costs 1 + 1
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("AC", node("A"), node("C")),
+    ]);
+    let weight: TraversalWeight<Subject> = Rc::new(|rel: &Pattern<Subject>, _dir| {
+        if rel.value.identity == Symbol("AC".to_string()) {
+            10.0
+        } else {
+            1.0
+        }
+    });
+
+    // Counting hops, A-C is direct and nothing lies between A and C
+    let unit = betweenness_centrality(&gq, &weight);
+    assert_eq!(unit[&Symbol("B".to_string())], 0.0);
+
+    // By cost, B carries both orderings of (A, C)
+    let weighted = weighted_betweenness_centrality(&gq, &weight);
+    assert!((weighted[&Symbol("B".to_string())] - 2.0).abs() < 1e-9);
+    assert_eq!(weighted[&Symbol("A".to_string())], 0.0);
+    assert_eq!(weighted[&Symbol("C".to_string())], 0.0);
+
+    // With equal costs it matches the unit-weight result
+    let weighted = weighted_betweenness_centrality(&gq, &undirected());
+    assert_eq!(weighted, betweenness_centrality(&gq, &undirected()));
+}
+
+// ============================================================================
+// harmonic_centrality
+// ============================================================================
+
+#[test]
+fn harmonic_centrality_path_graph() {
+    // Path: A-B-C (undirected): A reaches B at 1 and C at 2; B reaches both at 1
+    let centrality = harmonic_centrality(&chain_abc(), &undirected());
+    assert!((centrality[&Symbol("A".to_string())] - 1.5).abs() < 1e-9);
+    assert!((centrality[&Symbol("B".to_string())] - 2.0).abs() < 1e-9);
+    assert!((centrality[&Symbol("C".to_string())] - 1.5).abs() < 1e-9);
+}
+
+#[test]
+fn harmonic_centrality_disconnected_graph() {
+    // A-B and C-D are separate components; X is isolated
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("CD", node("C"), node("D")),
+        node("X"),
+    ]);
+    let centrality = harmonic_centrality(&gq, &undirected());
+    assert_eq!(centrality.len(), 5);
+    assert!((centrality[&Symbol("A".to_string())] - 1.0).abs() < 1e-9);
+    assert!((centrality[&Symbol("D".to_string())] - 1.0).abs() < 1e-9);
+    assert_eq!(centrality[&Symbol("X".to_string())], 0.0);
+}
+
+#[test]
+fn harmonic_centrality_respects_direction_and_weight() {
+    // Directed A→B→C: A reaches B (1) and C (2); C reaches nothing
+    let directed_scores = harmonic_centrality(&chain_abc(), &directed());
+    assert!((directed_scores[&Symbol("A".to_string())] - 1.5).abs() < 1e-9);
+    assert_eq!(directed_scores[&Symbol("C".to_string())], 0.0);
+
+    // Doubling every edge cost halves every score
+    let double: TraversalWeight<Subject> = Rc::new(|_rel, _dir| 2.0);
+    let doubled = harmonic_centrality(&chain_abc(), &double);
+    assert!((doubled[&Symbol("B".to_string())] - 1.0).abs() < 1e-9);
+}
+
+// ============================================================================
+// closeness_centrality / pagerank
+// ============================================================================
+
+#[test]
+fn closeness_centrality_path_graph() {
+    // Undirected A-B-C: A reaches 2 nodes at total cost 3; B reaches 2 at cost 2
+    let centrality = closeness_centrality(&chain_abc(), &undirected());
+    assert!((centrality[&Symbol("A".to_string())] - 2.0 / 3.0).abs() < 1e-9);
+    assert!((centrality[&Symbol("B".to_string())] - 1.0).abs() < 1e-9);
+
+    // Directed: C reaches nothing; B reaches only C, half of the other nodes
+    let centrality = closeness_centrality(&chain_abc(), &directed());
+    assert_eq!(centrality[&Symbol("C".to_string())], 0.0);
+    assert!((centrality[&Symbol("B".to_string())] - 0.5).abs() < 1e-9);
+
+    // An isolated node scores 0 and halves B's reach
+    let centrality = closeness_centrality(
+        &make_gq(vec![
+            rel("AB", node("A"), node("B")),
+            rel("BC", node("B"), node("C")),
+            node("X"),
+        ]),
+        &undirected(),
+    );
+    assert_eq!(centrality[&Symbol("X".to_string())], 0.0);
+    assert!((centrality[&Symbol("B".to_string())] - 2.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn pagerank_scores_sum_to_one_and_favor_targets() {
+    // Everyone links to hub H; H links back to A
+    let gq = make_gq(vec![
+        rel("AH", node("A"), node("H")),
+        rel("BH", node("B"), node("H")),
+        rel("CH", node("C"), node("H")),
+        rel("HA", node("H"), node("A")),
+    ]);
+    let ranks = pagerank(&gq, &directed(), 0.85);
+    assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+
+    let h = ranks[&Symbol("H".to_string())];
+    assert!(ranks.iter().all(|(id, &r)| id.0 == "H" || h > r));
+    assert!(ranks[&Symbol("A".to_string())] > ranks[&Symbol("B".to_string())]);
+
+    // Undirected symmetric cycle: all ranks equal
+    let cycle = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("CA", node("C"), node("A")),
+    ]);
+    let ranks = pagerank(&cycle, &undirected(), 0.85);
+    assert!(ranks.values().all(|r| (r - 1.0 / 3.0).abs() < 1e-9));
+}
+
+#[test]
+fn pagerank_with_iterations_star_hub_dominates() {
+    // Four spokes link to hub H, which is dangling
+    let gq = make_gq(
+        ["A", "B", "C", "D"]
+            .iter()
+            .map(|s| rel(&format!("{s}H"), node(s), node("H")))
+            .collect(),
+    );
+    let h = Symbol("H".to_string());
+
+    let uniform = pagerank_with_iterations(&gq, &directed(), 0.85, 0);
+    assert!(uniform.values().all(|r| (r - 0.2).abs() < 1e-12));
+
+    let ranks = pagerank_with_iterations(&gq, &directed(), 0.85, 100);
+    assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(ranks.iter().all(|(id, &r)| *id == h || ranks[&h] > 3.0 * r));
+    let spoke = ranks[&Symbol("A".to_string())];
+    assert!(ranks
+        .iter()
+        .all(|(id, &r)| *id == h || (r - spoke).abs() < 1e-12));
+    assert_eq!(ranks, pagerank(&gq, &directed(), 0.85));
+}
+
+// ============================================================================
+// annotate_centrality
+// ============================================================================
+
+fn centrality_of(pattern: &Pattern<Subject>) -> f64 {
+    match pattern.value.properties.get(DEFAULT_CENTRALITY_PROPERTY) {
+        Some(Value::VDecimal(score)) => *score,
+        other => panic!(
+            "missing centrality on {:?}: {:?}",
+            pattern.value.identity, other
+        ),
+    }
+}
+
+#[test]
+fn annotate_centrality_writes_scores_on_every_node_occurrence() {
+    let patterns = vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        node("X"),
+    ];
+    let annotated = annotate_centrality(
+        patterns,
+        CentralityMeasure::Betweenness,
+        &undirected(),
+        DEFAULT_CENTRALITY_PROPERTY,
+    );
+
+    // Relationships are untouched; both occurrences of B carry the same score
+    assert!(annotated[0].value.properties.is_empty());
+    assert!((centrality_of(&annotated[0].elements[1]) - 1.0 / 3.0).abs() < 1e-9);
+    assert_eq!(
+        centrality_of(&annotated[0].elements[1]),
+        centrality_of(&annotated[1].elements[0])
+    );
+    assert_eq!(centrality_of(&annotated[0].elements[0]), 0.0);
+    assert_eq!(centrality_of(&annotated[2]), 0.0);
+}
+
+#[test]
+fn annotate_centrality_scores_anonymous_nodes_by_assigned_identity() {
+    // A - () and () - B, as in `(A)-[L]-() ()-[R]-(B)`
+    let patterns = vec![rel("L", node("A"), node("")), rel("R", node(""), node("B"))];
+    let annotated = annotate_centrality(
+        patterns,
+        CentralityMeasure::Degree,
+        &undirected(),
+        DEFAULT_CENTRALITY_PROPERTY,
+    );
+
+    // The two `()` occurrences are distinct nodes, each with one relationship,
+    // and keep their empty identity
+    let anonymous = [&annotated[0].elements[1], &annotated[1].elements[0]];
+    for node in anonymous {
+        assert!(node.value.identity.0.is_empty());
+        assert_eq!(
+            centrality_of(node),
+            centrality_of(&annotated[0].elements[0])
+        );
+    }
+}
+
+#[test]
+fn annotate_centrality_uses_given_property_and_is_deterministic() {
+    let patterns = vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("CD", node("C"), node("D")),
+        rel("DA", node("D"), node("A")),
+        rel("AC", node("A"), node("C")),
+    ];
+    let measure = CentralityMeasure::PageRank { damping: 0.85 };
+
+    let first = annotate_centrality(patterns.clone(), measure, &directed(), "rank");
+    for _ in 0..5 {
+        assert_eq!(
+            annotate_centrality(patterns.clone(), measure, &directed(), "rank"),
+            first
+        );
+    }
+    let a = &first[0].elements[0];
+    assert!(a.value.properties.contains_key("rank"));
+    assert!(!a.value.properties.contains_key(DEFAULT_CENTRALITY_PROPERTY));
+}
+
+// ============================================================================
+// Node contraction
+// ============================================================================
+
+fn labeled(id: &str, label: &str, key: &str, value: i64) -> Pattern<Subject> {
+    let mut p = node(id);
+    p.value.labels.insert(label.to_string());
+    p.value
+        .properties
+        .insert(key.to_string(), Value::VInteger(value));
+    p
+}
+
+/// (relationship, source, target) identities, sorted
+fn edge_ids(patterns: &[Pattern<Subject>]) -> Vec<(String, String, String)> {
+    let mut edges: Vec<_> = patterns
+        .iter()
+        .filter(|p| p.elements.len() == 2)
+        .map(|p| {
+            (
+                p.value.identity.0.clone(),
+                p.elements[0].value.identity.0.clone(),
+                p.elements[1].value.identity.0.clone(),
+            )
+        })
+        .collect();
+    edges.sort();
+    edges
+}
+
+fn node_ids(patterns: &[Pattern<Subject>]) -> Vec<String> {
+    let mut ids: Vec<_> = patterns
+        .iter()
+        .filter(|p| p.elements.is_empty())
+        .map(|p| p.value.identity.0.clone())
+        .collect();
+    ids.sort();
+    ids
+}
+
+fn triple(r: &str, s: &str, t: &str) -> (String, String, String) {
+    (r.to_string(), s.to_string(), t.to_string())
+}
+
+#[test]
+fn contract_nodes_merges_subjects_and_rewires_relationships() {
+    let a = labeled("A", "Person", "age", 30);
+    let b = labeled("B", "Employee", "dept", 7);
+    let gq = make_gq(vec![
+        rel("AX", a.clone(), node("X")),
+        rel("XB", node("X"), b.clone()),
+        rel("BY", b, node("Y")),
+        a,
+    ]);
+    let group = [Symbol("A".to_string()), Symbol("B".to_string())];
+    let contracted = contract_nodes(
+        &gq,
+        &group,
+        Symbol("AB".to_string()),
+        &default_subject_merge_strategy(),
+        IntraGroupEdges::Drop,
+    );
+
+    assert_eq!(node_ids(&contracted), ["AB", "X", "Y"]);
+    assert_eq!(
+        edge_ids(&contracted),
+        [
+            triple("AX", "AB", "X"),
+            triple("BY", "AB", "Y"),
+            triple("XB", "X", "AB")
+        ]
+    );
+
+    let merged = contracted
+        .iter()
+        .find(|p| p.value.identity.0 == "AB")
+        .unwrap();
+    let labels: HashSet<String> = ["Person", "Employee"].map(String::from).into();
+    assert_eq!(merged.value.labels, labels);
+    assert_eq!(merged.value.properties["age"], Value::VInteger(30));
+    assert_eq!(merged.value.properties["dept"], Value::VInteger(7));
+    // Rewired endpoints carry the merged subject too.
+    for p in contracted.iter().filter(|p| p.elements.len() == 2) {
+        for endpoint in &p.elements {
+            if endpoint.value.identity.0 == "AB" {
+                assert_eq!(endpoint, merged);
+            }
+        }
+    }
+}
+
+#[test]
+fn contract_nodes_drops_or_loops_intra_group_edges() {
+    let gq = make_gq(vec![
+        rel("AB", node("A"), node("B")),
+        rel("BC", node("B"), node("C")),
+        rel("CD", node("C"), node("D")),
+    ]);
+    let group = [Symbol("A".to_string()), Symbol("B".to_string())];
+    let strategy = default_subject_merge_strategy();
+
+    let dropped = contract_nodes(
+        &gq,
+        &group,
+        Symbol("G".to_string()),
+        &strategy,
+        IntraGroupEdges::Drop,
+    );
+    assert_eq!(
+        edge_ids(&dropped),
+        [triple("BC", "G", "C"), triple("CD", "C", "D")]
+    );
+
+    let looped = contract_nodes(
+        &gq,
+        &group,
+        Symbol("G".to_string()),
+        &strategy,
+        IntraGroupEdges::SelfLoop,
+    );
+    assert_eq!(
+        edge_ids(&looped),
+        [
+            triple("AB", "G", "G"),
+            triple("BC", "G", "C"),
+            triple("CD", "C", "D")
+        ]
+    );
+    assert_eq!(node_ids(&looped), ["C", "D", "G"]);
+}
+
+#[test]
+fn contract_nodes_handles_existing_new_id_and_missing_members() {
+    let gq = chain_abc();
+    let strategy = default_subject_merge_strategy();
+
+    // Contracting into an existing identity absorbs that node.
+    let contracted = contract_nodes(
+        &gq,
+        &[Symbol("A".to_string())],
+        Symbol("B".to_string()),
+        &strategy,
+        IntraGroupEdges::Drop,
+    );
+    assert_eq!(node_ids(&contracted), ["B", "C"]);
+    assert_eq!(edge_ids(&contracted), [triple("BC", "B", "C")]);
+
+    // Identities that are not in the graph are ignored.
+    let unchanged = contract_nodes(
+        &gq,
+        &[Symbol("Z".to_string())],
+        Symbol("G".to_string()),
+        &strategy,
+        IntraGroupEdges::Drop,
+    );
+    assert_eq!(node_ids(&unchanged), ["A", "B", "C"]);
+    assert_eq!(
+        edge_ids(&unchanged),
+        [triple("AB", "A", "B"), triple("BC", "B", "C")]
+    );
+}
+
 // ============================================================================
 // Edge cases from spec
 // ============================================================================
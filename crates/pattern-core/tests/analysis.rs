@@ -129,3 +129,63 @@ fn test_analysis_with_100_plus_nesting_levels() {
     assert_eq!(analysis.depth_distribution.len(), 101); // 0-100 depths
     assert!(!analysis.summary.is_empty());
 }
+
+#[test]
+fn test_analysis_branching_balanced_vs_degenerate() {
+    fn balanced(depth: usize) -> Pattern<String> {
+        if depth == 0 {
+            Pattern::point("leaf".to_string())
+        } else {
+            Pattern::pattern(
+                "node".to_string(),
+                vec![balanced(depth - 1), balanced(depth - 1)],
+            )
+        }
+    }
+    fn degenerate(depth: usize) -> Pattern<String> {
+        if depth == 0 {
+            Pattern::point("leaf".to_string())
+        } else {
+            Pattern::pattern("node".to_string(), vec![degenerate(depth - 1)])
+        }
+    }
+
+    // Same depth, very different shapes
+    let wide = balanced(3).analyze_structure();
+    assert_eq!(wide.max_branching, 2);
+    assert_eq!(wide.avg_branching, 2.0);
+    assert_eq!(wide.leaf_count, 8);
+    assert!(wide.summary.contains("branching up to 2"));
+
+    let narrow = degenerate(3).analyze_structure();
+    assert_eq!(narrow.max_branching, 1);
+    assert_eq!(narrow.avg_branching, 1.0);
+    assert_eq!(narrow.leaf_count, 1);
+    assert!(narrow.summary.contains("branching up to 1"));
+
+    // Uneven branching averages over nodes that have elements
+    let uneven = Pattern::pattern(
+        "root".to_string(),
+        vec![
+            Pattern::pattern(
+                "a".to_string(),
+                vec![
+                    Pattern::point("x".to_string()),
+                    Pattern::point("y".to_string()),
+                    Pattern::point("z".to_string()),
+                ],
+            ),
+            Pattern::point("b".to_string()),
+        ],
+    )
+    .analyze_structure();
+    assert_eq!(uneven.max_branching, 3);
+    assert_eq!(uneven.avg_branching, 2.5);
+    assert_eq!(uneven.leaf_count, 4);
+
+    let atomic = Pattern::point("atom".to_string()).analyze_structure();
+    assert_eq!(atomic.max_branching, 0);
+    assert_eq!(atomic.avg_branching, 0.0);
+    assert_eq!(atomic.leaf_count, 1);
+    assert!(atomic.summary.contains("no branching"));
+}
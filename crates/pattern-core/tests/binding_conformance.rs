@@ -0,0 +1,110 @@
+//! Cross-binding conformance for core pattern operations
+//!
+//! `tests/common/pattern_ops_conformance.json` records, for a fixed set of
+//! patterns, the results of `map`, `fold`, `para`, `size`, `depth`, and
+//! `values` as computed by this crate. The Python and TypeScript test suites
+//! load the same file and assert their implementations produce identical
+//! results, so a divergence in traversal order or combine semantics in any
+//! binding shows up as a fixture mismatch.
+//!
+//! Every binding runs the same operations:
+//!
+//! - `map`: append `"!"` to every value
+//! - `fold`: starting from `""`, append each value followed by `"."`
+//! - `para`: render each pattern as `value(child,child,...)`
+//! - `size`, `depth`, `values`: as defined on `Pattern`
+//!
+//! Run with `UPDATE_CONFORMANCE_FIXTURE=1` to rewrite the expected results
+//! after an intentional semantic change.
+
+use pattern_core::Pattern;
+use serde_json::{json, Value as Json};
+use std::path::PathBuf;
+
+fn fixture_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/common/pattern_ops_conformance.json")
+}
+
+fn from_json(json: &Json) -> Pattern<String> {
+    let value = json["value"].as_str().expect("value must be a string");
+    let elements = json["elements"]
+        .as_array()
+        .expect("elements must be an array")
+        .iter()
+        .map(from_json)
+        .collect();
+    Pattern::pattern(value.to_string(), elements)
+}
+
+fn to_json(pattern: &Pattern<String>) -> Json {
+    json!({
+        "value": pattern.value,
+        "elements": pattern.elements.iter().map(to_json).collect::<Vec<_>>(),
+    })
+}
+
+/// Results of the shared operation sequence, in fixture form
+fn expected(pattern: &Pattern<String>) -> Json {
+    let mapped = pattern.clone().map(|v| format!("{}!", v));
+    let folded = pattern.fold(String::new(), |acc, v| format!("{}{}.", acc, v));
+    let rendered = pattern.para(|p: &Pattern<String>, children: &[String]| {
+        format!("{}({})", p.value, children.join(","))
+    });
+
+    json!({
+        "map": to_json(&mapped),
+        "fold": folded,
+        "para": rendered,
+        "size": pattern.size(),
+        "depth": pattern.depth(),
+        "values": pattern.values(),
+    })
+}
+
+#[test]
+fn fixture_matches_core_semantics() {
+    let path = fixture_path();
+    let text = std::fs::read_to_string(&path).expect("conformance fixture is readable");
+    let mut fixture: Json = serde_json::from_str(&text).expect("conformance fixture is JSON");
+
+    let cases = fixture["cases"]
+        .as_array_mut()
+        .expect("fixture has a cases array");
+    assert!(!cases.is_empty());
+
+    if std::env::var_os("UPDATE_CONFORMANCE_FIXTURE").is_some() {
+        for case in cases.iter_mut() {
+            case["expected"] = expected(&from_json(&case["pattern"]));
+        }
+        let mut text = serde_json::to_string_pretty(&fixture).unwrap();
+        text.push('\n');
+        std::fs::write(&path, text).expect("conformance fixture is writable");
+        return;
+    }
+
+    for case in cases.iter() {
+        let name = case["name"].as_str().unwrap_or("<unnamed>");
+        let pattern = from_json(&case["pattern"]);
+        assert_eq!(case["pattern"], to_json(&pattern), "{}: pattern", name);
+        assert_eq!(case["expected"], expected(&pattern), "{}", name);
+    }
+}
+
+#[test]
+fn fold_visits_values_in_the_same_order_as_values() {
+    let pattern = Pattern::pattern(
+        "a".to_string(),
+        vec![
+            Pattern::pattern("b".to_string(), vec![Pattern::point("c".to_string())]),
+            Pattern::point("d".to_string()),
+        ],
+    );
+    let folded = pattern.fold(Vec::new(), |mut acc, v| {
+        acc.push(v.clone());
+        acc
+    });
+    let values: Vec<String> = pattern.values().into_iter().cloned().collect();
+    assert_eq!(folded, values);
+    assert_eq!(values, ["a", "b", "c", "d"]);
+}
@@ -0,0 +1,66 @@
+//! Tests for Pattern::canonical
+
+use pattern_core::{Pattern, Subject};
+
+fn leaf(id: &str) -> Pattern<Subject> {
+    Pattern::point(Subject::from_id(id))
+}
+
+fn group(id: &str, elements: Vec<Pattern<Subject>>) -> Pattern<Subject> {
+    Pattern::pattern(Subject::build(id).label("Group").done(), elements)
+}
+
+#[test]
+fn reordered_children_are_equal_after_canonicalization() {
+    let a = group("g", vec![leaf("x"), leaf("y"), leaf("z")]);
+    let b = group("g", vec![leaf("z"), leaf("x"), leaf("y")]);
+
+    assert_ne!(a, b);
+    assert_eq!(a.canonical(), b.canonical());
+    assert_eq!(a.canonical(), a, "already sorted input is unchanged");
+}
+
+#[test]
+fn reordering_is_ignored_at_every_level() {
+    let a = group(
+        "outer",
+        vec![
+            group("team1", vec![leaf("alice"), leaf("bob")]),
+            group("team2", vec![leaf("carol"), leaf("dave"), leaf("erin")]),
+        ],
+    );
+    let b = group(
+        "outer",
+        vec![
+            group("team2", vec![leaf("erin"), leaf("carol"), leaf("dave")]),
+            group("team1", vec![leaf("bob"), leaf("alice")]),
+        ],
+    );
+
+    assert_ne!(a, b);
+    assert_eq!(a.canonical(), b.canonical());
+    assert_eq!(a.canonical().canonical(), a.canonical());
+}
+
+#[test]
+fn siblings_sharing_an_identity_are_ordered_by_their_members() {
+    // Two anonymous groups, told apart only by what they contain
+    let a = group(
+        "outer",
+        vec![group("", vec![leaf("b")]), group("", vec![leaf("a")])],
+    );
+    let b = group(
+        "outer",
+        vec![group("", vec![leaf("a")]), group("", vec![leaf("b")])],
+    );
+    assert_eq!(a.canonical(), b.canonical());
+}
+
+#[test]
+fn different_members_stay_different() {
+    let a = group("g", vec![leaf("x"), leaf("y")]);
+    let b = group("g", vec![leaf("y"), leaf("w")]);
+    let c = group("g", vec![leaf("x"), leaf("y"), leaf("y")]);
+    assert_ne!(a.canonical(), b.canonical());
+    assert_ne!(a.canonical(), c.canonical());
+}
@@ -0,0 +1,147 @@
+//! Tests for diff_patterns
+
+use pattern_core::{diff_patterns, Pattern, PatternChange, Subject, Symbol, Value};
+
+fn node(subject: Subject) -> Pattern<Subject> {
+    Pattern::point(subject)
+}
+
+fn person(id: &str, name: &str) -> Subject {
+    Subject::build(id)
+        .label("Person")
+        .property("name", name)
+        .done()
+}
+
+fn knows(id: &str, source: Subject, target: Subject) -> Pattern<Subject> {
+    Pattern::pattern(
+        Subject::build(id).label("KNOWS").done(),
+        vec![node(source), node(target)],
+    )
+}
+
+/// A document-like wrapper; keep it away from two elements, which would read
+/// as a relationship between them
+fn graph(elements: Vec<Pattern<Subject>>) -> Pattern<Subject> {
+    Pattern::pattern(Subject::from_id("g"), elements)
+}
+
+#[test]
+fn identical_patterns_have_no_changes() {
+    let g = graph(vec![knows("r1", person("a", "Alice"), person("b", "Bob"))]);
+    assert!(diff_patterns(&g, &g.clone()).is_empty());
+}
+
+#[test]
+fn reports_property_change() {
+    let old = graph(vec![knows("r1", person("a", "Alice"), person("b", "Bob"))]);
+    let new = graph(vec![knows("r1", person("a", "Alicia"), person("b", "Bob"))]);
+
+    let changes = diff_patterns(&old, &new);
+    assert_eq!(
+        changes,
+        [PatternChange::ChangedProperty {
+            id: Symbol::from("a"),
+            key: "name".to_string(),
+            old: Some(Value::from("Alice")),
+            new: Some(Value::from("Alicia")),
+        }]
+    );
+    assert_eq!(changes[0].to_string(), r#"~ a.name: "Alice" -> "Alicia""#);
+}
+
+#[test]
+fn reports_added_node() {
+    let old = graph(vec![knows("r1", person("a", "Alice"), person("b", "Bob"))]);
+    let carol = person("c", "Carol");
+    let new = graph(vec![
+        knows("r1", person("a", "Alice"), person("b", "Bob")),
+        node(carol.clone()),
+        node(person("a", "Alice")),
+    ]);
+
+    let changes = diff_patterns(&old, &new);
+    assert_eq!(changes, [PatternChange::AddedNode(carol)]);
+    assert_eq!(changes[0].to_string(), r#"+ (c:Person {name: "Carol"})"#);
+}
+
+#[test]
+fn reports_removed_relationship() {
+    let (a, b) = (person("a", "Alice"), person("b", "Bob"));
+    let old = graph(vec![
+        knows("r1", a.clone(), b.clone()),
+        knows("r2", b.clone(), a.clone()),
+    ]);
+    let new = graph(vec![knows("r1", a, b)]);
+
+    let changes = diff_patterns(&old, &new);
+    assert_eq!(changes.len(), 1);
+    let PatternChange::RemovedRelationship {
+        source,
+        target,
+        relationship,
+    } = &changes[0]
+    else {
+        panic!("expected a removed relationship, got {:?}", changes[0]);
+    };
+    assert_eq!((source.0.as_str(), target.0.as_str()), ("b", "a"));
+    assert_eq!(relationship.identity, Symbol::from("r2"));
+    assert_eq!(changes[0].to_string(), "- (b)-[r2:KNOWS]->(a)");
+}
+
+#[test]
+fn reports_label_and_property_changes_in_order() {
+    let old = graph(vec![node(
+        Subject::build("a")
+            .label("Person")
+            .property("age", 30i64)
+            .property("city", "Oslo")
+            .done(),
+    )]);
+    let new = graph(vec![node(
+        Subject::build("a")
+            .label("Employee")
+            .property("age", 31i64)
+            .property("team", "core")
+            .done(),
+    )]);
+
+    let lines: Vec<String> = diff_patterns(&old, &new)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            "+ a:Employee",
+            "- a:Person",
+            "~ a.age: 30 -> 31",
+            r#"- a.city: "Oslo""#,
+            r#"+ a.team: "core""#,
+        ]
+    );
+}
+
+#[test]
+fn moved_relationship_is_removed_and_added() {
+    let (a, b, c) = (person("a", "A"), person("b", "B"), person("c", "C"));
+    let old = graph(vec![
+        knows("r1", a.clone(), b.clone()),
+        node(c.clone()),
+        node(b),
+    ]);
+    let new = graph(vec![
+        knows("r1", a, c),
+        node(person("b", "B")),
+        node(person("c", "C")),
+    ]);
+
+    let changes = diff_patterns(&old, &new);
+    assert!(matches!(
+        changes.as_slice(),
+        [
+            PatternChange::RemovedRelationship { target: t1, .. },
+            PatternChange::AddedRelationship { target: t2, .. },
+        ] if t1.0 == "b" && t2.0 == "c"
+    ));
+}
@@ -0,0 +1,91 @@
+//! Tests for Pattern::to_edge_list
+
+use pattern_core::{Pattern, Subject, Symbol};
+
+fn node(id: &str) -> Pattern<Subject> {
+    Pattern::point(Subject::from_id(id))
+}
+
+/// An anonymous relationship labeled `label`, as `-[:label]->` parses
+fn rel(label: &str, source: Pattern<Subject>, target: Pattern<Subject>) -> Pattern<Subject> {
+    Pattern::pattern(Subject::build("").label(label).done(), vec![source, target])
+}
+
+fn edge_summary(edges: &[(Symbol, Symbol, Subject)]) -> Vec<(String, String, Vec<String>)> {
+    edges
+        .iter()
+        .map(|(s, t, r)| {
+            let mut labels: Vec<String> = r.labels.iter().cloned().collect();
+            labels.sort();
+            (s.0.clone(), t.0.clone(), labels)
+        })
+        .collect()
+}
+
+#[test]
+fn to_edge_list_flattens_parsed_path() {
+    // `(a)-[:R]->(b)-[:R2]->(c)` as the gram parser produces it: the outer
+    // relationship's source is the path so far
+    let path = rel("R2", rel("R", node("a"), node("b")), node("c"));
+
+    let (nodes, edges) = path.to_edge_list();
+
+    let ids: Vec<&str> = nodes.iter().map(|n| n.identity.0.as_str()).collect();
+    assert_eq!(ids, ["a", "b", "c"]);
+    assert_eq!(
+        edge_summary(&edges),
+        [
+            ("a".to_string(), "b".to_string(), vec!["R".to_string()]),
+            ("b".to_string(), "c".to_string(), vec!["R2".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn to_edge_list_merges_repeated_nodes_and_walks_containers() {
+    let person = Pattern::point(
+        Subject::build("b")
+            .label("Person")
+            .property("name", "Bob")
+            .done(),
+    );
+    let walk = Pattern::pattern(
+        Subject::from_id("w"),
+        vec![
+            rel("R", node("a"), node("b")),
+            rel("S", node("b"), node("c")),
+        ],
+    );
+    let annotated = Pattern::pattern(
+        Subject::from_id("note"),
+        vec![rel("T", node("c"), node("a"))],
+    );
+    let doc = Pattern::pattern(
+        Subject::from_id(""),
+        vec![walk, annotated, person, node("d")],
+    );
+
+    let (nodes, edges) = doc.to_edge_list();
+
+    let ids: Vec<&str> = nodes.iter().map(|n| n.identity.0.as_str()).collect();
+    assert_eq!(ids, ["a", "b", "c", "d"]);
+    // The later, labeled occurrence of `b` contributes its labels and properties
+    assert!(nodes[1].labels.contains("Person"));
+    assert!(nodes[1].properties.contains_key("name"));
+    // Containers, walks, and annotations are not nodes themselves
+    assert_eq!(
+        edge_summary(&edges),
+        [
+            ("a".to_string(), "b".to_string(), vec!["R".to_string()]),
+            ("b".to_string(), "c".to_string(), vec!["S".to_string()]),
+            ("c".to_string(), "a".to_string(), vec!["T".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn to_edge_list_of_a_node_has_no_edges() {
+    let (nodes, edges) = node("a").to_edge_list();
+    assert_eq!(nodes, [Subject::from_id("a")]);
+    assert!(edges.is_empty());
+}
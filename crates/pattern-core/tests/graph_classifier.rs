@@ -1,5 +1,6 @@
 use pattern_core::{
-    canonical_classifier, classify_by_shape, from_test_node, GraphClass, Pattern, Subject, Symbol,
+    canonical_classifier, classify_by_shape, from_test_node, GraphClass, ParseGraphClassError,
+    Pattern, Subject, Symbol,
 };
 use std::collections::{HashMap, HashSet};
 
@@ -122,3 +123,44 @@ fn from_test_node_classifies_non_matching_as_gother() {
         GraphClass::GOther(())
     );
 }
+
+// Display / FromStr: class names
+#[test]
+fn graph_class_names_round_trip() {
+    let classes: [GraphClass<()>; 5] = [
+        GraphClass::GNode,
+        GraphClass::GRelationship,
+        GraphClass::GAnnotation,
+        GraphClass::GWalk,
+        GraphClass::GOther(()),
+    ];
+    let names: Vec<String> = classes.iter().map(|c| c.to_string()).collect();
+    assert_eq!(
+        names,
+        ["node", "relationship", "annotation", "walk", "other"]
+    );
+    for (class, name) in classes.iter().zip(&names) {
+        assert_eq!(&name.parse::<GraphClass<()>>().unwrap(), class);
+    }
+}
+
+#[test]
+fn graph_class_other_payload_is_not_displayed() {
+    assert_eq!(GraphClass::GOther("detail").to_string(), "other");
+    assert_eq!(
+        "other".parse::<GraphClass<String>>().unwrap(),
+        GraphClass::GOther(String::new())
+    );
+}
+
+#[test]
+fn graph_class_unknown_name_is_an_error() {
+    let err = "Node".parse::<GraphClass<()>>().unwrap_err();
+    assert_eq!(
+        err,
+        ParseGraphClassError {
+            input: "Node".to_string()
+        }
+    );
+    assert!(err.to_string().contains("unknown graph class \"Node\""));
+}
@@ -1,5 +1,5 @@
 //! Tests for GraphQuery construction, structural invariants, weight functions,
-//! frame_query combinator, memoize_incident_rels, and query_containers.
+//! frame_query combinator, memoize_incident_rels, overlay, and query_containers.
 //!
 //! Corresponds to tasks T021, T034, T040. Haskell test IDs: HS-T015–HS-T017,
 //! HS-T047–HS-T051, HS-T056.
@@ -9,8 +9,9 @@ use std::rc::Rc;
 
 use pattern_core::{
     canonical_classifier, connected_components, directed, directed_reverse, frame_query,
-    from_patterns, graph_query_from_pattern_graph, is_connected, memoize_incident_rels, undirected,
-    GraphClass, GraphQuery, GraphValue, Pattern, PatternGraph, Subject, Symbol, TraversalDirection,
+    from_patterns, graph_query_from_pattern_graph, has_cycle, is_connected, memoize_incident_rels,
+    overlay, undirected, GraphClass, GraphQuery, GraphValue, Pattern, PatternGraph, Subject,
+    Symbol, TraversalDirection, Value,
 };
 
 // ============================================================================
@@ -131,6 +132,64 @@ fn hs_t015_query_relationship_by_id() {
     assert!(bc.is_some(), "BC must be found by id");
 }
 
+#[test]
+fn as_query_shares_graph_allocation() {
+    let (_, pg) = triangle_query();
+    let q1 = pg.as_query();
+    let q2 = pg.as_query();
+    // Each query holds handles to the same graph rather than a copy.
+    assert!(Rc::strong_count(&pg) > 2);
+    assert_eq!((q1.query_nodes)().len(), 3);
+    assert_eq!((q2.query_relationships)().len(), 3);
+
+    drop(q1);
+    drop(q2);
+    // The original query from triangle_query() was dropped; only `pg` remains.
+    assert_eq!(Rc::strong_count(&pg), 1);
+}
+
+#[test]
+fn query_nodes_where_filters_by_integer_age() {
+    let person = |id: &str, age: Option<i64>| {
+        let mut n = node(id);
+        if let Some(age) = age {
+            n.value
+                .properties
+                .insert("age".to_string(), Value::VInteger(age));
+        }
+        n
+    };
+    let mut named = node("eve");
+    named
+        .value
+        .properties
+        .insert("age".to_string(), Value::VString("unknown".to_string()));
+    let classifier = canonical_classifier::<Subject>();
+    let pg = Rc::new(from_patterns(
+        &classifier,
+        vec![
+            person("alice", Some(34)),
+            person("bob", Some(30)),
+            person("carol", Some(52)),
+            person("dave", None),
+            named,
+        ],
+    ));
+    let gq = pg.as_query();
+
+    let older_than_30 = |v: &Value| matches!(v, Value::VInteger(n) if *n > 30);
+    let ids: Vec<String> = gq
+        .query_nodes_where("age", &older_than_30)
+        .into_iter()
+        .map(|n| n.value.identity.0)
+        .collect();
+    assert_eq!(ids, ["alice", "carol"]);
+
+    // Nodes lacking the property never reach the predicate.
+    assert_eq!(gq.query_nodes_where("age", &|_| true).len(), 4);
+    assert!(gq.query_nodes_where("height", &|_| true).is_empty());
+}
+
 // ============================================================================
 // HS-T016: Structural invariants hold for a valid PatternGraph-backed GraphQuery
 // ============================================================================
@@ -519,6 +578,111 @@ fn hs_t056_query_co_members() {
     assert_eq!(co_members[0].value.identity, Symbol("BC".to_string()));
 }
 
+// ============================================================================
+// overlay combinator
+// ============================================================================
+
+/// Overlay for the triangle: C→A closes a cycle and C→D adds a new node.
+/// The overlay's copy of A carries an "Extra" label so precedence is visible.
+fn triangle_overlay_query() -> GraphQuery<Subject> {
+    let (base, _) = triangle_query();
+
+    let mut extra_a = node("A");
+    extra_a.value.labels.insert("Extra".to_string());
+    let rca = rel("CA", node("C"), extra_a);
+    let rcd = rel("CD", node("C"), node("D"));
+
+    let classifier = canonical_classifier::<Subject>();
+    let extra = graph_query_from_pattern_graph(Rc::new(from_patterns(&classifier, vec![rca, rcd])));
+    overlay(base, extra)
+}
+
+fn sorted_ids(patterns: &[Pattern<Subject>]) -> Vec<String> {
+    let mut ids: Vec<String> = patterns
+        .iter()
+        .map(|p| p.value.identity.0.clone())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn overlay_unions_nodes_and_relationships() {
+    let gq = triangle_overlay_query();
+    assert_eq!(sorted_ids(&(gq.query_nodes)()), vec!["A", "B", "C", "D"]);
+    assert_eq!(
+        sorted_ids(&(gq.query_relationships)()),
+        vec!["AB", "AC", "BC", "CA", "CD"]
+    );
+
+    let c = (gq.query_node_by_id)(&Symbol("C".to_string())).expect("C must exist");
+    assert_eq!(
+        sorted_ids(&(gq.query_incident_rels)(&c)),
+        vec!["AC", "BC", "CA", "CD"]
+    );
+    assert_eq!((gq.query_degree)(&c), 4);
+}
+
+#[test]
+fn overlay_base_wins_on_shared_identity() {
+    let gq = triangle_overlay_query();
+
+    let nodes = (gq.query_nodes)();
+    let a: Vec<_> = nodes
+        .iter()
+        .filter(|n| n.value.identity == Symbol("A".to_string()))
+        .collect();
+    assert_eq!(a.len(), 1, "shared identity appears once");
+    assert!(a[0].value.labels.is_empty(), "base node is kept");
+
+    let a_by_id = (gq.query_node_by_id)(&Symbol("A".to_string())).unwrap();
+    assert!(a_by_id.value.labels.is_empty());
+
+    // The overlay relationship resolves its endpoint to the base node
+    let ca = (gq.query_relationship_by_id)(&Symbol("CA".to_string())).unwrap();
+    let target = (gq.query_target)(&ca).unwrap();
+    assert!(target.value.labels.is_empty());
+}
+
+#[test]
+fn overlay_algorithms_see_combined_graph() {
+    let (base, _) = triangle_query();
+    assert!(!has_cycle(&base));
+
+    let gq = triangle_overlay_query();
+    assert!(has_cycle(&gq), "C→A from the overlay closes a cycle");
+    assert!(is_connected(&gq, &undirected()));
+}
+
+#[test]
+fn overlay_structural_invariants() {
+    let gq = triangle_overlay_query();
+    let node_ids: HashSet<Symbol> = (gq.query_nodes)()
+        .iter()
+        .map(|n| n.value.identity.clone())
+        .collect();
+
+    for rel_pat in (gq.query_relationships)() {
+        let src = (gq.query_source)(&rel_pat).expect("relationship has a source");
+        let tgt = (gq.query_target)(&rel_pat).expect("relationship has a target");
+        assert!(node_ids.contains(&src.value.identity));
+        assert!(node_ids.contains(&tgt.value.identity));
+        assert!((gq.query_relationship_by_id)(rel_pat.value.identify()).is_some());
+    }
+
+    for node_pat in (gq.query_nodes)() {
+        assert_eq!(
+            (gq.query_degree)(&node_pat),
+            (gq.query_incident_rels)(&node_pat).len()
+        );
+        for rel_pat in (gq.query_incident_rels)(&node_pat) {
+            let touches = (gq.query_source)(&rel_pat) == Some(node_pat.clone())
+                || (gq.query_target)(&rel_pat) == Some(node_pat.clone());
+            assert!(touches, "incident rel must touch its node");
+        }
+    }
+}
+
 // ============================================================================
 // GraphQuery manual construction (representation-independence smoke test)
 // ============================================================================
@@ -5,8 +5,9 @@
 //! - HashMap usage with pattern keys
 //! - Hash/Eq consistency
 //! - Structure distinguishes hashes
+//! - Value hashing, including decimals and maps
 
-use pattern_core::{Pattern, Symbol};
+use pattern_core::{Pattern, RangeValue, Symbol, Value};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
@@ -274,3 +275,97 @@ fn test_pattern_symbol_in_hashset() {
     assert!(set.contains(&p2));
     assert_eq!(set.len(), 2);
 }
+
+// ============================================================================
+// Value Hash Tests
+// ============================================================================
+
+fn hash_value(v: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    v.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_value_hash_consistent_with_eq() {
+    let pairs = [
+        (Value::VDecimal(0.0), Value::VDecimal(-0.0)),
+        (Value::VDecimal(f64::NAN), Value::VDecimal(-f64::NAN)),
+        (
+            Value::VArray(vec![Value::VDecimal(f64::NAN)]),
+            Value::VArray(vec![Value::VDecimal(f64::NAN)]),
+        ),
+        (
+            Value::VMap(HashMap::from([
+                ("a".to_string(), Value::VInteger(1)),
+                ("b".to_string(), Value::VString("x".to_string())),
+                ("c".to_string(), Value::VBoolean(true)),
+            ])),
+            Value::VMap(HashMap::from([
+                ("c".to_string(), Value::VBoolean(true)),
+                ("b".to_string(), Value::VString("x".to_string())),
+                ("a".to_string(), Value::VInteger(1)),
+            ])),
+        ),
+        (
+            Value::VRange(RangeValue {
+                lower: Some(1.0),
+                upper: None,
+            }),
+            Value::VRange(RangeValue {
+                lower: Some(1.0),
+                upper: None,
+            }),
+        ),
+    ];
+    for (a, b) in &pairs {
+        assert_eq!(a, b);
+        assert_eq!(hash_value(a), hash_value(b), "{:?}", a);
+    }
+}
+
+#[test]
+fn test_value_float_equality() {
+    let nan = Value::VDecimal(f64::NAN);
+    assert_eq!(nan, nan.clone(), "Eq must be reflexive, even for NaN");
+    assert_ne!(nan, Value::VDecimal(0.0));
+    assert_ne!(Value::VDecimal(0.1 + 0.2), Value::VDecimal(0.3));
+    assert_ne!(Value::VInteger(1), Value::VDecimal(1.0));
+    assert_eq!(
+        Value::VMeasurement {
+            unit: "kg".to_string(),
+            value: f64::NAN,
+        },
+        Value::VMeasurement {
+            unit: "kg".to_string(),
+            value: f64::NAN,
+        }
+    );
+    assert_ne!(
+        RangeValue {
+            lower: Some(1.0),
+            upper: None,
+        },
+        RangeValue {
+            lower: None,
+            upper: Some(1.0),
+        }
+    );
+}
+
+#[test]
+fn test_value_hashmap_counts() {
+    let values = [
+        Value::VString("NL".to_string()),
+        Value::VSymbol("NL".to_string()),
+        Value::VString("NL".to_string()),
+        Value::VInteger(1),
+        Value::VDecimal(1.0),
+    ];
+    let mut counts: HashMap<Value, usize> = HashMap::new();
+    for v in values {
+        *counts.entry(v).or_default() += 1;
+    }
+    assert_eq!(counts.len(), 4, "variants are distinct keys");
+    assert_eq!(counts[&Value::VString("NL".to_string())], 2);
+}
@@ -0,0 +1,89 @@
+//! Tests for `Pattern::<Subject>::map_subjects`
+
+use pattern_core::{Pattern, Subject, Value};
+
+fn normalize_labels(subject: &Subject) -> Subject {
+    Subject {
+        labels: subject.labels.iter().map(|l| l.to_uppercase()).collect(),
+        ..subject.clone()
+    }
+}
+
+#[test]
+fn map_subjects_normalizes_labels_across_nested_pattern() {
+    let pattern = Pattern::pattern(
+        Subject::build("walk").label("Walk").done(),
+        vec![
+            Pattern::pattern(
+                Subject::build("r1").label("knows").done(),
+                vec![
+                    Pattern::point(Subject::build("a").label("Person").done()),
+                    Pattern::point(Subject::build("b").label("person").done()),
+                ],
+            ),
+            Pattern::pattern(
+                Subject::build("r2").label("Knows").done(),
+                vec![
+                    Pattern::point(Subject::build("b").label("PERSON").done()),
+                    Pattern::point(
+                        Subject::build("c")
+                            .label("person")
+                            .property("name", "Carol")
+                            .done(),
+                    ),
+                ],
+            ),
+        ],
+    );
+
+    let normalized = pattern.map_subjects(normalize_labels);
+
+    // Same shape and identities, in the same order
+    assert_eq!(normalized.size(), pattern.size());
+    assert_eq!(normalized.depth(), pattern.depth());
+    let ids = |p: &Pattern<Subject>| -> Vec<String> {
+        p.values().iter().map(|s| s.identity.0.clone()).collect()
+    };
+    assert_eq!(ids(&normalized), ids(&pattern));
+
+    let labels: Vec<Vec<&String>> = normalized
+        .values()
+        .into_iter()
+        .map(|s| s.labels.iter().collect())
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            vec!["WALK"],
+            vec!["KNOWS"],
+            vec!["PERSON"],
+            vec!["PERSON"],
+            vec!["KNOWS"],
+            vec!["PERSON"],
+            vec!["PERSON"],
+        ]
+    );
+
+    // Properties are carried through, and the original is untouched
+    let carol = &normalized.elements[1].elements[1].value;
+    assert_eq!(
+        carol.properties.get("name"),
+        Some(&Value::VString("Carol".to_string()))
+    );
+    assert!(pattern.elements[0].value.labels.contains("knows"));
+}
+
+#[test]
+fn map_subjects_matches_generic_map() {
+    let pattern = Pattern::pattern(
+        Subject::build("r").label("R").done(),
+        vec![
+            Pattern::point(Subject::build("a").label("A").done()),
+            Pattern::point(Subject::build("b").done()),
+        ],
+    );
+    assert_eq!(
+        pattern.map_subjects(normalize_labels),
+        pattern.clone().map(normalize_labels)
+    );
+}
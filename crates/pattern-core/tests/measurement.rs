@@ -0,0 +1,61 @@
+//! Tests for measurement arithmetic on Value
+//!
+//! Measurements combine only when their units match exactly.
+
+use pattern_core::{MeasurementError, Value};
+
+fn measure(value: f64, unit: &str) -> Value {
+    Value::VMeasurement {
+        unit: unit.to_string(),
+        value,
+    }
+}
+
+#[test]
+fn add_and_sub_same_unit() {
+    assert_eq!(
+        measure(5.0, "kg").add(&measure(3.0, "kg")),
+        Ok(measure(8.0, "kg"))
+    );
+    assert_eq!(
+        measure(5.0, "kg").sub(&measure(3.0, "kg")),
+        Ok(measure(2.0, "kg"))
+    );
+}
+
+#[test]
+fn mismatched_units_are_rejected() {
+    let err = measure(5.0, "kg").add(&measure(3.0, "m")).unwrap_err();
+    assert_eq!(
+        err,
+        MeasurementError::UnitMismatch {
+            left: "kg".to_string(),
+            right: "m".to_string(),
+        }
+    );
+    assert_eq!(err.to_string(), "unit mismatch: kg vs m");
+    assert!(measure(5.0, "kg").sub(&measure(3.0, "g")).is_err());
+}
+
+#[test]
+fn non_measurements_are_rejected() {
+    assert_eq!(
+        measure(5.0, "kg").add(&Value::VInteger(3)),
+        Err(MeasurementError::NotAMeasurement(Value::VInteger(3)))
+    );
+    assert!(Value::VDecimal(2.0).scale(2.0).is_err());
+    assert!(Value::VNull.convert("g", 1000.0).is_err());
+}
+
+#[test]
+fn scale_keeps_the_unit() {
+    assert_eq!(measure(2.5, "m").scale(4.0), Ok(measure(10.0, "m")));
+    assert_eq!(measure(2.5, "m").scale(-1.0), Ok(measure(-2.5, "m")));
+}
+
+#[test]
+fn convert_then_add() {
+    let grams = measure(1.5, "kg").convert("g", 1000.0).unwrap();
+    assert_eq!(grams, measure(1500.0, "g"));
+    assert_eq!(grams.add(&measure(250.0, "g")), Ok(measure(1750.0, "g")));
+}
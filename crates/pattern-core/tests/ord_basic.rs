@@ -4,7 +4,7 @@
 //! including atomic patterns, nested patterns, and various edge cases like deep nesting
 //! and wide patterns.
 
-use pattern_core::Pattern;
+use pattern_core::{Pattern, Symbol};
 use std::cmp::Ordering;
 
 // ============================================================================
@@ -327,3 +327,141 @@ fn test_sort_stability() {
     assert!(tagged[2].original_index < tagged[3].original_index);
     assert!(tagged[3].original_index < tagged[4].original_index);
 }
+
+// ============================================================================
+// Symbol ordering (graph identity type)
+// ============================================================================
+
+#[test]
+fn test_symbol_orders_by_text() {
+    let mut symbols: Vec<Symbol> = ["node10", "node2", "Node", "a"]
+        .into_iter()
+        .map(Symbol::from)
+        .collect();
+    symbols.sort();
+
+    let texts: Vec<&str> = symbols.iter().map(Symbol::as_str).collect();
+    assert_eq!(texts, ["Node", "a", "node10", "node2"]);
+    assert_eq!(
+        Symbol::from("a").cmp(&Symbol::from("b")),
+        "a".cmp("b"),
+        "Symbol ordering must match its text"
+    );
+}
+
+#[test]
+fn test_pattern_of_symbols_orders_by_symbol() {
+    let a = Pattern::point(Symbol::from("a"));
+    let b = Pattern::point(Symbol::from("b"));
+    assert_eq!(a.cmp(&b), Ordering::Less);
+}
+
+// ============================================================================
+// Value ordering
+// ============================================================================
+
+#[test]
+fn test_value_numbers_compare_numerically() {
+    use pattern_core::Value;
+
+    assert!(Value::VInteger(2) < Value::VDecimal(2.5));
+    assert!(Value::VDecimal(2.5) < Value::VInteger(3));
+    assert!(Value::VInteger(3) < Value::VDecimal(3.0));
+    assert!(Value::VDecimal(3.0) < Value::VInteger(4));
+    assert_eq!(
+        Value::VDecimal(0.0).cmp(&Value::VDecimal(-0.0)),
+        Ordering::Equal
+    );
+    assert_eq!(
+        Value::VDecimal(f64::NAN).cmp(&Value::VDecimal(-f64::NAN)),
+        Ordering::Equal
+    );
+    assert!(Value::VDecimal(f64::INFINITY) < Value::VDecimal(f64::NAN));
+    assert!(Value::VInteger(i64::MAX) < Value::VDecimal(f64::NAN));
+
+    // Integers beyond f64 precision still compare exactly
+    let big = 1i64 << 53;
+    assert!(Value::VDecimal(big as f64) < Value::VInteger(big + 1));
+    assert!(Value::VInteger(big) < Value::VDecimal(big as f64));
+    assert!(Value::VInteger(i64::MAX) < Value::VDecimal(9.3e18));
+}
+
+#[test]
+fn test_value_mixed_sort_is_deterministic() {
+    use pattern_core::{RangeValue, Value};
+    use std::collections::HashMap;
+
+    let s = |s: &str| Value::VString(s.to_string());
+    let sorted = vec![
+        Value::VNull,
+        Value::VInteger(-1),
+        Value::VInteger(1),
+        Value::VDecimal(1.0),
+        Value::VDecimal(1.5),
+        Value::VDecimal(f64::NAN),
+        Value::VBoolean(false),
+        Value::VBoolean(true),
+        s("B"),
+        s("a"),
+        s("ab"),
+        Value::VSymbol("a".to_string()),
+        Value::VTaggedString {
+            tag: "date".to_string(),
+            content: "2024".to_string(),
+        },
+        Value::VArray(vec![]),
+        Value::VArray(vec![Value::VInteger(1)]),
+        Value::VArray(vec![Value::VInteger(1), Value::VInteger(0)]),
+        Value::VArray(vec![Value::VInteger(2)]),
+        Value::VMap(HashMap::from([("a".to_string(), Value::VInteger(2))])),
+        Value::VMap(HashMap::from([
+            ("b".to_string(), Value::VInteger(1)),
+            ("c".to_string(), Value::VInteger(1)),
+        ])),
+        Value::VRange(RangeValue {
+            lower: None,
+            upper: Some(5.0),
+        }),
+        Value::VRange(RangeValue {
+            lower: Some(1.0),
+            upper: None,
+        }),
+        Value::VRange(RangeValue {
+            lower: Some(1.0),
+            upper: Some(2.0),
+        }),
+        Value::VMeasurement {
+            unit: "kg".to_string(),
+            value: 5.0,
+        },
+        Value::VMeasurement {
+            unit: "m".to_string(),
+            value: 1.0,
+        },
+    ];
+
+    let mut shuffled: Vec<Value> = sorted.iter().rev().cloned().collect();
+    shuffled.rotate_left(7);
+    shuffled.sort();
+    assert_eq!(shuffled, sorted);
+
+    // Order agrees with equality
+    for (i, a) in sorted.iter().enumerate() {
+        for (j, b) in sorted.iter().enumerate() {
+            assert_eq!(a.cmp(b), i.cmp(&j), "{:?} vs {:?}", a, b);
+        }
+    }
+}
+
+#[test]
+fn test_value_map_order_ignores_entry_order() {
+    use pattern_core::Value;
+    use std::collections::HashMap;
+
+    let entries: Vec<(String, Value)> = (0..20)
+        .map(|i| (format!("k{}", i), Value::VInteger(i)))
+        .collect();
+    let forward = Value::VMap(entries.iter().cloned().collect::<HashMap<_, _>>());
+    let backward = Value::VMap(entries.into_iter().rev().collect::<HashMap<_, _>>());
+    assert_eq!(forward.cmp(&backward), Ordering::Equal);
+}
@@ -1,6 +1,9 @@
+use pattern_core::graph::StandardGraph;
+use pattern_core::reconcile::default_subject_merge_strategy;
 use pattern_core::{
-    canonical_classifier, classify_by_shape, from_patterns, from_patterns_with_policy, GraphClass,
-    GraphClassifier, Pattern, PatternGraph, ReconciliationPolicy, Subject, Symbol,
+    assign_identities, canonical_classifier, classify_by_shape, frame_query, from_patterns,
+    from_patterns_with_policy, match_subgraph, ElementMergeStrategy, GraphClass, GraphClassifier,
+    Pattern, PatternGraph, PatternGraphBuilder, ReconciliationPolicy, Subject, Symbol, Value,
 };
 use std::collections::{HashMap, HashSet};
 
@@ -139,7 +142,7 @@ fn walk_decomposition_stores_walk_rels_and_nodes() {
 }
 
 // Test 9 (US3): custom classifier with typed tag → pg_other stores (DomainHyperedge, pattern)
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 enum MyDomain {
     DomainHyperedge,
     DomainOther,
@@ -184,3 +187,759 @@ fn custom_classifier_routes_to_pg_other_with_typed_tag() {
         .expect("hyperedge should be in pg_other");
     assert_eq!(*tag, MyDomain::DomainHyperedge);
 }
+
+fn annotation(a: &str, inner: Pattern<Subject>, label: &str) -> Pattern<Subject> {
+    Pattern {
+        value: Subject {
+            identity: Symbol(a.to_string()),
+            labels: [label.to_string()].into_iter().collect(),
+            properties: HashMap::new(),
+        },
+        elements: vec![inner],
+    }
+}
+
+fn walk(w: &str, rels: Vec<Pattern<Subject>>, label: &str) -> Pattern<Subject> {
+    Pattern {
+        value: Subject {
+            identity: Symbol(w.to_string()),
+            labels: [label.to_string()].into_iter().collect(),
+            properties: HashMap::new(),
+        },
+        elements: rels,
+    }
+}
+
+// Test 10: two annotations sharing an identity → one entry under LastWriteWins / FirstWriteWins
+#[test]
+fn duplicate_annotation_identity_follows_policy() {
+    let classifier = canonical_classifier::<Subject>();
+    let patterns = vec![
+        annotation("ann", node("a"), "First"),
+        annotation("ann", node("b"), "Second"),
+    ];
+    let id = Symbol("ann".to_string());
+
+    let last = from_patterns_with_policy(
+        &classifier,
+        &ReconciliationPolicy::LastWriteWins,
+        patterns.clone(),
+    );
+    assert_eq!(last.pg_annotations.len(), 1);
+    assert!(last.pg_annotations[&id].value.labels.contains("Second"));
+    assert_eq!(last.pg_nodes.len(), 2);
+
+    let first =
+        from_patterns_with_policy(&classifier, &ReconciliationPolicy::FirstWriteWins, patterns);
+    assert_eq!(first.pg_annotations.len(), 1);
+    assert!(first.pg_annotations[&id].value.labels.contains("First"));
+}
+
+// Test 11: annotations sharing an identity are merged under Merge
+#[test]
+fn duplicate_annotation_identity_merges() {
+    let classifier = canonical_classifier::<Subject>();
+    let g = from_patterns_with_policy(
+        &classifier,
+        &ReconciliationPolicy::Merge(
+            ElementMergeStrategy::UnionElements,
+            default_subject_merge_strategy(),
+        ),
+        vec![
+            annotation("ann", node("a"), "First"),
+            annotation("ann", node("a"), "Second"),
+        ],
+    );
+    let merged = &g.pg_annotations[&Symbol("ann".to_string())];
+    assert!(merged.value.labels.contains("First"));
+    assert!(merged.value.labels.contains("Second"));
+    assert_eq!(merged.elements.len(), 1);
+    assert!(g.pg_conflicts.is_empty());
+}
+
+// Test 12: Strict records differing duplicate annotations in pg_conflicts
+#[test]
+fn strict_records_annotation_conflict() {
+    let classifier = canonical_classifier::<Subject>();
+    let g = from_patterns_with_policy(
+        &classifier,
+        &ReconciliationPolicy::Strict,
+        vec![
+            annotation("ann", node("a"), "First"),
+            annotation("ann", node("a"), "Second"),
+        ],
+    );
+    let id = Symbol("ann".to_string());
+    assert_eq!(g.pg_annotations.len(), 1);
+    assert!(g.pg_annotations[&id].value.labels.contains("First"));
+    assert_eq!(g.pg_conflicts[&id].len(), 1);
+    assert!(g.pg_conflicts[&id][0].value.labels.contains("Second"));
+}
+
+// Test 13: Strict accepts identical duplicate annotations without conflict
+#[test]
+fn strict_accepts_identical_annotations() {
+    let classifier = canonical_classifier::<Subject>();
+    let g = from_patterns_with_policy(
+        &classifier,
+        &ReconciliationPolicy::Strict,
+        vec![
+            annotation("ann", node("a"), "Same"),
+            annotation("ann", node("a"), "Same"),
+        ],
+    );
+    assert_eq!(g.pg_annotations.len(), 1);
+    assert!(g.pg_conflicts.is_empty());
+}
+
+// Test 14: walks sharing an identity follow the same policy as other collections
+#[test]
+fn duplicate_walk_identity_follows_policy() {
+    let classifier = canonical_classifier::<Subject>();
+    let patterns = vec![
+        walk(
+            "path",
+            vec![rel("r1", "a", "b"), rel("r2", "b", "c")],
+            "First",
+        ),
+        walk(
+            "path",
+            vec![rel("r1", "a", "b"), rel("r2", "b", "c")],
+            "Second",
+        ),
+    ];
+    let id = Symbol("path".to_string());
+
+    let last = from_patterns_with_policy(
+        &classifier,
+        &ReconciliationPolicy::LastWriteWins,
+        patterns.clone(),
+    );
+    assert_eq!(last.pg_walks.len(), 1);
+    assert!(last.pg_walks[&id].value.labels.contains("Second"));
+    assert!(last.pg_conflicts.is_empty());
+
+    let strict = from_patterns_with_policy(&classifier, &ReconciliationPolicy::Strict, patterns);
+    assert_eq!(strict.pg_walks.len(), 1);
+    assert!(strict.pg_walks[&id].value.labels.contains("First"));
+    assert_eq!(strict.pg_conflicts[&id].len(), 1);
+    assert_eq!(strict.pg_relationships.len(), 2);
+}
+
+// Test 14b: unclassified patterns sharing an identity follow the same policy
+#[test]
+fn duplicate_other_identity_follows_policy() {
+    let classifier = canonical_classifier::<Subject>();
+    let other = |label: &str| {
+        Pattern::pattern(
+            Subject::build("w").label(label).done(),
+            vec![node("a"), node("b"), node("c")],
+        )
+    };
+    let patterns = vec![other("First"), other("Second")];
+    let id = Symbol("w".to_string());
+
+    let merged = from_patterns_with_policy(
+        &classifier,
+        &ReconciliationPolicy::Merge(
+            ElementMergeStrategy::UnionElements,
+            default_subject_merge_strategy(),
+        ),
+        patterns.clone(),
+    );
+    assert_eq!(merged.pg_other.len(), 1);
+    let labels = &merged.pg_other[&id].1.value.labels;
+    assert!(labels.contains("First") && labels.contains("Second"));
+
+    let strict = from_patterns_with_policy(&classifier, &ReconciliationPolicy::Strict, patterns);
+    assert!(strict.pg_other[&id].1.value.labels.contains("First"));
+    assert_eq!(strict.pg_conflicts[&id].len(), 1);
+}
+
+// Test 15: PatternGraphBuilder pushes produce the same graph as from_patterns_with_policy
+#[test]
+fn builder_matches_from_patterns_with_policy() {
+    let mut labeled_a = node("a");
+    labeled_a.value.labels.insert("Person".to_string());
+    let patterns = vec![
+        node("a"),
+        walk(
+            "path",
+            vec![rel("r1", "a", "b"), rel("r2", "b", "c")],
+            "Path",
+        ),
+        annotation("note", node("d"), "Note"),
+        labeled_a,
+        node("e"),
+    ];
+
+    for policy in [
+        ReconciliationPolicy::LastWriteWins,
+        ReconciliationPolicy::FirstWriteWins,
+        ReconciliationPolicy::Strict,
+    ] {
+        let expected =
+            from_patterns_with_policy(&canonical_classifier(), &policy, patterns.clone());
+
+        let mut builder = PatternGraphBuilder::new(canonical_classifier(), policy);
+        for p in patterns.clone() {
+            builder.push(p);
+        }
+        let g = builder.build();
+
+        assert_eq!(g.pg_nodes, expected.pg_nodes);
+        assert_eq!(g.pg_relationships, expected.pg_relationships);
+        assert_eq!(g.pg_walks, expected.pg_walks);
+        assert_eq!(g.pg_annotations, expected.pg_annotations);
+        assert_eq!(g.pg_conflicts, expected.pg_conflicts);
+    }
+}
+
+// Test 16: the graph is observable between pushes, and extend pushes in order
+#[test]
+fn builder_exposes_partial_graph() {
+    let mut builder = PatternGraphBuilder::new(
+        canonical_classifier::<Subject>(),
+        ReconciliationPolicy::Strict,
+    );
+    builder.push(node("a"));
+    assert_eq!(builder.graph().pg_nodes.len(), 1);
+
+    builder.extend(vec![rel("r1", "a", "b"), node("c")]);
+    assert_eq!(builder.graph().pg_nodes.len(), 3);
+    assert_eq!(builder.graph().pg_relationships.len(), 1);
+}
+
+// Test 17: anonymous endpoints become distinct nodes; named nodes are untouched
+#[test]
+fn anonymous_nodes_get_distinct_identities() {
+    let classifier = canonical_classifier::<Subject>();
+    let patterns = vec![rel("r1", "a", ""), rel("r2", "b", ""), node("a")];
+    let g = from_patterns(&classifier, patterns.clone());
+
+    assert_eq!(g.pg_nodes.len(), 4);
+    let mut ids: Vec<&str> = g.pg_nodes.keys().map(|s| s.0.as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, ["_anon1", "_anon2", "a", "b"]);
+    assert_eq!(
+        g.pg_relationships[&Symbol("r1".into())].elements[1],
+        node("_anon1")
+    );
+    assert_eq!(
+        g.pg_relationships[&Symbol("r2".into())].elements[1],
+        node("_anon2")
+    );
+
+    let mut builder = PatternGraphBuilder::new(classifier, ReconciliationPolicy::LastWriteWins);
+    builder.extend(patterns);
+    assert_eq!(builder.graph().pg_nodes, g.pg_nodes);
+}
+
+// Test 18: generated identities skip identities already used in the input
+#[test]
+fn assign_identities_avoids_existing_identities() {
+    let assigned = assign_identities(vec![node(""), rel("_anon1", "_anon2", ""), node("")]);
+    assert_eq!(assigned[0], node("_anon3"));
+    assert_eq!(assigned[1], rel("_anon1", "_anon2", "_anon4"));
+    assert_eq!(assigned[2], node("_anon5"));
+}
+
+// Test 19: anonymous relationships are kept apart like anonymous nodes
+#[test]
+fn anonymous_relationships_get_distinct_identities() {
+    // (a)-->() (b)-->()
+    let patterns = vec![rel("", "a", ""), rel("", "b", "")];
+    let g = from_patterns(&canonical_classifier::<Subject>(), patterns.clone());
+
+    assert_eq!(g.pg_nodes.len(), 4);
+    assert_eq!(g.pg_relationships.len(), 2);
+
+    let assigned = assign_identities(patterns);
+    assert_eq!(assigned[0], rel("_anon1", "a", "_anon2"));
+    assert_eq!(assigned[1], rel("_anon3", "b", "_anon4"));
+}
+
+#[test]
+fn query_nodes_by_label_over_graph_and_query() {
+    let labeled = |id: &str, label: &str| {
+        let mut n = node(id);
+        n.value.labels.insert(label.to_string());
+        n
+    };
+    let mut both = labeled("acme-founder", "Person");
+    both.value.labels.insert("Company".to_string());
+    let classifier = canonical_classifier::<Subject>();
+    let g = from_patterns(
+        &classifier,
+        vec![
+            labeled("bob", "Person"),
+            labeled("acme", "Company"),
+            labeled("alice", "Person"),
+            labeled("globex", "Company"),
+            both,
+            node("unlabeled"),
+        ],
+    );
+
+    let ids = |nodes: Vec<Pattern<Subject>>| -> Vec<String> {
+        nodes.into_iter().map(|n| n.value.identity.0).collect()
+    };
+    assert_eq!(
+        ids(g.query_nodes_by_label("Person")),
+        ["acme-founder", "alice", "bob"]
+    );
+    assert_eq!(
+        ids(g.query_nodes_by_label("Company")),
+        ["acme", "acme-founder", "globex"]
+    );
+    assert!(g.query_nodes_by_label("Planet").is_empty());
+
+    let q = std::rc::Rc::new(g).as_query();
+    assert_eq!(
+        ids((q.query_nodes_by_label)("Person")),
+        ["acme-founder", "alice", "bob"]
+    );
+    assert!((q.query_nodes_by_label)("person").is_empty());
+}
+
+#[test]
+fn query_nodes_by_label_ignore_case_keeps_stored_casing() {
+    let labeled = |id: &str, label: &str| {
+        let mut n = node(id);
+        n.value.labels.insert(label.to_string());
+        n
+    };
+    let classifier = canonical_classifier::<Subject>();
+    let g = from_patterns(
+        &classifier,
+        vec![
+            labeled("alice", "Person"),
+            labeled("bob", "person"),
+            labeled("carol", "PERSON"),
+            labeled("acme", "Company"),
+        ],
+    );
+
+    let ids = |nodes: &[Pattern<Subject>]| -> Vec<String> {
+        nodes.iter().map(|n| n.value.identity.0.clone()).collect()
+    };
+    for query in ["Person", "person", "pErSoN"] {
+        let found = g.query_nodes_by_label_ignore_case(query);
+        assert_eq!(ids(&found), ["alice", "bob", "carol"], "{}", query);
+    }
+    // Exact matching is unchanged
+    assert_eq!(ids(&g.query_nodes_by_label("person")), ["bob"]);
+
+    // Only matching is case-folded; each node keeps its own casing
+    let found = g.query_nodes_by_label_ignore_case("person");
+    assert!(found[0].value.labels.contains("Person"));
+    assert!(found[1].value.labels.contains("person"));
+    assert!(found[2].value.labels.contains("PERSON"));
+
+    let q = std::rc::Rc::new(g).as_query();
+    assert_eq!(
+        ids(&q.query_nodes_by_label_ignore_case("COMPANY")),
+        ["acme"]
+    );
+}
+
+#[test]
+fn label_index_follows_updates() {
+    let labeled = |id: &str, label: &str| {
+        let mut n = node(id);
+        n.value.labels.insert(label.to_string());
+        n
+    };
+    let ids = |nodes: Vec<Pattern<Subject>>| -> Vec<String> {
+        nodes.into_iter().map(|n| n.value.identity.0).collect()
+    };
+    let classifier = canonical_classifier::<Subject>();
+    let policy = ReconciliationPolicy::LastWriteWins;
+
+    // A later occurrence replaces alice's labels under LastWriteWins
+    let g = from_patterns_with_policy(
+        &classifier,
+        &policy,
+        vec![labeled("alice", "Person"), labeled("alice", "Robot")],
+    );
+    assert!(g.query_nodes_by_label("Person").is_empty());
+    assert_eq!(ids(g.query_nodes_by_label("Robot")), ["alice"]);
+
+    let added = g.with_pattern_added(&classifier, &policy, labeled("bob", "Robot"));
+    assert_eq!(ids(added.query_nodes_by_label("Robot")), ["alice", "bob"]);
+    assert_eq!(ids(g.query_nodes_by_label("Robot")), ["alice"]);
+
+    let removed = added.with_pattern_removed(&Symbol("alice".to_string()));
+    assert_eq!(ids(removed.query_nodes_by_label("Robot")), ["bob"]);
+
+    let mut sg = StandardGraph::new();
+    sg.add_node(labeled("carol", "Person").value);
+    sg.add_node(labeled("carol", "Robot").value);
+    let q = sg.as_query();
+    assert!((q.query_nodes_by_label)("Person").is_empty());
+    assert_eq!(ids((q.query_nodes_by_label)("Robot")), ["carol"]);
+
+    let framed = frame_query(
+        std::rc::Rc::new(|n: &Pattern<Subject>| n.value.identity.0 != "bob"),
+        std::rc::Rc::new(added).as_query(),
+    );
+    assert_eq!(ids((framed.query_nodes_by_label)("Robot")), ["alice"]);
+}
+
+fn assert_same_graph(g: &PatternGraph<(), Subject>, expected: &PatternGraph<(), Subject>) {
+    assert_eq!(g.pg_nodes, expected.pg_nodes);
+    assert_eq!(g.pg_relationships, expected.pg_relationships);
+    assert_eq!(g.pg_walks, expected.pg_walks);
+    assert_eq!(g.pg_annotations, expected.pg_annotations);
+    assert_eq!(g.pg_conflicts, expected.pg_conflicts);
+}
+
+fn incremental_base() -> Vec<Pattern<Subject>> {
+    vec![
+        walk(
+            "path",
+            vec![rel("r1", "a", "b"), rel("r2", "b", "c")],
+            "Path",
+        ),
+        annotation("note", node("d"), "Note"),
+    ]
+}
+
+#[test]
+fn add_then_remove_node_restores_graph() {
+    let classifier = canonical_classifier::<Subject>();
+    let policy = ReconciliationPolicy::LastWriteWins;
+    let original = from_patterns_with_policy(&classifier, &policy, incremental_base());
+
+    let added = original.with_pattern_added(&classifier, &policy, node("z"));
+    assert_eq!(added.pg_nodes.len(), original.pg_nodes.len() + 1);
+    assert!(!original.pg_nodes.contains_key(&Symbol("z".to_string())));
+
+    let restored = added.with_pattern_removed(&Symbol("z".to_string()));
+    assert_same_graph(&restored, &original);
+}
+
+#[test]
+fn incremental_updates_copy_only_the_collections_they_change() {
+    let classifier = canonical_classifier::<Subject>();
+    let policy = ReconciliationPolicy::LastWriteWins;
+    let original = from_patterns_with_policy(&classifier, &policy, incremental_base());
+    let same = |a: &HashMap<Symbol, Pattern<Subject>>, b: &HashMap<Symbol, Pattern<Subject>>| {
+        std::ptr::eq(a, b)
+    };
+
+    let added = original.with_pattern_added(&classifier, &policy, node("z"));
+    assert!(!same(&added.pg_nodes, &original.pg_nodes));
+    assert!(same(&added.pg_relationships, &original.pg_relationships));
+    assert!(same(&added.pg_walks, &original.pg_walks));
+    assert!(same(&added.pg_annotations, &original.pg_annotations));
+
+    let removed = original.with_pattern_removed(&Symbol("note".to_string()));
+    assert!(same(&removed.pg_nodes, &original.pg_nodes));
+    assert!(same(&removed.pg_relationships, &original.pg_relationships));
+    assert!(!same(&removed.pg_annotations, &original.pg_annotations));
+    assert_eq!(original.pg_annotations.len(), 1);
+    assert!(removed.pg_annotations.is_empty());
+}
+
+#[test]
+fn with_pattern_added_matches_from_patterns_with_policy() {
+    let mut labeled_a = node("a");
+    labeled_a.value.labels.insert("Person".to_string());
+    let extra = vec![
+        labeled_a,
+        rel("r3", "c", "e"),
+        annotation("note", node("d"), "Memo"),
+    ];
+
+    for policy in [
+        ReconciliationPolicy::LastWriteWins,
+        ReconciliationPolicy::FirstWriteWins,
+        ReconciliationPolicy::Strict,
+    ] {
+        let classifier = canonical_classifier::<Subject>();
+        let mut all = incremental_base();
+        all.extend(extra.clone());
+        let expected = from_patterns_with_policy(&classifier, &policy, all);
+
+        let g = extra.iter().cloned().fold(
+            from_patterns_with_policy(&classifier, &policy, incremental_base()),
+            |g, p| g.with_pattern_added(&classifier, &policy, p),
+        );
+        assert_same_graph(&g, &expected);
+    }
+}
+
+#[test]
+fn with_pattern_added_gives_anonymous_nodes_fresh_identities() {
+    let classifier = canonical_classifier::<Subject>();
+    let policy = ReconciliationPolicy::LastWriteWins;
+    let g = from_patterns_with_policy(&classifier, &policy, vec![node(""), node("")]);
+    assert_eq!(g.pg_nodes.len(), 2);
+
+    let g = g.with_pattern_added(&classifier, &policy, node(""));
+    assert_eq!(g.pg_nodes.len(), 3);
+}
+
+#[test]
+fn with_pattern_removed_cascades_to_containers() {
+    let classifier = canonical_classifier::<Subject>();
+    let policy = ReconciliationPolicy::LastWriteWins;
+    let mut patterns = incremental_base();
+    patterns.push(annotation("about-r1", rel("r1", "a", "b"), "Note"));
+    let g = from_patterns_with_policy(&classifier, &policy, patterns);
+
+    let g = g.with_pattern_removed(&Symbol("a".to_string()));
+    let ids = |m: &HashMap<Symbol, Pattern<Subject>>| {
+        let mut ids: Vec<String> = m.keys().map(|s| s.0.clone()).collect();
+        ids.sort();
+        ids
+    };
+    assert_eq!(ids(&g.pg_nodes), ["b", "c", "d"]);
+    assert_eq!(ids(&g.pg_relationships), ["r2"]);
+    assert!(g.pg_walks.is_empty());
+    assert_eq!(ids(&g.pg_annotations), ["note"]);
+
+    // Removing a relationship keeps its endpoints.
+    let g = g.with_pattern_removed(&Symbol("r2".to_string()));
+    assert_eq!(ids(&g.pg_nodes), ["b", "c", "d"]);
+    assert!(g.pg_relationships.is_empty());
+}
+
+#[test]
+fn references_defer_to_definitions_under_every_policy() {
+    let mut alice = node("alice");
+    alice.value.labels.insert("Person".to_string());
+    alice
+        .value
+        .properties
+        .insert("age".to_string(), Value::VInteger(30));
+    let classifier = canonical_classifier::<Subject>();
+    let id = Symbol("alice".to_string());
+
+    for policy in [
+        ReconciliationPolicy::LastWriteWins,
+        ReconciliationPolicy::FirstWriteWins,
+        ReconciliationPolicy::Strict,
+        ReconciliationPolicy::merge_builder().build(),
+    ] {
+        for patterns in [
+            vec![alice.clone(), rel("r", "alice", "bob")],
+            vec![rel("r", "alice", "bob"), alice.clone()],
+        ] {
+            let g = from_patterns_with_policy(&classifier, &policy, patterns);
+            assert!(g.pg_conflicts.is_empty(), "{:?}", policy);
+            assert_eq!(g.pg_nodes[&id], alice, "{:?}", policy);
+        }
+    }
+}
+
+#[test]
+fn nested_paths_are_built_as_their_relationships() {
+    let endpoints = |g: &PatternGraph<(), Subject>, r: &str| {
+        let rel = &g.pg_relationships[&Symbol(r.to_string())];
+        (
+            rel.elements[0].value.identity.0.clone(),
+            rel.elements[1].value.identity.0.clone(),
+        )
+    };
+
+    // (a)-[r1]->(b)-[r2]->(c), as the gram parser nests it
+    let path = Pattern::pattern(Subject::from_id("r2"), vec![rel("r1", "a", "b"), node("c")]);
+    let chained = from_patterns(&canonical_classifier(), vec![path]);
+    let separate = from_patterns(
+        &canonical_classifier(),
+        vec![rel("r1", "a", "b"), rel("r2", "b", "c")],
+    );
+    assert_eq!(chained.pg_nodes.len(), 3);
+    assert_eq!(chained.pg_relationships.len(), 2);
+    assert_eq!(chained.pg_other.len(), 0);
+    assert_eq!(chained.pg_relationships, separate.pg_relationships);
+
+    // (a)<-[r1]-(b)-[r2]->(c): the backward r1 stores b first, and the path
+    // continues from b
+    let mut r1 = rel("r1", "b", "a");
+    r1.value.set_direction(pattern_core::Direction::Backward);
+    let path = Pattern::pattern(Subject::from_id("r2"), vec![r1, node("c")]);
+    let g = from_patterns(&canonical_classifier(), vec![path]);
+    assert_eq!(endpoints(&g, "r1"), ("b".to_string(), "a".to_string()));
+    assert_eq!(endpoints(&g, "r2"), ("b".to_string(), "c".to_string()));
+
+    // An anonymous joint is one node
+    let path = Pattern::pattern(
+        Subject::from_id("r2"),
+        vec![
+            Pattern::pattern(Subject::from_id("r1"), vec![node("a"), node("")]),
+            node("c"),
+        ],
+    );
+    let g = from_patterns(&canonical_classifier(), vec![path]);
+    assert_eq!(g.pg_nodes.len(), 3);
+    assert_eq!(endpoints(&g, "r1").1, endpoints(&g, "r2").0);
+}
+
+#[test]
+fn with_pattern_removed_drops_conflicts() {
+    let classifier = canonical_classifier::<Subject>();
+    let policy = ReconciliationPolicy::Strict;
+    let mut robot_a = node("a");
+    robot_a.value.labels.insert("Robot".to_string());
+    let original = from_patterns_with_policy(&classifier, &policy, vec![robot_a, node("b")]);
+    let mut labeled_a = node("a");
+    labeled_a.value.labels.insert("Person".to_string());
+
+    let g = original.with_pattern_added(&classifier, &policy, labeled_a);
+    assert_eq!(g.pg_conflicts.len(), 1);
+
+    let g = g.with_pattern_removed(&Symbol("a".to_string()));
+    assert!(g.pg_conflicts.is_empty());
+    assert!(!g.pg_nodes.contains_key(&Symbol("a".to_string())));
+}
+
+#[test]
+fn custom_policy_max_age_wins() {
+    let person = |age: i64, city: &str| {
+        Pattern::point(
+            Subject::build("p1")
+                .label("Person")
+                .property("age", age)
+                .property("city", city)
+                .done(),
+        )
+    };
+    let age = |s: &Subject| match s.properties.get("age") {
+        Some(Value::VInteger(n)) => *n,
+        _ => i64::MIN,
+    };
+    let max_age: pattern_core::MergeFn = std::rc::Rc::new(move |a: &Subject, b: &Subject| {
+        if age(b) > age(a) {
+            b.clone()
+        } else {
+            a.clone()
+        }
+    });
+    let policy = ReconciliationPolicy::Custom(max_age);
+    assert_eq!(policy, policy.clone());
+
+    let g = from_patterns_with_policy(
+        &canonical_classifier(),
+        &policy,
+        vec![person(41, "Oslo"), person(29, "Lima"), person(35, "Kyiv")],
+    );
+    let merged = &g.pg_nodes[&Symbol("p1".to_string())].value;
+    assert_eq!(merged.properties["age"], Value::VInteger(41));
+    assert_eq!(
+        merged.properties["city"],
+        Value::VString("Oslo".to_string())
+    );
+    assert!(g.pg_conflicts.is_empty());
+
+    // A custom merge cannot change the identity it is resolving.
+    let rename: pattern_core::MergeFn =
+        std::rc::Rc::new(|_: &Subject, _: &Subject| Subject::from_id("other"));
+    let g = from_patterns_with_policy(
+        &canonical_classifier(),
+        &ReconciliationPolicy::Custom(rename),
+        vec![person(41, "Oslo"), person(29, "Lima")],
+    );
+    assert_eq!(g.pg_nodes.len(), 1);
+    assert!(g.pg_nodes[&Symbol("p1".to_string())]
+        .value
+        .labels
+        .is_empty());
+}
+
+#[test]
+fn custom_policy_keeps_last_value_without_merge_with() {
+    use pattern_core::reconcile::{reconcile, HasIdentity, Mergeable, Refinable};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Tagged(Symbol, u32);
+
+    impl HasIdentity<Tagged, Symbol> for Tagged {
+        fn identity(v: &Tagged) -> &Symbol {
+            &v.0
+        }
+    }
+    impl Mergeable for Tagged {
+        type MergeStrategy = ();
+        fn merge(_: &(), a: Tagged, b: Tagged) -> Tagged {
+            Tagged(a.0, a.1 + b.1)
+        }
+    }
+    impl Refinable for Tagged {
+        fn is_refinement_of(sup: &Tagged, sub: &Tagged) -> bool {
+            sup == sub
+        }
+    }
+
+    let tagged = |tag| Tagged(Symbol("t".to_string()), tag);
+    let pattern = Pattern::pattern(
+        tagged(1),
+        vec![Pattern::point(tagged(2)), Pattern::point(tagged(3))],
+    );
+    let ignored: pattern_core::MergeFn = std::rc::Rc::new(|a: &Subject, _: &Subject| a.clone());
+    let reconciled = reconcile(&ReconciliationPolicy::Custom(ignored), &pattern).unwrap();
+    assert_eq!(reconciled, Pattern::point(tagged(3)));
+}
+
+#[test]
+fn match_subgraph_finds_knows_edges_in_triangle() {
+    let person = |id: &str| Pattern::point(Subject::build(id).label("Person").done());
+    let typed = |id: &str, label: &str, a: &str, b: &str| {
+        Pattern::pattern(
+            Subject::build(id).label(label).done(),
+            vec![node(a), node(b)],
+        )
+    };
+    let g: PatternGraph<(), Subject> = from_patterns(
+        &canonical_classifier(),
+        // Labeled nodes last, so LastWriteWins keeps them over the bare endpoints.
+        vec![
+            typed("k1", "KNOWS", "alice", "bob"),
+            typed("k2", "KNOWS", "bob", "carol"),
+            typed("w1", "WORKS_WITH", "carol", "alice"),
+            person("alice"),
+            person("bob"),
+            person("carol"),
+        ],
+    );
+    let bound = |m: &HashMap<Symbol, Pattern<Subject>>, q: &str| {
+        m[&Symbol(q.to_string())].value.identity.0.clone()
+    };
+
+    // (a:Person)-[:KNOWS]->(b:Person)
+    let query = Pattern::pattern(
+        Subject::build("").label("KNOWS").done(),
+        vec![person("a"), person("b")],
+    );
+    let matches = match_subgraph(&g, &query);
+    assert_eq!(matches.len(), 2);
+    assert_eq!(bound(&matches[0], "a"), "alice");
+    assert_eq!(bound(&matches[0], "b"), "bob");
+    assert_eq!(bound(&matches[1], "a"), "bob");
+    assert_eq!(bound(&matches[1], "b"), "carol");
+    assert!(matches[0][&Symbol::from("a")]
+        .value
+        .labels
+        .contains("Person"));
+
+    // (a)-[:KNOWS]->(b)-[:KNOWS]->(c), in the parser's nested path form
+    let hop = |a: Pattern<Subject>, b: &str| {
+        Pattern::pattern(Subject::build("").label("KNOWS").done(), vec![a, node(b)])
+    };
+    let matches = match_subgraph(&g, &hop(hop(node("a"), "b"), "c"));
+    assert_eq!(matches.len(), 1);
+    assert_eq!(
+        ["a", "b", "c"].map(|q| bound(&matches[0], q)),
+        ["alice", "bob", "carol"]
+    );
+
+    // A repeated query identity must bind one node, and no KNOWS edge is a loop.
+    assert!(match_subgraph(&g, &hop(node("a"), "a")).is_empty());
+    let works = Pattern::pattern(
+        Subject::build("").label("WORKS_WITH").done(),
+        vec![person("x"), node("y")],
+    );
+    assert_eq!(bound(&match_subgraph(&g, &works)[0], "y"), "alice");
+}
@@ -0,0 +1,75 @@
+//! Tests for Pattern::leaves and Pattern::leaf_values
+//!
+//! These operations collect the atomic subpatterns (and their values) of a
+//! pattern in pre-order.
+
+use pattern_core::Pattern;
+
+#[test]
+fn test_leaves_atomic_root_is_its_own_leaf() {
+    let pat = Pattern::point("solo");
+
+    let leaves = pat.leaves();
+
+    assert_eq!(leaves.len(), 1);
+    assert!(std::ptr::eq(leaves[0], &pat));
+    assert_eq!(pat.leaf_values(), vec![&"solo"]);
+}
+
+#[test]
+fn test_leaves_skip_branches_in_pre_order() {
+    let pat = Pattern::pattern(
+        "root",
+        vec![
+            Pattern::point("leaf1"),
+            Pattern::pattern(
+                "branch",
+                vec![
+                    Pattern::point("leaf2"),
+                    Pattern::pattern("inner", vec![Pattern::point("leaf3")]),
+                ],
+            ),
+            Pattern::point("leaf4"),
+        ],
+    );
+
+    assert_eq!(
+        pat.leaf_values(),
+        vec![&"leaf1", &"leaf2", &"leaf3", &"leaf4"]
+    );
+}
+
+#[test]
+fn test_leaves_match_atomic_filter() {
+    let pat = Pattern::pattern(
+        1,
+        vec![
+            Pattern::pattern(2, vec![Pattern::point(3), Pattern::point(4)]),
+            Pattern::point(5),
+            Pattern::pattern(6, vec![Pattern::pattern(7, vec![Pattern::point(8)])]),
+        ],
+    );
+
+    let leaves = pat.leaves();
+    let filtered = pat.filter(|p| p.is_atomic());
+
+    assert_eq!(leaves.len(), filtered.len());
+    assert!(leaves
+        .iter()
+        .zip(filtered.iter())
+        .all(|(a, b)| std::ptr::eq(*a, *b)));
+}
+
+#[test]
+fn test_leaves_of_graph_pattern_are_endpoint_nodes() {
+    // A path of two relationships: (a)-[r1]->(b)-[r2]->(c)
+    let path = Pattern::pattern(
+        "path",
+        vec![
+            Pattern::pattern("r1", vec![Pattern::point("a"), Pattern::point("b")]),
+            Pattern::pattern("r2", vec![Pattern::point("b"), Pattern::point("c")]),
+        ],
+    );
+
+    assert_eq!(path.leaf_values(), vec![&"a", &"b", &"b", &"c"]);
+}
@@ -0,0 +1,70 @@
+//! Tests for RangeValue membership, overlap, and intersection
+//!
+//! Bounds are inclusive; a `None` bound is unbounded on that side.
+
+use pattern_core::RangeValue;
+
+fn range(lower: Option<f64>, upper: Option<f64>) -> RangeValue {
+    RangeValue { lower, upper }
+}
+
+fn bounds(r: &RangeValue) -> (Option<f64>, Option<f64>) {
+    (r.lower, r.upper)
+}
+
+#[test]
+fn contains_respects_inclusive_bounds() {
+    let r = range(Some(1.0), Some(10.0));
+    assert!(r.contains(1.0));
+    assert!(r.contains(5.5));
+    assert!(r.contains(10.0));
+    assert!(!r.contains(0.999));
+    assert!(!r.contains(10.001));
+    assert!(!r.contains(f64::NAN));
+}
+
+#[test]
+fn contains_unbounded_above() {
+    let r = range(Some(0.0), None);
+    assert!(r.contains(0.0));
+    assert!(r.contains(1e300));
+    assert!(r.contains(f64::INFINITY));
+    assert!(!r.contains(-0.5));
+}
+
+#[test]
+fn contains_unbounded_below_and_fully_unbounded() {
+    let r = range(None, Some(10.0));
+    assert!(r.contains(f64::NEG_INFINITY));
+    assert!(!r.contains(10.5));
+    assert!(range(None, None).contains(-42.0));
+}
+
+#[test]
+fn intersection_keeps_tighter_bounds() {
+    let a = range(Some(0.0), None);
+    let b = range(Some(-5.0), Some(3.0));
+    assert_eq!(bounds(&a.intersection(&b).unwrap()), (Some(0.0), Some(3.0)));
+    assert_eq!(bounds(&b.intersection(&a).unwrap()), (Some(0.0), Some(3.0)));
+
+    let c = range(Some(2.0), None);
+    assert_eq!(bounds(&a.intersection(&c).unwrap()), (Some(2.0), None));
+    assert_eq!(
+        bounds(&range(None, None).intersection(&range(None, None)).unwrap()),
+        (None, None)
+    );
+}
+
+#[test]
+fn overlaps_when_ranges_share_a_number() {
+    let a = range(Some(0.0), Some(5.0));
+    assert!(a.overlaps(&range(Some(5.0), None)));
+    assert!(a.overlaps(&range(Some(1.0), Some(2.0))));
+    assert!(!a.overlaps(&range(Some(5.1), None)));
+    assert!(!a.overlaps(&range(None, Some(-0.1))));
+    assert!(a.intersection(&range(Some(6.0), Some(7.0))).is_none());
+    assert_eq!(
+        bounds(&a.intersection(&range(Some(5.0), None)).unwrap()),
+        (Some(5.0), Some(5.0))
+    );
+}
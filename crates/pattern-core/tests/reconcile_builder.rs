@@ -0,0 +1,60 @@
+//! Tests for the fluent `ReconciliationPolicy::merge_builder()` API.
+
+use pattern_core::reconcile::default_subject_merge_strategy;
+use pattern_core::{
+    canonical_classifier, from_patterns_with_policy, ElementMergeStrategy, LabelMerge, Pattern,
+    PropertyMerge, ReconciliationPolicy, Subject, SubjectMergeStrategy, Symbol, Value,
+};
+
+fn node(id: &str, label: &str, key: &str, value: &str) -> Pattern<Subject> {
+    Pattern::point(Subject::build(id).label(label).property(key, value).done())
+}
+
+#[test]
+fn merge_builder_defaults_match_wasm_merge_policy() {
+    assert_eq!(
+        ReconciliationPolicy::merge_builder().build(),
+        ReconciliationPolicy::Merge(
+            ElementMergeStrategy::UnionElements,
+            default_subject_merge_strategy(),
+        )
+    );
+}
+
+#[test]
+fn merge_builder_sets_each_field() {
+    let policy = ReconciliationPolicy::merge_builder()
+        .labels(LabelMerge::IntersectLabels)
+        .properties(PropertyMerge::ReplaceProperties)
+        .elements(ElementMergeStrategy::AppendElements)
+        .build();
+    assert_eq!(
+        policy,
+        ReconciliationPolicy::Merge(
+            ElementMergeStrategy::AppendElements,
+            SubjectMergeStrategy {
+                label_merge: LabelMerge::IntersectLabels,
+                property_merge: PropertyMerge::ReplaceProperties,
+            },
+        )
+    );
+}
+
+#[test]
+fn merge_builder_policy_merges_duplicates() {
+    let classifier = canonical_classifier::<Subject>();
+    let policy = ReconciliationPolicy::merge_builder()
+        .labels(LabelMerge::UnionLabels)
+        .properties(PropertyMerge::DeepMerge)
+        .build();
+    let g = from_patterns_with_policy(
+        &classifier,
+        &policy,
+        vec![node("a", "First", "x", "1"), node("a", "Second", "y", "2")],
+    );
+
+    let merged = &g.pg_nodes[&Symbol::from("a")].value;
+    assert!(merged.labels.contains("First") && merged.labels.contains("Second"));
+    assert_eq!(merged.properties["x"], Value::VString("1".to_string()));
+    assert_eq!(merged.properties["y"], Value::VString("2".to_string()));
+}
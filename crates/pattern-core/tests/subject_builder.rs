@@ -0,0 +1,66 @@
+//! Tests for fluent Subject and Pattern construction
+
+use pattern_core::{
+    classify_by_shape, GraphClass, Pattern, Subject, SubjectBuilder, Symbol, Value,
+};
+
+#[test]
+fn builds_labeled_person_node() {
+    let alice = Pattern::node(
+        SubjectBuilder::new()
+            .identity("alice")
+            .label("Person")
+            .label("Employee")
+            .property("name", "Alice")
+            .property("age", 30i64)
+            .build(),
+    );
+
+    assert!(alice.is_atomic());
+    let subject = &alice.value;
+    assert_eq!(subject.identity, Symbol("alice".to_string()));
+    assert!(subject.has_label("Person"));
+    assert!(subject.has_label("Employee"));
+    assert_eq!(subject.get_str("name"), Some("Alice"));
+    assert_eq!(subject.get_int("age"), Some(30));
+    assert!(matches!(classify_by_shape(&alice), GraphClass::GNode));
+}
+
+#[test]
+fn builds_relationship_between_nodes() {
+    let knows = Pattern::relationship(
+        Subject::build("r1")
+            .label("KNOWS")
+            .property("since", 2020i64),
+        Subject::build("alice").label("Person"),
+        Subject::build("bob").label("Person"),
+    );
+
+    assert_eq!(knows.value.identity.0, "r1");
+    assert_eq!(
+        knows.value.properties.get("since"),
+        Some(&Value::VInteger(2020))
+    );
+    let ends: Vec<&str> = knows
+        .elements
+        .iter()
+        .map(|e| e.value.identity.0.as_str())
+        .collect();
+    assert_eq!(ends, ["alice", "bob"]);
+    assert!(knows.elements.iter().all(|e| e.is_atomic()));
+    assert!(matches!(
+        classify_by_shape(&knows),
+        GraphClass::GRelationship
+    ));
+}
+
+#[test]
+fn identity_replaces_earlier_identity_and_build_matches_done() {
+    let built = Subject::build("first")
+        .identity("second")
+        .label("L")
+        .build();
+    let done = Subject::build("second").label("L").done();
+    assert_eq!(built, done);
+    assert_eq!(SubjectBuilder::default().build(), Subject::from_id(""));
+}
@@ -0,0 +1,135 @@
+//! Tests for the typed property accessors and `has_label` on Subject
+
+use pattern_core::{
+    Direction, Quantifier, RangeValue, Subject, Value, DIRECTION_PROPERTY, QUANTIFIER_PROPERTY,
+};
+
+fn sample() -> Subject {
+    let mut subject = Subject::build("alice")
+        .label("Person")
+        .property("name", "Alice")
+        .property("age", 42i64)
+        .property("score", 9.5)
+        .property("active", true)
+        .done();
+    subject
+        .properties
+        .insert("status".to_string(), Value::VSymbol("member".to_string()));
+    subject
+}
+
+#[test]
+fn accessors_return_present_values() {
+    let alice = sample();
+    assert_eq!(alice.get_str("name"), Some("Alice"));
+    assert_eq!(alice.get_int("age"), Some(42));
+    assert_eq!(alice.get_float("score"), Some(9.5));
+    assert_eq!(alice.get_bool("active"), Some(true));
+}
+
+#[test]
+fn accessors_return_none_for_absent_keys() {
+    let alice = sample();
+    assert_eq!(alice.get_str("missing"), None);
+    assert_eq!(alice.get_int("missing"), None);
+    assert_eq!(alice.get_float("missing"), None);
+    assert_eq!(alice.get_bool("missing"), None);
+    assert_eq!(Subject::from_id("empty").get_str("name"), None);
+}
+
+#[test]
+fn accessors_return_none_for_wrong_types() {
+    let alice = sample();
+    assert_eq!(alice.get_str("age"), None);
+    assert_eq!(alice.get_str("status"), None, "symbols are not strings");
+    assert_eq!(alice.get_int("score"), None);
+    assert_eq!(alice.get_int("name"), None);
+    assert_eq!(alice.get_float("age"), None, "integers are not widened");
+    assert_eq!(alice.get_bool("name"), None);
+}
+
+#[test]
+fn has_label_is_exact() {
+    let alice = sample();
+    assert!(alice.has_label("Person"));
+    assert!(!alice.has_label("person"));
+    assert!(!alice.has_label("Company"));
+    assert!(alice.has_label_ignore_case("person"));
+}
+
+#[test]
+fn direction_is_kept_in_a_reserved_property() {
+    let mut rel = Subject::from_id("r");
+    assert_eq!(rel.direction(), Direction::Forward);
+
+    rel.set_direction(Direction::Backward);
+    assert_eq!(rel.direction(), Direction::Backward);
+    assert_eq!(
+        rel.properties.get(DIRECTION_PROPERTY),
+        Some(&Value::VSymbol("backward".to_string()))
+    );
+
+    rel.set_direction(Direction::Forward);
+    assert_eq!(rel, Subject::from_id("r"));
+}
+
+#[test]
+fn direction_ignores_values_that_are_not_directions() {
+    let mut rel = Subject::build("r")
+        .property(DIRECTION_PROPERTY, "backward")
+        .done();
+    assert_eq!(rel.direction(), Direction::Forward);
+    rel.set_direction(Direction::Forward);
+    assert_eq!(rel.get_str(DIRECTION_PROPERTY), Some("backward"));
+}
+
+#[test]
+fn quantifier_is_kept_in_a_reserved_property() {
+    let mut rel = Subject::from_id("r");
+    assert_eq!(rel.quantifier(), None);
+
+    let q = Quantifier {
+        min: Some(2),
+        max: None,
+    };
+    rel.set_quantifier(Some(q));
+    assert_eq!(rel.quantifier(), Some(q));
+    assert_eq!(
+        rel.properties.get(QUANTIFIER_PROPERTY),
+        Some(&Value::VRange(RangeValue {
+            lower: Some(2.0),
+            upper: None
+        }))
+    );
+
+    rel.set_quantifier(None);
+    assert_eq!(rel, Subject::from_id("r"));
+}
+
+#[test]
+fn quantifier_needs_whole_non_negative_bounds() {
+    let range = |lower, upper| {
+        Subject::build("r")
+            .property(
+                QUANTIFIER_PROPERTY,
+                Value::VRange(RangeValue { lower, upper }),
+            )
+            .done()
+    };
+    assert_eq!(
+        range(None, Some(4.0)).quantifier(),
+        Some(Quantifier {
+            min: None,
+            max: Some(4)
+        })
+    );
+    assert_eq!(range(Some(1.5), None).quantifier(), None);
+    assert_eq!(range(Some(-1.0), None).quantifier(), None);
+    assert_eq!(
+        Subject::build("r")
+            .property(QUANTIFIER_PROPERTY, "many")
+            .done()
+            .quantifier(),
+        None
+    );
+}
@@ -83,6 +83,12 @@ export class StructureAnalysis {
   readonly elementCounts: number[];
   /** Identified nesting patterns (e.g., "linear", "tree", "balanced") */
   readonly nestingPatterns: string[];
+  /** Largest number of elements of any one node */
+  readonly maxBranching: number;
+  /** Mean number of elements of the nodes that have elements (0 if none do) */
+  readonly avgBranching: number;
+  /** Number of nodes without elements */
+  readonly leafCount: number;
 }
 
 // ============================================================================
@@ -93,7 +99,7 @@ export class StructureAnalysis {
  * Value represents property values with multiple type variants.
  *
  * This is a discriminated union that can hold:
- * - Primitives: string, int, decimal, boolean
+ * - Primitives: string, int, decimal, boolean, null (JavaScript `null`)
  * - Structured: symbol, array, map, range, measurement, tagged string
  */
 export type Value = any; // Runtime representation from WASM
@@ -265,7 +271,7 @@ export class Subject {
  * - Pattern<Pattern<V>> - nested patterns
  *
  * Patterns support:
- * - Construction: point, of, pattern, fromValues
+ * - Construction: point, of, pattern, fromValues, fromSubjectPattern
  * - Inspection: size, depth, length, isAtomic, values
  * - Query: anyValue, allValues, filter, findFirst, matches, contains
  * - Transformation: map, fold, para (paramorphism)
@@ -346,6 +352,27 @@ export class Pattern<V = any> {
    */
   static fromValues<V>(values: V[]): Pattern<V>[];
 
+  /**
+   * Create a pattern of Subjects whose values are Subject instances.
+   *
+   * Accepts a Pattern, or a plain `{ value, elements }` object, in which
+   * every value is a Subject instance. Throws naming the first value that
+   * is not a Subject.
+   *
+   * @param pattern - A pattern of Subjects
+   * @returns A new Pattern with Subject values
+   *
+   * @example
+   * ```typescript
+   * const alice = Subject.fromId("alice");
+   * const pattern = Pattern.fromSubjectPattern({ value: alice, elements: [] });
+   * const graph = NativePatternGraph.fromPatterns([pattern]);
+   * ```
+   */
+  static fromSubjectPattern(
+    pattern: Pattern<Subject> | { value: Subject; elements?: unknown[] }
+  ): Pattern<Subject>;
+
   // ========================================================================
   // Accessors
   // ========================================================================
@@ -578,6 +605,25 @@ export class Pattern<V = any> {
    */
   map<W>(fn: (v: V) => W): Pattern<W>;
 
+  /**
+   * Transform every Subject in the pattern, preserving Subject values.
+   *
+   * Unlike `map`, the result is guaranteed to hold Subjects, so it can be
+   * passed to graph operations. Throws if any value is not a Subject or if
+   * the function returns something other than a Subject.
+   *
+   * @param fn - Function that takes a Subject and returns a Subject
+   * @returns A new Pattern with transformed Subjects
+   *
+   * @example
+   * ```typescript
+   * const tagged = pattern.mapSubjects(s =>
+   *   new Subject(s.identity, [...s.labels, "Reviewed"], s.properties)
+   * );
+   * ```
+   */
+  mapSubjects(this: Pattern<Subject>, fn: (s: Subject) => Subject): Pattern<Subject>;
+
   /**
    * Fold the pattern into a single value using an accumulator.
    *
@@ -802,9 +848,22 @@ export class Pattern<V = any> {
 // NativePatternGraph and NativeGraphQuery (re-exported from graph bindings)
 // ============================================================================
 
+/** Why NativePatternGraph.fromPatternsStrict rejected its input. */
+export interface PatternConversionError {
+  /** Position of the offending pattern in the input array. */
+  readonly index: number;
+  /** What is wrong with it, e.g. "elements[0]: value is not a Subject". */
+  readonly reason: string;
+}
+
 /** Classified, indexed collection of patterns organized by graph role. */
 export class NativePatternGraph {
   static fromPatterns(patterns: Pattern[], policy?: NativeReconciliationPolicy): NativePatternGraph;
+  /** Like fromPatterns, but reports the first pattern that is not a pattern of Subjects. */
+  static fromPatternsStrict(
+    patterns: Pattern[],
+    policy?: NativeReconciliationPolicy
+  ): Either<PatternConversionError, NativePatternGraph>;
   static empty(): NativePatternGraph;
   readonly nodes: Pattern[];
   readonly relationships: Pattern[];
@@ -813,10 +872,13 @@ export class NativePatternGraph {
   readonly conflicts: Record<string, Pattern[]>;
   readonly size: number;
   merge(other: NativePatternGraph): NativePatternGraph;
+  /** Query handle sharing this graph's storage (no copy). */
+  queryHandle(): NativeGraphQuery;
 }
 
 /** Read-only query handle over a NativePatternGraph. */
 export class NativeGraphQuery {
+  /** Shares the graph's storage (no copy); same as `graph.queryHandle()`. */
   static fromPatternGraph(graph: NativePatternGraph): NativeGraphQuery;
   nodes(): Pattern[];
   relationships(): Pattern[];
@@ -825,6 +887,21 @@ export class NativeGraphQuery {
 /** Reconciliation policy for merging graphs. */
 export class NativeReconciliationPolicy {}
 
+/**
+ * Incremental graph builder for patterns that arrive one at a time.
+ *
+ * `build()` returns the same graph as `NativePatternGraph.fromPatterns` on
+ * every pattern pushed so far.
+ */
+export class WasmPatternGraphBuilder {
+  /** Create an empty builder. The policy defaults to LastWriteWins, as in `fromPatterns`. */
+  constructor(policy?: NativeReconciliationPolicy);
+  /** Add one pattern. Returns false, leaving the graph unchanged, if it is not a pattern of Subjects. */
+  push(pattern: Pattern): boolean;
+  /** Snapshot of the graph built so far. The builder can keep accepting patterns. */
+  build(): NativePatternGraph;
+}
+
 // ============================================================================
 // SubjectBuilder (T035)
 // ============================================================================
@@ -1,8 +1,10 @@
 # relateby.gram — parse/stringify gram notation
 #
 # parse/stringify return/accept list[Pattern[Subject]] (native Python dataclasses).
+# serialize writes a single Pattern[Subject].
 # parse_with_header/stringify_with_header handle the optional leading header record.
-# Errors surface as GramParseError with .input and .cause attributes.
+# Errors surface as GramParseError (a ValueError) with .input, .cause, .line, and
+# .column attributes.
 
 from __future__ import annotations
 
@@ -13,13 +15,15 @@ if TYPE_CHECKING:
     from relateby.pattern._subject import Subject
 
 
-class GramParseError(Exception):
+class GramParseError(ValueError):
     """Raised when gram notation parsing or serialization fails.
 
     Attributes:
         input: The original gram string that caused the error (empty string
             for serialization errors where there is no input text).
         cause: Human-readable description of what went wrong.
+        line: 1-indexed line where parsing failed, or ``None`` when unknown.
+        column: 1-indexed column where parsing failed, or ``None`` when unknown.
 
     Example::
 
@@ -30,12 +34,21 @@ class GramParseError(Exception):
         except GramParseError as err:
             print(err.input)   # "(unclosed"
             print(err.cause)   # parse error description
+            print(err.line, err.column)  # where parsing failed
     """
 
-    def __init__(self, input: str, cause: str) -> None:
+    def __init__(
+        self,
+        input: str,
+        cause: str,
+        line: "int | None" = None,
+        column: "int | None" = None,
+    ) -> None:
         super().__init__(f"Gram parse error: {cause}")
         self._input = input
         self._cause = cause
+        self._line = line
+        self._column = column
 
     @property
     def input(self) -> str:
@@ -47,6 +60,25 @@ class GramParseError(Exception):
         """Human-readable description of what went wrong."""
         return self._cause
 
+    @property
+    def line(self) -> "int | None":
+        """1-indexed line where parsing failed, if known."""
+        return self._line
+
+    @property
+    def column(self) -> "int | None":
+        """1-indexed column where parsing failed, if known."""
+        return self._column
+
+    @classmethod
+    def _from_native(cls, input: str, exc: Exception) -> "GramParseError":
+        return cls(
+            input=input,
+            cause=str(exc),
+            line=getattr(exc, "line", None),
+            column=getattr(exc, "column", None),
+        )
+
 
 def _pattern_to_dict(p: "Pattern[Subject]") -> dict:
     from relateby.pattern._value import value_to_dict
@@ -92,7 +124,7 @@ def parse(input: str) -> "list[Pattern[Subject]]":
         raw_patterns = _gram.parse(input)
         return [pattern_from_dict(d) for d in raw_patterns]
     except Exception as exc:
-        raise GramParseError(input=input, cause=str(exc)) from exc
+        raise GramParseError._from_native(input, exc) from exc
 
 
 def stringify(patterns: "list[Pattern[Subject]]") -> str:
@@ -129,6 +161,35 @@ def stringify(patterns: "list[Pattern[Subject]]") -> str:
         raise GramParseError(input="", cause=str(exc)) from exc
 
 
+def serialize(pattern: "Pattern[Subject]") -> str:
+    """Serialize a single ``Pattern[Subject]`` to gram notation.
+
+    Args:
+        pattern: The pattern to serialize, e.g. one entry of :func:`parse`.
+
+    Returns:
+        Gram notation string for *pattern*.
+
+    Raises:
+        GramParseError: If the pattern contains a value type that cannot be
+            represented in gram notation (e.g. ``NullVal``).
+
+    Example::
+
+        from relateby.gram import parse, serialize
+
+        [alice] = parse("(alice:Person {name: \"Alice\"})")
+        serialize(alice)
+        # '(alice:Person {name: "Alice"})'
+    """
+    from relateby._native import gram_codec as _gram
+
+    try:
+        return _gram.serialize(_pattern_to_dict(pattern))
+    except Exception as exc:
+        raise GramParseError(input="", cause=str(exc)) from exc
+
+
 def parse_with_header(input: str) -> "tuple[dict | None, list[Pattern[Subject]]]":
     """Parse gram notation, separating an optional header record from the patterns.
 
@@ -178,7 +239,7 @@ def parse_with_header(input: str) -> "tuple[dict | None, list[Pattern[Subject]]]
         patterns = [pattern_from_dict(d) for d in raw.get("patterns", [])]
         return header, patterns
     except Exception as exc:
-        raise GramParseError(input=input, cause=str(exc)) from exc
+        raise GramParseError._from_native(input, exc) from exc
 
 
 def stringify_with_header(
@@ -262,6 +323,7 @@ __all__ = [
     "GramParseError",
     "parse",
     "stringify",
+    "serialize",
     "parse_with_header",
     "stringify_with_header",
     "gram_validate",
@@ -4,15 +4,20 @@ from relateby.pattern import Pattern
 from relateby.pattern._subject import Subject
 
 
-class GramParseError(Exception):
+class GramParseError(ValueError):
     @property
     def input(self) -> str: ...
     @property
     def cause(self) -> str: ...
+    @property
+    def line(self) -> int | None: ...
+    @property
+    def column(self) -> int | None: ...
 
 
 def parse(input: str) -> list[Pattern[Subject]]: ...
 def stringify(patterns: list[Pattern[Subject]]) -> str: ...
+def serialize(pattern: Pattern[Subject]) -> str: ...
 def parse_with_header(input: str) -> tuple[dict | None, list[Pattern[Subject]]]: ...
 def stringify_with_header(
     header: dict | None, patterns: list[Pattern[Subject]]
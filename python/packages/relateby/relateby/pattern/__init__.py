@@ -33,6 +33,14 @@ from ._graph_transforms import (
     map_with_context,
     para_graph,
 )
+from ._graph_algorithms import (
+    Weight,
+    bfs,
+    dfs,
+    shortest_path,
+    topological_sort,
+    degree_centrality,
+)
 
 __all__ = [
     "Pattern",
@@ -62,4 +70,10 @@ __all__ = [
     "fold_graph",
     "map_with_context",
     "para_graph",
+    "Weight",
+    "bfs",
+    "dfs",
+    "shortest_path",
+    "topological_sort",
+    "degree_centrality",
 ]
@@ -183,6 +183,23 @@ def para_graph(
     f: Callable[[GraphQuery, Pattern[Subject], dict[str, R]], R],
 ) -> dict[str, R]: ...
 
+Weight = Union[str, Callable[[Pattern[Subject], str], float]]
+
+def bfs(
+    graph: StandardGraph, start: Pattern[Subject], weight: Weight = ...
+) -> list[Pattern[Subject]]: ...
+def dfs(
+    graph: StandardGraph, start: Pattern[Subject], weight: Weight = ...
+) -> list[Pattern[Subject]]: ...
+def shortest_path(
+    graph: StandardGraph,
+    start: Pattern[Subject],
+    end: Pattern[Subject],
+    weight: Weight = ...,
+) -> Optional[list[Pattern[Subject]]]: ...
+def topological_sort(graph: StandardGraph) -> Optional[list[Pattern[Subject]]]: ...
+def degree_centrality(graph: StandardGraph) -> dict[str, float]: ...
+
 
 class StandardGraph:
     def __init__(self) -> None: ...
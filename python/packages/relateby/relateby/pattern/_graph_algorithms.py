@@ -0,0 +1,245 @@
+"""Graph algorithm functions for StandardGraph.
+
+Standalone traversal, path, ordering, and centrality functions that mirror
+the algorithms exposed by the WASM bindings (bfs, dfs, shortestPath,
+topologicalSort, degreeCentrality), with the same ordering rules as the
+Rust implementations in pattern-core.
+"""
+from __future__ import annotations
+
+import heapq
+import math
+from collections import deque
+from typing import Callable, Iterator, Optional, Union
+
+from ._graph_transforms import GraphQuery
+from ._pattern import Pattern
+from ._standard_graph import StandardGraph
+from ._subject import Subject
+
+# Traversal weight:
+# - "undirected": every relationship can be traversed both ways at cost 1
+# - "directed": source to target only, at cost 1
+# - "directed_reverse": target to source only, at cost 1
+# - a callable (relationship, direction) -> cost, where direction is
+#   "forward" or "backward"; an infinite cost makes the step impassable
+# Any other string raises ValueError.
+Weight = Union[str, Callable[["Pattern[Subject]", str], float]]
+
+
+def _weight_fn(weight: Weight) -> Callable[[Pattern[Subject], str], float]:
+    if callable(weight):
+        return weight
+    if weight == "undirected":
+        return lambda _rel, _d: 1.0
+    if weight == "directed":
+        return lambda _rel, d: 1.0 if d == "forward" else math.inf
+    if weight == "directed_reverse":
+        return lambda _rel, d: 1.0 if d == "backward" else math.inf
+    raise ValueError(
+        f"Unknown weight: {weight!r} "
+        "(expected 'undirected', 'directed', 'directed_reverse', or a callable)"
+    )
+
+
+def _reachable(
+    query: GraphQuery,
+    weight: Callable[[Pattern[Subject], str], float],
+    node: Pattern[Subject],
+) -> Iterator[tuple[Pattern[Subject], float]]:
+    """Yield each neighbor reachable from *node* in one step, with its cost."""
+    node_id = node.value.identity
+    for rel in query.incident_rels(node):
+        source = query.source(rel)
+        target = query.target(rel)
+        if source is not None and target is not None:
+            if source.value.identity == node_id:
+                cost = weight(rel, "forward")
+                if math.isfinite(cost):
+                    yield target, cost
+            if target.value.identity == node_id:
+                cost = weight(rel, "backward")
+                if math.isfinite(cost):
+                    yield source, cost
+
+
+def bfs(
+    graph: StandardGraph,
+    start: Pattern[Subject],
+    weight: Weight = "undirected",
+) -> list[Pattern[Subject]]:
+    """Return the nodes reachable from *start* in breadth-first order.
+
+    Args:
+        graph: The graph to traverse.
+        start: The node to start from; it is always the first result.
+        weight: Traversal weight; see :data:`Weight`.
+
+    Returns:
+        Nodes in BFS visit order.
+
+    Raises:
+        ValueError: If *weight* is a string other than the ones
+            :data:`Weight` lists.
+
+    Example:
+        >>> g = StandardGraph.from_gram("(a)-->(b)-->(c)")
+        >>> [n.value.identity for n in bfs(g, g.node("a"))]
+        ['a', 'b', 'c']
+    """
+    query = GraphQuery(graph)
+    w = _weight_fn(weight)
+    visited = {start.value.identity}
+    queue = deque([start])
+    result: list[Pattern[Subject]] = []
+    while queue:
+        current = queue.popleft()
+        result.append(current)
+        for neighbor, _cost in _reachable(query, w, current):
+            if neighbor.value.identity not in visited:
+                visited.add(neighbor.value.identity)
+                queue.append(neighbor)
+    return result
+
+
+def dfs(
+    graph: StandardGraph,
+    start: Pattern[Subject],
+    weight: Weight = "undirected",
+) -> list[Pattern[Subject]]:
+    """Return the nodes reachable from *start* in depth-first order.
+
+    Args:
+        graph: The graph to traverse.
+        start: The node to start from; it is always the first result.
+        weight: Traversal weight; see :data:`Weight`.
+
+    Returns:
+        Nodes in DFS visit order.
+
+    Raises:
+        ValueError: If *weight* is a string other than the ones
+            :data:`Weight` lists.
+    """
+    query = GraphQuery(graph)
+    w = _weight_fn(weight)
+    visited: set[str] = set()
+    stack = [start]
+    result: list[Pattern[Subject]] = []
+    while stack:
+        current = stack.pop()
+        if current.value.identity in visited:
+            continue
+        visited.add(current.value.identity)
+        result.append(current)
+        for neighbor, _cost in _reachable(query, w, current):
+            if neighbor.value.identity not in visited:
+                stack.append(neighbor)
+    return result
+
+
+def shortest_path(
+    graph: StandardGraph,
+    start: Pattern[Subject],
+    end: Pattern[Subject],
+    weight: Weight = "undirected",
+) -> Optional[list[Pattern[Subject]]]:
+    """Return the minimum-cost path from *start* to *end* (Dijkstra).
+
+    Args:
+        graph: The graph to search.
+        start: The first node of the path.
+        end: The last node of the path.
+        weight: Traversal weight; see :data:`Weight`.
+
+    Returns:
+        The nodes along the path, ``[start]`` when *start* is *end*, or
+        ``None`` when *end* is unreachable.
+
+    Raises:
+        ValueError: If *weight* is a string other than the ones
+            :data:`Weight` lists.
+    """
+    query = GraphQuery(graph)
+    w = _weight_fn(weight)
+    start_id = start.value.identity
+    end_id = end.value.identity
+    if start_id == end_id:
+        return [start]
+
+    nodes: dict[str, Pattern[Subject]] = {start_id: start}
+    dist: dict[str, float] = {start_id: 0.0}
+    prev: dict[str, str] = {}
+    heap: list[tuple[float, str]] = [(0.0, start_id)]
+    done: set[str] = set()
+    while heap:
+        d, current_id = heapq.heappop(heap)
+        if current_id in done:
+            continue
+        done.add(current_id)
+        if current_id == end_id:
+            path = [nodes[end_id]]
+            while path[-1].value.identity != start_id:
+                path.append(nodes[prev[path[-1].value.identity]])
+            path.reverse()
+            return path
+        for neighbor, cost in _reachable(query, w, nodes[current_id]):
+            nid = neighbor.value.identity
+            if d + cost < dist.get(nid, math.inf):
+                dist[nid] = d + cost
+                prev[nid] = current_id
+                nodes[nid] = neighbor
+                heapq.heappush(heap, (d + cost, nid))
+    return None
+
+
+def topological_sort(graph: StandardGraph) -> Optional[list[Pattern[Subject]]]:
+    """Return the nodes in topological order, or ``None`` if there is a cycle.
+
+    Relationships are followed from source to target regardless of any
+    weight. Whenever several nodes are ready, the one with the smallest
+    identity comes first, so the same DAG always yields the same order.
+    """
+    query = GraphQuery(graph)
+    by_id = {n.value.identity: n for n in query.nodes()}
+    in_degree = {node_id: 0 for node_id in by_id}
+    successors: dict[str, list[str]] = {}
+    for rel in query.relationships():
+        source = query.source(rel)
+        target = query.target(rel)
+        if source is None or target is None:
+            continue
+        successors.setdefault(source.value.identity, []).append(target.value.identity)
+        in_degree[target.value.identity] += 1
+
+    ready = [node_id for node_id, d in in_degree.items() if d == 0]
+    heapq.heapify(ready)
+    result: list[Pattern[Subject]] = []
+    while ready:
+        node_id = heapq.heappop(ready)
+        result.append(by_id[node_id])
+        for target_id in successors.get(node_id, []):
+            in_degree[target_id] -= 1
+            if in_degree[target_id] == 0:
+                heapq.heappush(ready, target_id)
+
+    # Any node left unemitted lies on (or behind) a cycle
+    if len(result) < len(by_id):
+        return None
+    return result
+
+
+def degree_centrality(graph: StandardGraph) -> dict[str, float]:
+    """Return the degree centrality of every node, keyed by identity.
+
+    The centrality of a node is its number of incident relationships divided
+    by ``n - 1`` for a graph of ``n`` nodes, and 0.0 in a single-node graph.
+    Direction is ignored.
+    """
+    query = GraphQuery(graph)
+    nodes = query.nodes()
+    n = len(nodes)
+    return {
+        node.value.identity: query.degree(node) / (n - 1) if n > 1 else 0.0
+        for node in nodes
+    }
@@ -0,0 +1,65 @@
+"""Conformance with the Rust core on shared pattern-operation fixtures.
+
+The fixture is generated by crates/pattern-core/tests/binding_conformance.rs;
+see that file for the operation definitions.
+"""
+
+import json
+from pathlib import Path
+
+import pytest
+
+from relateby.pattern import Pattern
+
+_FIXTURE = (
+    Path(__file__).resolve().parents[4] / "tests" / "common" / "pattern_ops_conformance.json"
+)
+
+if not _FIXTURE.exists():
+    pytest.skip(f"conformance fixture not found: {_FIXTURE}", allow_module_level=True)
+
+_CASES = json.loads(_FIXTURE.read_text(encoding="utf-8"))["cases"]
+
+
+def _from_json(data):
+    return Pattern(value=data["value"], elements=[_from_json(e) for e in data["elements"]])
+
+
+def _to_json(pattern):
+    return {"value": pattern.value, "elements": [_to_json(e) for e in pattern.elements]}
+
+
+@pytest.fixture(params=_CASES, ids=[case["name"] for case in _CASES])
+def case(request):
+    return _from_json(request.param["pattern"]), request.param["expected"]
+
+
+def test_map(case):
+    pattern, expected = case
+    assert _to_json(pattern.map(lambda v: v + "!")) == expected["map"]
+
+
+def test_fold(case):
+    pattern, expected = case
+    assert pattern.fold("", lambda acc, v: acc + v + ".") == expected["fold"]
+
+
+def test_para(case):
+    pattern, expected = case
+    rendered = pattern.para(lambda p, rs: f"{p.value}({','.join(rs)})")
+    assert rendered == expected["para"]
+
+
+def test_size(case):
+    pattern, expected = case
+    assert pattern.size == expected["size"]
+
+
+def test_depth(case):
+    pattern, expected = case
+    assert pattern.depth == expected["depth"]
+
+
+def test_values(case):
+    pattern, expected = case
+    assert pattern.values() == expected["values"]
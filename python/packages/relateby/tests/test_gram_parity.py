@@ -11,10 +11,11 @@ from relateby.gram import (
     GramParseError,
     parse,
     parse_with_header,
+    serialize,
     stringify,
     stringify_with_header,
 )
-from relateby.pattern import Pattern, Subject
+from relateby.pattern import Pattern, StringVal, Subject
 
 
 # --- parse / stringify ---
@@ -54,6 +55,20 @@ def test_stringify_raises_for_null_values():
     assert "not representable" in exc_info.value.cause
 
 
+def test_parse_and_serialize_single_pattern():
+    [alice] = parse('(a:Person {name:"Alice"})')
+    assert alice.value.identity == "a"
+    assert alice.value.properties["name"] == StringVal("Alice")
+    assert serialize(alice) == '(a:Person {name: "Alice"})'
+
+
+def test_parse_error_is_value_error_with_location():
+    with pytest.raises(ValueError) as exc_info:
+        parse("(unclosed")
+    assert isinstance(exc_info.value, GramParseError)
+    assert (exc_info.value.line, exc_info.value.column) == (1, 10)
+
+
 # --- parse_with_header ---
 
 
@@ -0,0 +1,122 @@
+"""Tests for graph algorithm functions: bfs, dfs, shortest_path,
+topological_sort, degree_centrality.
+"""
+import math
+
+import pytest
+
+from relateby.pattern import (
+    Pattern,
+    Subject,
+    StandardGraph,
+    bfs,
+    dfs,
+    shortest_path,
+    topological_sort,
+    degree_centrality,
+)
+
+
+# ---------------------------------------------------------------------------
+# Helpers
+# ---------------------------------------------------------------------------
+
+
+def make_node(identity: str) -> Pattern[Subject]:
+    return Pattern.point(Subject.from_id(identity))
+
+
+def make_relationship(rel_id: str, source: str, target: str) -> Pattern[Subject]:
+    return Pattern(
+        value=Subject.from_id(rel_id),
+        elements=[make_node(source), make_node(target)],
+    )
+
+
+def make_chain() -> StandardGraph:
+    """a -[r1]-> b -[r2]-> c"""
+    return StandardGraph.from_patterns(
+        [make_relationship("r1", "a", "b"), make_relationship("r2", "b", "c")]
+    )
+
+
+def ids(patterns: list[Pattern[Subject]]) -> list[str]:
+    return [p.value.identity for p in patterns]
+
+
+# ---------------------------------------------------------------------------
+# bfs / dfs
+# ---------------------------------------------------------------------------
+
+
+def test_bfs_visits_chain_in_order():
+    g = make_chain()
+    assert ids(bfs(g, g.node("a"))) == ["a", "b", "c"]
+
+
+def test_bfs_undirected_reaches_upstream_nodes():
+    g = make_chain()
+    assert ids(bfs(g, g.node("b"))) == ["b", "a", "c"]
+
+
+def test_bfs_directed_follows_relationship_direction():
+    g = make_chain()
+    assert ids(bfs(g, g.node("b"), "directed")) == ["b", "c"]
+    assert ids(bfs(g, g.node("b"), "directed_reverse")) == ["b", "a"]
+
+
+def test_bfs_callable_weight_blocks_infinite_steps():
+    g = make_chain()
+
+    def blocked(rel, _direction):
+        return math.inf if rel.value.identity == "r2" else 1.0
+
+    assert ids(bfs(g, g.node("a"), blocked)) == ["a", "b"]
+
+
+@pytest.mark.parametrize("weight", ["Directed", "reverse", ""])
+def test_unknown_weight_string_is_rejected(weight):
+    g = make_chain()
+    for algorithm in (bfs, dfs):
+        with pytest.raises(ValueError, match="Unknown weight"):
+            algorithm(g, g.node("a"), weight)
+    with pytest.raises(ValueError, match="Unknown weight"):
+        shortest_path(g, g.node("a"), g.node("c"), weight)
+
+
+def test_dfs_visits_chain_in_order():
+    g = make_chain()
+    assert ids(dfs(g, g.node("a"))) == ["a", "b", "c"]
+
+
+# ---------------------------------------------------------------------------
+# shortest_path
+# ---------------------------------------------------------------------------
+
+
+def test_shortest_path_along_chain():
+    g = make_chain()
+    assert ids(shortest_path(g, g.node("a"), g.node("c"))) == ["a", "b", "c"]
+
+
+def test_shortest_path_same_node_and_unreachable():
+    g = make_chain()
+    assert ids(shortest_path(g, g.node("a"), g.node("a"))) == ["a"]
+    assert shortest_path(g, g.node("c"), g.node("a"), "directed") is None
+
+
+# ---------------------------------------------------------------------------
+# topological_sort / degree_centrality
+# ---------------------------------------------------------------------------
+
+
+def test_topological_sort_orders_chain_and_detects_cycles():
+    assert ids(topological_sort(make_chain())) == ["a", "b", "c"]
+    cyclic = StandardGraph.from_patterns(
+        [make_relationship("r1", "a", "b"), make_relationship("r2", "b", "a")]
+    )
+    assert topological_sort(cyclic) is None
+
+
+def test_degree_centrality_normalizes_by_node_count():
+    assert degree_centrality(make_chain()) == {"a": 0.5, "b": 1.0, "c": 0.5}
@@ -376,3 +376,37 @@ def test_indices_at_preserves_structure():
     annotated = make_tree().indices_at()
     assert len(annotated.elements) == 2
     assert len(annotated.elements[1].elements) == 1
+
+
+# --- comonad helpers on Pattern[Subject] ---
+
+def make_subject_tree():
+    return Pattern(
+        value=Subject.from_id("root"),
+        elements=[
+            Pattern.point(Subject.from_id("left")),
+            Pattern(
+                value=Subject.from_id("right"),
+                elements=[Pattern.point(Subject.from_id("leaf"))],
+            ),
+        ],
+    )
+
+
+def test_depth_at_on_subject_pattern_replaces_subjects_with_depths():
+    annotated = make_subject_tree().depth_at()
+    assert annotated.value == 2
+    assert annotated.elements[1].value == 1
+    assert annotated.elements[1].elements[0].value == 0
+
+
+def test_size_at_on_subject_pattern_replaces_subjects_with_sizes():
+    annotated = make_subject_tree().size_at()
+    assert annotated.value == 4
+    assert [e.value for e in annotated.elements] == [1, 2]
+
+
+def test_indices_at_on_subject_pattern_replaces_subjects_with_paths():
+    annotated = make_subject_tree().indices_at()
+    assert annotated.value == []
+    assert annotated.elements[1].elements[0].value == [1, 0]
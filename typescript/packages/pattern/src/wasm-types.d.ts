@@ -29,6 +29,7 @@ declare module "../wasm/pattern_wasm.js" {
   export function parse_gram(input: string): ParseResult
   export function parse_to_ast(input: string): any
   export function round_trip(input: string): string
+  export function roundTripCheck(input: string): { ok: boolean; diff?: string }
   export function validate_gram(input: string): boolean
   export function version(): string
 }
@@ -60,6 +61,7 @@ declare module "../wasm-node/pattern_wasm.js" {
   export function parse_gram(input: string): ParseResult
   export function parse_to_ast(input: string): any
   export function round_trip(input: string): string
+  export function roundTripCheck(input: string): { ok: boolean; diff?: string }
   export function validate_gram(input: string): boolean
   export function version(): string
 }
@@ -0,0 +1,74 @@
+// Conformance with the Rust core on shared pattern-operation fixtures.
+//
+// The fixture is generated by crates/pattern-core/tests/binding_conformance.rs;
+// see that file for the operation definitions.
+
+import { readFileSync } from "node:fs"
+import { describe, expect, it } from "vitest"
+import { pipe } from "../src/fp.js"
+import { fold, map, para, values } from "../src/ops.js"
+import { Pattern } from "../src/pattern.js"
+
+interface PatternJson {
+  readonly value: string
+  readonly elements: ReadonlyArray<PatternJson>
+}
+
+interface ConformanceCase {
+  readonly name: string
+  readonly pattern: PatternJson
+  readonly expected: {
+    readonly map: PatternJson
+    readonly fold: string
+    readonly para: string
+    readonly size: number
+    readonly depth: number
+    readonly values: ReadonlyArray<string>
+  }
+}
+
+const fixture = new URL("../../../../tests/common/pattern_ops_conformance.json", import.meta.url)
+const cases: ReadonlyArray<ConformanceCase> = JSON.parse(readFileSync(fixture, "utf8")).cases
+
+const fromJson = (data: PatternJson): Pattern<string> =>
+  new Pattern({ value: data.value, elements: data.elements.map(fromJson) })
+
+const toJson = (p: Pattern<string>): PatternJson => ({
+  value: p.value,
+  elements: p.elements.map(toJson),
+})
+
+describe("Binding conformance with the Rust core", () => {
+  it("loads the shared fixture", () => {
+    expect(cases.length).toBeGreaterThan(0)
+  })
+
+  describe.each(cases)("$name", ({ pattern: data, expected }) => {
+    const pattern = fromJson(data)
+
+    it("map", () => {
+      expect(toJson(pipe(pattern, map((v: string) => `${v}!`)))).toEqual(expected.map)
+    })
+
+    it("fold", () => {
+      expect(pipe(pattern, fold("", (acc: string, v: string) => `${acc}${v}.`))).toBe(expected.fold)
+    })
+
+    it("para", () => {
+      const render = para<string, string>((p, rs) => `${p.value}(${rs.join(",")})`)
+      expect(render(pattern)).toBe(expected.para)
+    })
+
+    it("size", () => {
+      expect(pattern.size).toBe(expected.size)
+    })
+
+    it("depth", () => {
+      expect(pattern.depth).toBe(expected.depth)
+    })
+
+    it("values", () => {
+      expect(values(pattern)).toEqual(expected.values)
+    })
+  })
+})